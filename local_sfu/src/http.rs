@@ -0,0 +1,191 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! A tiny HTTP/1.1 server. One request per connection is plenty for peeking and joining.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+};
+
+use anyhow::{anyhow, Result};
+
+// Nothing the clients send comes close.
+const MAX_BODY_LEN: usize = 64 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Keys are lowercase.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    fn read_from(reader: &mut impl BufRead) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next().ok_or_else(|| anyhow!("Missing method"))?;
+        let target = parts.next().ok_or_else(|| anyhow!("Missing path"))?;
+        // Query strings aren't used by any of the endpoints.
+        let path = target.split('?').next().unwrap_or_default().to_string();
+        let method = method.to_string();
+
+        let mut headers = HashMap::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(anyhow!("Connection closed in headers"));
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow!("Invalid header line"))?;
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+
+        let body_len = match headers.get("content-length") {
+            Some(len) => len.parse::<usize>()?,
+            None => 0,
+        };
+        if body_len > MAX_BODY_LEN {
+            return Err(anyhow!("Body too large: {}", body_len));
+        }
+        let mut body = vec![0; body_len];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self {
+            method,
+            path,
+            headers,
+            body,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn json(status: u16, body: &impl serde::Serialize) -> Self {
+        Self {
+            status,
+            body: serde_json::to_vec(body).expect("always valid"),
+        }
+    }
+
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            body: vec![],
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "",
+        }
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.reason(),
+            self.body.len()
+        )?;
+        writer.write_all(&self.body)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn handle_connection(stream: TcpStream, handler: &(dyn Fn(Request) -> Response + Send + Sync)) {
+    let peer = stream.peer_addr().ok();
+    let mut reader = BufReader::new(&stream);
+    let response = match Request::read_from(&mut reader) {
+        Ok(request) => {
+            debug!("HTTP {} {} from {:?}", request.method, request.path, peer);
+            handler(request)
+        }
+        Err(err) => {
+            warn!("Invalid HTTP request from {:?}: {}", peer, err);
+            Response::empty(400)
+        }
+    };
+    if let Err(err) = response.write_to(&mut &stream) {
+        warn!("Failed to write HTTP response to {:?}: {}", peer, err);
+    }
+}
+
+/// Serves requests on `listener` forever, one thread per connection.
+pub fn serve(
+    listener: TcpListener,
+    handler: Arc<dyn Fn(Request) -> Response + Send + Sync>,
+) -> Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let handler = handler.clone();
+        thread::spawn(move || handle_connection(stream, &*handler));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request() {
+        let raw = b"PUT /v2/conference/participants?x=y HTTP/1.1\r\nHost: localhost\r\nX-Room-Id: abcd\r\nContent-Length: 2\r\n\r\n{}";
+        let request = Request::read_from(&mut &raw[..]).unwrap();
+        assert_eq!("PUT", request.method);
+        assert_eq!("/v2/conference/participants", request.path);
+        assert_eq!(Some("abcd"), request.header("X-Room-Id"));
+        assert_eq!(b"{}", &request.body[..]);
+    }
+
+    #[test]
+    fn parse_truncated_request() {
+        let raw = b"GET / HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert!(Request::read_from(&mut &raw[..]).is_err());
+        let raw = b"GET / HTTP/1.1\r\nHost: localhost\r\n";
+        assert!(Request::read_from(&mut &raw[..]).is_err());
+    }
+
+    #[test]
+    fn write_response() {
+        let mut written = vec![];
+        Response::empty(404).write_to(&mut written).unwrap();
+        assert_eq!(
+            "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            String::from_utf8(written).unwrap()
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! An ICE-lite responder: just enough STUN (RFC 5389) to answer the binding requests
+//! that clients send to check connectivity and keep their candidate pair alive.
+
+use std::net::SocketAddr;
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+const HEADER_LEN: usize = 20;
+const MAGIC_COOKIE: u32 = 0x2112A442;
+const BINDING_REQUEST: u16 = 0x0001;
+const BINDING_SUCCESS_RESPONSE: u16 = 0x0101;
+
+const ATTR_USERNAME: u16 = 0x0006;
+const ATTR_MESSAGE_INTEGRITY: u16 = 0x0008;
+const ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+const ATTR_FINGERPRINT: u16 = 0x8028;
+
+const MESSAGE_INTEGRITY_LEN: usize = 20;
+const FINGERPRINT_LEN: usize = 4;
+const FINGERPRINT_XOR: u32 = 0x5354554e;
+
+pub type TransactionId = [u8; 12];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BindingRequest<'a> {
+    pub transaction_id: TransactionId,
+    /// "{server ufrag}:{client ufrag}"
+    pub username: &'a str,
+    // The offset of the MESSAGE-INTEGRITY attribute, so it can be verified once
+    // the password for the username has been looked up.
+    message_integrity_offset: usize,
+}
+
+impl<'a> BindingRequest<'a> {
+    pub fn parse(packet: &'a [u8]) -> Option<Self> {
+        if packet.len() < HEADER_LEN
+            || u16::from_be_bytes([packet[0], packet[1]]) != BINDING_REQUEST
+            || u32::from_be_bytes(packet[4..8].try_into().ok()?) != MAGIC_COOKIE
+        {
+            return None;
+        }
+        let message_len = u16::from_be_bytes([packet[2], packet[3]]) as usize;
+        let packet = packet.get(..HEADER_LEN + message_len)?;
+        let transaction_id: TransactionId = packet[8..HEADER_LEN].try_into().ok()?;
+
+        let mut username = None;
+        let mut message_integrity_offset = None;
+        let mut offset = HEADER_LEN;
+        while offset + 4 <= packet.len() {
+            let attr_type = u16::from_be_bytes([packet[offset], packet[offset + 1]]);
+            let attr_len = u16::from_be_bytes([packet[offset + 2], packet[offset + 3]]) as usize;
+            let value = packet.get(offset + 4..offset + 4 + attr_len)?;
+            match attr_type {
+                ATTR_USERNAME => username = std::str::from_utf8(value).ok(),
+                ATTR_MESSAGE_INTEGRITY => {
+                    message_integrity_offset = Some(offset);
+                    // Anything after this other than the fingerprint isn't covered by
+                    // the integrity check, so stop here.
+                    break;
+                }
+                _ => {}
+            }
+            offset += 4 + attr_len.next_multiple_of(4);
+        }
+
+        Some(Self {
+            transaction_id,
+            username: username?,
+            message_integrity_offset: message_integrity_offset?,
+        })
+    }
+
+    /// The server ufrag, which identifies the client for us.
+    pub fn server_ufrag(&self) -> &'a str {
+        self.username.split(':').next().unwrap_or_default()
+    }
+
+    /// Checks MESSAGE-INTEGRITY, which uses the server's ICE password as the key.
+    pub fn verify(&self, packet: &[u8], server_ice_pwd: &str) -> bool {
+        let offset = self.message_integrity_offset;
+        let Some(expected) = packet.get(offset + 4..offset + 4 + MESSAGE_INTEGRITY_LEN) else {
+            return false;
+        };
+        let mut covered = packet[..offset].to_vec();
+        set_message_len(&mut covered, offset + 4 + MESSAGE_INTEGRITY_LEN);
+        let mut mac = Hmac::<Sha1>::new_from_slice(server_ice_pwd.as_bytes())
+            .expect("HMAC accepts any key length");
+        mac.update(&covered);
+        mac.verify_slice(expected).is_ok()
+    }
+}
+
+fn set_message_len(message: &mut [u8], total_len: usize) {
+    let message_len = (total_len - HEADER_LEN) as u16;
+    message[2..4].copy_from_slice(&message_len.to_be_bytes());
+}
+
+fn push_attribute(message: &mut Vec<u8>, attr_type: u16, value: &[u8]) {
+    message.extend_from_slice(&attr_type.to_be_bytes());
+    message.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    message.extend_from_slice(value);
+    message.resize(message.len().next_multiple_of(4), 0);
+}
+
+fn xor_mapped_address(transaction_id: &TransactionId, addr: SocketAddr) -> Vec<u8> {
+    let cookie = MAGIC_COOKIE.to_be_bytes();
+    let port = addr.port() ^ (MAGIC_COOKIE >> 16) as u16;
+    let mut value = vec![0];
+    match addr {
+        SocketAddr::V4(addr) => {
+            value.push(0x01);
+            value.extend_from_slice(&port.to_be_bytes());
+            value.extend(addr.ip().octets().iter().zip(cookie).map(|(a, c)| a ^ c));
+        }
+        SocketAddr::V6(addr) => {
+            value.push(0x02);
+            value.extend_from_slice(&port.to_be_bytes());
+            value.extend(
+                addr.ip()
+                    .octets()
+                    .iter()
+                    .zip(cookie.iter().chain(transaction_id))
+                    .map(|(a, c)| a ^ c),
+            );
+        }
+    }
+    value
+}
+
+/// Builds a success response to a binding request received from `client_addr`.
+pub fn binding_success_response(
+    transaction_id: &TransactionId,
+    client_addr: SocketAddr,
+    server_ice_pwd: &str,
+) -> Vec<u8> {
+    let mut response = Vec::with_capacity(80);
+    response.extend_from_slice(&BINDING_SUCCESS_RESPONSE.to_be_bytes());
+    response.extend_from_slice(&[0, 0]);
+    response.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+    response.extend_from_slice(transaction_id);
+    push_attribute(
+        &mut response,
+        ATTR_XOR_MAPPED_ADDRESS,
+        &xor_mapped_address(transaction_id, client_addr),
+    );
+
+    let integrity_len = response.len() + 4 + MESSAGE_INTEGRITY_LEN;
+    set_message_len(&mut response, integrity_len);
+    let mut mac = Hmac::<Sha1>::new_from_slice(server_ice_pwd.as_bytes())
+        .expect("HMAC accepts any key length");
+    mac.update(&response);
+    push_attribute(
+        &mut response,
+        ATTR_MESSAGE_INTEGRITY,
+        &mac.finalize().into_bytes(),
+    );
+
+    let fingerprint_len = response.len() + 4 + FINGERPRINT_LEN;
+    set_message_len(&mut response, fingerprint_len);
+    let fingerprint = crc32fast::hash(&response) ^ FINGERPRINT_XOR;
+    push_attribute(&mut response, ATTR_FINGERPRINT, &fingerprint.to_be_bytes());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding_request(username: &str, pwd: &str) -> Vec<u8> {
+        let mut request = Vec::new();
+        request.extend_from_slice(&BINDING_REQUEST.to_be_bytes());
+        request.extend_from_slice(&[0, 0]);
+        request.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        request.extend_from_slice(&[7; 12]);
+        push_attribute(&mut request, ATTR_USERNAME, username.as_bytes());
+
+        let integrity_len = request.len() + 4 + MESSAGE_INTEGRITY_LEN;
+        set_message_len(&mut request, integrity_len);
+        let mut mac = Hmac::<Sha1>::new_from_slice(pwd.as_bytes()).unwrap();
+        mac.update(&request);
+        push_attribute(
+            &mut request,
+            ATTR_MESSAGE_INTEGRITY,
+            &mac.finalize().into_bytes(),
+        );
+        let fingerprint_len = request.len() + 4 + FINGERPRINT_LEN;
+        set_message_len(&mut request, fingerprint_len);
+        let fingerprint = crc32fast::hash(&request) ^ FINGERPRINT_XOR;
+        push_attribute(&mut request, ATTR_FINGERPRINT, &fingerprint.to_be_bytes());
+        request
+    }
+
+    #[test]
+    fn parse_and_verify() {
+        let packet = binding_request("server:client", "password");
+        let request = BindingRequest::parse(&packet).unwrap();
+        assert_eq!([7; 12], request.transaction_id);
+        assert_eq!("server:client", request.username);
+        assert_eq!("server", request.server_ufrag());
+        assert!(request.verify(&packet, "password"));
+        assert!(!request.verify(&packet, "wrong password"));
+    }
+
+    #[test]
+    fn not_a_binding_request() {
+        let mut packet = binding_request("server:client", "password");
+        packet[1] = 0x11;
+        assert_eq!(None, BindingRequest::parse(&packet));
+        assert_eq!(None, BindingRequest::parse(&packet[..10]));
+    }
+
+    #[test]
+    fn response() {
+        let transaction_id = [1; 12];
+        let addr: SocketAddr = "192.0.2.1:32853".parse().unwrap();
+        let response = binding_success_response(&transaction_id, addr, "password");
+
+        assert_eq!(
+            BINDING_SUCCESS_RESPONSE,
+            u16::from_be_bytes([response[0], response[1]])
+        );
+        assert_eq!(
+            response.len() - HEADER_LEN,
+            u16::from_be_bytes([response[2], response[3]]) as usize
+        );
+        // XOR-MAPPED-ADDRESS, from RFC 5769 section 2.2 (same address and port).
+        assert_eq!(
+            &[0x00, 0x20, 0x00, 0x08, 0x00, 0x01, 0xa1, 0x47, 0xe1, 0x12, 0xa6, 0x43],
+            &response[HEADER_LEN..HEADER_LEN + 12]
+        );
+        let fingerprint_offset = response.len() - 8;
+        let fingerprint =
+            u32::from_be_bytes(response[fingerprint_offset + 4..].try_into().unwrap());
+        assert_eq!(
+            crc32fast::hash(&response[..fingerprint_offset]) ^ FINGERPRINT_XOR,
+            fingerprint
+        );
+    }
+}
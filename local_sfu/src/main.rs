@@ -0,0 +1,132 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! A stand-in for the SFU that group calls go through, so that `group_call` and `call_sim-cli`
+//! can run full group calls on one machine without access to Signal's servers.
+
+#[macro_use]
+extern crate log;
+
+mod http;
+mod ice;
+mod rtp;
+mod sfu;
+mod srtp;
+
+pub mod group_call {
+    #![allow(clippy::derive_partial_eq_without_eq)]
+    protobuf::include_groupcall_proto!();
+
+    // Lets MrpStream reassemble messages split across several packets, as in ringrtc.
+    impl Extend<DeviceToSfu> for DeviceToSfu {
+        fn extend<T: IntoIterator<Item = DeviceToSfu>>(&mut self, iter: T) {
+            if self.mrp_header.and_then(|h| h.num_packets).is_some() {
+                let content = self.content.get_or_insert_with(Vec::new);
+                for message in iter {
+                    if let Some(other_content) = message.content {
+                        content.extend(other_content);
+                    }
+                }
+            }
+        }
+    }
+}
+
+use std::{
+    net::{IpAddr, Ipv4Addr, TcpListener, UdpSocket},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use clap::Parser;
+
+use crate::sfu::{Config, Sfu};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+// Big enough for any RTP packet on a typical network.
+const MAX_PACKET_LEN: usize = 2048;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// The IP address to listen on and to hand out to clients.
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    ip: IpAddr,
+
+    /// The port for the HTTP API. Clients should use http://{ip}:{http_port} as the SFU URL.
+    #[arg(long, default_value_t = 8080)]
+    http_port: u16,
+
+    /// The port for media (ICE and SRTP).
+    #[arg(long, default_value_t = 10000)]
+    udp_port: u16,
+
+    /// The maximum number of devices in a call, including pending ones.
+    #[arg(long, default_value_t = 50)]
+    max_devices: u32,
+
+    /// Require everyone but the creator of a call to be approved by an admin.
+    #[arg(long)]
+    approval_required: bool,
+
+    /// A base64 admin passkey that grants admin rights to clients joining with it.
+    #[arg(long)]
+    admin_passkey: Option<String>,
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+
+    let admin_passkey = args
+        .admin_passkey
+        .as_deref()
+        .map(|passkey| base64.decode(passkey))
+        .transpose()?;
+    let socket = UdpSocket::bind((args.ip, args.udp_port))?;
+    let listener = TcpListener::bind((args.ip, args.http_port))?;
+    let sfu = Arc::new(Mutex::new(Sfu::new(
+        Config {
+            ip: args.ip,
+            udp_port: args.udp_port,
+            max_devices: args.max_devices,
+            approval_required: args.approval_required,
+            admin_passkey,
+        },
+        socket.try_clone()?,
+    )));
+
+    {
+        let sfu = sfu.clone();
+        thread::spawn(move || {
+            let mut buf = [0u8; MAX_PACKET_LEN];
+            loop {
+                match socket.recv_from(&mut buf) {
+                    Ok((len, from)) => sfu.lock().unwrap().handle_packet(from, &buf[..len]),
+                    Err(err) => warn!("Failed to receive UDP: {}", err),
+                }
+            }
+        });
+    }
+
+    {
+        let sfu = sfu.clone();
+        thread::spawn(move || loop {
+            thread::sleep(TICK_INTERVAL);
+            sfu.lock().unwrap().tick(Instant::now());
+        });
+    }
+
+    info!(
+        "Local SFU listening on http://{}:{} (media on UDP port {})",
+        args.ip, args.http_port, args.udp_port
+    );
+    http::serve(
+        listener,
+        Arc::new(move |request| sfu.lock().unwrap().handle_http_request(request)),
+    )
+}
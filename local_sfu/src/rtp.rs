@@ -0,0 +1,159 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Just enough RTP parsing to decrypt, route, and re-encrypt packets.
+
+pub type PayloadType = u8;
+pub type SequenceNumber = u16;
+pub type Timestamp = u32;
+pub type Ssrc = u32;
+
+const VERSION: u8 = 2;
+const FIXED_HEADER_LEN: usize = 12;
+const CSRC_LEN: usize = 4;
+const EXTENSIONS_HEADER_LEN: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub marker: bool,
+    pub pt: PayloadType,
+    pub seqnum: SequenceNumber,
+    pub timestamp: Timestamp,
+    pub ssrc: Ssrc,
+    /// The length of the whole header, including CSRCs and extensions.
+    /// This is the part of the packet that is authenticated but not encrypted.
+    pub len: usize,
+}
+
+impl Header {
+    /// Parses the header of an RTP packet.
+    /// Returns None for anything that isn't a well-formed RTP packet, including RTCP.
+    pub fn parse(packet: &[u8]) -> Option<Self> {
+        if packet.len() < FIXED_HEADER_LEN || packet[0] >> 6 != VERSION || is_rtcp(packet) {
+            return None;
+        }
+        let has_extensions = (packet[0] & 0b0001_0000) != 0;
+        let csrc_count = (packet[0] & 0b0000_1111) as usize;
+        let marker = (packet[1] & 0b1000_0000) != 0;
+        let pt = packet[1] & 0b0111_1111;
+        let seqnum = u16::from_be_bytes(packet[2..4].try_into().ok()?);
+        let timestamp = u32::from_be_bytes(packet[4..8].try_into().ok()?);
+        let ssrc = u32::from_be_bytes(packet[8..12].try_into().ok()?);
+
+        let mut len = FIXED_HEADER_LEN + csrc_count * CSRC_LEN;
+        if has_extensions {
+            let extensions_header = packet.get(len..len + EXTENSIONS_HEADER_LEN)?;
+            let extensions_len = u16::from_be_bytes([extensions_header[2], extensions_header[3]]);
+            len += EXTENSIONS_HEADER_LEN + (extensions_len as usize) * 4;
+        }
+        if len > packet.len() {
+            return None;
+        }
+
+        Some(Self {
+            marker,
+            pt,
+            seqnum,
+            timestamp,
+            ssrc,
+            len,
+        })
+    }
+
+    /// Writes a header with no CSRCs or extensions, which is all the SFU ever originates.
+    pub fn write_simple(
+        pt: PayloadType,
+        seqnum: SequenceNumber,
+        timestamp: Timestamp,
+        ssrc: Ssrc,
+    ) -> Vec<u8> {
+        let mut header = Vec::with_capacity(FIXED_HEADER_LEN);
+        header.push(VERSION << 6);
+        header.push(pt & 0b0111_1111);
+        header.extend_from_slice(&seqnum.to_be_bytes());
+        header.extend_from_slice(&timestamp.to_be_bytes());
+        header.extend_from_slice(&ssrc.to_be_bytes());
+        header
+    }
+}
+
+/// RTCP is multiplexed with RTP on the same port, distinguished by the packet type
+/// (which overlaps with the marker bit and payload type of RTP). See RFC 5761.
+pub fn is_rtcp(packet: &[u8]) -> bool {
+    packet.len() >= 2 && (192..=223).contains(&packet[1])
+}
+
+/// STUN, DTLS, and RTP/RTCP are demultiplexed by the first byte. See RFC 7983.
+pub fn looks_like_rtp_or_rtcp(packet: &[u8]) -> bool {
+    packet
+        .first()
+        .map(|first| (128..=191).contains(first))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_simple() {
+        let packet = [
+            Header::write_simple(101, 0x1234, 0x56789abc, 1),
+            b"payload".to_vec(),
+        ]
+        .concat();
+        assert_eq!(
+            Some(Header {
+                marker: false,
+                pt: 101,
+                seqnum: 0x1234,
+                timestamp: 0x56789abc,
+                ssrc: 1,
+                len: 12,
+            }),
+            Header::parse(&packet)
+        );
+    }
+
+    #[test]
+    fn parse_with_csrcs_and_extensions() {
+        let mut packet = vec![
+            0b1001_0001, // V=2, X=1, CC=1
+            0b1000_0000 | 111,
+            0,
+            1,
+            0,
+            0,
+            0,
+            2,
+            0,
+            0,
+            0,
+            3,
+        ];
+        packet.extend_from_slice(&[0, 0, 0, 4]); // CSRC
+        packet.extend_from_slice(&[0xBE, 0xDE, 0, 1]); // One word of extensions
+        packet.extend_from_slice(&[0x10, 0xFF, 0, 0]);
+        packet.extend_from_slice(b"payload");
+
+        let header = Header::parse(&packet).unwrap();
+        assert!(header.marker);
+        assert_eq!(111, header.pt);
+        assert_eq!(3, header.ssrc);
+        assert_eq!(24, header.len);
+        assert_eq!(b"payload", &packet[header.len..]);
+
+        // Truncated extensions
+        assert_eq!(None, Header::parse(&packet[..20]));
+    }
+
+    #[test]
+    fn rtcp_is_not_rtp() {
+        let sender_report = [0x80, 200, 0, 6, 0, 0, 0, 1, 0, 0, 0, 0];
+        assert!(looks_like_rtp_or_rtcp(&sender_report));
+        assert!(is_rtcp(&sender_report));
+        assert_eq!(None, Header::parse(&sender_report));
+    }
+}
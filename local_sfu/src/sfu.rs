@@ -0,0 +1,1063 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! The state of all the calls hosted by the stand-in SFU, and how it reacts to HTTP requests,
+//! STUN, and SRTP from clients.
+//!
+//! Compared to the real SFU, this one is deliberately simple:
+//! - Only the lowest video layer is forwarded and there's no congestion control.
+//! - SRTCP is dropped, so there are no keyframe requests or NACKs through the SFU.
+//! - Membership proofs and call link credentials aren't validated, only parsed.
+//! - There's no active speaker detection.
+
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    net::{IpAddr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use hex::ToHex;
+use mrp::MrpStream;
+use prost::Message;
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::{
+    group_call::{
+        device_to_sfu::{
+            video_request_message::VideoRequest, AdminAction, GenericAdminAction, RaiseHand,
+            VideoRequestMessage,
+        },
+        sfu_to_device::{
+            peek_info::PeekDeviceInfo, CurrentDevices, DeviceJoinedOrLeft, PeekInfo, RaisedHands,
+            Removed,
+        },
+        DeviceToSfu, MrpHeader, SfuToDevice,
+    },
+    http::{Request, Response},
+    ice, rtp, srtp,
+};
+
+pub type DemuxId = u32;
+pub type OpaqueUserId = String;
+type RoomId = String;
+
+// These must stay in sync with RingRTC's group_call.rs and the SDP it generates.
+const RTP_DATA_PAYLOAD_TYPE: rtp::PayloadType = 101;
+const RTP_DATA_TO_SFU_SSRC: rtp::Ssrc = 1;
+const AUDIO_SSRC_OFFSET: rtp::Ssrc = 0;
+// The first (lowest) of the simulcast layers, and the only video SSRC receivers expect.
+const VIDEO_SSRC_OFFSET: rtp::Ssrc = 2;
+const RTP_DATA_THROUGH_SFU_SSRC_OFFSET: rtp::Ssrc = 0xD;
+const DEMUX_ID_SSRC_MASK: rtp::Ssrc = !0xF;
+
+const PARTICIPANTS_PATH: &str = "/v2/conference/participants";
+
+/// Clients that haven't sent anything for this long are removed from the call.
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+/// A client is considered to be sending video if it has sent some this recently.
+const VIDEO_TIMEOUT: Duration = Duration::from_secs(3);
+/// What to tell clients they're receiving when they haven't asked for a particular height.
+const DEFAULT_ALLOCATED_HEIGHT: u32 = 360;
+const MAX_RECEIVE_WINDOW: usize = 64;
+
+pub struct Config {
+    pub ip: IpAddr,
+    pub udp_port: u16,
+    pub max_devices: u32,
+    /// If set, everyone but the creator of a call starts out pending and must be approved.
+    pub approval_required: bool,
+    /// Clients presenting this passkey when joining get to take admin actions.
+    /// The creator of a call always can.
+    pub admin_passkey: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ClientStatus {
+    Active,
+    Pending,
+}
+
+impl ClientStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            ClientStatus::Active => "ACTIVE",
+            ClientStatus::Pending => "PENDING",
+        }
+    }
+}
+
+/// Who is making a request, according to its headers.
+#[derive(Debug, PartialEq, Eq)]
+struct Caller {
+    room_id: RoomId,
+    opaque_user_id: Option<OpaqueUserId>,
+}
+
+impl Caller {
+    /// Call link rooms are identified by the X-Room-Id header and authenticated with an
+    /// anonymous credential, so we don't know who the user is. Group rooms are identified by
+    /// the group ID in the membership proof, which looks like
+    /// "{hex(user ID ciphertext)}:{hex(group ID)}:{timestamp}:{mac}".
+    fn from_request(request: &Request) -> Option<Self> {
+        if let Some(room_id) = request.header("X-Room-Id") {
+            return Some(Self {
+                room_id: format!("call-link:{}", room_id),
+                opaque_user_id: None,
+            });
+        }
+
+        let credentials = request.header("Authorization")?.strip_prefix("Basic ")?;
+        let credentials = String::from_utf8(base64.decode(credentials).ok()?).ok()?;
+        let (_, token) = credentials.split_once(':')?;
+        let mut fields = token.split(':');
+        let user_id_ciphertext = hex::decode(fields.next()?).ok()?;
+        let group_id = fields.next().filter(|group_id| !group_id.is_empty())?;
+        Some(Self {
+            room_id: format!("group:{}", group_id),
+            // This matches how clients map group members to opaque IDs.
+            opaque_user_id: Some(Sha256::digest(&user_id_ciphertext).encode_hex()),
+        })
+    }
+}
+
+// The client's ICE password is left out because, as an ICE-lite server, we never send
+// connectivity checks of our own.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JoinRequest {
+    admin_passkey: Option<String>,
+    ice_ufrag: String,
+    #[serde(with = "hex")]
+    dhe_public_key: [u8; 32],
+    #[serde(with = "hex")]
+    hkdf_extra_info: Vec<u8>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JoinResponse {
+    demux_id: DemuxId,
+    ips: Vec<IpAddr>,
+    port: u16,
+    port_tcp: u16,
+    ice_ufrag: String,
+    ice_pwd: String,
+    #[serde(with = "hex")]
+    dhe_public_key: [u8; 32],
+    call_creator: String,
+    conference_id: String,
+    client_status: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializedPeekDeviceInfo<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opaque_user_id: Option<&'a str>,
+    demux_id: DemuxId,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializedPeekInfo<'a> {
+    conference_id: &'a str,
+    max_devices: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creator: Option<&'a str>,
+    participants: Vec<SerializedPeekDeviceInfo<'a>>,
+    pending_clients: Vec<SerializedPeekDeviceInfo<'a>>,
+}
+
+struct Client {
+    demux_id: DemuxId,
+    opaque_user_id: Option<OpaqueUserId>,
+    status: ClientStatus,
+    is_admin: bool,
+    server_ice_ufrag: String,
+    server_ice_pwd: String,
+    // Decrypts with the client's key.
+    srtp_from_client: srtp::Context,
+    // Encrypts with the server's key.
+    srtp_to_client: srtp::Context,
+    // Set once ICE has checked connectivity.
+    addr: Option<SocketAddr>,
+    last_received: Instant,
+    last_video_received: Option<Instant>,
+    reliable_stream: MrpStream<(), DeviceToSfu>,
+    // A Cell so messages can be sent while the rest of the state is borrowed.
+    next_rtp_data_seqnum: Cell<u32>,
+    raise_hand_seqnum: u32,
+    // The heights this client has asked to receive, by sender. 0 means "don't send".
+    requested_heights: HashMap<DemuxId, u32>,
+}
+
+struct Room {
+    era_id: String,
+    creator: Option<OpaqueUserId>,
+    // In the order they joined, which is the order pending clients are reported in.
+    clients: Vec<Client>,
+    // In the order hands were raised.
+    raised_hands: Vec<DemuxId>,
+    blocked_users: HashSet<OpaqueUserId>,
+}
+
+impl Room {
+    fn new(creator: Option<OpaqueUserId>) -> Self {
+        Self {
+            era_id: hex::encode(OsRng.gen::<[u8; 16]>()),
+            creator,
+            clients: Vec::new(),
+            raised_hands: Vec::new(),
+            blocked_users: HashSet::new(),
+        }
+    }
+
+    fn client(&self, demux_id: DemuxId) -> Option<&Client> {
+        self.clients
+            .iter()
+            .find(|client| client.demux_id == demux_id)
+    }
+
+    fn client_mut(&mut self, demux_id: DemuxId) -> Option<&mut Client> {
+        self.clients
+            .iter_mut()
+            .find(|client| client.demux_id == demux_id)
+    }
+
+    fn active_demux_ids(&self) -> Vec<DemuxId> {
+        self.clients
+            .iter()
+            .filter(|client| client.status == ClientStatus::Active)
+            .map(|client| client.demux_id)
+            .collect()
+    }
+
+    fn new_demux_id(&self) -> DemuxId {
+        loop {
+            // Leave room below for the SSRCs derived from the demux ID, and skip 0.
+            let demux_id = OsRng.gen_range(1..(DEMUX_ID_SSRC_MASK >> 4)) << 4;
+            if self.client(demux_id).is_none() {
+                return demux_id;
+            }
+        }
+    }
+
+    fn peek_info(&self, max_devices: u32) -> PeekInfo {
+        let devices_with_status = |status| {
+            self.clients
+                .iter()
+                .filter(|client| client.status == status)
+                .map(|client| PeekDeviceInfo {
+                    demux_id: Some(client.demux_id),
+                    opaque_user_id: client.opaque_user_id.clone(),
+                })
+                .collect()
+        };
+        PeekInfo {
+            era_id: Some(self.era_id.clone()),
+            max_devices: Some(max_devices),
+            creator: self.creator.clone(),
+            devices: devices_with_status(ClientStatus::Active),
+            pending_devices: devices_with_status(ClientStatus::Pending),
+            call_link_state: None,
+        }
+    }
+
+    fn serialized_peek_info(&self, max_devices: u32) -> SerializedPeekInfo<'_> {
+        let devices_with_status = |status| {
+            self.clients
+                .iter()
+                .filter(|client| client.status == status)
+                .map(|client| SerializedPeekDeviceInfo {
+                    opaque_user_id: client.opaque_user_id.as_deref(),
+                    demux_id: client.demux_id,
+                })
+                .collect()
+        };
+        SerializedPeekInfo {
+            conference_id: &self.era_id,
+            max_devices,
+            creator: self.creator.as_deref(),
+            participants: devices_with_status(ClientStatus::Active),
+            pending_clients: devices_with_status(ClientStatus::Pending),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ClientKey {
+    room_id: RoomId,
+    demux_id: DemuxId,
+}
+
+pub struct Sfu {
+    config: Config,
+    socket: UdpSocket,
+    rooms: HashMap<RoomId, Room>,
+    clients_by_server_ufrag: HashMap<String, ClientKey>,
+    clients_by_addr: HashMap<SocketAddr, ClientKey>,
+}
+
+impl Sfu {
+    pub fn new(config: Config, socket: UdpSocket) -> Self {
+        Self {
+            config,
+            socket,
+            rooms: HashMap::new(),
+            clients_by_server_ufrag: HashMap::new(),
+            clients_by_addr: HashMap::new(),
+        }
+    }
+
+    pub fn handle_http_request(&mut self, request: Request) -> Response {
+        if request.path != PARTICIPANTS_PATH {
+            return Response::empty(404);
+        }
+        let Some(caller) = Caller::from_request(&request) else {
+            return Response::empty(401);
+        };
+        match request.method.as_str() {
+            "GET" => self.peek(&caller),
+            "PUT" => self.join(&caller, &request.body),
+            _ => Response::empty(405),
+        }
+    }
+
+    fn peek(&self, caller: &Caller) -> Response {
+        match self.rooms.get(&caller.room_id) {
+            Some(room) => Response::json(200, &room.serialized_peek_info(self.config.max_devices)),
+            // An empty body means the call just hasn't started.
+            None => Response::empty(404),
+        }
+    }
+
+    fn join(&mut self, caller: &Caller, body: &[u8]) -> Response {
+        let request: JoinRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(err) => {
+                warn!("Invalid join request: {}", err);
+                return Response::empty(400);
+            }
+        };
+
+        let room = self
+            .rooms
+            .entry(caller.room_id.clone())
+            .or_insert_with(|| Room::new(caller.opaque_user_id.clone()));
+        if let Some(opaque_user_id) = &caller.opaque_user_id {
+            if room.blocked_users.contains(opaque_user_id) {
+                info!("Rejecting join from blocked user in {}", caller.room_id);
+                if room.clients.is_empty() {
+                    self.rooms.remove(&caller.room_id);
+                }
+                return Response::empty(403);
+            }
+        }
+
+        if room.clients.len() >= self.config.max_devices as usize {
+            info!("Rejecting join to full call {}", caller.room_id);
+            return Response::empty(413);
+        }
+
+        let is_creator = room.clients.is_empty();
+        let has_admin_passkey = match (&request.admin_passkey, &self.config.admin_passkey) {
+            (Some(presented), Some(expected)) => base64
+                .decode(presented)
+                .map(|presented| &presented == expected)
+                .unwrap_or(false),
+            _ => false,
+        };
+        let is_admin = is_creator || has_admin_passkey;
+        let status = if self.config.approval_required && !is_admin {
+            ClientStatus::Pending
+        } else {
+            ClientStatus::Active
+        };
+
+        let server_secret = StaticSecret::random_from_rng(OsRng);
+        let server_dhe_pub_key = PublicKey::from(&server_secret);
+        let keys = srtp::MasterKeys::negotiate(
+            &server_secret,
+            &PublicKey::from(request.dhe_public_key),
+            &request.hkdf_extra_info,
+        );
+        let server_ice_ufrag = random_ice_string(8);
+        let server_ice_pwd = random_ice_string(24);
+        let demux_id = room.new_demux_id();
+
+        info!(
+            "Client {} joining {} as {:?} (client ufrag: {}, admin: {})",
+            demux_id, caller.room_id, status, request.ice_ufrag, is_admin
+        );
+        room.clients.push(Client {
+            demux_id,
+            opaque_user_id: caller.opaque_user_id.clone(),
+            status,
+            is_admin,
+            server_ice_ufrag: server_ice_ufrag.clone(),
+            server_ice_pwd: server_ice_pwd.clone(),
+            srtp_from_client: srtp::Context::new(&keys.client),
+            srtp_to_client: srtp::Context::new(&keys.server),
+            addr: None,
+            last_received: Instant::now(),
+            last_video_received: None,
+            reliable_stream: MrpStream::with_capacity_limit(MAX_RECEIVE_WINDOW),
+            next_rtp_data_seqnum: Cell::new(1),
+            raise_hand_seqnum: 0,
+            requested_heights: HashMap::new(),
+        });
+        let response = JoinResponse {
+            demux_id,
+            ips: vec![self.config.ip],
+            port: self.config.udp_port,
+            // TCP isn't supported; clients will just fail to connect over it.
+            port_tcp: self.config.udp_port,
+            ice_ufrag: server_ice_ufrag.clone(),
+            ice_pwd: server_ice_pwd,
+            dhe_public_key: server_dhe_pub_key.to_bytes(),
+            call_creator: room.creator.clone().unwrap_or_default(),
+            conference_id: room.era_id.clone(),
+            client_status: status.as_str(),
+        };
+
+        self.clients_by_server_ufrag.insert(
+            server_ice_ufrag,
+            ClientKey {
+                room_id: caller.room_id.clone(),
+                demux_id,
+            },
+        );
+        self.send_membership_update(&caller.room_id);
+        Response::json(200, &response)
+    }
+
+    /// Handles a UDP packet, which could be STUN or SRTP.
+    pub fn handle_packet(&mut self, from: SocketAddr, packet: &[u8]) {
+        if let Some(request) = ice::BindingRequest::parse(packet) {
+            self.handle_binding_request(from, packet, request);
+        } else if rtp::looks_like_rtp_or_rtcp(packet) {
+            if !rtp::is_rtcp(packet) {
+                self.handle_rtp(from, packet);
+            }
+        } else {
+            debug!("Ignoring unknown packet from {}", from);
+        }
+    }
+
+    fn handle_binding_request(
+        &mut self,
+        from: SocketAddr,
+        packet: &[u8],
+        request: ice::BindingRequest,
+    ) {
+        let Some(key) = self
+            .clients_by_server_ufrag
+            .get(request.server_ufrag())
+            .cloned()
+        else {
+            debug!("Ignoring binding request for unknown ufrag from {}", from);
+            return;
+        };
+        let Some(client) = self.client_mut(&key) else {
+            return;
+        };
+        if !request.verify(packet, &client.server_ice_pwd) {
+            warn!("Ignoring binding request with bad integrity from {}", from);
+            return;
+        }
+        client.last_received = Instant::now();
+        let previous_addr = client.addr.replace(from);
+        let response =
+            ice::binding_success_response(&request.transaction_id, from, &client.server_ice_pwd);
+        self.send(from, &response);
+
+        if previous_addr != Some(from) {
+            info!(
+                "Client {} in {} connected from {}",
+                key.demux_id, key.room_id, from
+            );
+            if let Some(previous_addr) = previous_addr {
+                self.clients_by_addr.remove(&previous_addr);
+            }
+            self.clients_by_addr.insert(from, key.clone());
+            // Catch the client up on who is in the call, since anything sent before now
+            // had nowhere to go.
+            self.send_membership_update_to(&key);
+            self.send_raised_hands(&key.room_id);
+        }
+    }
+
+    fn handle_rtp(&mut self, from: SocketAddr, packet: &[u8]) {
+        let Some(key) = self.clients_by_addr.get(&from).cloned() else {
+            debug!("Ignoring RTP from unknown address {}", from);
+            return;
+        };
+        let Some(client) = self.client_mut(&key) else {
+            return;
+        };
+        let (header, roc, payload) = match client.srtp_from_client.decrypt(packet) {
+            Ok(decrypted) => decrypted,
+            Err(err) => {
+                debug!("Dropping RTP from {}: {}", key.demux_id, err);
+                return;
+            }
+        };
+        client.last_received = Instant::now();
+
+        if header.pt == RTP_DATA_PAYLOAD_TYPE && header.ssrc == RTP_DATA_TO_SFU_SSRC {
+            match DeviceToSfu::decode(payload.as_slice()) {
+                Ok(msg) => self.handle_device_to_sfu_reliably(&key, msg),
+                Err(err) => warn!("Invalid DeviceToSfu from {}: {}", key.demux_id, err),
+            }
+            return;
+        }
+
+        if client.status != ClientStatus::Active || header.ssrc & DEMUX_ID_SSRC_MASK != key.demux_id
+        {
+            return;
+        }
+        let is_video = match header.ssrc - key.demux_id {
+            AUDIO_SSRC_OFFSET | RTP_DATA_THROUGH_SFU_SSRC_OFFSET => false,
+            VIDEO_SSRC_OFFSET => true,
+            // Higher video layers and retransmissions
+            _ => return,
+        };
+        if is_video {
+            client.last_video_received = Some(Instant::now());
+        }
+        self.forward(
+            &key,
+            &header,
+            &packet[..header.len],
+            roc,
+            &payload,
+            is_video,
+        );
+    }
+
+    fn forward(
+        &self,
+        sender: &ClientKey,
+        header: &rtp::Header,
+        header_bytes: &[u8],
+        roc: u32,
+        payload: &[u8],
+        is_video: bool,
+    ) {
+        let Some(room) = self.rooms.get(&sender.room_id) else {
+            return;
+        };
+        for receiver in &room.clients {
+            if receiver.demux_id == sender.demux_id || receiver.status != ClientStatus::Active {
+                continue;
+            }
+            let Some(addr) = receiver.addr else {
+                continue;
+            };
+            if is_video && receiver.requested_heights.get(&sender.demux_id) == Some(&0) {
+                continue;
+            }
+            match receiver
+                .srtp_to_client
+                .encrypt(header, header_bytes, roc, payload)
+            {
+                Ok(packet) => self.send(addr, &packet),
+                Err(err) => warn!("Failed to encrypt for {}: {}", receiver.demux_id, err),
+            }
+        }
+    }
+
+    fn handle_device_to_sfu_reliably(&mut self, key: &ClientKey, msg: DeviceToSfu) {
+        let Some(mrp_header) = msg.mrp_header else {
+            self.handle_device_to_sfu(key, msg);
+            return;
+        };
+        let Some(client) = self.client_mut(key) else {
+            return;
+        };
+        let ready = match client
+            .reliable_stream
+            .receive_and_merge(&from_proto(mrp_header), msg)
+        {
+            Ok(ready) => ready,
+            Err(err) => {
+                warn!(
+                    "Dropping reliable DeviceToSfu from {}: {}",
+                    key.demux_id, err
+                );
+                return;
+            }
+        };
+        let mut ack = None;
+        let _ = client.reliable_stream.try_send_ack(|header| {
+            ack = Some(SfuToDevice {
+                mrp_header: Some(to_proto(header)),
+                ..Default::default()
+            });
+            Ok(())
+        });
+        if let Some(ack) = ack {
+            self.send_to_client(key, &ack);
+        }
+        for msg in ready {
+            self.handle_device_to_sfu(key, msg);
+        }
+    }
+
+    fn handle_device_to_sfu(&mut self, key: &ClientKey, msg: DeviceToSfu) {
+        let DeviceToSfu {
+            video_request,
+            leave,
+            admin_action,
+            raise_hand,
+            mrp_header: _,
+            content,
+        } = msg;
+
+        if let Some(content) = content {
+            match DeviceToSfu::decode(content.as_slice()) {
+                Ok(msg) => self.handle_device_to_sfu(key, msg),
+                Err(err) => warn!("Invalid DeviceToSfu content from {}: {}", key.demux_id, err),
+            }
+            // Like the clients, ignore all other fields to prevent ordering issues.
+            return;
+        }
+
+        if let Some(VideoRequestMessage { requests, .. }) = video_request {
+            if let Some(client) = self.client_mut(key) {
+                client.requested_heights = requests
+                    .into_iter()
+                    .filter_map(|VideoRequest { height, demux_id }| Some((demux_id?, height?)))
+                    .collect();
+            }
+        }
+        if let Some(RaiseHand {
+            raise: Some(raise),
+            seqnum: Some(seqnum),
+        }) = raise_hand
+        {
+            self.handle_raise_hand(key, raise, seqnum);
+        }
+        if let Some(admin_action) = admin_action {
+            self.handle_admin_action(key, admin_action);
+        }
+        if leave.is_some() {
+            info!("Client {} left {}", key.demux_id, key.room_id);
+            self.remove_client(key);
+        }
+    }
+
+    fn handle_raise_hand(&mut self, key: &ClientKey, raise: bool, seqnum: u32) {
+        let Some(room) = self.rooms.get_mut(&key.room_id) else {
+            return;
+        };
+        let Some(client) = room.client_mut(key.demux_id) else {
+            return;
+        };
+        if seqnum <= client.raise_hand_seqnum {
+            return;
+        }
+        client.raise_hand_seqnum = seqnum;
+        room.raised_hands
+            .retain(|demux_id| *demux_id != key.demux_id);
+        if raise {
+            room.raised_hands.push(key.demux_id);
+        }
+        self.send_raised_hands(&key.room_id);
+    }
+
+    fn handle_admin_action(&mut self, admin: &ClientKey, admin_action: AdminAction) {
+        let Some(room) = self.rooms.get_mut(&admin.room_id) else {
+            return;
+        };
+        if !room
+            .client(admin.demux_id)
+            .map(|client| client.is_admin)
+            .unwrap_or(false)
+        {
+            warn!(
+                "Ignoring admin action from non-admin {} in {}",
+                admin.demux_id, admin.room_id
+            );
+            return;
+        }
+
+        let (AdminAction::Approve(GenericAdminAction { target_demux_id })
+        | AdminAction::Deny(GenericAdminAction { target_demux_id })
+        | AdminAction::Remove(GenericAdminAction { target_demux_id })
+        | AdminAction::Block(GenericAdminAction { target_demux_id })) = admin_action;
+        let Some(target_demux_id) = target_demux_id else {
+            return;
+        };
+        let target = ClientKey {
+            room_id: admin.room_id.clone(),
+            demux_id: target_demux_id,
+        };
+        let Some(target_client) = room.client_mut(target_demux_id) else {
+            return;
+        };
+        info!(
+            "Admin {} in {}: {:?}",
+            admin.demux_id, admin.room_id, admin_action
+        );
+
+        match admin_action {
+            AdminAction::Approve(_) => {
+                if target_client.status == ClientStatus::Pending {
+                    target_client.status = ClientStatus::Active;
+                    self.send_membership_update(&admin.room_id);
+                }
+            }
+            AdminAction::Deny(_) => {
+                if target_client.status == ClientStatus::Pending {
+                    self.send_removed_and_remove_client(&target);
+                }
+            }
+            AdminAction::Remove(_) => {
+                self.send_removed_and_remove_client(&target);
+            }
+            AdminAction::Block(_) => {
+                if let Some(opaque_user_id) = target_client.opaque_user_id.clone() {
+                    room.blocked_users.insert(opaque_user_id);
+                }
+                self.send_removed_and_remove_client(&target);
+            }
+        }
+    }
+
+    fn send_removed_and_remove_client(&mut self, key: &ClientKey) {
+        self.send_to_client(
+            key,
+            &SfuToDevice {
+                removed: Some(Removed {}),
+                ..Default::default()
+            },
+        );
+        self.remove_client(key);
+    }
+
+    fn remove_client(&mut self, key: &ClientKey) {
+        let Some(room) = self.rooms.get_mut(&key.room_id) else {
+            return;
+        };
+        let Some(index) = room
+            .clients
+            .iter()
+            .position(|client| client.demux_id == key.demux_id)
+        else {
+            return;
+        };
+        let client = room.clients.remove(index);
+        room.raised_hands
+            .retain(|demux_id| *demux_id != key.demux_id);
+        self.clients_by_server_ufrag
+            .remove(&client.server_ice_ufrag);
+        if let Some(addr) = client.addr {
+            self.clients_by_addr.remove(&addr);
+        }
+
+        if room.clients.is_empty() {
+            info!("Call in {} ended", key.room_id);
+            self.rooms.remove(&key.room_id);
+        } else {
+            self.send_membership_update(&key.room_id);
+            self.send_raised_hands(&key.room_id);
+        }
+    }
+
+    /// Called periodically to drop clients that went away without saying so and to keep
+    /// everyone up to date on what video they're receiving.
+    pub fn tick(&mut self, now: Instant) {
+        let timed_out: Vec<ClientKey> = self
+            .rooms
+            .iter()
+            .flat_map(|(room_id, room)| {
+                room.clients
+                    .iter()
+                    .filter(|client| {
+                        now.saturating_duration_since(client.last_received) > CLIENT_TIMEOUT
+                    })
+                    .map(|client| ClientKey {
+                        room_id: room_id.clone(),
+                        demux_id: client.demux_id,
+                    })
+            })
+            .collect();
+        for key in timed_out {
+            info!("Client {} in {} timed out", key.demux_id, key.room_id);
+            self.remove_client(&key);
+        }
+
+        for key in self.clients_by_addr.values() {
+            if let Some(current_devices) = self.current_devices_for(key, now) {
+                self.send_to_client(
+                    key,
+                    &SfuToDevice {
+                        current_devices: Some(current_devices),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+
+    fn current_devices_for(&self, key: &ClientKey, now: Instant) -> Option<CurrentDevices> {
+        let room = self.rooms.get(&key.room_id)?;
+        let receiver = room.client(key.demux_id)?;
+        let (demux_ids_with_video, allocated_heights): (Vec<_>, Vec<_>) = room
+            .clients
+            .iter()
+            .filter(|sender| {
+                sender.demux_id != key.demux_id
+                    && sender.status == ClientStatus::Active
+                    && sender
+                        .last_video_received
+                        .is_some_and(|last_video_received| {
+                            now.saturating_duration_since(last_video_received) < VIDEO_TIMEOUT
+                        })
+            })
+            .filter_map(|sender| {
+                let height = receiver
+                    .requested_heights
+                    .get(&sender.demux_id)
+                    .copied()
+                    .unwrap_or(DEFAULT_ALLOCATED_HEIGHT);
+                (height > 0).then_some((sender.demux_id, height))
+            })
+            .unzip();
+        Some(CurrentDevices {
+            demux_ids_with_video,
+            all_demux_ids: room.active_demux_ids(),
+            allocated_heights,
+        })
+    }
+
+    fn send_membership_update(&self, room_id: &str) {
+        let Some(room) = self.rooms.get(room_id) else {
+            return;
+        };
+        for client in &room.clients {
+            self.send_membership_update_to(&ClientKey {
+                room_id: room_id.to_string(),
+                demux_id: client.demux_id,
+            });
+        }
+    }
+
+    fn send_membership_update_to(&self, key: &ClientKey) {
+        let Some(room) = self.rooms.get(&key.room_id) else {
+            return;
+        };
+        self.send_to_client(
+            key,
+            &SfuToDevice {
+                device_joined_or_left: Some(DeviceJoinedOrLeft {
+                    peek_info: Some(room.peek_info(self.config.max_devices)),
+                }),
+                current_devices: self.current_devices_for(key, Instant::now()),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn send_raised_hands(&self, room_id: &str) {
+        let Some(room) = self.rooms.get(room_id) else {
+            return;
+        };
+        let seqnums: Vec<u32> = room
+            .raised_hands
+            .iter()
+            .map(|demux_id| {
+                room.client(*demux_id)
+                    .map(|client| client.raise_hand_seqnum)
+                    .unwrap_or_default()
+            })
+            .collect();
+        for client in &room.clients {
+            self.send_to_client(
+                &ClientKey {
+                    room_id: room_id.to_string(),
+                    demux_id: client.demux_id,
+                },
+                &SfuToDevice {
+                    raised_hands: Some(RaisedHands {
+                        demux_ids: room.raised_hands.clone(),
+                        seqnums: seqnums.clone(),
+                        target_seqnum: Some(client.raise_hand_seqnum),
+                    }),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// Sends an SfuToDevice message over RTP. Clients that haven't connected yet miss out.
+    fn send_to_client(&self, key: &ClientKey, msg: &SfuToDevice) {
+        // We need to mutate the sequence number, but everything else can be shared.
+        let packet = {
+            let Some(room) = self.rooms.get(&key.room_id) else {
+                return;
+            };
+            let Some(client) = room.client(key.demux_id) else {
+                return;
+            };
+            let Some(addr) = client.addr else {
+                return;
+            };
+            let seqnum = client.next_rtp_data_seqnum.get();
+            client.next_rtp_data_seqnum.set(seqnum.wrapping_add(1));
+            let header_bytes = rtp::Header::write_simple(
+                RTP_DATA_PAYLOAD_TYPE,
+                seqnum as u16,
+                seqnum,
+                RTP_DATA_TO_SFU_SSRC,
+            );
+            let header = rtp::Header::parse(&header_bytes).expect("valid header");
+            match client.srtp_to_client.encrypt(
+                &header,
+                &header_bytes,
+                seqnum >> 16,
+                &msg.encode_to_vec(),
+            ) {
+                Ok(packet) => (addr, packet),
+                Err(err) => {
+                    warn!("Failed to encrypt for {}: {}", key.demux_id, err);
+                    return;
+                }
+            }
+        };
+        self.send(packet.0, &packet.1);
+    }
+
+    fn send(&self, addr: SocketAddr, packet: &[u8]) {
+        if let Err(err) = self.socket.send_to(packet, addr) {
+            warn!("Failed to send to {}: {}", addr, err);
+        }
+    }
+
+    fn client_mut(&mut self, key: &ClientKey) -> Option<&mut Client> {
+        self.rooms.get_mut(&key.room_id)?.client_mut(key.demux_id)
+    }
+}
+
+fn random_ice_string(len: usize) -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn from_proto(header: MrpHeader) -> mrp::MrpHeader {
+    mrp::MrpHeader::new_with_length(header.seqnum, header.ack_num, header.num_packets)
+}
+
+fn to_proto(header: mrp::MrpHeader) -> MrpHeader {
+    MrpHeader {
+        seqnum: header.seqnum,
+        ack_num: header.ack_num,
+        num_packets: header.num_packets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn request_with_headers(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: PARTICIPANTS_PATH.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+                .collect(),
+            body: vec![],
+        }
+    }
+
+    #[test]
+    fn caller_from_membership_proof() {
+        // As sent by bin/group_call by default.
+        let auth = format!(
+            "Basic {}",
+            base64.encode("757365725f6964:757365725f6964:67726f75705f6964:1:")
+        );
+        let caller =
+            Caller::from_request(&request_with_headers(&[("Authorization", &auth)])).unwrap();
+        assert_eq!("group:67726f75705f6964", caller.room_id);
+        assert_eq!(
+            Some(Sha256::digest(b"user_id").encode_hex::<String>()),
+            caller.opaque_user_id
+        );
+    }
+
+    #[test]
+    fn caller_from_room_id() {
+        let caller = Caller::from_request(&request_with_headers(&[
+            ("Authorization", "Bearer auth.AAAA"),
+            ("X-Room-Id", "abcd"),
+        ]))
+        .unwrap();
+        assert_eq!("call-link:abcd", caller.room_id);
+        assert_eq!(None, caller.opaque_user_id);
+    }
+
+    #[test]
+    fn caller_rejected() {
+        assert_eq!(None, Caller::from_request(&request_with_headers(&[])));
+        let auth = format!("Basic {}", base64.encode("user:not hex"));
+        assert_eq!(
+            None,
+            Caller::from_request(&request_with_headers(&[("Authorization", &auth)]))
+        );
+    }
+
+    #[test]
+    fn join_rejected_when_full() {
+        let mut sfu = Sfu::new(
+            Config {
+                ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                udp_port: 0,
+                max_devices: 2,
+                approval_required: false,
+                admin_passkey: None,
+            },
+            UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap(),
+        );
+        let caller = Caller {
+            room_id: "call-link:abcd".to_string(),
+            opaque_user_id: None,
+        };
+        let body = serde_json::json!({
+            "iceUfrag": "ufrag",
+            "dhePublicKey": hex::encode([1u8; 32]),
+            "hkdfExtraInfo": "",
+        })
+        .to_string();
+
+        assert_eq!(200, sfu.join(&caller, body.as_bytes()).status);
+        assert_eq!(200, sfu.join(&caller, body.as_bytes()).status);
+        assert_eq!(413, sfu.join(&caller, body.as_bytes()).status);
+    }
+
+    #[test]
+    fn demux_ids_leave_room_for_ssrcs() {
+        let room = Room::new(None);
+        for _ in 0..100 {
+            let demux_id = room.new_demux_id();
+            assert_ne!(0, demux_id);
+            assert_eq!(demux_id, demux_id & DEMUX_ID_SSRC_MASK);
+            assert!(demux_id.checked_add(0xF).is_some());
+        }
+    }
+}
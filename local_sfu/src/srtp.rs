@@ -0,0 +1,276 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! SRTP with AEAD_AES_128_GCM (RFC 7714), the only suite RingRTC uses for group calls.
+//! Keys come from the DHE done over HTTP when joining rather than from DTLS.
+//! SRTCP isn't supported.
+
+use std::collections::HashMap;
+
+use aes::Aes128;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes128Gcm, Nonce,
+};
+use anyhow::{anyhow, Result};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::rtp;
+
+pub const KEY_LEN: usize = 16;
+pub const SALT_LEN: usize = 12;
+const MASTER_KEY_MATERIAL_LEN: usize = KEY_LEN + SALT_LEN + KEY_LEN + SALT_LEN;
+const TAG_LEN: usize = 16;
+
+// These labels are from RFC 3711 section 4.3.2.
+const RTP_ENCRYPTION_KEY_LABEL: u8 = 0;
+const RTP_SALT_LABEL: u8 = 2;
+
+// This must stay in sync with DheState::negotiate in RingRTC.
+const HKDF_INFO_PREFIX: &[u8] = b"Signal_Group_Call_20211105_SignallingDH_SRTPKey_KDF";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MasterKey {
+    pub key: [u8; KEY_LEN],
+    pub salt: [u8; SALT_LEN],
+}
+
+/// The keys for both directions of one client's connection.
+/// "client" is what the client encrypts with; "server" is what the SFU encrypts with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MasterKeys {
+    pub client: MasterKey,
+    pub server: MasterKey,
+}
+
+impl MasterKeys {
+    /// Does the server side of the DHE that the client started when it sent its public key
+    /// in the join request.
+    pub fn negotiate(
+        server_secret: &StaticSecret,
+        client_pub_key: &PublicKey,
+        hkdf_extra_info: &[u8],
+    ) -> Self {
+        let shared_secret = server_secret.diffie_hellman(client_pub_key);
+        let mut master_key_material = [0u8; MASTER_KEY_MATERIAL_LEN];
+        Hkdf::<Sha256>::new(Some(&[0u8; 32]), shared_secret.as_bytes())
+            .expand_multi_info(
+                &[HKDF_INFO_PREFIX, hkdf_extra_info],
+                &mut master_key_material,
+            )
+            .expect("SRTP master key material expansion");
+        Self::from_master_key_material(&master_key_material)
+    }
+
+    fn from_master_key_material(material: &[u8; MASTER_KEY_MATERIAL_LEN]) -> Self {
+        let (client_key, rest) = material.split_at(KEY_LEN);
+        let (client_salt, rest) = rest.split_at(SALT_LEN);
+        let (server_key, server_salt) = rest.split_at(KEY_LEN);
+        Self {
+            client: MasterKey {
+                key: client_key.try_into().expect("correct length"),
+                salt: client_salt.try_into().expect("correct length"),
+            },
+            server: MasterKey {
+                key: server_key.try_into().expect("correct length"),
+                salt: server_salt.try_into().expect("correct length"),
+            },
+        }
+    }
+}
+
+/// The SRTP key derivation function from RFC 3711 section 4.3, with a key derivation rate of 0.
+/// The 96-bit GCM salt is zero-padded, as in libsrtp.
+fn derive_session_key(master: &MasterKey, label: u8, out: &mut [u8]) {
+    let mut iv = [0u8; 16];
+    iv[..SALT_LEN].copy_from_slice(&master.salt);
+    iv[7] ^= label;
+    let mut cipher = ctr::Ctr128BE::<Aes128>::new(&master.key.into(), &iv.into());
+    out.fill(0);
+    cipher.apply_keystream(out);
+}
+
+// Tracks the rollover counter (ROC) of one SSRC so we can compute the packet index.
+#[derive(Clone, Copy, Debug)]
+struct RolloverState {
+    roc: u32,
+    highest_seqnum: rtp::SequenceNumber,
+}
+
+impl RolloverState {
+    // Guesses the ROC of a received packet per RFC 3711 appendix A.
+    fn guess_roc(&self, seqnum: rtp::SequenceNumber) -> u32 {
+        const HALF: u16 = 1 << 15;
+        if self.highest_seqnum < HALF {
+            if seqnum > self.highest_seqnum && seqnum - self.highest_seqnum > HALF {
+                self.roc.wrapping_sub(1)
+            } else {
+                self.roc
+            }
+        } else if self.highest_seqnum - HALF > seqnum {
+            self.roc.wrapping_add(1)
+        } else {
+            self.roc
+        }
+    }
+
+    fn update(&mut self, roc: u32, seqnum: rtp::SequenceNumber) {
+        if roc > self.roc || (roc == self.roc && seqnum > self.highest_seqnum) {
+            self.roc = roc;
+            self.highest_seqnum = seqnum;
+        }
+    }
+}
+
+/// One direction of SRTP for one client.
+pub struct Context {
+    cipher: Aes128Gcm,
+    salt: [u8; SALT_LEN],
+    rollover_by_ssrc: HashMap<rtp::Ssrc, RolloverState>,
+}
+
+impl Context {
+    pub fn new(master: &MasterKey) -> Self {
+        let mut key = [0u8; KEY_LEN];
+        derive_session_key(master, RTP_ENCRYPTION_KEY_LABEL, &mut key);
+        let mut salt = [0u8; SALT_LEN];
+        derive_session_key(master, RTP_SALT_LABEL, &mut salt);
+        Self {
+            cipher: Aes128Gcm::new(&key.into()),
+            salt,
+            rollover_by_ssrc: HashMap::new(),
+        }
+    }
+
+    // See RFC 7714 section 8.1.
+    fn nonce(&self, ssrc: rtp::Ssrc, roc: u32, seqnum: rtp::SequenceNumber) -> [u8; SALT_LEN] {
+        let mut nonce = [0u8; SALT_LEN];
+        nonce[2..6].copy_from_slice(&ssrc.to_be_bytes());
+        nonce[6..10].copy_from_slice(&roc.to_be_bytes());
+        nonce[10..12].copy_from_slice(&seqnum.to_be_bytes());
+        for (n, s) in nonce.iter_mut().zip(self.salt.iter()) {
+            *n ^= s;
+        }
+        nonce
+    }
+
+    /// Decrypts a received SRTP packet, returning the header and the plaintext payload,
+    /// along with the ROC so the packet can be re-encrypted with the same index.
+    pub fn decrypt(&mut self, packet: &[u8]) -> Result<(rtp::Header, u32, Vec<u8>)> {
+        let header = rtp::Header::parse(packet).ok_or_else(|| anyhow!("Invalid RTP header"))?;
+        if packet.len() < header.len + TAG_LEN {
+            return Err(anyhow!("SRTP packet too short"));
+        }
+        // The first packet of an SSRC starts with a ROC of 0.
+        let roc = self
+            .rollover_by_ssrc
+            .get(&header.ssrc)
+            .map_or(0, |rollover| rollover.guess_roc(header.seqnum));
+        let nonce = self.nonce(header.ssrc, roc, header.seqnum);
+        let payload = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &packet[header.len..],
+                    aad: &packet[..header.len],
+                },
+            )
+            .map_err(|_| anyhow!("SRTP authentication failed"))?;
+        // Only trust the ROC guess once the packet has been authenticated.
+        self.rollover_by_ssrc
+            .entry(header.ssrc)
+            .or_insert(RolloverState {
+                roc,
+                highest_seqnum: header.seqnum,
+            })
+            .update(roc, header.seqnum);
+        Ok((header, roc, payload))
+    }
+
+    /// Encrypts a packet given its serialized header and plaintext payload.
+    pub fn encrypt(
+        &self,
+        header: &rtp::Header,
+        header_bytes: &[u8],
+        roc: u32,
+        payload: &[u8],
+    ) -> Result<Vec<u8>> {
+        let nonce = self.nonce(header.ssrc, roc, header.seqnum);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: payload,
+                    aad: header_bytes,
+                },
+            )
+            .map_err(|_| anyhow!("SRTP encryption failed"))?;
+        Ok([header_bytes, &ciphertext].concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    fn keys() -> MasterKeys {
+        let client_secret = StaticSecret::random_from_rng(OsRng);
+        let server_secret = StaticSecret::random_from_rng(OsRng);
+        let server_keys =
+            MasterKeys::negotiate(&server_secret, &PublicKey::from(&client_secret), b"extra");
+        let client_keys =
+            MasterKeys::negotiate(&client_secret, &PublicKey::from(&server_secret), b"extra");
+        assert_eq!(client_keys, server_keys);
+        assert_ne!(server_keys.client, server_keys.server);
+        server_keys
+    }
+
+    #[test]
+    fn round_trip() {
+        let keys = keys();
+        let sender = Context::new(&keys.server);
+        let mut receiver = Context::new(&keys.server);
+
+        let header_bytes = rtp::Header::write_simple(101, 65535, 1, 1);
+        let header = rtp::Header::parse(&header_bytes).unwrap();
+        let packet = sender.encrypt(&header, &header_bytes, 0, b"hello").unwrap();
+        let (decrypted_header, roc, payload) = receiver.decrypt(&packet).unwrap();
+        assert_eq!(header, decrypted_header);
+        assert_eq!(0, roc);
+        assert_eq!(b"hello", &payload[..]);
+
+        // The next packet wraps around.
+        let header_bytes = rtp::Header::write_simple(101, 0, 2, 1);
+        let header = rtp::Header::parse(&header_bytes).unwrap();
+        let packet = sender.encrypt(&header, &header_bytes, 1, b"again").unwrap();
+        let (_, roc, payload) = receiver.decrypt(&packet).unwrap();
+        assert_eq!(1, roc);
+        assert_eq!(b"again", &payload[..]);
+    }
+
+    #[test]
+    fn wrong_key_or_tampering_fails() {
+        let keys = keys();
+        let sender = Context::new(&keys.client);
+        let mut wrong_receiver = Context::new(&keys.server);
+        let mut receiver = Context::new(&keys.client);
+
+        let header_bytes = rtp::Header::write_simple(101, 1, 1, 1);
+        let header = rtp::Header::parse(&header_bytes).unwrap();
+        let mut packet = sender.encrypt(&header, &header_bytes, 0, b"hello").unwrap();
+        assert!(wrong_receiver.decrypt(&packet).is_err());
+
+        // Changing the authenticated header breaks it too.
+        packet[3] ^= 1;
+        assert!(receiver.decrypt(&packet).is_err());
+    }
+}
@@ -724,6 +724,29 @@ public class CallManager {
                                             int    status,
                                    @NonNull byte[] body)
     throws CallException
+  {
+    receivedHttpResponse(requestId, status, Collections.emptyList(), body);
+  }
+
+  /**
+   *
+   * Provides a HTTP response that has been received for a prior request
+   * to the RingRTC Call Manager for handling, including its headers
+   * (such as Retry-After).
+   *
+   * @param requestId       the Id of the request that the response belongs to
+   * @param status          the standard HTTP status value of the response
+   * @param headers         the headers of the response
+   * @param body            the body of the response
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void receivedHttpResponse(         long             requestId,
+                                            int              status,
+                                   @NonNull List<HttpHeader> headers,
+                                   @NonNull byte[]           body)
+    throws CallException
   {
     checkCallManagerExists();
 
@@ -731,6 +754,7 @@ public class CallManager {
     ringrtcReceivedHttpResponse(nativeCallManager,
                                 requestId,
                                 status,
+                                headers,
                                 body);
   }
 
@@ -845,6 +869,184 @@ public class CallManager {
     ringrtcHangup(nativeCallManager);
   }
 
+  /**
+   *
+   * Puts the active call on hold, or resumes it.
+   *
+   * Media stops flowing in both directions while the call is on hold,
+   * but the connection is kept. The remote side is notified with
+   * {@link CallEvent#REMOTE_HOLD} and {@link CallEvent#REMOTE_RESUME}.
+   *
+   * @param onHold  if true, then put the call on hold
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void setOnHold(boolean onHold)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "setOnHold(): " + onHold);
+    ringrtcSetOnHold(nativeCallManager, onHold);
+  }
+
+  /**
+   *
+   * Continues the active call as a group call.
+   *
+   * The remote peer is invited to the group call, which is then joined.
+   * The call ends with {@link CallEvent#ENDED_ESCALATED_TO_GROUP_CALL}
+   * once either side has joined, so media keeps flowing in the meantime.
+   *
+   * The remote peer calls this too, after
+   * {@link Observer#onGroupCallInvitation}.
+   *
+   * @param groupCall  the group call or call link call to continue in
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void escalateToGroupCall(@NonNull GroupCall groupCall)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "escalateToGroupCall():");
+    ringrtcEscalateToGroupCall(nativeCallManager, groupCall.clientId);
+  }
+
+  /**
+   *
+   * Hands the active call over to another of the user's devices.
+   *
+   * The remote peer is told to expect the target device, which gets
+   * {@link Observer#onCallTransferOffered} and then calls
+   * {@link #acceptCallTransfer}. Once it has taken over, this device's
+   * call ends with {@link CallEvent#ENDED_TRANSFERRED}.
+   *
+   * @param remoteUuid      the UUID of the remote user of the call
+   * @param targetDeviceId  the user's device to hand the call to
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void transferCall(@NonNull UUID    remoteUuid,
+                           @NonNull Integer targetDeviceId)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "transferCall(): " + targetDeviceId);
+    ringrtcTransferCall(nativeCallManager, Util.getBytesFromUuid(remoteUuid), targetDeviceId);
+  }
+
+  /**
+   *
+   * Takes over a call offered by another of the user's devices, by
+   * calling the remote peer with the same callId.
+   *
+   * The call then proceeds like one started with {@link #call}.
+   *
+   * @param remote         remote side of the call
+   * @param callId         callId from {@link Observer#onCallTransferOffered}
+   * @param callMediaType  used to specify an audio or video call
+   * @param localDeviceId  the local deviceId of the client
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void acceptCallTransfer(         Remote        remote,
+                                 @NonNull CallId        callId,
+                                 @NonNull CallMediaType callMediaType,
+                                 @NonNull Integer       localDeviceId)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "acceptCallTransfer(): " + callId);
+    ringrtcAcceptCallTransfer(nativeCallManager, remote, callId.longValue(), callMediaType.ordinal(), localDeviceId);
+  }
+
+  /**
+   *
+   * Enables or disables call waiting.
+   *
+   * When enabled, an incoming call that arrives during an accepted
+   * call is reported with {@link CallEvent#RECEIVED_CALL_WAITING} and
+   * then started like any other incoming call, instead of being
+   * rejected as busy. Accepting it puts the active call on hold.
+   *
+   * @param enabled  if true, then allow a second incoming call
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void setCallWaitingEnabled(boolean enabled)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "setCallWaitingEnabled(): " + enabled);
+    ringrtcSetCallWaitingEnabled(nativeCallManager, enabled);
+  }
+
+  /**
+   *
+   * Turns do not disturb on or off.
+   *
+   * While on, incoming calls and group rings from anyone not in the
+   * allow-lists are declined during quiet hours, without being reported
+   * to the application. Declined offers are reported with
+   * {@link CallEvent#RECEIVED_OFFER_DECLINED_BY_POLICY}.
+   *
+   * @param enabled                   if true, then apply the settings below
+   * @param quietHours                when calls don't ring at all, or null for never
+   * @param allowedRemotes            1:1 callers that always ring
+   * @param allowedUuids              group ring senders that always ring
+   * @param maxConcurrentGroupRings   how many group rings can be outstanding at once, or null for no limit
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void setDoNotDisturb(         boolean            enabled,
+                              @Nullable QuietHours         quietHours,
+                              @NonNull  Collection<Remote> allowedRemotes,
+                              @NonNull  Collection<UUID>   allowedUuids,
+                              @Nullable Integer            maxConcurrentGroupRings)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "setDoNotDisturb(): " + enabled);
+    ringrtcSetDoNotDisturb(nativeCallManager,
+                           enabled,
+                           quietHours != null,
+                           quietHours != null ? quietHours.startMinute : 0,
+                           quietHours != null ? quietHours.endMinute : 0,
+                           quietHours != null ? quietHours.utcOffsetMinutes : 0,
+                           allowedRemotes.toArray(new Remote[0]),
+                           Util.serializeFromUuids(allowedUuids),
+                           maxConcurrentGroupRings != null ? maxConcurrentGroupRings : -1);
+  }
+
+  /**
+   *
+   * Notification from application to put the active call on hold and
+   * resume the held call.
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void swapCalls()
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "swapCalls():");
+    ringrtcSwapCalls(nativeCallManager);
+  }
+
   /** Describes why a ring was cancelled. */
   public enum RingCancelReason {
     /** The user explicitly clicked "Decline". */
@@ -913,6 +1115,25 @@ public class CallManager {
     }
   }
 
+  /**
+   *
+   * A daily window during which do not disturb declines calls, in
+   * minutes since local midnight. If the start equals the end, the
+   * window covers the whole day.
+   *
+   */
+  public static class QuietHours {
+    public final int startMinute;
+    public final int endMinute;
+    public final int utcOffsetMinutes;
+
+    public QuietHours(int startMinute, int endMinute, int utcOffsetMinutes) {
+      this.startMinute      = startMinute;
+      this.endMinute        = endMinute;
+      this.utcOffsetMinutes = utcOffsetMinutes;
+    }
+  }
+
   static class Requests<T> {
     private long nextId = 1;
     @NonNull private LongSparseArray<ResponseHandler<T>> handlerById = new LongSparseArray<>();
@@ -1181,8 +1402,9 @@ public class CallManager {
    *
    * Possible (synthetic) failure codes include:
    * <ul>
-   *   <li>{@link PeekInfo#EXPIRED_CALL_LINK_STATUS}: the call link has expired or been revoked
+   *   <li>{@link PeekInfo#EXPIRED_CALL_LINK_STATUS}: the call link has expired
    *   <li>{@link PeekInfo#INVALID_CALL_LINK_STATUS}: the call link is invalid; it may have expired a long time ago
+   *   <li>{@link PeekInfo#REVOKED_CALL_LINK_STATUS}: the call link has been revoked
    * </ul>
    *
    * Will produce an "empty" {@link PeekInfo} if the link is valid but no call is active.
@@ -1210,6 +1432,96 @@ public class CallManager {
     ringrtcPeekCallLinkCall(nativeCallManager, requestId, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes());
   }
 
+  /**
+   *
+   * Keeps peeking a group call in the background until {@link #unwatchPeek} is called.
+   * Each change is reported via {@link Observer#onWatchedPeekChanged}, identified by the
+   * group ID.
+   *
+   * @param groupId          the ID of the group, used to identify the watch
+   * @param sfuUrl           the URL to use when accessing the SFU
+   * @param membershipProof  byte array containing the proof for accessing a specific group call
+   * @param groupMembers     a GroupMemberInfo object for each member in a group
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void watchGroupCallPeek(@NonNull byte[]                                groupId,
+                                 @NonNull String                                sfuUrl,
+                                 @NonNull byte[]                                membershipProof,
+                                 @NonNull Collection<GroupCall.GroupMemberInfo> groupMembers)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "watchGroupCallPeek():");
+
+    ringrtcWatchGroupCallPeek(nativeCallManager, groupId, sfuUrl, membershipProof, Util.serializeFromGroupMemberInfo(groupMembers));
+  }
+
+  /**
+   *
+   * Keeps peeking a call link's call in the background until {@link #unwatchPeek} is called.
+   * Each change is reported via {@link Observer#onWatchedPeekChanged}, identified by the
+   * call link's room ID.
+   *
+   * @param sfuUrl                     the URL to use when accessing the SFU
+   * @param authCredentialPresentation a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey                the root key for the call link
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void watchCallLinkPeek(@NonNull String          sfuUrl,
+                                @NonNull byte[]          authCredentialPresentation,
+                                @NonNull CallLinkRootKey linkRootKey)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "watchCallLinkPeek():");
+
+    ringrtcWatchCallLinkPeek(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes());
+  }
+
+  /**
+   *
+   * Stops watching a call started with {@link #watchGroupCallPeek} or {@link #watchCallLinkPeek}.
+   *
+   * @param id  the group ID or call link room ID of the watched call
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void unwatchPeek(@NonNull byte[] id)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "unwatchPeek():");
+
+    ringrtcUnwatchPeek(nativeCallManager, id);
+  }
+
+  /**
+   *
+   * Peeks a watched call as soon as possible, such as after receiving a group call update.
+   *
+   * @param id  the group ID or call link room ID of the watched call
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void refreshPeek(@NonNull byte[] id)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "refreshPeek():");
+
+    ringrtcRefreshPeek(nativeCallManager, id);
+  }
+
   /**
    *
    * Creates and returns a GroupCall object.
@@ -1527,6 +1839,40 @@ public class CallManager {
     observer.onCallConcluded(remote);
   }
 
+  @CalledByNative
+  private void onCallRecord(Remote remote, @NonNull CallRecord record) {
+    Log.i(TAG, "onCallRecord(): " + record.getOutcome());
+    observer.onCallRecord(remote, record);
+  }
+
+  @CalledByNative
+  private void onGroupRingRecord(@NonNull GroupRingRecord record) {
+    Log.i(TAG, "onGroupRingRecord(): " + record.getOutcome());
+    observer.onGroupRingRecord(record);
+  }
+
+  @CalledByNative
+  private void onGroupCallInvitation(Remote remote, long callId, @Nullable byte[] groupId, @Nullable byte[] callLinkRootKey, @Nullable Long callLinkEpoch) {
+    Log.i(TAG, "onGroupCallInvitation():");
+
+    CallLinkRootKey rootKey = null;
+    if (callLinkRootKey != null) {
+      try {
+        rootKey = new CallLinkRootKey(callLinkRootKey);
+      } catch (CallException e) {
+        Log.w(TAG, "Ignoring group call invitation with an invalid call link root key", e);
+        return;
+      }
+    }
+    observer.onGroupCallInvitation(remote, new CallId(callId), groupId, rootKey, callLinkEpoch);
+  }
+
+  @CalledByNative
+  private void onCallTransferOffered(@NonNull byte[] remoteUserId, long callId) {
+    Log.i(TAG, "onCallTransferOffered():");
+    observer.onCallTransferOffered(Util.getUuidFromBytes(remoteUserId), new CallId(callId));
+  }
+
   @CalledByNative
   private void onSendOffer(long callId, Remote remote, int remoteDeviceId, boolean broadcast, @NonNull byte[] opaque, CallMediaType callMediaType) {
     Log.i(TAG, "onSendOffer():");
@@ -1605,6 +1951,12 @@ public class CallManager {
     }
   }
 
+  @CalledByNative
+  private void handleWatchedPeekChanged(@NonNull byte[] id, @NonNull HttpResult<PeekInfo> result) {
+    Log.i(TAG, "handleWatchedPeekChanged():");
+    observer.onWatchedPeekChanged(id, result);
+  }
+
   @CalledByNative
   private void handleCallLinkResponse(long requestId, HttpResult<CallLinkState> response) {
     if (!this.callLinkRequests.resolve(requestId, response)) {
@@ -1717,6 +2069,17 @@ public class CallManager {
     groupCall.handleRaisedHands(raisedHands);
   }
 
+  @CalledByNative
+  private void handlePresenterChanged(long clientId, Long currentPresenter) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handlePresenterChanged(currentPresenter);
+  }
+
   @CalledByNative
   private void handleJoinStateChanged(long clientId, GroupCall.JoinState joinState, Long demuxId) {
     Log.i(TAG, "handleJoinStateChanged():");
@@ -1770,6 +2133,17 @@ public class CallManager {
     groupCall.handlePeekChanged(info);
   }
 
+  @CalledByNative
+  private void handleCallSummary(long clientId, GroupCall.CallSummary callSummary) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleCallSummary(callSummary);
+  }
+
   @CalledByNative
   private void handleEnded(long clientId, GroupCall.GroupCallEndReason reason) {
     Log.i(TAG, "handleEnded():");
@@ -1981,7 +2355,25 @@ public class CallManager {
     RECEIVED_OFFER_WHILE_ACTIVE,
 
     /** Received an offer while already handling an active call and glare was detected. */
-    RECEIVED_OFFER_WITH_GLARE;
+    RECEIVED_OFFER_WITH_GLARE,
+
+    /** Received an offer while in an accepted call; the new call is waiting to be accepted. */
+    RECEIVED_CALL_WAITING,
+
+    /** The remote side put the call on hold. */
+    REMOTE_HOLD,
+
+    /** The remote side took the call off hold. */
+    REMOTE_RESUME,
+
+    /** The call ended because it continues as a group call, which has now been joined. */
+    ENDED_ESCALATED_TO_GROUP_CALL,
+
+    /** The call ended because another of the user's devices took it over. */
+    ENDED_TRANSFERRED,
+
+    /** Received an offer that the incoming call policy declined without ringing. */
+    RECEIVED_OFFER_DECLINED_BY_POLICY;
 
     @CalledByNative
     static CallEvent fromNativeIndex(int nativeIndex) {
@@ -2104,6 +2496,8 @@ public class CallManager {
     BUSY_ON_ANOTHER_DEVICE,
     /** The sender cancelled the ring request. */
     CANCELLED_BY_RINGER,
+    /** The incoming call policy declined the ring without ringing. */
+    DECLINED_BY_POLICY,
   }
 
   /**
@@ -2177,6 +2571,60 @@ public class CallManager {
      */
     void onCallConcluded(Remote remote);
 
+    /**
+     *
+     * Notification of how a 1:1 call went, for call history
+     *
+     * Sent once per call, just before {@link #onCallConcluded}.
+     *
+     * @param remote  remote peer of the call
+     * @param record  the outcome, timing, and setup metrics of the call
+     *
+     */
+    void onCallRecord(Remote remote, @NonNull CallRecord record);
+
+    /**
+     *
+     * Notification of how a ring for a group call went, for call history
+     *
+     * Sent once the ring is over, whether or not it was accepted.
+     *
+     * @param record  the outcome and timing of the ring
+     *
+     */
+    void onGroupRingRecord(@NonNull GroupRingRecord record);
+
+    /**
+     *
+     * Notification that the remote peer wants to continue the call as a
+     * group call
+     *
+     * To go along, create a group call for the group or call link and pass
+     * it to {@link CallManager#escalateToGroupCall}.
+     *
+     * @param remote           remote peer of the call
+     * @param callId           callId for the call
+     * @param groupId          the group to join, or null for a call link
+     * @param callLinkRootKey  the call link to join, or null for a group
+     * @param callLinkEpoch    the call link's epoch, if it has one
+     *
+     */
+    void onGroupCallInvitation(Remote remote, CallId callId, @Nullable byte[] groupId, @Nullable CallLinkRootKey callLinkRootKey, @Nullable Long callLinkEpoch);
+
+    /**
+     *
+     * Notification that another of the user's devices wants to hand over
+     * its call
+     *
+     * To take the call, pass the callId to
+     * {@link CallManager#acceptCallTransfer}.
+     *
+     * @param remoteUuid  the UUID of the remote user of the call
+     * @param callId      callId for the call
+     *
+     */
+    void onCallTransferOffered(@NonNull UUID remoteUuid, CallId callId);
+
     /**
      *
      * Notification that an offer is ready to be sent
@@ -2290,6 +2738,15 @@ public class CallManager {
      * @param update  the updated state to handle
      */
     void onGroupCallRingUpdate(@NonNull byte[] groupId, long ringId, @NonNull UUID sender, RingUpdate update);
+
+    /**
+     *
+     * The peek result of a watched call has changed, or it was peeked for the first time.
+     *
+     * @param id      the group ID or call link room ID passed to or derived by the watch call
+     * @param result  the peek info, or the failure status code
+     */
+    void onWatchedPeekChanged(@NonNull byte[] id, @NonNull HttpResult<PeekInfo> result);
   }
 
   /**
@@ -2354,6 +2811,42 @@ public class CallManager {
     void ringrtcHangup(long nativeCallManager)
     throws CallException;
 
+  private native
+    void ringrtcSetOnHold(long nativeCallManager, boolean onHold)
+    throws CallException;
+
+  private native
+    void ringrtcEscalateToGroupCall(long nativeCallManager, long clientId)
+    throws CallException;
+
+  private native
+    void ringrtcTransferCall(long nativeCallManager, byte[] remoteUserId, int targetDeviceId)
+    throws CallException;
+
+  private native
+    void ringrtcAcceptCallTransfer(long nativeCallManager, Remote remote, long callId, int callMediaType, int localDeviceId)
+    throws CallException;
+
+  private native
+    void ringrtcSetCallWaitingEnabled(long nativeCallManager, boolean enabled)
+    throws CallException;
+
+  private native
+    void ringrtcSetDoNotDisturb(long     nativeCallManager,
+                                boolean  enabled,
+                                boolean  hasQuietHours,
+                                int      startMinute,
+                                int      endMinute,
+                                int      utcOffsetMinutes,
+                                Remote[] allowedRemotes,
+                                byte[]   allowedUuids,
+                                int      maxConcurrentGroupRings)
+    throws CallException;
+
+  private native
+    void ringrtcSwapCalls(long nativeCallManager)
+    throws CallException;
+
   private native
     void ringrtcCancelGroupRing(long nativeCallManager, byte[] groupId, long ringId, int reason)
     throws CallException;
@@ -2411,10 +2904,11 @@ public class CallManager {
     throws CallException;
 
   private native
-    void ringrtcReceivedHttpResponse(long   nativeCallManager,
-                                     long   requestId,
-                                     int    status,
-                                     byte[] body)
+    void ringrtcReceivedHttpResponse(long             nativeCallManager,
+                                     long             requestId,
+                                     int              status,
+                                     List<HttpHeader> headers,
+                                     byte[]           body)
     throws CallException;
 
   private native
@@ -2513,4 +3007,29 @@ public class CallManager {
                                  byte[] authCredentialPresentation,
                                  byte[] rootKeyBytes)
     throws CallException;
+
+  private native
+    void ringrtcWatchGroupCallPeek(long   nativeCallManager,
+                                   byte[] groupId,
+                                   String sfuUrl,
+                                   byte[] membershipProof,
+                                   byte[] serializedGroupMembers)
+    throws CallException;
+
+  private native
+    void ringrtcWatchCallLinkPeek(long   nativeCallManager,
+                                  String sfuUrl,
+                                  byte[] authCredentialPresentation,
+                                  byte[] rootKeyBytes)
+    throws CallException;
+
+  private native
+    void ringrtcUnwatchPeek(long   nativeCallManager,
+                            byte[] id)
+    throws CallException;
+
+  private native
+    void ringrtcRefreshPeek(long   nativeCallManager,
+                            byte[] id)
+    throws CallException;
 }
@@ -0,0 +1,163 @@
+/*
+ * Copyright 2024 Signal Messenger, LLC
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+package org.signal.ringrtc;
+
+import androidx.annotation.NonNull;
+import androidx.annotation.Nullable;
+import java.time.Duration;
+import java.time.Instant;
+
+/**
+ *
+ * How a 1:1 call went, reported once the call has concluded.
+ *
+ */
+public class CallRecord {
+  /** The order must match the Rust CallOutcome enum. */
+  public enum Outcome {
+    ACCEPTED,
+    MISSED,
+    UNANSWERED,
+    DECLINED,
+    CANCELLED,
+    ACCEPTED_ON_ANOTHER_DEVICE,
+    DECLINED_ON_ANOTHER_DEVICE,
+    DECLINED_BY_POLICY,
+    BUSY,
+    NEED_PERMISSION,
+    SUPERSEDED,
+    ESCALATED_TO_GROUP_CALL,
+    TRANSFERRED,
+    FAILED;
+
+    @NonNull
+    static Outcome fromNativeIndex(int nativeIndex) {
+      Outcome[] values = values();
+      return nativeIndex >= 0 && nativeIndex < values.length ? values[nativeIndex] : FAILED;
+    }
+  }
+
+  /**
+   * How long setting up the call took, as offsets from when this device
+   * started handling it. Milestones that weren't reached are null.
+   */
+  public static class SetupMetrics {
+    /** Incoming calls only: how long the offer took to arrive. */
+    @Nullable public final Duration offerAge;
+    @Nullable public final Duration offerSent;
+    @Nullable public final Duration answerSent;
+    @Nullable public final Duration answerReceived;
+    @Nullable public final Duration firstIceCandidateSent;
+    @Nullable public final Duration firstIceCandidateReceived;
+    @Nullable public final Duration iceConnected;
+    @Nullable public final Duration accepted;
+    @Nullable public final Duration firstMedia;
+
+    private SetupMetrics(@NonNull long[] millis) {
+      this.offerAge                  = fromMillis(millis[0]);
+      this.offerSent                 = fromMillis(millis[1]);
+      this.answerSent                = fromMillis(millis[2]);
+      this.answerReceived            = fromMillis(millis[3]);
+      this.firstIceCandidateSent     = fromMillis(millis[4]);
+      this.firstIceCandidateReceived = fromMillis(millis[5]);
+      this.iceConnected              = fromMillis(millis[6]);
+      this.accepted                  = fromMillis(millis[7]);
+      this.firstMedia                = fromMillis(millis[8]);
+    }
+
+    @Nullable
+    private static Duration fromMillis(long millis) {
+      return millis < 0 ? null : Duration.ofMillis(millis);
+    }
+  }
+
+  @NonNull
+  private final CallId callId;
+  private final boolean incoming;
+  private final boolean video;
+  @NonNull
+  private final Instant started;
+  @Nullable
+  private final Instant answered;
+  @NonNull
+  private final Instant ended;
+  @NonNull
+  private final Outcome outcome;
+  @Nullable
+  private final Integer answeredByDeviceId;
+  @NonNull
+  private final SetupMetrics setup;
+
+  @CalledByNative
+  private CallRecord(long    callId,
+                     boolean incoming,
+                     boolean video,
+                     long    startedMillis,
+                     long    answeredMillis,
+                     long    endedMillis,
+                     int     rawOutcome,
+                     int     answeredByDeviceId,
+                     long[]  setupMillis) {
+    this.callId             = new CallId(callId);
+    this.incoming           = incoming;
+    this.video              = video;
+    this.started            = Instant.ofEpochMilli(startedMillis);
+    this.answered           = answeredMillis < 0 ? null : Instant.ofEpochMilli(answeredMillis);
+    this.ended              = Instant.ofEpochMilli(endedMillis);
+    this.outcome            = Outcome.fromNativeIndex(rawOutcome);
+    this.answeredByDeviceId = answeredByDeviceId < 0 ? null : answeredByDeviceId;
+    this.setup              = new SetupMetrics(setupMillis);
+  }
+
+  @NonNull
+  public CallId getCallId() {
+    return callId;
+  }
+
+  public boolean isIncoming() {
+    return incoming;
+  }
+
+  public boolean isVideo() {
+    return video;
+  }
+
+  /** When this device started handling the call. */
+  @NonNull
+  public Instant getStarted() {
+    return started;
+  }
+
+  /** When the call was accepted, if it was. */
+  @Nullable
+  public Instant getAnswered() {
+    return answered;
+  }
+
+  @NonNull
+  public Instant getEnded() {
+    return ended;
+  }
+
+  @NonNull
+  public Outcome getOutcome() {
+    return outcome;
+  }
+
+  /**
+   * The device that answered, if any: the callee's device for outgoing
+   * calls, and this or another of the user's devices for incoming calls.
+   */
+  @Nullable
+  public Integer getAnsweredByDeviceId() {
+    return answeredByDeviceId;
+  }
+
+  @NonNull
+  public SetupMetrics getSetup() {
+    return setup;
+  }
+}
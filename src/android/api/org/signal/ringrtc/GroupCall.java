@@ -63,6 +63,8 @@ public final class GroupCall {
     @NonNull  private LongSparseArray<RemoteDeviceState> remoteDeviceStates;
 
     @Nullable private PeekInfo                           peekInfo;
+    @Nullable private Long                               currentPresenter;
+    @Nullable private CallSummary                        callSummary;
 
     @Nullable private AudioSource                        outgoingAudioSource;
     @Nullable private AudioTrack                         outgoingAudioTrack;
@@ -358,6 +360,26 @@ public final class GroupCall {
         return this.peekInfo;
     }
 
+    /**
+     * Returns the demux ID of the one device (possibly the local one) that
+     * everyone agrees is presenting, or null if no one is.
+     */
+    @Nullable
+    public Long getCurrentPresenter()
+    {
+        return this.currentPresenter;
+    }
+
+    /**
+     * Returns statistics about the whole call. Only set once the call
+     * has ended, so it can be read from onEnded().
+     */
+    @Nullable
+    public CallSummary getCallSummary()
+    {
+        return this.callSummary;
+    }
+
     /**
      *
      * Mute (or unmute) outgoing audio. This adjusts the outgoing audio
@@ -635,6 +657,24 @@ public final class GroupCall {
         ringrtcRaiseHand(nativeCallManager, this.clientId, raise);
     }
 
+    /**
+     *
+     * Ask to become the presenter. If the presenter changes as a result,
+     * that is reported via onPresenterChanged(); an ignored request, or one
+     * made before joining, is not reported at all.
+     *
+     * @param takeOver Set to true to take over from whoever is presenting;
+     *                 otherwise the request is ignored if someone else is.
+     *
+     * @throws CallException for native code failures
+     */
+    public void requestToPresent(boolean takeOver)
+        throws CallException {
+        Log.i(TAG, "requestToPresent(): takeOver: " + takeOver);
+
+        ringrtcRequestToPresent(nativeCallManager, this.clientId, takeOver);
+    }
+
     /**
      *
      * Callback from RingRTC when the group call object needs an updated
@@ -757,6 +797,11 @@ public final class GroupCall {
         this.observer.onRaisedHands(this, raisedHands);
     }
 
+    void handlePresenterChanged(@Nullable Long currentPresenter) {
+        this.currentPresenter = currentPresenter;
+        this.observer.onPresenterChanged(this);
+    }
+
     /**
      *
      * Callback from RingRTC when the remote device states have changed.
@@ -826,6 +871,10 @@ public final class GroupCall {
         this.observer.onPeekChanged(this);
     }
 
+    void handleCallSummary(@NonNull CallSummary callSummary) {
+        this.callSummary = callSummary;
+    }
+
     /**
      *
      * Callback from RingRTC when the group call ends. Called via the
@@ -969,7 +1018,28 @@ public final class GroupCall {
         SERVER_CHANGED_DEMUXID,
 
         /** The SFU reported that the group call is full. */
-        HAS_MAX_DEVICES;
+        HAS_MAX_DEVICES,
+
+        /** The call link has expired. */
+        CALL_LINK_EXPIRED,
+
+        /** The call link is not valid. */
+        CALL_LINK_INVALID,
+
+        /** The call link has been revoked by its admin. */
+        CALL_LINK_REVOKED,
+
+        /** The credentials used to join have expired. */
+        AUTH_EXPIRED,
+
+        /** The SFU is limiting how often the call can be joined. */
+        RATE_LIMITED,
+
+        /** The SFU is temporarily unavailable. */
+        SFU_UNAVAILABLE,
+
+        /** The SFU sent a response that could not be understood. */
+        SFU_MALFORMED_RESPONSE;
 
         @CalledByNative
         static GroupCallEndReason fromNativeIndex(int nativeIndex) {
@@ -1208,6 +1278,67 @@ public final class GroupCall {
         }
     }
 
+    /**
+     * How long one remote user was the primary speaker.
+     */
+    public static class SpeakingTime {
+        public @NonNull byte[] userId;
+        public long speakingTimeMillis;
+
+        public SpeakingTime(@NonNull byte[] userId, long speakingTimeMillis) {
+            this.userId = userId;
+            this.speakingTimeMillis = speakingTimeMillis;
+        }
+    }
+
+    /**
+     * Statistics about a whole group call, available via getCallSummary()
+     * once the call has ended.
+     */
+    public static class CallSummary {
+        public long callDurationMillis;
+        public long joinDurationMillis;
+        public int peakDeviceCount;
+        public int uniqueUserCount;
+        public @NonNull List<SpeakingTime> speakingTimes;
+        public int reactionsSent;
+        public int reactionsReceived;
+        public int raisedHandCount;
+        public long averageSendBps;
+        public long averageReceiveBps;
+        public float sendPacketsLostPct;
+        public float receivePacketsLostPct;
+        public int reconnectCount;
+
+        public CallSummary(long callDurationMillis,
+                           long joinDurationMillis,
+                           int peakDeviceCount,
+                           int uniqueUserCount,
+                           @NonNull List<SpeakingTime> speakingTimes,
+                           int reactionsSent,
+                           int reactionsReceived,
+                           int raisedHandCount,
+                           long averageSendBps,
+                           long averageReceiveBps,
+                           float sendPacketsLostPct,
+                           float receivePacketsLostPct,
+                           int reconnectCount) {
+            this.callDurationMillis = callDurationMillis;
+            this.joinDurationMillis = joinDurationMillis;
+            this.peakDeviceCount = peakDeviceCount;
+            this.uniqueUserCount = uniqueUserCount;
+            this.speakingTimes = speakingTimes;
+            this.reactionsSent = reactionsSent;
+            this.reactionsReceived = reactionsReceived;
+            this.raisedHandCount = raisedHandCount;
+            this.averageSendBps = averageSendBps;
+            this.averageReceiveBps = averageReceiveBps;
+            this.sendPacketsLostPct = sendPacketsLostPct;
+            this.receivePacketsLostPct = receivePacketsLostPct;
+            this.reconnectCount = reconnectCount;
+        }
+    }
+
     /**
      * The client must provide an observer for each group call object
      * which is used to convey callbacks and notifications from
@@ -1266,6 +1397,12 @@ public final class GroupCall {
          */
         void onRaisedHands(GroupCall groupCall, List<Long> raisedHands);
 
+        /**
+         * Notification that the current presenter has changed.
+         * Use getCurrentPresenter() to find out who it is.
+         */
+        void onPresenterChanged(GroupCall groupCall);
+
         /**
          * Notification that the remote device states have changed.
          */
@@ -1416,4 +1553,10 @@ public final class GroupCall {
                               long clientId,
                               boolean raise)
         throws CallException;
+
+    private native
+        void ringrtcRequestToPresent(long nativeCallManager,
+                                     long clientId,
+                                     boolean takeOver)
+        throws CallException;
 }
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2024 Signal Messenger, LLC
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+package org.signal.ringrtc;
+
+import androidx.annotation.NonNull;
+import java.time.Instant;
+import java.util.UUID;
+
+/**
+ *
+ * How a ring for a group call went, reported once it's over.
+ *
+ */
+public class GroupRingRecord {
+  /** The order must match the Rust GroupRingOutcome enum. */
+  public enum Outcome {
+    ACCEPTED,
+    ACCEPTED_ON_ANOTHER_DEVICE,
+    DECLINED,
+    DECLINED_ON_ANOTHER_DEVICE,
+    DECLINED_BY_POLICY,
+    BUSY,
+    BUSY_ON_ANOTHER_DEVICE,
+    CANCELLED_BY_RINGER,
+    EXPIRED;
+
+    @NonNull
+    static Outcome fromNativeIndex(int nativeIndex) {
+      Outcome[] values = values();
+      return nativeIndex >= 0 && nativeIndex < values.length ? values[nativeIndex] : EXPIRED;
+    }
+  }
+
+  @NonNull
+  private final byte[] groupId;
+  private final long ringId;
+  @NonNull
+  private final UUID sender;
+  @NonNull
+  private final Instant received;
+  @NonNull
+  private final Instant ended;
+  @NonNull
+  private final Outcome outcome;
+
+  @CalledByNative
+  private GroupRingRecord(@NonNull byte[] groupId,
+                          long            ringId,
+                          @NonNull byte[] senderId,
+                          long            receivedMillis,
+                          long            endedMillis,
+                          int             rawOutcome) {
+    this.groupId  = groupId;
+    this.ringId   = ringId;
+    this.sender   = Util.getUuidFromBytes(senderId);
+    this.received = Instant.ofEpochMilli(receivedMillis);
+    this.ended    = Instant.ofEpochMilli(endedMillis);
+    this.outcome  = Outcome.fromNativeIndex(rawOutcome);
+  }
+
+  @NonNull
+  public byte[] getGroupId() {
+    return groupId;
+  }
+
+  public long getRingId() {
+    return ringId;
+  }
+
+  @NonNull
+  public UUID getSender() {
+    return sender;
+  }
+
+  @NonNull
+  public Instant getReceived() {
+    return received;
+  }
+
+  @NonNull
+  public Instant getEnded() {
+    return ended;
+  }
+
+  @NonNull
+  public Outcome getOutcome() {
+    return outcome;
+  }
+}
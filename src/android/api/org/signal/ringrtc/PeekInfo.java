@@ -20,7 +20,7 @@ public final class PeekInfo {
   // These "synthetic" status codes match up with Rust's lite::http::ResponseStatus.
 
   /**
-   * As a peek result, indicates that a call link has expired or been revoked.
+   * As a peek result, indicates that a call link has expired.
    */
   public static final short EXPIRED_CALL_LINK_STATUS = 703;
 
@@ -31,6 +31,11 @@ public final class PeekInfo {
    */
   public static final short INVALID_CALL_LINK_STATUS = 704;
 
+  /**
+   * As a peek result, indicates that a call link has been revoked by its admin.
+   */
+  public static final short REVOKED_CALL_LINK_STATUS = 705;
+
   @NonNull
   private static final String TAG = PeekInfo.class.getSimpleName();
 
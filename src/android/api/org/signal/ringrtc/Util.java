@@ -28,6 +28,19 @@ public final class Util {
         return new UUID(high, low);
     }
 
+    // Convert a collection of UUIDs to a byte[] using 16-byte chunks.
+    public static byte[] serializeFromUuids(@NonNull Collection<UUID> uuids) {
+        byte[] serializedUuids = new byte[uuids.size() * 16];
+        int position = 0;
+
+        for (UUID uuid : uuids) {
+            System.arraycopy(getBytesFromUuid(uuid), 0, serializedUuids, position, 16);
+            position += 16;
+        }
+
+        return serializedUuids;
+    }
+
     // Convert an array of GroupMemberInfo classes to a byte[] using 32-byte chunks.
     public static byte[] serializeFromGroupMemberInfo(@NonNull Collection<GroupCall.GroupMemberInfo> groupMembers) {
         if (groupMembers != null && groupMembers.size() > 0) {
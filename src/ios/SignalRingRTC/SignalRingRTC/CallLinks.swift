@@ -30,7 +30,7 @@ public struct CallLinkRootKey: CustomStringConvertible {
         self.bytes = bytes
     }
 
-    private init(validatedBytes bytes: rtc_Bytes) {
+    fileprivate init(validatedBytes bytes: rtc_Bytes) {
         self.bytes = bytes.toData()!
     }
 
@@ -77,6 +77,47 @@ public struct CallLinkRootKey: CustomStringConvertible {
     }
 }
 
+/// Everything in a shareable call link, e.g. `https://signal.link/call/#key=bcdf-ghkm-...`.
+public struct CallLinkUrl: CustomStringConvertible {
+    public var rootKey: CallLinkRootKey
+    /// Present for links that have one; older links don't.
+    public var epoch: UInt32?
+
+    public init(rootKey: CallLinkRootKey, epoch: UInt32? = nil) {
+        self.rootKey = rootKey
+        self.epoch = epoch
+    }
+
+    /// Parses a shareable call link URL, such as one pasted by the user.
+    ///
+    /// Surrounding and embedded whitespace, letter case, and missing separators in the key are
+    /// tolerated. Throws if the URL isn't a valid call link.
+    public init(_ string: String) throws {
+        var result: Self? = nil
+        let success = rtc_calllinks_CallLinkUrl_parse(string, &result) { resultOpaquePtr, parsedBytes, parsedEpoch in
+            resultOpaquePtr!.assumingMemoryBound(to: Optional<Self>.self).pointee = Self(rootKey: CallLinkRootKey(validatedBytes: parsedBytes), epoch: parsedEpoch.asUInt32())
+        }
+        guard success, let result else {
+            throw CallLinkRootKey.ValidationError()
+        }
+        self = result
+    }
+
+    public var description: String {
+        var result: String? = nil
+        let rtcEpoch = rtc_OptionalU32(value: epoch ?? 0, valid: epoch != nil)
+        let errorCStr = rootKey.bytes.withRtcBytes { bytes in
+            rtc_calllinks_CallLinkUrl_format(bytes, rtcEpoch, &result) { resultOpaquePtr, rtcString in
+                resultOpaquePtr!.assumingMemoryBound(to: Optional<String>.self).pointee = rtcString.toString()
+            }
+        }
+        if let errorCStr {
+            fail(String(cString: errorCStr))
+        }
+        return result!
+    }
+}
+
 public struct CallLinkState {
     public enum Restrictions {
       case none, adminApproval, unknown
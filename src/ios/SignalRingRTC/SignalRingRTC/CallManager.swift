@@ -77,6 +77,18 @@ public enum CallManagerEvent: Int32 {
     case receivedOfferWhileActive
     /// Received an offer while already handling an active call and glare was detected.
     case receivedOfferWithGlare
+    /// Received an offer while in an accepted call; the new call is waiting to be accepted.
+    case receivedCallWaiting
+    /// The remote side put the call on hold.
+    case remoteHold
+    /// The remote side took the call off hold.
+    case remoteResume
+    /// The call ended because it continues as a group call, which has now been joined.
+    case endedEscalatedToGroupCall
+    /// The call ended because another of the user's devices took it over.
+    case endedTransferred
+    /// Received an offer that the incoming call policy declined without ringing.
+    case receivedOfferDeclinedByPolicy
 }
 
 // In sync with WebRTC's PeerConnection.AdapterType.
@@ -116,6 +128,131 @@ public struct NetworkRoute {
     }
 }
 
+/// What the remote peer of a 1:1 call wants to continue the call as.
+@available(iOSApplicationExtension, unavailable)
+public enum GroupCallInvitation {
+    /// A Signal group call.
+    case group(groupId: Data)
+    /// A call link call.
+    case callLink(CallLinkUrl)
+}
+
+/// A daily window during which do not disturb declines calls, in minutes since local midnight.
+/// If the start equals the end, the window covers the whole day.
+@available(iOSApplicationExtension, unavailable)
+public struct QuietHours {
+    public let startMinute: UInt16
+    public let endMinute: UInt16
+    public let utcOffsetMinutes: Int32
+
+    public init(startMinute: UInt16, endMinute: UInt16, utcOffsetMinutes: Int32) {
+        self.startMinute = startMinute
+        self.endMinute = endMinute
+        self.utcOffsetMinutes = utcOffsetMinutes
+    }
+}
+
+/// How a 1:1 call ended.
+@available(iOSApplicationExtension, unavailable)
+public enum CallOutcome: Int32 {
+    /// The call was accepted, by this device for incoming calls or by the callee for outgoing calls.
+    case accepted = 0
+    /// Incoming calls only: the caller gave up, or the call timed out, before anyone answered.
+    case missed
+    /// Outgoing calls only: the callee didn't answer in time.
+    case unanswered
+    /// The call was declined, by this device for incoming calls or by the callee for outgoing calls.
+    case declined
+    /// Outgoing calls only: this device hung up before the callee answered.
+    case cancelled
+    /// Incoming calls only: another of the user's devices answered.
+    case acceptedOnAnotherDevice
+    /// Incoming calls only: another of the user's devices declined.
+    case declinedOnAnotherDevice
+    /// Incoming calls only: the incoming call policy declined the call.
+    case declinedByPolicy
+    /// The callee, or this device for incoming calls, was on another call.
+    case busy
+    /// Outgoing calls only: the callee hasn't given permission to be called.
+    case needPermission
+    /// The call was replaced by another call with the same peer, because of glare or a recall.
+    case superseded
+    /// The call was accepted and then continued as a group call.
+    case escalatedToGroupCall
+    /// The call was accepted and then continued on another of the user's devices.
+    case transferred
+    /// The call failed before it was accepted.
+    case failed
+}
+
+/// How long setting up a 1:1 call took, in milliseconds from when this device started handling
+/// the call. Milestones that weren't reached are nil.
+@available(iOSApplicationExtension, unavailable)
+public struct CallSetupMetrics {
+    /// Incoming calls only: how long the offer took to arrive.
+    public let offerAgeMillis: UInt32?
+    public let offerSentMillis: UInt32?
+    public let answerSentMillis: UInt32?
+    public let answerReceivedMillis: UInt32?
+    public let firstIceCandidateSentMillis: UInt32?
+    public let firstIceCandidateReceivedMillis: UInt32?
+    public let iceConnectedMillis: UInt32?
+    public let acceptedMillis: UInt32?
+    public let firstMediaMillis: UInt32?
+}
+
+/// How a 1:1 call went, reported once the call has concluded.
+@available(iOSApplicationExtension, unavailable)
+public struct CallRecord {
+    public let callId: UInt64
+    public let isIncoming: Bool
+    public let isVideo: Bool
+    /// When this device started handling the call.
+    public let started: Date
+    /// When the call was accepted, if it was.
+    public let answered: Date?
+    public let ended: Date
+    public let outcome: CallOutcome
+    /// The device that answered, if any: the callee's device for outgoing calls, and this or
+    /// another of the user's devices for incoming calls.
+    public let answeredByDeviceId: UInt32?
+    public let setup: CallSetupMetrics
+}
+
+/// How a ring for a group call ended.
+@available(iOSApplicationExtension, unavailable)
+public enum GroupRingOutcome: Int32 {
+    /// This device joined the call.
+    case accepted = 0
+    /// Another of the user's devices joined the call.
+    case acceptedOnAnotherDevice
+    /// The user declined the ring on this device.
+    case declined
+    /// The user declined the ring on another device.
+    case declinedOnAnotherDevice
+    /// The incoming call policy declined the ring.
+    case declinedByPolicy
+    /// This device was on another call.
+    case busy
+    /// Another of the user's devices was on another call.
+    case busyOnAnotherDevice
+    /// The sender cancelled the ring.
+    case cancelledByRinger
+    /// Nobody responded before the ring expired.
+    case expired
+}
+
+/// How a ring for a group call went, reported once it's over.
+@available(iOSApplicationExtension, unavailable)
+public struct GroupRingRecord {
+    public let groupId: Data
+    public let ringId: Int64
+    public let sender: UUID
+    public let received: Date
+    public let ended: Date
+    public let outcome: GroupRingOutcome
+}
+
 /// The data mode allows the client to limit the media bandwidth used.
 @available(iOSApplicationExtension, unavailable)
 public enum DataMode: Int32 {
@@ -173,6 +310,8 @@ public enum RingUpdate: Int32 {
     case busyOnAnotherDevice
     /// The sender cancelled the ring request.
     case cancelledByRinger
+    /// The incoming call policy declined the ring without ringing.
+    case declinedByPolicy
 }
 
 /// Class to wrap the group call dictionary so group call objects can reference
@@ -227,6 +366,38 @@ public protocol CallManagerDelegate: AnyObject {
     @MainActor
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onLowBandwidthForVideoFor call: CallManagerDelegateCallType, recovered: Bool)
 
+    /**
+     * The remote peer wants to continue the call as a group call.
+     *
+     * To go along, create a group call for the invitation and pass it to escalateToGroupCall().
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onGroupCallInvitation call: CallManagerDelegateCallType, callId: UInt64, invitation: GroupCallInvitation)
+
+    /**
+     * Another of the user's devices wants to hand over its call with the given user.
+     *
+     * To take the call, pass the callId to acceptCallTransfer().
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onCallTransferOffered remoteUuid: UUID, callId: UInt64)
+
+    /**
+     * How a 1:1 call went, for call history.
+     *
+     * Sent once per call, just before the call is concluded.
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onCallRecord record: CallRecord, call: CallManagerDelegateCallType)
+
+    /**
+     * How a ring for a group call went, for call history.
+     *
+     * Sent once the ring is over, whether or not it was accepted.
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onGroupRingRecord record: GroupRingRecord)
+
     /**
      * An Offer message should be sent to the given remote.
      * If there is any error, the UI can reset UI state and invoke the reset() API.
@@ -307,6 +478,15 @@ public protocol CallManagerDelegate: AnyObject {
      */
     @MainActor
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, didUpdateRingForGroup groupId: Data, ringId: Int64, sender: UUID, update: RingUpdate)
+
+    /**
+     * The peek result of a call watched with `watchGroupCallPeek` or `watchCallLinkPeek`
+     * has changed, or the call was peeked for the first time.
+     *
+     * `id` is the group ID, or the room ID derived from the call link's root key.
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, didChangeWatchedPeek id: Data, peekResponse: PeekResponse)
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -433,6 +613,138 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Puts the active call on hold, or resumes it.
+    ///
+    /// Media stops flowing in both directions while the call is on hold, but the connection is
+    /// kept. The remote side is notified with `.remoteHold` and `.remoteResume`.
+    @MainActor
+    public func setOnHold(_ onHold: Bool) throws {
+        Logger.debug("setOnHold(\(onHold))")
+
+        let retPtr = ringrtcSetOnHold(ringRtcCallManager, onHold)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "setOnHold() function failure")
+        }
+    }
+
+    /// Continues the active call as a group call.
+    ///
+    /// The remote peer is invited to the group call, which is then joined. The call ends with
+    /// `.endedEscalatedToGroupCall` once either side has joined, so media keeps flowing in the
+    /// meantime. The remote peer calls this too, after `onGroupCallInvitation`.
+    @MainActor
+    public func escalateToGroupCall(_ groupCall: GroupCall) throws {
+        Logger.debug("escalateToGroupCall")
+
+        guard let clientId = groupCall.clientId else {
+            throw CallManagerError.apiFailed(description: "escalateToGroupCall() group call has no client")
+        }
+
+        let retPtr = ringrtcEscalateToGroupCall(ringRtcCallManager, clientId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "escalateToGroupCall() function failure")
+        }
+    }
+
+    /// Hands the active call over to another of the user's devices.
+    ///
+    /// The remote peer is told to expect the target device, which gets `onCallTransferOffered`
+    /// and then calls `acceptCallTransfer`. Once it has taken over, this device's call ends
+    /// with `.endedTransferred`.
+    @MainActor
+    public func transferCall(remoteUuid: UUID, targetDeviceId: UInt32) throws {
+        Logger.debug("transferCall")
+
+        let uuidSlice = allocatedAppByteSliceFromData(maybe_data: remoteUuid.data)
+        defer { uuidSlice.bytes?.deallocate() }
+
+        let retPtr = ringrtcTransferCall(ringRtcCallManager, uuidSlice, targetDeviceId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "transferCall() function failure")
+        }
+    }
+
+    /// Takes over a call offered by another of the user's devices, by calling the remote peer
+    /// with the same callId. The call then proceeds like one started with `placeCall`.
+    @MainActor
+    public func acceptCallTransfer(call: CallType, callId: UInt64, callMediaType: CallMediaType, localDevice: UInt32) throws {
+        Logger.debug("acceptCallTransfer")
+
+        let unmanagedCall: Unmanaged<CallType> = Unmanaged.passUnretained(call)
+
+        let retPtr = ringrtcAcceptCallTransfer(ringRtcCallManager, unmanagedCall.toOpaque(), callId, callMediaType.rawValue, localDevice)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "acceptCallTransfer() function failure")
+        }
+
+        // Keep the call reference around until rust says we're done with the call.
+        _ = unmanagedCall.retain()
+    }
+
+    /// Enables or disables call waiting.
+    ///
+    /// When enabled, an incoming call that arrives during an accepted call is reported with
+    /// `.receivedCallWaiting` and then started like any other incoming call, instead of being
+    /// rejected as busy. Accepting it puts the active call on hold.
+    @MainActor
+    public func setCallWaitingEnabled(_ enabled: Bool) throws {
+        Logger.debug("setCallWaitingEnabled(\(enabled))")
+
+        let retPtr = ringrtcSetCallWaitingEnabled(ringRtcCallManager, enabled)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "setCallWaitingEnabled() function failure")
+        }
+    }
+
+    /// Turns do not disturb on or off.
+    ///
+    /// While on, incoming calls and group rings from anyone not in `allowedUuids` are declined
+    /// during `quietHours`, without being reported to the delegate. Declined offers are reported
+    /// with `.receivedOfferDeclinedByPolicy`.
+    ///
+    /// - Parameters:
+    ///   - enabled: If true, then apply the settings below
+    ///   - quietHours: When calls don't ring at all, or nil for never
+    ///   - allowedUuids: Group ring senders that always ring
+    ///   - maxConcurrentGroupRings: How many group rings can be outstanding at once, or nil for no limit
+    @MainActor
+    public func setDoNotDisturb(_ enabled: Bool, quietHours: QuietHours?, allowedUuids: [UUID], maxConcurrentGroupRings: UInt32?) throws {
+        Logger.debug("setDoNotDisturb(\(enabled))")
+
+        let uuidsSlice = allocatedAppByteSliceFromData(maybe_data: Data(allowedUuids.map { $0.data }.joined()))
+        defer { uuidsSlice.bytes?.deallocate() }
+
+        let appMaxConcurrentGroupRings: AppOptionalUInt32
+        if let maxConcurrentGroupRings = maxConcurrentGroupRings {
+            appMaxConcurrentGroupRings = AppOptionalUInt32(value: maxConcurrentGroupRings, valid: true)
+        } else {
+            appMaxConcurrentGroupRings = AppOptionalUInt32(value: 0, valid: false)
+        }
+
+        let retPtr = ringrtcSetDoNotDisturb(ringRtcCallManager,
+                                            enabled,
+                                            quietHours != nil,
+                                            quietHours?.startMinute ?? 0,
+                                            quietHours?.endMinute ?? 0,
+                                            quietHours?.utcOffsetMinutes ?? 0,
+                                            uuidsSlice,
+                                            appMaxConcurrentGroupRings)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "setDoNotDisturb() function failure")
+        }
+    }
+
+    /// Puts the active call on hold and resumes the held call.
+    @MainActor
+    public func swapCalls() throws {
+        Logger.debug("swapCalls")
+
+        let retPtr = ringrtcSwapCalls(ringRtcCallManager)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "swapCalls() function failure")
+        }
+    }
+
     @MainActor
     public func cancelGroupRing(groupId: Data, ringId: Int64, reason: RingCancelReason?) throws {
         Logger.debug("cancelGroupRing")
@@ -772,6 +1084,82 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         return groupCall
     }
 
+    /// Keeps peeking a group call in the background until `unwatchPeek` is called.
+    ///
+    /// Changes are reported via `didChangeWatchedPeek`, identified by `groupId`.
+    public func watchGroupCallPeek(groupId: Data, sfuUrl: String, membershipProof: Data, groupMembers: [GroupMember]) {
+        Logger.debug("watchGroupCallPeek")
+
+        let groupIdSlice = allocatedAppByteSliceFromData(maybe_data: groupId)
+        let sfuUrlSlice = allocatedAppByteSliceFromString(maybe_string: sfuUrl)
+        let membershipProofSlice = allocatedAppByteSliceFromData(maybe_data: membershipProof)
+        let appMembers: [AppGroupMemberInfo] = groupMembers.map { member in
+            let userIdSlice = allocatedAppByteSliceFromData(maybe_data: member.userId.data)
+            let memberIdSlice = allocatedAppByteSliceFromData(maybe_data: member.userIdCipherText)
+
+            return AppGroupMemberInfo(userId: userIdSlice, memberId: memberIdSlice)
+        }
+
+        // Make sure to release the allocated memory when the function exists,
+        // to ensure that the pointers are still valid when used in the RingRTC
+        // API function.
+        defer {
+            groupIdSlice.bytes?.deallocate()
+            sfuUrlSlice.bytes?.deallocate()
+            membershipProofSlice.bytes?.deallocate()
+            for appMember in appMembers {
+                appMember.userId.bytes?.deallocate()
+                appMember.memberId.bytes?.deallocate()
+            }
+        }
+
+        appMembers.withUnsafeBufferPointer { appMembersBytes in
+            var appGroupMemberInfoArray = AppGroupMemberInfoArray(
+                members: appMembersBytes.baseAddress,
+                count: appMembers.count
+            )
+            ringrtcWatchGroupCallPeek(ringRtcCallManager, groupIdSlice, sfuUrlSlice, membershipProofSlice, &appGroupMemberInfoArray)
+        }
+    }
+
+    /// Keeps peeking a call link's call in the background until `unwatchPeek` is called.
+    ///
+    /// Changes are reported via `didChangeWatchedPeek`, identified by `linkRootKey.deriveRoomId()`.
+    public func watchCallLinkPeek(sfuUrl: String, authCredentialPresentation: [UInt8], linkRootKey: CallLinkRootKey) {
+        Logger.debug("watchCallLinkPeek")
+
+        let sfuUrlSlice = allocatedAppByteSliceFromString(maybe_string: sfuUrl)
+        let authCredentialPresentationSlice = allocatedAppByteSliceFromArray(maybe_bytes: authCredentialPresentation)
+        let rootKeySlice = allocatedAppByteSliceFromData(maybe_data: linkRootKey.bytes)
+        defer {
+            sfuUrlSlice.bytes?.deallocate()
+            authCredentialPresentationSlice.bytes?.deallocate()
+            rootKeySlice.bytes?.deallocate()
+        }
+
+        ringrtcWatchCallLinkPeek(ringRtcCallManager, sfuUrlSlice, authCredentialPresentationSlice, rootKeySlice)
+    }
+
+    /// Stops watching a call started with `watchGroupCallPeek` or `watchCallLinkPeek`.
+    public func unwatchPeek(id: Data) {
+        Logger.debug("unwatchPeek")
+
+        let idSlice = allocatedAppByteSliceFromData(maybe_data: id)
+        defer { idSlice.bytes?.deallocate() }
+
+        ringrtcUnwatchPeek(ringRtcCallManager, idSlice)
+    }
+
+    /// Peeks a watched call as soon as possible, such as after receiving a group call update.
+    public func refreshPeek(id: Data) {
+        Logger.debug("refreshPeek")
+
+        let idSlice = allocatedAppByteSliceFromData(maybe_data: id)
+        defer { idSlice.bytes?.deallocate() }
+
+        ringrtcRefreshPeek(ringRtcCallManager, idSlice)
+    }
+
     // MARK: - Event Observers
 
     func onStartCall(remote: UnsafeRawPointer, callId: UInt64, isOutgoing: Bool, callMediaType: CallMediaType) {
@@ -841,6 +1229,44 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func onGroupCallInvitation(remote: UnsafeRawPointer, callId: UInt64, invitation: GroupCallInvitation) {
+        Logger.debug("onGroupCallInvitation")
+
+        Task { @MainActor in
+            Logger.debug("onGroupCallInvitation - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            delegate.callManager(self, onGroupCallInvitation: callReference, callId: callId, invitation: invitation)
+        }
+    }
+
+    func onCallTransferOffered(remoteUuid: UUID, callId: UInt64) {
+        Logger.debug("onCallTransferOffered")
+
+        Task { @MainActor in
+            Logger.debug("onCallTransferOffered - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            delegate.callManager(self, onCallTransferOffered: remoteUuid, callId: callId)
+        }
+    }
+
+    func onCallRecord(remote: UnsafeRawPointer, record: CallRecord) {
+        Logger.debug("onCallRecord")
+
+        Task { @MainActor in
+            Logger.debug("onCallRecord - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            delegate.callManager(self, onCallRecord: record, call: callReference)
+        }
+    }
+
     // MARK: - Signaling Observers
 
     func onSendOffer(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType) {
@@ -942,6 +1368,16 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func onGroupRingRecord(record: GroupRingRecord) {
+        Logger.debug("onGroupRingRecord")
+
+        Task { @MainActor in
+            Logger.debug("onGroupRingRecord - main.async")
+
+            self.delegate?.callManager(self, onGroupRingRecord: record)
+        }
+    }
+
     // MARK: - Utility Observers
 
     func onCreateConnection(pcObserverOwned: UnsafeMutableRawPointer?, deviceId: UInt32, appCallContext: CallContext, audioJitterBufferMaxPackets: Int32, audioJitterBufferMaxTargetDelayMs: Int32) -> (connection: Connection, pc: UnsafeMutableRawPointer?) {
@@ -1148,6 +1584,20 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func handlePresenterChanged(clientId: UInt32, currentPresenter: UInt32?) {
+        Logger.debug("handlePresenterChanged")
+
+        Task { @MainActor in
+            Logger.debug("handlePresenterChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handlePresenterChanged(currentPresenter: currentPresenter)
+        }
+    }
+
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?) {
         Logger.debug("handleJoinStateChanged")
 
@@ -1217,6 +1667,30 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func handleCallSummary(clientId: UInt32, callSummary: CallSummary) {
+        Logger.debug("handleCallSummary")
+
+        Task { @MainActor in
+            Logger.debug("handleCallSummary - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleCallSummary(callSummary: callSummary)
+        }
+    }
+
+    func handleWatchedPeekChanged(id: Data, peekResponse: PeekResponse) {
+        Logger.debug("handleWatchedPeekChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleWatchedPeekChanged - main.async")
+
+            self.delegate?.callManager(self, didChangeWatchedPeek: id, peekResponse: peekResponse)
+        }
+    }
+
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason) {
         Logger.debug("handleEnded")
 
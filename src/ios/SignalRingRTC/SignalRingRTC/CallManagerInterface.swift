@@ -24,10 +24,14 @@ protocol CallManagerInterfaceDelegate: AnyObject {
     func onConnectMedia(remote: UnsafeRawPointer, appCallContext: CallContext, stream: RTCMediaStream)
     func onCompareRemotes(remote1: UnsafeRawPointer, remote2: UnsafeRawPointer) -> Bool
     func onCallConcluded(remote: UnsafeRawPointer)
+    func onGroupCallInvitation(remote: UnsafeRawPointer, callId: UInt64, invitation: GroupCallInvitation)
+    func onCallTransferOffered(remoteUuid: UUID, callId: UInt64)
+    func onCallRecord(remote: UnsafeRawPointer, record: CallRecord)
 
     // Group Calls
 
     func groupCallRingUpdate(groupId: Data, ringId: Int64, sender: UUID, update: RingUpdate)
+    func onGroupRingRecord(record: GroupRingRecord)
 
     func requestMembershipProof(clientId: UInt32)
     func requestGroupMembers(clientId: UInt32)
@@ -37,10 +41,13 @@ protocol CallManagerInterfaceDelegate: AnyObject {
     func handleLowBandwidthForVideo(clientId: UInt32, recovered: Bool)
     func handleReactions(clientId: UInt32, reactions: [Reaction])
     func handleRaisedHands(clientId: UInt32, raisedHands: [UInt32])
+    func handlePresenterChanged(clientId: UInt32, currentPresenter: UInt32?)
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?)
     func handleRemoteDevicesChanged(clientId: UInt32, remoteDeviceStates: [RemoteDeviceState])
     func handleIncomingVideoTrack(clientId: UInt32, remoteDemuxId: UInt32, nativeVideoTrackBorrowedRc: UnsafeMutableRawPointer?)
     func handlePeekChanged(clientId: UInt32, peekInfo: PeekInfo)
+    func handleCallSummary(clientId: UInt32, callSummary: CallSummary)
+    func handleWatchedPeekChanged(id: Data, peekResponse: PeekResponse)
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason)
     func handleSpeakingNotification(clientId: UInt32, event: SpeechEvent)
 }
@@ -83,10 +90,14 @@ class CallManagerInterface {
             onConnectMedia: callManagerInterfaceOnConnectMedia,
             onCompareRemotes: callManagerInterfaceOnCompareRemotes,
             onCallConcluded: callManagerInterfaceOnCallConcluded,
+            onGroupCallInvitation: callManagerInterfaceOnGroupCallInvitation,
+            onCallTransferOffered: callManagerInterfaceOnCallTransferOffered,
+            onCallRecord: callManagerInterfaceOnCallRecord,
 
             // Group Calls
 
             groupCallRingUpdate: callManagerInterfaceGroupCallRingUpdate,
+            onGroupRingRecord: callManagerInterfaceOnGroupRingRecord,
 
             requestMembershipProof: callManagerInterfaceRequestMembershipProof,
             requestGroupMembers: callManagerInterfaceRequestGroupMembers,
@@ -96,10 +107,13 @@ class CallManagerInterface {
             handleLowBandwidthForVideo: callManagerInterfaceHandleLowBandwidthForVideo,
             handleReactions: callManagerInterfaceHandleReactions,
             handleRaisedHands: callManagerInterfaceHandleRaisedHands,
+            handlePresenterChanged: callManagerInterfaceHandlePresenterChanged,
             handleJoinStateChanged: callManagerInterfaceHandleJoinStateChanged,
             handleRemoteDevicesChanged: callManagerInterfaceHandleRemoteDevicesChanged,
             handleIncomingVideoTrack: callManagerInterfaceHandleIncomingVideoTrack,
             handlePeekChanged: callManagerInterfaceHandlePeekChanged,
+            handleCallSummary: callManagerInterfaceHandleCallSummary,
+            handleWatchedPeekChanged: callManagerInterfaceHandleWatchedPeekChanged,
             handleEnded: callManagerInterfaceHandleEnded,
             handleSpeakingNotification: callManagerInterfaceHandleSpeakingNotification
         )
@@ -245,6 +259,30 @@ class CallManagerInterface {
         delegate.onCallConcluded(remote: remote)
     }
 
+    func onGroupCallInvitation(remote: UnsafeRawPointer, callId: UInt64, invitation: GroupCallInvitation) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onGroupCallInvitation(remote: remote, callId: callId, invitation: invitation)
+    }
+
+    func onCallTransferOffered(remoteUuid: UUID, callId: UInt64) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onCallTransferOffered(remoteUuid: remoteUuid, callId: callId)
+    }
+
+    func onCallRecord(remote: UnsafeRawPointer, record: CallRecord) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onCallRecord(remote: remote, record: record)
+    }
+
     // Group Calls
 
     func groupCallRingUpdate(groupId: Data, ringId: Int64, sender: UUID, update: RingUpdate) {
@@ -255,6 +293,14 @@ class CallManagerInterface {
         delegate.groupCallRingUpdate(groupId: groupId, ringId: ringId, sender: sender, update: update)
     }
 
+    func onGroupRingRecord(record: GroupRingRecord) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onGroupRingRecord(record: record)
+    }
+
     func requestMembershipProof(clientId: UInt32) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -319,6 +365,14 @@ class CallManagerInterface {
         delegate.handleRaisedHands(clientId: clientId, raisedHands: raisedHands)
     }
 
+    func handlePresenterChanged(clientId: UInt32, currentPresenter: UInt32?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handlePresenterChanged(clientId: clientId, currentPresenter: currentPresenter)
+    }
+
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -351,6 +405,22 @@ class CallManagerInterface {
         delegate.handlePeekChanged(clientId: clientId, peekInfo: peekInfo)
     }
 
+    func handleCallSummary(clientId: UInt32, callSummary: CallSummary) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleCallSummary(clientId: clientId, callSummary: callSummary)
+    }
+
+    func handleWatchedPeekChanged(id: Data, peekResponse: PeekResponse) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleWatchedPeekChanged(id: id, peekResponse: peekResponse)
+    }
+
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -822,6 +892,95 @@ func callManagerInterfaceOnCallConcluded(object: UnsafeMutableRawPointer?, remot
     obj.onCallConcluded(remote: remote)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceOnGroupCallInvitation(object: UnsafeMutableRawPointer?, remote: UnsafeRawPointer?, callId: UInt64, groupId: AppByteSlice, callLinkRootKey: AppByteSlice, callLinkEpoch: AppOptionalUInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        failDebug("remote was unexpectedly nil")
+        return
+    }
+
+    let invitation: GroupCallInvitation
+    if let groupId = groupId.asData() {
+        invitation = .group(groupId: groupId)
+    } else if let callLinkRootKey = callLinkRootKey.asData(), let rootKey = try? CallLinkRootKey(callLinkRootKey) {
+        var epoch: UInt32?
+        if callLinkEpoch.valid {
+            epoch = callLinkEpoch.value
+        }
+        invitation = .callLink(CallLinkUrl(rootKey: rootKey, epoch: epoch))
+    } else {
+        failDebug("invitation was unexpectedly empty or invalid")
+        return
+    }
+
+    obj.onGroupCallInvitation(remote: remote, callId: callId, invitation: invitation)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceOnCallTransferOffered(object: UnsafeMutableRawPointer?, remoteUserId: AppByteSlice, callId: UInt64) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remoteUuid = remoteUserId.toUUID() else {
+        return
+    }
+
+    obj.onCallTransferOffered(remoteUuid: remoteUuid, callId: callId)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceOnCallRecord(object: UnsafeMutableRawPointer?, remote: UnsafeRawPointer?, record: AppCallRecord) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        failDebug("remote was unexpectedly nil")
+        return
+    }
+
+    func millis(_ offset: AppOptionalUInt32) -> UInt32? {
+        return offset.valid ? offset.value : nil
+    }
+
+    let setup = CallSetupMetrics(
+        offerAgeMillis: millis(record.setup.offerAge),
+        offerSentMillis: millis(record.setup.offerSent),
+        answerSentMillis: millis(record.setup.answerSent),
+        answerReceivedMillis: millis(record.setup.answerReceived),
+        firstIceCandidateSentMillis: millis(record.setup.firstIceCandidateSent),
+        firstIceCandidateReceivedMillis: millis(record.setup.firstIceCandidateReceived),
+        iceConnectedMillis: millis(record.setup.iceConnected),
+        acceptedMillis: millis(record.setup.accepted),
+        firstMediaMillis: millis(record.setup.firstMedia)
+    )
+
+    let callRecord = CallRecord(
+        callId: record.callId,
+        isIncoming: record.isIncoming,
+        isVideo: record.isVideo,
+        started: Date(timeIntervalSince1970: TimeInterval(record.startedMillis) / 1000),
+        answered: record.answeredMillis.valid ? Date(timeIntervalSince1970: TimeInterval(record.answeredMillis.value) / 1000) : nil,
+        ended: Date(timeIntervalSince1970: TimeInterval(record.endedMillis) / 1000),
+        outcome: CallOutcome(rawValue: record.outcome) ?? .failed,
+        answeredByDeviceId: record.answeredByDeviceId.valid ? record.answeredByDeviceId.value : nil,
+        setup: setup
+    )
+
+    obj.onCallRecord(remote: remote, record: callRecord)
+}
+
 // Group Calls
 
 @available(iOSApplicationExtension, unavailable)
@@ -950,6 +1109,22 @@ func callManagerInterfaceHandleRaisedHands(object: UnsafeMutableRawPointer?, cli
     obj.handleRaisedHands(clientId: clientId, raisedHands: finalRaisedHands)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandlePresenterChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, currentPresenter: AppOptionalUInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalCurrentPresenter: UInt32?
+    if currentPresenter.valid {
+        finalCurrentPresenter = currentPresenter.value
+    }
+
+    obj.handlePresenterChanged(clientId: clientId, currentPresenter: finalCurrentPresenter)
+}
+
 @available(iOSApplicationExtension, unavailable)
 func callManagerInterfaceHandleJoinStateChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinState: Int32, demuxId: AppOptionalUInt32) {
     guard let object = object else {
@@ -1063,6 +1238,41 @@ func callManagerInterfaceGroupCallRingUpdate(object: UnsafeMutableRawPointer?, g
     obj.groupCallRingUpdate(groupId: groupId, ringId: ringId, sender: sender, update: update)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceOnGroupRingRecord(object: UnsafeMutableRawPointer?, record: AppGroupRingRecord) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let groupId = record.groupId.asData() else {
+        failDebug("groupId was unexpectedly empty")
+        return
+    }
+
+    guard let sender = record.senderUuid.toUUID() else {
+        failDebug("sender was unexpectedly empty")
+        return
+    }
+
+    guard let outcome = GroupRingOutcome(rawValue: record.outcome) else {
+        failDebug("unrecognized outcome")
+        return
+    }
+
+    let groupRingRecord = GroupRingRecord(
+        groupId: groupId,
+        ringId: record.ringId,
+        sender: sender,
+        received: Date(timeIntervalSince1970: TimeInterval(record.receivedMillis) / 1000),
+        ended: Date(timeIntervalSince1970: TimeInterval(record.endedMillis) / 1000),
+        outcome: outcome
+    )
+
+    obj.onGroupRingRecord(record: groupRingRecord)
+}
+
 @available(iOSApplicationExtension, unavailable)
 func callManagerInterfaceHandlePeekChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinedMembers: AppUuidArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCountIncludingPendingDevices: UInt32, deviceCountExcludingPendingDevices: UInt32, pendingUsers: AppUuidArray) {
     guard let object = object else {
@@ -1102,6 +1312,87 @@ func callManagerInterfaceHandlePeekChanged(object: UnsafeMutableRawPointer?, cli
     obj.handlePeekChanged(clientId: clientId, peekInfo: peekInfo)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleCallSummary(object: UnsafeMutableRawPointer?, clientId: UInt32, summary: AppCallSummary) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var speakingTimeMillis: [UUID: UInt64] = [:]
+    for index in 0..<summary.speakingTimes.count {
+        let speakingTime = summary.speakingTimes.speakingTimes[index]
+        guard let userId = speakingTime.userId.toUUID() else {
+            Logger.debug("missing userId for speaking time")
+            continue
+        }
+        speakingTimeMillis[userId] = speakingTime.speakingTimeMillis
+    }
+
+    let callSummary = CallSummary(
+        callDurationMillis: summary.callDurationMillis,
+        joinDurationMillis: summary.joinDurationMillis,
+        peakDeviceCount: summary.peakDeviceCount,
+        uniqueUserCount: summary.uniqueUserCount,
+        speakingTimeMillis: speakingTimeMillis,
+        reactionsSent: summary.reactionsSent,
+        reactionsReceived: summary.reactionsReceived,
+        raisedHandCount: summary.raisedHandCount,
+        averageSendBps: summary.averageSendBps,
+        averageReceiveBps: summary.averageReceiveBps,
+        sendPacketsLostPct: summary.sendPacketsLostPct,
+        receivePacketsLostPct: summary.receivePacketsLostPct,
+        reconnectCount: summary.reconnectCount
+    )
+
+    obj.handleCallSummary(clientId: clientId, callSummary: callSummary)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleWatchedPeekChanged(object: UnsafeMutableRawPointer?, id: AppByteSlice, statusCode: UInt16, joinedMembers: AppUuidArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCountIncludingPendingDevices: UInt32, deviceCountExcludingPendingDevices: UInt32, pendingUsers: AppUuidArray) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let id = id.asData() else {
+        failDebug("id was unexpectedly nil")
+        return
+    }
+
+    var finalJoinedMembers: [UUID] = []
+    for index in 0..<joinedMembers.count {
+        guard let userId = joinedMembers.uuids[index].toUUID() else {
+            Logger.debug("missing userId")
+            continue
+        }
+
+        finalJoinedMembers.append(userId)
+    }
+
+    var finalPendingUsers: [UUID] = []
+    for index in 0..<pendingUsers.count {
+        guard let userId = pendingUsers.uuids[index].toUUID() else {
+            Logger.debug("missing userId")
+            continue
+        }
+
+        finalPendingUsers.append(userId)
+    }
+
+    var finalMaxDevices: UInt32?
+    if maxDevices.valid {
+        finalMaxDevices = maxDevices.value
+    }
+
+    let peekInfo = PeekInfo(joinedMembers: finalJoinedMembers, creator: creator.toUUID(), eraId: eraId.asString(), maxDevices: finalMaxDevices, deviceCountIncludingPendingDevices: deviceCountIncludingPendingDevices, deviceCountExcludingPendingDevices: deviceCountExcludingPendingDevices, pendingUsers: finalPendingUsers)
+    let peekResponse = PeekResponse(errorStatusCode: statusCode == 200 ? nil : statusCode, peekInfo: peekInfo)
+
+    obj.handleWatchedPeekChanged(id: id, peekResponse: peekResponse)
+}
+
 @available(iOSApplicationExtension, unavailable)
 func callManagerInterfaceHandleEnded(object: UnsafeMutableRawPointer?, clientId: UInt32, reason: Int32) {
     guard let object = object else {
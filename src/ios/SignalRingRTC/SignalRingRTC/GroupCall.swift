@@ -46,6 +46,13 @@ public enum GroupCallEndReason: Int32 {
     case iceFailedAfterConnected
     case serverChangedDemuxId
     case hasMaxDevices
+    case callLinkExpired
+    case callLinkInvalid
+    case callLinkRevoked
+    case authExpired
+    case rateLimited
+    case sfuUnavailable
+    case sfuMalformedResponse
 }
 
 /// The inferred state of user speech (e.g. to suggest lowering hand)
@@ -94,6 +101,25 @@ public class Reaction {
     }
 }
 
+/// Statistics about a whole group call, available via `GroupCall.callSummary` once the call
+/// has ended.
+public struct CallSummary {
+    public let callDurationMillis: UInt64
+    public let joinDurationMillis: UInt64
+    public let peakDeviceCount: UInt32
+    public let uniqueUserCount: UInt32
+    /// How long each remote user was the primary speaker.
+    public let speakingTimeMillis: [UUID: UInt64]
+    public let reactionsSent: UInt32
+    public let reactionsReceived: UInt32
+    public let raisedHandCount: UInt32
+    public let averageSendBps: UInt64
+    public let averageReceiveBps: UInt64
+    public let sendPacketsLostPct: Float
+    public let receivePacketsLostPct: Float
+    public let reconnectCount: UInt32
+}
+
 /// All remote devices in a group call and their associated state.
 @available(iOSApplicationExtension, unavailable)
 public class RemoteDeviceState: Hashable {
@@ -238,6 +264,13 @@ public protocol GroupCallDelegate: AnyObject {
     @MainActor
     func groupCall(onRaisedHands groupCall: GroupCall, raisedHands: [UInt32])
 
+    /**
+     * Indication that the current presenter changed. The new presenter is
+     * available via currentPresenter.
+     */
+    @MainActor
+    func groupCall(onPresenterChanged groupCall: GroupCall)
+
     /**
      * Indication that the application can retrieve an updated PeekInfo which
      * includes a list of users that are actively in the group call.
@@ -291,6 +324,10 @@ public class GroupCall {
     public private(set) var localDeviceState: LocalDeviceState
     public private(set) var remoteDeviceStates: [UInt32: RemoteDeviceState]
     public private(set) var peekInfo: PeekInfo?
+    // The demux ID of the one device (possibly the local one) that everyone agrees is presenting.
+    public private(set) var currentPresenter: UInt32?
+    // Only set once the call has ended, so it can be read from onEnded.
+    public private(set) var callSummary: CallSummary?
 
     let videoCaptureController: VideoCaptureController
     var audioTrack: RTCAudioTrack?
@@ -511,6 +548,22 @@ public class GroupCall {
         ringrtcRaiseHand(self.ringRtcCallManager, clientId, raise)
     }
 
+    /// Asks to become the presenter. Without `takeOver`, the request is ignored if someone else
+    /// is presenting. A resulting change of presenter is reported via
+    /// `groupCall(onPresenterChanged:)`; an ignored request, or one made before joining, is not
+    /// reported at all.
+    @MainActor
+    public func requestToPresent(takeOver: Bool) {
+        Logger.debug("requestToPresent")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcRequestToPresent(self.ringRtcCallManager, clientId, takeOver)
+    }
+
     private var _isOutgoingAudioMuted = false
     @MainActor
     public var isOutgoingAudioMuted: Bool {
@@ -798,6 +851,12 @@ public class GroupCall {
         self.delegate?.groupCall(onRaisedHands: self, raisedHands: raisedHands)
     }
 
+    @MainActor
+    func handlePresenterChanged(currentPresenter: UInt32?) {
+        self.currentPresenter = currentPresenter
+        self.delegate?.groupCall(onPresenterChanged: self)
+    }
+
     @MainActor
     func handleJoinStateChanged(joinState: JoinState, demuxId: UInt32?) {
        self.localDeviceState.joinState = joinState
@@ -849,6 +908,11 @@ public class GroupCall {
         self.delegate?.groupCall(onPeekChanged: self)
     }
 
+    @MainActor
+    func handleCallSummary(callSummary: CallSummary) {
+        self.callSummary = callSummary
+    }
+
     @MainActor
     func handleEnded(reason: GroupCallEndReason) {
         guard let clientId = self.clientId else {
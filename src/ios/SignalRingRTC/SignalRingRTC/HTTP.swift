@@ -67,21 +67,44 @@ extension rtc_http_Headers {
 // Same as rust http::Response (nicer version of rtc_http_Response)
 public struct HTTPResponse {
     public let statusCode: UInt16
+    // Optional, but some (like Retry-After) are used when present.
+    public let headers: [String: String]
     public let body: Data?
 
-    public init(statusCode: UInt16, body: Data?) {
+    public init(statusCode: UInt16, headers: [String: String] = [:], body: Data?) {
         self.statusCode = statusCode
+        self.headers = headers
         self.body = body
     }
 }
 
+extension rtc_http_Headers {
+    // Don't forget to call deallocate()
+    static func allocate(from headers: [String: String]) -> Self {
+        let ptr = UnsafeMutablePointer<rtc_http_Header>.allocate(capacity: headers.count)
+        for (index, (name, value)) in headers.enumerated() {
+            (ptr + index).initialize(to: rtc_http_Header(name: rtc_String.allocate(from: name), value: rtc_String.allocate(from: value)))
+        }
+        return Self(ptr: ptr, count: headers.count)
+    }
+
+    func deallocate() {
+        for header in self.asUnsafeBufferPointer() {
+            header.name.deallocate()
+            header.value.deallocate()
+        }
+        UnsafeMutablePointer(mutating: self.ptr)?.deallocate()
+    }
+}
+
 extension rtc_http_Response {
     // Don't forget to call deallocate()
     static func allocate(from response: HTTPResponse) -> Self {
-        return Self(status_code: response.statusCode, body: rtc_Bytes.allocate(from: response.body))
+        return Self(status_code: response.statusCode, headers: rtc_http_Headers.allocate(from: response.headers), body: rtc_Bytes.allocate(from: response.body))
     }
 
     func deallocate() {
+        self.headers.deallocate()
         self.body.deallocate()
     }
 }
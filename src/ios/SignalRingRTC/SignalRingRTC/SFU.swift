@@ -103,7 +103,7 @@ public struct PeekResponse {
 
 // Same as rust sfu::PeekInfo (nicer version of rtc_sfu_PeekInfo)
 public struct PeekInfo {
-    /// In a peek response, indicates that a call link has expired or been revoked.
+    /// In a peek response, indicates that a call link has expired.
     public static let expiredCallLinkStatus: UInt16 = 703
 
     /// In a peek response, indicates that a call link is invalid.
@@ -111,6 +111,9 @@ public struct PeekInfo {
     /// It may have expired a long time ago.
     public static let invalidCallLinkStatus: UInt16 = 704
 
+    /// In a peek response, indicates that a call link has been revoked by its admin.
+    public static let revokedCallLinkStatus: UInt16 = 705
+
     public let joinedMembers: [UUID]
     public let creator: UUID?
     public let eraId: String?
@@ -183,8 +186,9 @@ public class SFUClient {
     /// call link. Does not require a group call object.
     ///
     /// Possible (synthetic) failure codes include:
-    /// - `PeekInfo.expiredCallLinkStatus`: the call link has expired or been revoked
+    /// - `PeekInfo.expiredCallLinkStatus`: the call link has expired
     /// - `PeekInfo.invalidCallLinkStatus`: the call link is invalid; it may have expired a long time ago
+    /// - `PeekInfo.revokedCallLinkStatus`: the call link has been revoked
     ///
     /// Will produce an "empty" `PeekInfo` if the link is valid but no call is active.
     ///
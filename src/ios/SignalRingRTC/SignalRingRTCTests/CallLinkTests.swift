@@ -24,6 +24,22 @@ final class CallLinkTests: XCTestCase {
         XCTAssertEqual(String(describing: Self.EXAMPLE_KEY), "bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz")
     }
 
+    func testUrl() throws {
+        let url = CallLinkUrl(rootKey: Self.EXAMPLE_KEY)
+        XCTAssertEqual(String(describing: url), "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz")
+
+        let withEpoch = CallLinkUrl(rootKey: Self.EXAMPLE_KEY, epoch: 0x01020304)
+        let formatted = String(describing: withEpoch)
+        XCTAssertEqual(formatted, "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&epoch=bcbd-bfbg")
+
+        let parsed = try CallLinkUrl(formatted)
+        XCTAssertEqual(parsed.rootKey.bytes, Self.EXAMPLE_KEY.bytes)
+        XCTAssertEqual(parsed.epoch, 0x01020304)
+
+        XCTAssertNil(try CallLinkUrl("https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz").epoch)
+        XCTAssertThrowsError(try CallLinkUrl("https://signal.link/call/#key=bcdf"))
+    }
+
     @MainActor
     func testCreateSuccess() async throws {
         let delegate = TestDelegate()
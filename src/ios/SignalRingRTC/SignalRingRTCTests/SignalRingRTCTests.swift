@@ -336,6 +336,24 @@ final class TestDelegate: CallManagerDelegate & HTTPDelegate {
         case .receivedOfferWithGlare:
             Logger.debug("TestDelegate:receivedOfferWithGlare")
             eventReceivedOfferWithGlare = true
+
+        case .receivedCallWaiting:
+            Logger.debug("TestDelegate:receivedCallWaiting")
+
+        case .remoteHold:
+            Logger.debug("TestDelegate:remoteHold")
+
+        case .remoteResume:
+            Logger.debug("TestDelegate:remoteResume")
+
+        case .endedEscalatedToGroupCall:
+            Logger.debug("TestDelegate:endedEscalatedToGroupCall")
+
+        case .endedTransferred:
+            Logger.debug("TestDelegate:endedTransferred")
+
+        case .receivedOfferDeclinedByPolicy:
+            Logger.debug("TestDelegate:receivedOfferDeclinedByPolicy")
         }
     }
 
@@ -351,6 +369,22 @@ final class TestDelegate: CallManagerDelegate & HTTPDelegate {
         Logger.debug("TestDelegate:onLowBandwidthForVideoFor - \(recovered)")
     }
 
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onGroupCallInvitation call: OpaqueCallData, callId: UInt64, invitation: GroupCallInvitation) {
+        Logger.debug("TestDelegate:onGroupCallInvitation")
+    }
+
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onCallTransferOffered remoteUuid: UUID, callId: UInt64) {
+        Logger.debug("TestDelegate:onCallTransferOffered")
+    }
+
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onCallRecord record: CallRecord, call: OpaqueCallData) {
+        Logger.debug("TestDelegate:onCallRecord")
+    }
+
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onGroupRingRecord record: GroupRingRecord) {
+        Logger.debug("TestDelegate:onGroupRingRecord")
+    }
+
     func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldSendOffer callId: UInt64, call: OpaqueCallData, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType) {
         Logger.debug("TestDelegate:shouldSendOffer")
         generalInvocationDetected = true
@@ -622,6 +656,11 @@ final class TestDelegate: CallManagerDelegate & HTTPDelegate {
         didUpdateRingForGroupUpdate = update
     }
 
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, didChangeWatchedPeek id: Data, peekResponse: PeekResponse) {
+        Logger.debug("TestDelegate:didChangeWatchedPeek")
+        generalInvocationDetected = true
+    }
+
     func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldCompareCalls call1: OpaqueCallData, call2: OpaqueCallData) -> Bool {
         Logger.debug("TestDelegate:shouldCompareCalls")
         generalInvocationDetected = true
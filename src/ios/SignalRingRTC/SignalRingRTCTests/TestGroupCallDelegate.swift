@@ -15,6 +15,7 @@ class TestGroupCallDelegate: GroupCallDelegate {
     var onLowBandwidthForVideoCount = 0
     var onReactionsCount = 0
     var onRaisedHandsCount = 0
+    var onPresenterChangedCount = 0
     var onPeekChangedCount = 0
     var onEndedCount = 0
     var onSpeakingCount = 0
@@ -53,6 +54,10 @@ class TestGroupCallDelegate: GroupCallDelegate {
         onRaisedHandsCount += 1
     }
 
+    func groupCall(onPresenterChanged groupCall: GroupCall) {
+        onPresenterChangedCount += 1
+    }
+
     func groupCall(onPeekChanged groupCall: GroupCall) {
         onPeekChangedCount += 1
     }
@@ -6,6 +6,8 @@
 import { RingRTCType } from './ringrtc/Service';
 
 export {
+  AdminActionOutcome,
+  AdminActionResult,
   AnswerMessage,
   AudioDevice,
   DataMode,
@@ -15,16 +17,26 @@ export {
   CallId,
   CallLogLevel,
   CallMessageUrgency,
+  CallOutcome,
+  CallRecord,
   CallSettings,
+  CallSetupMetrics,
   CallState,
   CallingMessage,
+  ConnectionQuality,
   ConnectionState,
   DeviceId,
+  DoNotDisturbSettings,
   GroupCall,
   GroupCallEndReason,
+  GroupCallInvitation,
   GroupCallKind,
   GroupCallObserver,
+  GroupCallSnapshot,
+  GroupCallSummary,
   GroupMemberInfo,
+  GroupRingOutcome,
+  GroupRingRecord,
   HangupMessage,
   HangupType,
   HttpMethod,
@@ -38,6 +50,7 @@ export {
   PeekDeviceInfo,
   PeekInfo,
   PeekStatusCodes,
+  QuietHours,
   Reaction,
   RemoteDeviceState,
   RingCancelReason,
@@ -66,6 +79,7 @@ export {
   CallLinkRootKey,
   CallLinkRestrictions,
   CallLinkState,
+  CallLinkUrl,
 } from './ringrtc/CallLinks';
 
 export const RingRTC = new RingRTCType();
@@ -7,9 +7,12 @@ import Native from './Native';
 
 export class CallLinkRootKey {
   readonly bytes: Buffer;
+  // Links from before epochs existed don't have one.
+  readonly epoch?: number;
 
-  private constructor(bytes: Buffer) {
+  private constructor(bytes: Buffer, epoch?: number) {
     this.bytes = bytes;
+    this.epoch = epoch;
   }
 
   static parse(str: string): CallLinkRootKey {
@@ -30,9 +33,19 @@ export class CallLinkRootKey {
     return Native.CallLinkRootKey_generateAdminPasskey();
   }
 
+  /** A new random epoch, for use with `RingRTC.resetCallLink`. */
+  static generateEpoch(): number {
+    return Native.CallLinkEpoch_generate() as number;
+  }
+
+  /** The same key for a different epoch, which gives the link a different room. */
+  withEpoch(epoch: number | undefined): CallLinkRootKey {
+    return new CallLinkRootKey(this.bytes, epoch);
+  }
+
   deriveRoomId(): Buffer {
     // eslint-disable-next-line @typescript-eslint/no-unsafe-return
-    return Native.CallLinkRootKey_deriveRoomId(this.bytes);
+    return Native.CallLinkRootKey_deriveRoomId(this.bytes, this.epoch);
   }
 
   toString(): string {
@@ -41,12 +54,39 @@ export class CallLinkRootKey {
   }
 }
 
+export class CallLinkUrl {
+  // Includes the link's epoch, if it has one.
+  constructor(public rootKey: CallLinkRootKey) {}
+
+  /**
+   * Parses a shareable call link URL, such as one pasted by the user.
+   *
+   * Surrounding and embedded whitespace, letter case, and missing separators in the key are
+   * tolerated. Throws if the URL isn't a valid call link.
+   */
+  static parse(url: string): CallLinkUrl {
+    const { rootKey, epoch } = Native.CallLinkUrl_parse(url) as {
+      rootKey: Buffer;
+      epoch: number | undefined;
+    };
+    return new CallLinkUrl(CallLinkRootKey.fromBytes(rootKey).withEpoch(epoch));
+  }
+
+  toString(): string {
+    // eslint-disable-next-line @typescript-eslint/no-unsafe-return
+    return Native.CallLinkUrl_format(this.rootKey.bytes, this.rootKey.epoch);
+  }
+}
+
 export class CallLinkState {
   constructor(
     public name: string,
     public restrictions: CallLinkRestrictions,
     public revoked: boolean,
-    public expiration: Date
+    public expiration: Date,
+    public description: string = '',
+    // When the admin expects the call to start, if they've said.
+    public scheduledStart?: Date
   ) {}
 }
 
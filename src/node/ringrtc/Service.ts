@@ -92,6 +92,17 @@ class NativeCallManager {
 (NativeCallManager.prototype as any).accept = Native.cm_accept;
 (NativeCallManager.prototype as any).ignore = Native.cm_ignore;
 (NativeCallManager.prototype as any).hangup = Native.cm_hangup;
+(NativeCallManager.prototype as any).setOnHold = Native.cm_setOnHold;
+(NativeCallManager.prototype as any).setCallWaitingEnabled =
+  Native.cm_setCallWaitingEnabled;
+(NativeCallManager.prototype as any).setDoNotDisturb =
+  Native.cm_setDoNotDisturb;
+(NativeCallManager.prototype as any).swapCalls = Native.cm_swapCalls;
+(NativeCallManager.prototype as any).escalateToGroupCall =
+  Native.cm_escalateToGroupCall;
+(NativeCallManager.prototype as any).transferCall = Native.cm_transferCall;
+(NativeCallManager.prototype as any).acceptCallTransfer =
+  Native.cm_acceptCallTransfer;
 (NativeCallManager.prototype as any).cancelGroupRing =
   Native.cm_cancelGroupRing;
 (NativeCallManager.prototype as any).signalingMessageSent =
@@ -135,6 +146,8 @@ class NativeCallManager {
 (NativeCallManager.prototype as any).groupRing = Native.cm_groupRing;
 (NativeCallManager.prototype as any).groupReact = Native.cm_groupReact;
 (NativeCallManager.prototype as any).groupRaiseHand = Native.cm_groupRaiseHand;
+(NativeCallManager.prototype as any).groupRequestToPresent =
+  Native.cm_groupRequestToPresent;
 (NativeCallManager.prototype as any).setOutgoingAudioMuted =
   Native.cm_setOutgoingAudioMuted;
 (NativeCallManager.prototype as any).setOutgoingVideoMuted =
@@ -148,6 +161,12 @@ class NativeCallManager {
 (NativeCallManager.prototype as any).requestVideo = Native.cm_requestVideo;
 (NativeCallManager.prototype as any).approveUser = Native.cm_approveUser;
 (NativeCallManager.prototype as any).denyUser = Native.cm_denyUser;
+(NativeCallManager.prototype as any).approveUsers = Native.cm_approveUsers;
+(NativeCallManager.prototype as any).denyUsers = Native.cm_denyUsers;
+(NativeCallManager.prototype as any).approveAllPendingUsers =
+  Native.cm_approveAllPendingUsers;
+(NativeCallManager.prototype as any).denyAllPendingUsers =
+  Native.cm_denyAllPendingUsers;
 (NativeCallManager.prototype as any).removeClient = Native.cm_removeClient;
 (NativeCallManager.prototype as any).blockClient = Native.cm_blockClient;
 (NativeCallManager.prototype as any).setGroupMembers =
@@ -157,10 +176,17 @@ class NativeCallManager {
 (NativeCallManager.prototype as any).readCallLink = Native.cm_readCallLink;
 (NativeCallManager.prototype as any).createCallLink = Native.cm_createCallLink;
 (NativeCallManager.prototype as any).updateCallLink = Native.cm_updateCallLink;
+(NativeCallManager.prototype as any).resetCallLink = Native.cm_resetCallLink;
 (NativeCallManager.prototype as any).deleteCallLink = Native.cm_deleteCallLink;
 (NativeCallManager.prototype as any).peekGroupCall = Native.cm_peekGroupCall;
 (NativeCallManager.prototype as any).peekCallLinkCall =
   Native.cm_peekCallLinkCall;
+(NativeCallManager.prototype as any).watchGroupCallPeek =
+  Native.cm_watchGroupCallPeek;
+(NativeCallManager.prototype as any).watchCallLinkPeek =
+  Native.cm_watchCallLinkPeek;
+(NativeCallManager.prototype as any).unwatchPeek = Native.cm_unwatchPeek;
+(NativeCallManager.prototype as any).refreshPeek = Native.cm_refreshPeek;
 (NativeCallManager.prototype as any).getAudioInputs = Native.cm_getAudioInputs;
 (NativeCallManager.prototype as any).setAudioInput = Native.cm_setAudioInput;
 (NativeCallManager.prototype as any).getAudioOutputs =
@@ -169,6 +195,8 @@ class NativeCallManager {
 (NativeCallManager.prototype as any).processEvents = Native.cm_processEvents;
 (NativeCallManager.prototype as any).setRtcStatsInterval =
   Native.cm_setRtcStatsInterval;
+(NativeCallManager.prototype as any).getGroupCallSnapshot =
+  Native.cm_getGroupCallSnapshot;
 
 type GroupId = Buffer;
 type GroupCallUserId = Buffer;
@@ -214,6 +242,7 @@ export interface PeekInfo {
 export enum PeekStatusCodes {
   EXPIRED_CALL_LINK = 703,
   INVALID_CALL_LINK = 704,
+  REVOKED_CALL_LINK = 705,
 }
 
 // In sync with WebRTC's PeerConnection.AdapterType.
@@ -271,6 +300,8 @@ interface RawCallLinkState {
   rawRestrictions: number;
   revoked: boolean;
   expiration: Date;
+  description: string;
+  scheduledStart: Date | undefined;
 }
 
 function normalizeAudioLevel(raw: RawAudioLevel): NormalizedAudioLevel {
@@ -297,7 +328,9 @@ function rawCallLinkStateToCallLinkState(
       raw.name,
       restrictions,
       raw.revoked,
-      raw.expiration
+      raw.expiration,
+      raw.description,
+      raw.scheduledStart
     );
   } else {
     return undefined;
@@ -359,6 +392,10 @@ export type HttpResult<T> =
 export class RingRTCType {
   private readonly callManager: CallManager;
   private _call: Call | null;
+  // A call that is waiting to be answered or on hold while _call is active.
+  private _otherCall: Call | null;
+  // The ID of the next incoming call, if another call is active.
+  private _waitingCallId: CallId | null;
   private _groupCallByClientId: Map<GroupCallClientId, GroupCall>;
   private _peekRequests: Requests<HttpResult<PeekInfo>>;
   private _callLinkRequests: Requests<HttpResult<CallLinkState>>;
@@ -441,11 +478,35 @@ export class RingRTCType {
       ) => void)
     | null = null;
 
+  handleGroupCallInvitation:
+    | ((
+        remoteUserId: UserId,
+        callId: CallId,
+        invitation: GroupCallInvitation
+      ) => void)
+    | null = null;
+
+  handleCallTransferOffered:
+    | ((remoteUuid: Buffer, callId: CallId) => void)
+    | null = null;
+
+  handleCallRecord:
+    | ((remoteUserId: UserId, record: CallRecord) => void)
+    | null = null;
+
+  handleGroupRingRecord: ((record: GroupRingRecord) => void) | null = null;
+
   handleRtcStatsReport: ((reportJson: string) => void) | null = null;
 
+  handleWatchedPeekChanged:
+    | ((id: Buffer, result: HttpResult<PeekInfo>) => void)
+    | null = null;
+
   constructor() {
     this.callManager = new NativeCallManager(this) as unknown as CallManager;
     this._call = null;
+    this._otherCall = null;
+    this._waitingCallId = null;
     this._groupCallByClientId = new Map();
     this._peekRequests = new Requests();
     this._callLinkRequests = new Requests();
@@ -528,16 +589,176 @@ export class RingRTCType {
       });
   }
 
+  // Called by UX
+  setCallWaitingEnabled(enabled: boolean): void {
+    this.callManager.setCallWaitingEnabled(enabled);
+  }
+
+  // Called by UX
+  // While set, incoming calls and group rings from anyone not allowed are
+  // declined during quiet hours without being started, and are reported
+  // through handleAutoEndedIncomingCallRequest. Pass null to turn it off.
+  setDoNotDisturb(settings: DoNotDisturbSettings | null): void {
+    if (!settings) {
+      this.callManager.setDoNotDisturb(
+        false,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        [],
+        []
+      );
+      return;
+    }
+    const { quietHours } = settings;
+    this.callManager.setDoNotDisturb(
+      true,
+      quietHours?.startMinute,
+      quietHours?.endMinute,
+      quietHours?.utcOffsetMinutes,
+      settings.maxConcurrentGroupRings,
+      settings.allowedUserIds,
+      settings.allowedRemoteUserIds
+    );
+  }
+
+  // Called by UX
+  // Puts the active call on hold and resumes the held call.
+  swapCalls(): void {
+    const heldCall = this._otherCall;
+    if (!heldCall || heldCall.isWaiting) {
+      return;
+    }
+    sillyDeadlockProtection(() => {
+      this.callManager.swapCalls();
+    });
+    const activeCall = this._call;
+    this._call = heldCall;
+    this._otherCall =
+      activeCall && activeCall.state !== CallState.Ended ? activeCall : null;
+  }
+
+  // Called by UX
+  // Moves the active 1:1 call into the given group call, which should already
+  // have been invited to with the remote user.
+  escalateToGroupCall(groupCall: GroupCall): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.escalateToGroupCall(groupCall.clientId);
+    });
+  }
+
+  // Called by Rust
+  onGroupCallInvitation(
+    remoteUserId: UserId,
+    callId: CallId,
+    groupId: Buffer | undefined,
+    callLinkRootKey: Buffer | undefined,
+    callLinkEpoch: number | undefined
+  ): void {
+    let invitation: GroupCallInvitation;
+    if (groupId) {
+      invitation = { type: 'group', groupId };
+    } else if (callLinkRootKey) {
+      try {
+        invitation = {
+          type: 'callLink',
+          rootKey:
+            CallLinkRootKey.fromBytes(callLinkRootKey).withEpoch(callLinkEpoch),
+        };
+      } catch (e) {
+        this.logWarn(`onGroupCallInvitation: invalid root key: ${e}`);
+        return;
+      }
+    } else {
+      return;
+    }
+    if (this.handleGroupCallInvitation) {
+      this.handleGroupCallInvitation(remoteUserId, callId, invitation);
+    } else {
+      this.logError('RingRTC.handleGroupCallInvitation is not set!');
+    }
+  }
+
+  // Called by UX
+  // Hands the active call over to another of the user's devices, which is
+  // told via handleCallTransferOffered.
+  transferCall(remoteUuid: Buffer, targetDeviceId: DeviceId): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.transferCall(remoteUuid, targetDeviceId);
+    });
+  }
+
+  // Called by UX
+  // Takes over a call offered by another of the user's devices. The call then
+  // proceeds like one from startOutgoingCall.
+  acceptCallTransfer(
+    remoteUserId: UserId,
+    callId: CallId,
+    isVideoCall: boolean,
+    localDeviceId: DeviceId
+  ): Call {
+    this.callManager.acceptCallTransfer(
+      remoteUserId,
+      callId,
+      isVideoCall,
+      localDeviceId
+    );
+    const isIncoming = false;
+    const call = new Call(
+      this.callManager,
+      remoteUserId,
+      callId,
+      isIncoming,
+      isVideoCall,
+      CallState.Prering
+    );
+    this._call = call;
+    call.outgoingVideoEnabled = isVideoCall;
+    return call;
+  }
+
+  // Called by Rust
+  onCallTransferOffered(remoteUuid: Buffer, callId: CallId): void {
+    if (this.handleCallTransferOffered) {
+      this.handleCallTransferOffered(remoteUuid, callId);
+    } else {
+      this.logError('RingRTC.handleCallTransferOffered is not set!');
+    }
+  }
+
+  // Called by Rust
+  // Sent once per call, just before the call is concluded.
+  onCallRecord(remoteUserId: UserId, record: CallRecord): void {
+    if (this.handleCallRecord) {
+      this.handleCallRecord(remoteUserId, record);
+    } else {
+      this.logError('RingRTC.handleCallRecord is not set!');
+    }
+  }
+
+  // Called by Rust
+  // Sent right before onStartIncomingCall when another call is active.
+  onCallWaiting(_remoteUserId: UserId, callId: CallId): void {
+    this._waitingCallId = callId;
+  }
+
   // Called by Rust
   onStartIncomingCall(
     remoteUserId: UserId,
     callId: CallId,
     isVideoCall: boolean
   ): void {
+    const isWaiting =
+      this._waitingCallId !== null &&
+      this.isSameCallId(this._waitingCallId, callId);
+    this._waitingCallId = null;
+
     // Temporary: Force hangup in all glare scenarios until handled gracefully.
     // In case of a glare loser, an incoming call will be generated right
     // after the outgoing call is ended. In that case, ignore it once.
     if (
+      !isWaiting &&
       this._call &&
       (this._call.endedReason === CallEndedReason.Glare ||
         this._call.endedReason === CallEndedReason.ReCall)
@@ -564,12 +785,17 @@ export class RingRTCType {
       isVideoCall,
       CallState.Prering
     );
+    call.isWaiting = isWaiting;
     const { handleIncomingCall, handleStartCall } = this;
     if (!handleIncomingCall || !handleStartCall) {
       call.ignore();
       return;
     }
-    this._call = call;
+    if (isWaiting) {
+      this._otherCall = call;
+    } else {
+      this._call = call;
+    }
 
     handleIncomingCall(call)
       .then(success => {
@@ -614,10 +840,16 @@ export class RingRTCType {
 
   // Called by Rust
   onCallState(remoteUserId: UserId, state: CallState): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
+    if (call === this._otherCall && state === CallState.Accepted) {
+      // Accepting a waiting call puts the active call on hold.
+      call.isWaiting = false;
+      this._otherCall = this._call;
+      this._call = call;
+    }
     call.state = state;
   }
 
@@ -636,6 +868,14 @@ export class RingRTCType {
     };
     this._callInfoByCallId.delete(this.getCallInfoKey(callId));
 
+    const otherCall = this._otherCall;
+    if (otherCall && this.isSameCallId(otherCall.callId, callId)) {
+      this._otherCall = null;
+      otherCall.endedReason = reason;
+      otherCall.state = CallState.Ended;
+      return;
+    }
+
     const call = this._call;
     if (call && reason == CallEndedReason.ReceivedOfferWithGlare) {
       // The current call is the outgoing call.
@@ -665,6 +905,7 @@ export class RingRTCType {
       call.remoteUserId !== remoteUserId ||
       reason === CallEndedReason.ReceivedOfferWhileActive ||
       reason === CallEndedReason.ReceivedOfferExpired ||
+      reason === CallEndedReason.ReceivedOfferDeclinedByPolicy ||
       (call.state === CallState.Prering && call.isIncoming)
     ) {
       if (this.handleAutoEndedIncomingCallRequest) {
@@ -689,15 +930,35 @@ export class RingRTCType {
       return;
     }
 
+    // Like RingRTC, carry on with a waiting call once the active call has ended.
+    if (otherCall?.isWaiting) {
+      this._call = otherCall;
+      this._otherCall = null;
+    }
+
     // Send the end reason first because setting the state triggers
     // call.handleStateChanged, which may look at call.endedReason.
     call.endedReason = reason;
     call.state = CallState.Ended;
   }
 
+  private callFor(remoteUserId: UserId): Call | null {
+    if (this._call?.remoteUserId === remoteUserId) {
+      return this._call;
+    }
+    if (this._otherCall?.remoteUserId === remoteUserId) {
+      return this._otherCall;
+    }
+    return null;
+  }
+
+  private isSameCallId(a: CallId, b: CallId): boolean {
+    return a.high === b.high && a.low === b.low;
+  }
+
   onRemoteAudioEnabled(remoteUserId: UserId, enabled: boolean): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -708,8 +969,8 @@ export class RingRTCType {
   }
 
   onRemoteVideoEnabled(remoteUserId: UserId, enabled: boolean): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -720,8 +981,8 @@ export class RingRTCType {
   }
 
   onRemoteSharingScreen(remoteUserId: UserId, enabled: boolean): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -731,12 +992,24 @@ export class RingRTCType {
     }
   }
 
+  onRemoteHold(remoteUserId: UserId, onHold: boolean): void {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
+      return;
+    }
+
+    call.remoteOnHold = onHold;
+    if (call.handleRemoteHold) {
+      call.handleRemoteHold();
+    }
+  }
+
   onNetworkRouteChanged(
     remoteUserId: UserId,
     localNetworkAdapterType: NetworkAdapterType
   ): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -751,8 +1024,8 @@ export class RingRTCType {
     capturedLevel: RawAudioLevel,
     receivedLevel: RawAudioLevel
   ): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -764,8 +1037,8 @@ export class RingRTCType {
   }
 
   onLowBandwidthForVideo(remoteUserId: UserId, recovered: boolean): void {
-    const call = this._call;
-    if (!call || call.remoteUserId !== remoteUserId) {
+    const call = this.callFor(remoteUserId);
+    if (!call) {
       return;
     }
 
@@ -937,7 +1210,8 @@ export class RingRTCType {
         requestId,
         sfuUrl,
         authCredentialPresentation,
-        linkRootKey.bytes
+        linkRootKey.bytes,
+        linkRootKey.epoch
       );
     });
     return promise;
@@ -997,7 +1271,8 @@ export class RingRTCType {
         linkRootKey.bytes,
         adminPasskey,
         callLinkPublicParams,
-        restrictions
+        restrictions,
+        linkRootKey.epoch
       );
     });
     return promise;
@@ -1009,6 +1284,7 @@ export class RingRTCType {
    * Possible failure codes include:
    * - 401: the room does not exist (and this is the wrong API to create a new room)
    * - 403: the admin passkey is incorrect
+   * - 603: the name is too long (no request is sent)
    *
    * This request is idempotent; if it fails due to a network issue, it is safe to retry.
    *
@@ -1036,6 +1312,9 @@ export class RingRTCType {
         adminPasskey,
         newName,
         undefined,
+        undefined,
+        linkRootKey.epoch,
+        undefined,
         undefined
       );
     });
@@ -1076,12 +1355,141 @@ export class RingRTCType {
         adminPasskey,
         undefined,
         restrictions,
+        undefined,
+        linkRootKey.epoch,
+        undefined,
+        undefined
+      );
+    });
+    return promise;
+  }
+  /**
+   * Asynchronous request to update a call link's description, such as an agenda.
+   *
+   * Possible failure codes include:
+   * - 401: the room does not exist (and this is the wrong API to create a new room)
+   * - 403: the admin passkey is incorrect
+   *
+   * - 603: the description is too long (no request is sent)
+   *
+   * This request is idempotent; if it fails due to a network issue, it is safe to retry.
+   *
+   * @param sfuUrl - the URL to use when accessing the SFU
+   * @param authCredentialPresentation - a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey - the root key for the call link
+   * @param adminPasskey - the passkey specified when the link was created
+   * @param newDescription - the new description to use, or an empty string to clear it
+   */
+  updateCallLinkDescription(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: CallLinkRootKey,
+    adminPasskey: Buffer,
+    newDescription: string
+  ): Promise<HttpResult<CallLinkState>> {
+    const [requestId, promise] = this._callLinkRequests.add();
+    // Response comes back via handleCallLinkResponse
+    sillyDeadlockProtection(() => {
+      this.callManager.updateCallLink(
+        requestId,
+        sfuUrl,
+        authCredentialPresentation,
+        linkRootKey.bytes,
+        adminPasskey,
+        undefined,
+        undefined,
+        undefined,
+        linkRootKey.epoch,
+        newDescription,
         undefined
       );
     });
     return promise;
   }
 
+  /**
+   * Asynchronous request to update when a call link's call is expected to start.
+   *
+   * Possible failure codes include:
+   * - 401: the room does not exist (and this is the wrong API to create a new room)
+   * - 403: the admin passkey is incorrect
+   *
+   * This request is idempotent; if it fails due to a network issue, it is safe to retry.
+   *
+   * @param sfuUrl - the URL to use when accessing the SFU
+   * @param authCredentialPresentation - a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey - the root key for the call link
+   * @param adminPasskey - the passkey specified when the link was created
+   * @param scheduledStart - the new start time (to the second), or undefined to clear it
+   */
+  updateCallLinkScheduledStart(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: CallLinkRootKey,
+    adminPasskey: Buffer,
+    scheduledStart: Date | undefined
+  ): Promise<HttpResult<CallLinkState>> {
+    const [requestId, promise] = this._callLinkRequests.add();
+    // Response comes back via handleCallLinkResponse
+    sillyDeadlockProtection(() => {
+      this.callManager.updateCallLink(
+        requestId,
+        sfuUrl,
+        authCredentialPresentation,
+        linkRootKey.bytes,
+        adminPasskey,
+        undefined,
+        undefined,
+        undefined,
+        linkRootKey.epoch,
+        undefined,
+        scheduledStart ?? null
+      );
+    });
+    return promise;
+  }
+
+  /**
+   * Asynchronous request to reset a call link, moving it to a new epoch so that links without
+   * that epoch stop working. The name, restrictions, and admin passkey are kept.
+   *
+   * The root key stays the same and epochs are only 32 bits, so this does not keep out anyone who
+   * already has the root key: they can search for the new epoch. Create a new link for that.
+   *
+   * Possible failure codes include:
+   * - 403: the admin passkey is incorrect
+   * - 409: conflict - there is an ongoing call for this room.
+   *
+   * @param sfuUrl - the URL to use when accessing the SFU
+   * @param authCredentialPresentation - a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey - the root key for the call link, with its current epoch
+   * @param adminPasskey - the passkey specified when the link was created
+   * @param newEpoch - the link's new epoch, from `CallLinkRootKey.generateEpoch()`; the new link
+   *                   uses `linkRootKey.withEpoch(newEpoch)`
+   */
+  resetCallLink(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: CallLinkRootKey,
+    adminPasskey: Buffer,
+    newEpoch: number
+  ): Promise<HttpResult<CallLinkState>> {
+    const [requestId, promise] = this._callLinkRequests.add();
+    // Response comes back via handleCallLinkResponse
+    sillyDeadlockProtection(() => {
+      this.callManager.resetCallLink(
+        requestId,
+        sfuUrl,
+        authCredentialPresentation,
+        linkRootKey.bytes,
+        adminPasskey,
+        newEpoch,
+        linkRootKey.epoch
+      );
+    });
+    return promise;
+  }
+
   /**
    * Asynchronous request to delete a call link.
    *
@@ -1110,7 +1518,8 @@ export class RingRTCType {
         sfuUrl,
         authCredentialPresentation,
         linkRootKey.bytes,
-        adminPasskey
+        adminPasskey,
+        linkRootKey.epoch
       );
     });
     return promise;
@@ -1118,10 +1527,16 @@ export class RingRTCType {
 
   // HTTP callbacks
 
-  receivedHttpResponse(requestId: number, status: number, body: Buffer): void {
+  // Headers are optional, but some (like Retry-After) are used when present.
+  receivedHttpResponse(
+    requestId: number,
+    status: number,
+    body: Buffer,
+    headers?: { [name: string]: string }
+  ): void {
     sillyDeadlockProtection(() => {
       try {
-        this.callManager.receivedHttpResponse(requestId, status, body);
+        this.callManager.receivedHttpResponse(requestId, status, body, headers);
       } catch {
         // We may not have an active connection any more.
         // In which case it doesn't matter
@@ -1189,7 +1604,8 @@ export class RingRTCType {
       rootKey.bytes,
       adminPasskey,
       hkdfExtraInfo,
-      audioLevelsIntervalMillis || 0
+      audioLevelsIntervalMillis || 0,
+      rootKey.epoch
     );
     if (clientId === INVALID_CLIENT_ID) {
       // Return undefined since the call link client creation failed.
@@ -1253,12 +1669,65 @@ export class RingRTCType {
         requestId,
         sfuUrl,
         authCredentialPresentation,
-        rootKey.bytes
+        rootKey.bytes,
+        rootKey.epoch
       );
     });
     return promise;
   }
 
+  // Called by UX
+  // Keeps peeking the group call until unwatchPeek is called.
+  // Changes come back via handleWatchedPeekChanged, identified by the group ID.
+  watchGroupCallPeek(
+    groupId: Buffer,
+    sfuUrl: string,
+    membershipProof: Buffer,
+    groupMembers: Array<GroupMemberInfo>
+  ): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.watchGroupCallPeek(
+        groupId,
+        sfuUrl,
+        membershipProof,
+        groupMembers
+      );
+    });
+  }
+
+  // Called by UX
+  // Keeps peeking the call link's call until unwatchPeek is called.
+  // Changes come back via handleWatchedPeekChanged, identified by the room ID.
+  watchCallLinkPeek(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    rootKey: CallLinkRootKey
+  ): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.watchCallLinkPeek(
+        sfuUrl,
+        authCredentialPresentation,
+        rootKey.bytes,
+        rootKey.epoch
+      );
+    });
+  }
+
+  // Called by UX
+  unwatchPeek(id: Buffer): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.unwatchPeek(id);
+    });
+  }
+
+  // Called by UX
+  // Peeks a watched call as soon as possible, such as after a group call update message.
+  refreshPeek(id: Buffer): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.refreshPeek(id);
+    });
+  }
+
   // Called by Rust
   requestMembershipProof(clientId: GroupCallClientId): void {
     sillyDeadlockProtection(() => {
@@ -1391,6 +1860,19 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  handlePresenterChanged(
+    clientId: GroupCallClientId,
+    currentPresenter: number | null
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handlePresenterChanged(currentPresenter ?? undefined);
+      }
+    });
+  }
+
   // Called by Rust
   handleRemoteDevicesChanged(
     clientId: GroupCallClientId,
@@ -1443,6 +1925,28 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  handleWatchedPeekChanged(
+    id: Buffer,
+    statusCode: number,
+    rawInfo: RawPeekInfo | undefined
+  ): void {
+    sillyDeadlockProtection(() => {
+      let result: HttpResult<PeekInfo>;
+      if (rawInfo) {
+        const info = rawPeekInfoToPeekInfo(rawInfo);
+        result = { success: true, value: info };
+      } else {
+        result = { success: false, errorStatusCode: statusCode };
+      }
+      if (this.handleWatchedPeekChanged) {
+        this.handleWatchedPeekChanged(id, result);
+      } else {
+        this.logError('RingRTC.handleWatchedPeekChanged is not set!');
+      }
+    });
+  }
+
   // Called by Rust
   handleCallLinkResponse(
     requestId: number,
@@ -1490,6 +1994,32 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  handleCallSummary(
+    clientId: GroupCallClientId,
+    summary: GroupCallSummary
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleCallSummary(summary);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleAdminActionResults(
+    clientId: GroupCallClientId,
+    results: Array<AdminActionResult>
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleAdminActionResults(results);
+      }
+    });
+  }
+
   // Called by Rust
   handleEnded(clientId: GroupCallClientId, reason: GroupCallEndReason): void {
     sillyDeadlockProtection(() => {
@@ -1521,6 +2051,18 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  // Sent once the ring is over, whether or not it was accepted.
+  onGroupRingRecord(record: GroupRingRecord): void {
+    sillyDeadlockProtection(() => {
+      if (this.handleGroupRingRecord) {
+        this.handleGroupRingRecord(record);
+      } else {
+        this.logError('RingRTC.handleGroupRingRecord is not set!');
+      }
+    });
+  }
+
   // Called by Rust
   handleRtcStatsReportComplete(reportJson: string): void {
     if (this.handleRtcStatsReport) {
@@ -1778,15 +2320,16 @@ export class RingRTCType {
     return this._call;
   }
 
-  getCall(callId: CallId): Call | null {
-    const call = this.call;
+  // The call that is waiting to be answered or is on hold, if any.
+  get otherCall(): Call | null {
+    return this._otherCall;
+  }
 
-    if (
-      call &&
-      call.callId.high === callId.high &&
-      call.callId.low === callId.low
-    ) {
-      return call;
+  getCall(callId: CallId): Call | null {
+    for (const call of [this._call, this._otherCall]) {
+      if (call && this.isSameCallId(call.callId, callId)) {
+        return call;
+      }
     }
     return null;
   }
@@ -1948,16 +2491,21 @@ export class Call {
   outgoingAudioLevel: NormalizedAudioLevel = 0;
   remoteAudioLevel: NormalizedAudioLevel = 0;
   remoteSharingScreen = false;
+  remoteOnHold = false;
+  private _onHold = false;
   networkRoute: NetworkRoute = new NetworkRoute();
   private _videoCapturer: VideoCapturer | null = null;
   private _videoRenderer: VideoRenderer | null = null;
   endedReason?: CallEndedReason;
+  // Whether this call arrived during another call and hasn't been accepted.
+  isWaiting = false;
 
   // These callbacks should be set by the UX code.
   handleStateChanged?: () => void;
   handleRemoteAudioEnabled?: () => void;
   handleRemoteVideoEnabled?: () => void;
   handleRemoteSharingScreen?: () => void;
+  handleRemoteHold?: () => void;
   handleNetworkRouteChanged?: () => void;
   handleAudioLevels?: () => void;
 
@@ -2043,6 +2591,11 @@ export class Call {
   }
 
   decline(): void {
+    if (this.isWaiting) {
+      // Hanging up would end the active call instead.
+      this.ignore();
+      return;
+    }
     this.hangup();
   }
 
@@ -2065,6 +2618,19 @@ export class Call {
     });
   }
 
+  get onHold(): boolean {
+    return this._onHold;
+  }
+
+  // Stops sending and playing media without hanging up, and tells the remote side.
+  set onHold(onHold: boolean) {
+    this._onHold = onHold;
+    // This assumes we only have one active call.
+    sillyDeadlockProtection(() => {
+      this._callManager.setOnHold(onHold);
+    });
+  }
+
   get outgoingAudioEnabled(): boolean {
     return this._outgoingAudioEnabled;
   }
@@ -2255,6 +2821,13 @@ export enum GroupCallEndReason {
   IceFailedAfterConnected,
   ServerChangedDemuxId,
   HasMaxDevices,
+  CallLinkExpired,
+  CallLinkInvalid,
+  CallLinkRevoked,
+  AuthExpired,
+  RateLimited,
+  SfuUnavailable,
+  SfuMalformedResponse,
 }
 
 // Matches SpeechEvent in rust.
@@ -2285,6 +2858,8 @@ export enum RingUpdate {
   BusyOnAnotherDevice,
   /// The sender cancelled the ring request.
   CancelledByRinger,
+  /// The incoming call policy declined the ring without ringing.
+  DeclinedByPolicy,
 }
 
 // HTTP request methods.
@@ -2295,6 +2870,14 @@ export enum HttpMethod {
   Delete = 3,
 }
 
+// How well media from a remote device is reaching us.
+// Must be kept in sync with the Rust enum.
+export enum ConnectionQuality {
+  Good = 0,
+  Degraded = 1,
+  Poor = 2,
+}
+
 // The local device state for a group call.
 export class LocalDeviceState {
   connectionState: ConnectionState;
@@ -2336,6 +2919,9 @@ export class RemoteDeviceState {
   speakerTime: string; // unix millis; 0 if they've never spoken (to be converted to a numeric type)
   forwardingVideo: boolean | undefined;
   isHigherResolutionPending: boolean;
+  connectionQuality: ConnectionQuality | undefined;
+  // True if this device has stopped sending heartbeats for a while.
+  isUnresponsive: boolean;
 
   constructor(
     demuxId: number,
@@ -2351,9 +2937,80 @@ export class RemoteDeviceState {
     this.addedTime = addedTime;
     this.speakerTime = speakerTime;
     this.isHigherResolutionPending = false;
+    this.isUnresponsive = false;
   }
 }
 
+/** type returned by CallManager.getGroupCallSnapshot */
+interface RawGroupCallSnapshot {
+  connectionState: ConnectionState;
+  joinState: JoinState;
+  demuxId: number | null;
+  localDeviceState: {
+    audioMuted: boolean | undefined;
+    videoMuted: boolean | undefined;
+    presenting: boolean | undefined;
+    sharingScreen: boolean | undefined;
+  };
+  remoteDeviceStates: Array<RemoteDeviceState>;
+  peekInfo?: RawPeekInfo;
+  raisedHands: Array<number>;
+  currentPresenter: number | null;
+}
+
+// A consistent view of the state of a group call, read directly from RingRTC
+// rather than assembled from the most recent callbacks.
+export interface GroupCallSnapshot {
+  connectionState: ConnectionState;
+  joinState: JoinState;
+  // Set after joined
+  demuxId?: number;
+  audioMuted: boolean | undefined;
+  videoMuted: boolean | undefined;
+  presenting: boolean | undefined;
+  sharingScreen: boolean | undefined;
+  remoteDeviceStates: Array<RemoteDeviceState>;
+  peekInfo?: PeekInfo;
+  raisedHands: Array<number>;
+  currentPresenter?: number;
+}
+
+// Statistics about a whole group call, available from GroupCall.getCallSummary()
+// once the call has ended.
+export interface GroupCallSummary {
+  callDurationMillis: number;
+  joinDurationMillis: number;
+  peakDeviceCount: number;
+  uniqueUserCount: number;
+  speakingTimes: Array<{ userId: Buffer; speakingTimeMillis: number }>;
+  reactionsSent: number;
+  reactionsReceived: number;
+  raisedHandCount: number;
+  averageSendBps: number;
+  averageReceiveBps: number;
+  sendPacketsLostPct: number;
+  receivePacketsLostPct: number;
+  reconnectCount: number;
+}
+
+// What happened to one user in a bulk approval or denial.
+export enum AdminActionOutcome {
+  // Sent to the SFU, which will retry if it's lost.
+  Sent,
+  // The user was already in the call.
+  AlreadyJoined,
+  // The user wasn't pending; they may have left or been handled by another admin.
+  NotPending,
+  // Couldn't be sent, such as because the call has been left.
+  Failed,
+}
+
+export interface AdminActionResult {
+  userId: Buffer;
+  approved: boolean;
+  outcome: AdminActionOutcome;
+}
+
 // Used to communicate the group membership to RingRTC for a group call.
 export class GroupMemberInfo {
   userId: Buffer;
@@ -2400,9 +3057,16 @@ export interface GroupCallObserver {
   onLowBandwidthForVideo(groupCall: GroupCall, recovered: boolean): void;
   onReactions(groupCall: GroupCall, reactions: Array<Reaction>): void;
   onRaisedHands(groupCall: GroupCall, raisedHands: Array<number>): void;
+  onPresenterChanged(groupCall: GroupCall): void;
   onPeekChanged(groupCall: GroupCall): void;
   onEnded(groupCall: GroupCall, reason: GroupCallEndReason): void;
   onSpeechEvent(groupCall: GroupCall, event: SpeechEvent): void;
+  // Called once each batch from approveUsers, denyUsers, approveAllPendingUsers, or
+  // denyAllPendingUsers has been completely handled.
+  onAdminActionResults?(
+    groupCall: GroupCall,
+    results: Array<AdminActionResult>
+  ): void;
 }
 
 export class GroupCall {
@@ -2420,6 +3084,8 @@ export class GroupCall {
   private _remoteDeviceStates: Array<RemoteDeviceState> | undefined;
 
   private _peekInfo: PeekInfo | undefined;
+  private _currentPresenter: number | undefined;
+  private _callSummary: GroupCallSummary | undefined;
 
   // Called by UI via RingRTC object
   constructor(
@@ -2474,6 +3140,21 @@ export class GroupCall {
     return this._peekInfo;
   }
 
+  // Called by UI
+  //
+  // The demux ID of the one device (possibly the local one) that everyone
+  // agrees is presenting.
+  getCurrentPresenter(): number | undefined {
+    return this._currentPresenter;
+  }
+
+  // Called by UI
+  //
+  // Only set once the call has ended, so it can be read from onEnded.
+  getCallSummary(): GroupCallSummary | undefined {
+    return this._callSummary;
+  }
+
   // Called by UI
   getCallId(): CallId | undefined {
     const eraId = this._peekInfo?.eraId;
@@ -2502,6 +3183,15 @@ export class GroupCall {
     this._callManager.groupRaiseHand(this._clientId, raise);
   }
 
+  // Called by UI
+  //
+  // Without takeOver, the request is ignored if someone else is presenting.
+  // A resulting change of presenter is reported via onPresenterChanged; an
+  // ignored request, or one made before joining, is not reported at all.
+  requestToPresent(takeOver: boolean): void {
+    this._callManager.groupRequestToPresent(this._clientId, takeOver);
+  }
+
   // Called by UI
   setOutgoingVideoMuted(muted: boolean): void {
     this._localDeviceState.videoMuted = muted;
@@ -2563,6 +3253,36 @@ export class GroupCall {
     this._callManager.denyUser(this._clientId, otherUserId);
   }
 
+  // Called by UI
+  //
+  // Approvals are sent a few at a time, so large batches may take a few seconds.
+  // The results are delivered to onAdminActionResults.
+  approveUsers(otherUserIds: Array<Buffer>): void {
+    this._callManager.approveUsers(this._clientId, otherUserIds);
+  }
+
+  // Called by UI
+  //
+  // Denials are sent a few at a time, so large batches may take a few seconds.
+  // The results are delivered to onAdminActionResults.
+  denyUsers(otherUserIds: Array<Buffer>): void {
+    this._callManager.denyUsers(this._clientId, otherUserIds);
+  }
+
+  // Called by UI
+  //
+  // Approves everyone in the most recent peek info's pendingUsers.
+  approveAllPendingUsers(): void {
+    this._callManager.approveAllPendingUsers(this._clientId);
+  }
+
+  // Called by UI
+  //
+  // Denies everyone in the most recent peek info's pendingUsers.
+  denyAllPendingUsers(): void {
+    this._callManager.denyAllPendingUsers(this._clientId);
+  }
+
   // Called by UI
   removeClient(otherClientDemuxId: number): void {
     this._callManager.removeClient(this._clientId, otherClientDemuxId);
@@ -2653,6 +3373,12 @@ export class GroupCall {
     this._observer.onRaisedHands(this, raisedHands);
   }
 
+  // Called by Rust via RingRTC object
+  handlePresenterChanged(currentPresenter: number | undefined): void {
+    this._currentPresenter = currentPresenter;
+    this._observer.onPresenterChanged(this);
+  }
+
   // Called by Rust via RingRTC object
   handleRemoteDevicesChanged(
     remoteDeviceStates: Array<RemoteDeviceState>
@@ -2677,6 +3403,16 @@ export class GroupCall {
     this._observer.onPeekChanged(this);
   }
 
+  // Called by Rust via RingRTC object
+  handleCallSummary(summary: GroupCallSummary): void {
+    this._callSummary = summary;
+  }
+
+  // Called by Rust via RingRTC object
+  handleAdminActionResults(results: Array<AdminActionResult>): void {
+    this._observer.onAdminActionResults?.(this, results);
+  }
+
   // Called by Rust via RingRTC object
   handleEnded(reason: GroupCallEndReason): void {
     this._callManager.deleteGroupCallClient(this._clientId);
@@ -2719,6 +3455,35 @@ export class GroupCall {
     this._callManager.setRtcStatsInterval(this._clientId, intervalMillis);
   }
 
+  // Called by UI
+  //
+  // Returns undefined if the call has already ended.
+  getSnapshot(): GroupCallSnapshot | undefined {
+    const raw = this._callManager.getGroupCallSnapshot(this._clientId);
+    if (!raw) {
+      return undefined;
+    }
+    // We don't get aspect ratios or audio levels from the snapshot, so copy
+    // over the most recent ones.
+    for (const noo of raw.remoteDeviceStates) {
+      const old = this._remoteDeviceStates?.find(
+        old => old.demuxId == noo.demuxId
+      );
+      noo.videoAspectRatio = old?.videoAspectRatio;
+      noo.audioLevel = old?.audioLevel ?? 0;
+    }
+    return {
+      connectionState: raw.connectionState,
+      joinState: raw.joinState,
+      demuxId: raw.demuxId ?? undefined,
+      ...raw.localDeviceState,
+      remoteDeviceStates: raw.remoteDeviceStates,
+      peekInfo: raw.peekInfo ? rawPeekInfoToPeekInfo(raw.peekInfo) : undefined,
+      raisedHands: raw.raisedHands,
+      currentPresenter: raw.currentPresenter ?? undefined,
+    };
+  }
+
   handleSpeechEvent(event: SpeechEvent): void {
     this._observer.onSpeechEvent(this, event);
   }
@@ -2898,6 +3663,26 @@ export interface CallManager {
   accept(callId: CallId): void;
   ignore(callId: CallId): void;
   hangup(): void;
+  setOnHold(onHold: boolean): void;
+  setCallWaitingEnabled(enabled: boolean): void;
+  setDoNotDisturb(
+    enabled: boolean,
+    startMinute: number | undefined,
+    endMinute: number | undefined,
+    utcOffsetMinutes: number | undefined,
+    maxConcurrentGroupRings: number | undefined,
+    allowedUserIds: Array<Buffer>,
+    allowedRemoteUserIds: Array<UserId>
+  ): void;
+  swapCalls(): void;
+  escalateToGroupCall(clientId: GroupCallClientId): void;
+  transferCall(remoteUuid: Buffer, targetDeviceId: DeviceId): void;
+  acceptCallTransfer(
+    remoteUserId: UserId,
+    callId: CallId,
+    isVideoCall: boolean,
+    localDeviceId: DeviceId
+  ): void;
   cancelGroupRing(
     groupId: GroupId,
     ringId: string,
@@ -2965,7 +3750,12 @@ export interface CallManager {
     messageAgeSec: number
   ): void;
 
-  receivedHttpResponse(requestId: number, status: number, body: Buffer): void;
+  receivedHttpResponse(
+    requestId: number,
+    status: number,
+    body: Buffer,
+    headers: { [name: string]: string } | undefined
+  ): void;
   httpRequestFailed(requestId: number, debugInfo: string | undefined): void;
 
   // Group Calls
@@ -2982,7 +3772,8 @@ export interface CallManager {
     linkRootKey: Buffer,
     adminPasskey: Buffer | undefined,
     hkdfExtraInfo: Buffer,
-    audioLevelsIntervalMillis: number
+    audioLevelsIntervalMillis: number,
+    linkEpoch: number | undefined
   ): GroupCallClientId;
   deleteGroupCallClient(clientId: GroupCallClientId): void;
   connect(clientId: GroupCallClientId): void;
@@ -2999,6 +3790,7 @@ export interface CallManager {
   groupRing(clientId: GroupCallClientId, recipient: Buffer | undefined): void;
   groupReact(clientId: GroupCallClientId, value: string): void;
   groupRaiseHand(clientId: GroupCallClientId, raise: boolean): void;
+  groupRequestToPresent(clientId: GroupCallClientId, takeOver: boolean): void;
   resendMediaKeys(clientId: GroupCallClientId): void;
   setDataMode(clientId: GroupCallClientId, dataMode: DataMode): void;
   requestVideo(
@@ -3008,6 +3800,10 @@ export interface CallManager {
   ): void;
   approveUser(clientId: GroupCallClientId, otherUserId: Buffer): void;
   denyUser(clientId: GroupCallClientId, otherUserId: Buffer): void;
+  approveUsers(clientId: GroupCallClientId, otherUserIds: Array<Buffer>): void;
+  denyUsers(clientId: GroupCallClientId, otherUserIds: Array<Buffer>): void;
+  approveAllPendingUsers(clientId: GroupCallClientId): void;
+  denyAllPendingUsers(clientId: GroupCallClientId): void;
   removeClient(clientId: GroupCallClientId, otherClientDemuxId: number): void;
   blockClient(clientId: GroupCallClientId, otherClientDemuxId: number): void;
   setGroupMembers(
@@ -3027,12 +3823,16 @@ export interface CallManager {
     clientId: GroupCallClientId,
     intervalMillis: number
   ): void;
+  getGroupCallSnapshot(
+    clientId: GroupCallClientId
+  ): RawGroupCallSnapshot | undefined;
   // Responses come back via handleCallLinkResponse
   readCallLink(
     requestId: number,
     sfuUrl: string,
     authCredentialPresentation: Buffer,
-    linkRootKey: Buffer
+    linkRootKey: Buffer,
+    linkEpoch: number | undefined
   ): void;
   createCallLink(
     requestId: number,
@@ -3041,7 +3841,8 @@ export interface CallManager {
     linkRootKey: Buffer,
     adminPasskey: Buffer,
     callLinkPublicParams: Buffer,
-    restrictions: number | undefined
+    restrictions: number | undefined,
+    linkEpoch: number | undefined
   ): void;
   updateCallLink(
     requestId: number,
@@ -3051,14 +3852,27 @@ export interface CallManager {
     adminPasskey: Buffer,
     newName: string | undefined,
     newRestrictions: number | undefined,
-    newRevoked: boolean | undefined
+    newRevoked: boolean | undefined,
+    linkEpoch: number | undefined,
+    newDescription: string | undefined,
+    newScheduledStart: Date | null | undefined
+  ): void;
+  resetCallLink(
+    requestId: number,
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: Buffer,
+    adminPasskey: Buffer,
+    newEpoch: number,
+    linkEpoch: number | undefined
   ): void;
   deleteCallLink(
     requestId: number,
     sfuUrl: string,
     authCredentialPresentation: Buffer,
     linkRootKey: Buffer,
-    adminPasskey: Buffer
+    adminPasskey: Buffer,
+    linkEpoch: number | undefined
   ): void;
   // Response comes back via handlePeekResponse
   peekGroupCall(
@@ -3072,8 +3886,25 @@ export interface CallManager {
     requestId: number,
     sfuUrl: string,
     authCredentialPresentation: Buffer,
-    linkRootKey: Buffer
+    linkRootKey: Buffer,
+    linkEpoch: number | undefined
   ): void;
+  // Changes come back via handleWatchedPeekChanged
+  watchGroupCallPeek(
+    groupId: Buffer,
+    sfuUrl: string,
+    membershipProof: Buffer,
+    groupMembers: Array<GroupMemberInfo>
+  ): void;
+  // Changes come back via handleWatchedPeekChanged
+  watchCallLinkPeek(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: Buffer,
+    linkEpoch: number | undefined
+  ): void;
+  unwatchPeek(id: Buffer): void;
+  refreshPeek(id: Buffer): void;
 
   getAudioInputs(): Array<AudioDevice>;
   setAudioInput(index: number): void;
@@ -3088,6 +3919,17 @@ export interface CallManagerCallbacks {
     callId: CallId,
     isVideoCall: boolean
   ): void;
+  onCallWaiting(remoteUserId: UserId, callId: CallId): void;
+  onGroupCallInvitation(
+    remoteUserId: UserId,
+    callId: CallId,
+    groupId: Buffer | undefined,
+    callLinkRootKey: Buffer | undefined,
+    callLinkEpoch: number | undefined
+  ): void;
+  onCallTransferOffered(remoteUuid: Buffer, callId: CallId): void;
+  onCallRecord(remoteUserId: UserId, record: CallRecord): void;
+  onGroupRingRecord(record: GroupRingRecord): void;
   onCallState(remoteUserId: UserId, state: CallState): void;
   onCallEnded(
     remoteUserId: UserId,
@@ -3098,6 +3940,7 @@ export interface CallManagerCallbacks {
   onRemoteAudioEnabled(remoteUserId: UserId, enabled: boolean): void;
   onRemoteVideoEnabled(remoteUserId: UserId, enabled: boolean): void;
   onRemoteSharingScreen(remoteUserId: UserId, enabled: boolean): void;
+  onRemoteHold(remoteUserId: UserId, onHold: boolean): void;
   onSendOffer(
     remoteUserId: UserId,
     remoteDeviceId: DeviceId,
@@ -3176,6 +4019,19 @@ export interface CallManagerCallbacks {
     statusCode: number,
     rawInfo: RawPeekInfo | undefined
   ): void;
+  handleWatchedPeekChanged(
+    id: Buffer,
+    statusCode: number,
+    rawInfo: RawPeekInfo | undefined
+  ): void;
+  handleCallSummary(
+    clientId: GroupCallClientId,
+    summary: GroupCallSummary
+  ): void;
+  handleAdminActionResults(
+    clientId: GroupCallClientId,
+    results: Array<AdminActionResult>
+  ): void;
   handleEnded(clientId: GroupCallClientId, reason: GroupCallEndReason): void;
 
   onLogMessage(
@@ -3205,6 +4061,7 @@ export enum CallEndedReason {
   ReceivedOfferExpired = 'ReceivedOfferExpired',
   ReceivedOfferWhileActive = 'ReceivedOfferWhileActive',
   ReceivedOfferWithGlare = 'ReceivedOfferWithGlare',
+  ReceivedOfferDeclinedByPolicy = 'ReceivedOfferDeclinedByPolicy',
   SignalingFailure = 'SignalingFailure',
   GlareFailure = 'GlareFailure',
   ConnectionFailure = 'ConnectionFailure',
@@ -3213,8 +4070,105 @@ export enum CallEndedReason {
   AcceptedOnAnotherDevice = 'AcceptedOnAnotherDevice',
   DeclinedOnAnotherDevice = 'DeclinedOnAnotherDevice',
   BusyOnAnotherDevice = 'BusyOnAnotherDevice',
+  EscalatedToGroupCall = 'EscalatedToGroupCall',
+  Transferred = 'Transferred',
+}
+
+// Where the remote user has invited us to continue a 1:1 call.
+export type GroupCallInvitation =
+  | { type: 'group'; groupId: Buffer }
+  | { type: 'callLink'; rootKey: CallLinkRootKey };
+
+// How a 1:1 call ended.
+export enum CallOutcome {
+  Accepted = 'Accepted',
+  Missed = 'Missed',
+  Unanswered = 'Unanswered',
+  Declined = 'Declined',
+  Cancelled = 'Cancelled',
+  AcceptedOnAnotherDevice = 'AcceptedOnAnotherDevice',
+  DeclinedOnAnotherDevice = 'DeclinedOnAnotherDevice',
+  DeclinedByPolicy = 'DeclinedByPolicy',
+  Busy = 'Busy',
+  NeedPermission = 'NeedPermission',
+  Superseded = 'Superseded',
+  EscalatedToGroupCall = 'EscalatedToGroupCall',
+  Transferred = 'Transferred',
+  Failed = 'Failed',
+}
+
+// How long setting up a 1:1 call took, in milliseconds from when this device
+// started handling the call. Milestones that weren't reached are left out.
+export type CallSetupMetrics = {
+  // Incoming calls only: how long the offer took to arrive.
+  offerAgeMillis?: number;
+  offerSentMillis?: number;
+  answerSentMillis?: number;
+  answerReceivedMillis?: number;
+  firstIceCandidateSentMillis?: number;
+  firstIceCandidateReceivedMillis?: number;
+  iceConnectedMillis?: number;
+  acceptedMillis?: number;
+  firstMediaMillis?: number;
+};
+
+// How a 1:1 call went, reported once the call has concluded.
+export type CallRecord = {
+  callId: CallId;
+  isIncoming: boolean;
+  isVideoCall: boolean;
+  // When this device started handling the call.
+  startedMillis: number;
+  // When the call was accepted, if it was.
+  answeredMillis?: number;
+  endedMillis: number;
+  outcome: CallOutcome;
+  answeredByDeviceId?: DeviceId;
+  setup: CallSetupMetrics;
+};
+
+// How a ring for a group call ended.
+export enum GroupRingOutcome {
+  Accepted = 'Accepted',
+  AcceptedOnAnotherDevice = 'AcceptedOnAnotherDevice',
+  Declined = 'Declined',
+  DeclinedOnAnotherDevice = 'DeclinedOnAnotherDevice',
+  DeclinedByPolicy = 'DeclinedByPolicy',
+  Busy = 'Busy',
+  BusyOnAnotherDevice = 'BusyOnAnotherDevice',
+  CancelledByRinger = 'CancelledByRinger',
+  Expired = 'Expired',
 }
 
+// How a ring for a group call went, reported once it's over.
+export type GroupRingRecord = {
+  groupId: Buffer;
+  ringId: bigint;
+  senderId: Buffer;
+  receivedMillis: number;
+  endedMillis: number;
+  outcome: GroupRingOutcome;
+};
+
+// A daily window in minutes since local midnight. If the start equals the
+// end, the window covers the whole day.
+export type QuietHours = {
+  startMinute: number;
+  endMinute: number;
+  utcOffsetMinutes: number;
+};
+
+export type DoNotDisturbSettings = {
+  // When calls don't ring at all, if ever.
+  quietHours?: QuietHours;
+  // 1:1 callers that always ring.
+  allowedRemoteUserIds: Array<UserId>;
+  // Group ring senders that always ring.
+  allowedUserIds: Array<Buffer>;
+  // How many group rings can be outstanding at once, if limited.
+  maxConcurrentGroupRings?: number;
+};
+
 export enum CallLogLevel {
   Off,
   Error,
@@ -25,6 +25,7 @@ use crate::{
     },
     core::{
         call::Call,
+        call_record::{self, CallRecord, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         platform::{Platform, PlatformItem},
@@ -33,7 +34,7 @@ use crate::{
     lite::{
         call_links::{CallLinkRestrictions, CallLinkState, Empty},
         http, sfu,
-        sfu::{DemuxId, PeekInfo, PeekResult, UserId},
+        sfu::{DemuxId, PeekInfo, PeekResult, PeekWatchId, UserId},
     },
     webrtc::{
         media::{MediaStream, VideoTrack},
@@ -48,12 +49,16 @@ const CALL_MANAGER_CLASS: &str = "CallManager";
 const GROUP_CALL_CLASS: &str = "GroupCall";
 const HTTP_HEADER_CLASS: &str = jni_class_name!(org.signal.ringrtc.HttpHeader);
 const HTTP_RESULT_CLASS: &str = jni_class_name!(org.signal.ringrtc.CallManager::HttpResult);
+const CALL_SUMMARY_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::CallSummary);
+const CALL_RECORD_CLASS: &str = jni_class_name!(org.signal.ringrtc.CallRecord);
+const GROUP_RING_RECORD_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupRingRecord);
 const PEEK_INFO_CLASS: &str = jni_class_name!(org.signal.ringrtc.PeekInfo);
 const REACTION_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::Reaction);
 const REMOTE_DEVICE_STATE_CLASS: &str =
     jni_class_name!(org.signal.ringrtc.GroupCall::RemoteDeviceState);
 const RECEIVED_AUDIO_LEVEL_CLASS: &str =
     jni_class_name!(org.signal.ringrtc.GroupCall::ReceivedAudioLevel);
+const SPEAKING_TIME_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::SpeakingTime);
 
 /// Android implementation for platform::Platform::AppIncomingMedia
 pub type AndroidMediaStream = JavaMediaStream;
@@ -909,6 +914,160 @@ impl Platform for AndroidPlatform {
         Ok(())
     }
 
+    fn on_call_record(&self, remote_peer: &Self::AppRemotePeer, record: CallRecord) -> Result<()> {
+        info!(
+            "on_call_record(): call_id: {}, outcome: {:?}",
+            record.call_id, record.outcome
+        );
+
+        let env = &mut self.java_env()?;
+        let jni_call_manager = self.jni_call_manager.as_obj();
+
+        let jni_remote_peer = remote_peer.as_obj();
+
+        // Setup offsets that weren't reached are passed as -1.
+        let setup_millis: Vec<jlong> = record
+            .setup
+            .offsets()
+            .iter()
+            .map(|offset| offset.map_or(-1, |offset| offset.as_millis() as jlong))
+            .collect();
+        let jni_setup_millis = env.new_long_array(setup_millis.len() as i32)?;
+        env.set_long_array_region(&jni_setup_millis, 0, &setup_millis)?;
+        let jni_setup_millis = JObject::from(jni_setup_millis);
+
+        let call_record_class = self.class_cache.get_class(CALL_RECORD_CLASS)?;
+        let args = jni_args!((
+            u64::from(record.call_id) as jlong => long,
+            record.direction == CallDirection::Incoming => boolean,
+            record.media_type == CallMediaType::Video => boolean,
+            call_record::millis_since_epoch(record.started) as jlong => long,
+            record
+                .answered
+                .map_or(-1, |answered| call_record::millis_since_epoch(answered) as jlong) => long,
+            call_record::millis_since_epoch(record.ended) as jlong => long,
+            record.outcome as jint => int,
+            record.answered_by_device_id.map_or(-1, |device_id| device_id as jint) => int,
+            jni_setup_millis => [long],
+        ) -> void);
+        let jni_call_record = env.new_object(call_record_class, args.sig, &args.args)?;
+
+        jni_call_method(
+            env,
+            jni_call_manager,
+            "onCallRecord",
+            jni_args!((
+                jni_remote_peer => org.signal.ringrtc.Remote,
+                jni_call_record => org.signal.ringrtc.CallRecord,
+            ) -> void),
+        )?;
+
+        Ok(())
+    }
+
+    fn on_group_ring_record(&self, record: GroupRingRecord) -> Result<()> {
+        info!(
+            "on_group_ring_record(): ring_id: {}, outcome: {:?}",
+            record.ring_id, record.outcome
+        );
+
+        let env = &mut self.java_env()?;
+        let jni_call_manager = self.jni_call_manager.as_obj();
+
+        let jni_group_id = JObject::from(env.byte_array_from_slice(&record.group_id)?);
+        let jni_sender_id = JObject::from(env.byte_array_from_slice(&record.sender_id)?);
+
+        let group_ring_record_class = self.class_cache.get_class(GROUP_RING_RECORD_CLASS)?;
+        let args = jni_args!((
+            jni_group_id => [byte],
+            i64::from(record.ring_id) as jlong => long,
+            jni_sender_id => [byte],
+            call_record::millis_since_epoch(record.received) as jlong => long,
+            call_record::millis_since_epoch(record.ended) as jlong => long,
+            record.outcome as jint => int,
+        ) -> void);
+        let jni_group_ring_record =
+            env.new_object(group_ring_record_class, args.sig, &args.args)?;
+
+        jni_call_method(
+            env,
+            jni_call_manager,
+            "onGroupRingRecord",
+            jni_args!((
+                jni_group_ring_record => org.signal.ringrtc.GroupRingRecord,
+            ) -> void),
+        )?;
+
+        Ok(())
+    }
+
+    fn on_group_call_invitation(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        info!("on_group_call_invitation(): call_id: {}", call_id);
+
+        let env = &mut self.java_env()?;
+        let jni_call_manager = self.jni_call_manager.as_obj();
+
+        let jni_remote_peer = remote_peer.as_obj();
+        let call_id_jlong = u64::from(call_id) as jlong;
+        let (group_id, root_key, epoch) = match invitation {
+            signaling::GroupCallInvitation::Group { group_id } => (Some(group_id), None, None),
+            signaling::GroupCallInvitation::CallLink { root_key, epoch } => {
+                (None, Some(root_key), *epoch)
+            }
+        };
+        let jni_group_id = match group_id {
+            Some(group_id) => JObject::from(env.byte_array_from_slice(group_id)?),
+            None => JObject::null(),
+        };
+        let jni_root_key = match root_key {
+            Some(root_key) => JObject::from(env.byte_array_from_slice(root_key)?),
+            None => JObject::null(),
+        };
+        let jni_epoch = self.get_optional_u32_long_object(env, epoch)?;
+
+        jni_call_method(
+            env,
+            jni_call_manager,
+            "onGroupCallInvitation",
+            jni_args!((
+                jni_remote_peer => org.signal.ringrtc.Remote,
+                call_id_jlong => long,
+                jni_group_id => [byte],
+                jni_root_key => [byte],
+                jni_epoch => java.lang.Long,
+            ) -> void),
+        )?;
+
+        Ok(())
+    }
+
+    fn on_call_transfer_offered(&self, remote_user_id: UserId, call_id: CallId) -> Result<()> {
+        info!("on_call_transfer_offered(): call_id: {}", call_id);
+
+        let env = &mut self.java_env()?;
+        let jni_call_manager = self.jni_call_manager.as_obj();
+
+        let jni_remote_user_id = JObject::from(env.byte_array_from_slice(&remote_user_id)?);
+        let call_id_jlong = u64::from(call_id) as jlong;
+
+        jni_call_method(
+            env,
+            jni_call_manager,
+            "onCallTransferOffered",
+            jni_args!((
+                jni_remote_user_id => [byte],
+                call_id_jlong => long,
+            ) -> void),
+        )?;
+
+        Ok(())
+    }
+
     // Group Calls
 
     fn group_call_ring_update(
@@ -1261,6 +1420,41 @@ impl Platform for AndroidPlatform {
         }
     }
 
+    fn handle_presenter_changed(
+        &self,
+        client_id: group_call::ClientId,
+        current_presenter: Option<DemuxId>,
+    ) {
+        info!(
+            "handle_presenter_changed(): client_id: {}, current_presenter: {:?}",
+            client_id, current_presenter,
+        );
+
+        if let Ok(env) = &mut self.java_env() {
+            let jni_current_presenter =
+                match self.get_optional_u32_long_object(env, current_presenter) {
+                    Ok(v) => v,
+                    Err(error) => {
+                        error!("{:?}", error);
+                        return;
+                    }
+                };
+
+            let result = jni_call_method(
+                env,
+                self.jni_call_manager.as_obj(),
+                "handlePresenterChanged",
+                jni_args!((
+                    client_id as jlong => long,
+                    jni_current_presenter => java.lang.Long,
+                ) -> void),
+            );
+            if result.is_err() {
+                error!("jni_call_method: {:?}", result.err());
+            }
+        }
+    }
+
     fn handle_join_state_changed(
         &self,
         client_id: group_call::ClientId,
@@ -1513,6 +1707,105 @@ impl Platform for AndroidPlatform {
         }
     }
 
+    fn handle_call_summary(
+        &self,
+        client_id: group_call::ClientId,
+        summary: group_call::CallSummary,
+    ) {
+        info!("handle_call_summary():");
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (2) + elements (N * 2 per speaking time).
+            let capacity = (5 + 2 + summary.speaking_time.len() * 2) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<GroupCall.SpeakingTime>
+                let speaking_time_class = self.class_cache.get_class(SPEAKING_TIME_CLASS)?;
+
+                let list = jni_new_arraylist(env, summary.speaking_time.len())?;
+                let speaking_times_list = env.get_list(&list)?;
+
+                for (user_id, speaking_time) in &summary.speaking_time {
+                    let jni_user_id = JObject::from(env.byte_array_from_slice(user_id)?);
+                    let args = jni_args!((
+                        jni_user_id => [byte],
+                        speaking_time.as_millis() as jlong => long,
+                    ) -> void);
+
+                    let speaking_time_obj =
+                        match env.new_object(speaking_time_class, args.sig, &args.args) {
+                            Ok(v) => v,
+                            Err(error) => {
+                                error!("jni_speaking_time: {:?}", error);
+                                continue;
+                            }
+                        };
+
+                    let result = speaking_times_list.add(env, &speaking_time_obj);
+                    if result.is_err() {
+                        error!("jni_speaking_time.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let call_summary_class = self.class_cache.get_class(CALL_SUMMARY_CLASS)?;
+                let args = jni_args!((
+                    summary.call_duration.as_millis() as jlong => long,
+                    summary.join_duration.as_millis() as jlong => long,
+                    summary.peak_device_count as jint => int,
+                    summary.unique_user_count as jint => int,
+                    speaking_times_list => java.util.List,
+                    summary.reactions_sent as jint => int,
+                    summary.reactions_received as jint => int,
+                    summary.raised_hand_count as jint => int,
+                    summary.media.average_send_rate.as_bps() as jlong => long,
+                    summary.media.average_receive_rate.as_bps() as jlong => long,
+                    summary.media.send_packets_lost_pct => float,
+                    summary.media.receive_packets_lost_pct => float,
+                    summary.reconnect_count as jint => int,
+                ) -> void);
+                let call_summary_obj = env.new_object(call_summary_class, args.sig, &args.args)?;
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleCallSummary",
+                    jni_args!((
+                        client_id as jlong => long,
+                        call_summary_obj => org.signal.ringrtc.GroupCall::CallSummary,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_call_summary: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_watched_peek_changed(&self, id: PeekWatchId, peek_result: PeekResult) {
+        info!("handle_watched_peek_changed():");
+
+        if let Ok(env) = &mut self.java_env() {
+            let result = env.with_local_frame(5, |env| -> Result<()> {
+                let jni_id = JObject::from(env.byte_array_from_slice(&id)?);
+                let result_object = self.make_peek_result_object(env, peek_result)?;
+                jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleWatchedPeekChanged",
+                    jni_args!((
+                        jni_id => [byte],
+                        result_object => org.signal.ringrtc.CallManager::HttpResult,
+                    ) -> void),
+                )?;
+                Ok(())
+            });
+            if result.is_err() {
+                error!("handle_watched_peek_changed: {:?}", result.err());
+            }
+        }
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
         info!("handle_ended():");
 
@@ -1557,12 +1850,16 @@ impl AndroidPlatform {
             jni_class_name!(org.signal.ringrtc.GroupCall::JoinState),
             jni_class_name!(org.signal.ringrtc.GroupCall::SpeechEvent),
             CALL_LINK_STATE_CLASS,
+            CALL_SUMMARY_CLASS,
+            CALL_RECORD_CLASS,
+            GROUP_RING_RECORD_CLASS,
             HTTP_HEADER_CLASS,
             HTTP_RESULT_CLASS,
             PEEK_INFO_CLASS,
             REACTION_CLASS,
             REMOTE_DEVICE_STATE_CLASS,
             RECEIVED_AUDIO_LEVEL_CLASS,
+            SPEAKING_TIME_CLASS,
             jni_class_name!(java.lang.Boolean),
             jni_class_name!(java.lang.Float),
             jni_class_name!(java.lang.Integer),
@@ -1959,6 +2256,40 @@ impl AndroidPlatform {
         }
     }
 
+    fn make_peek_result_object<'a>(
+        &self,
+        env: &mut JNIEnv<'a>,
+        peek_result: PeekResult,
+    ) -> Result<JObject<'a>> {
+        let http_result_class = self.class_cache.get_class(HTTP_RESULT_CLASS)?;
+        match peek_result {
+            Ok(peek_info) => {
+                let joined_members = peek_info.unique_users();
+
+                // Set a frame capacity of min (5) + objects (5) + elements (N * 1 object per element).
+                let capacity = (10 + joined_members.len()) as i32;
+                env.with_local_frame_returning_local(capacity, |env| -> Result<_> {
+                    let jni_peek_info = self.make_peek_info_object(
+                        env,
+                        &peek_info,
+                        &mut joined_members.into_iter(),
+                    )?;
+
+                    let args = jni_args!((
+                        jni_peek_info => java.lang.Object,
+                    ) -> void);
+                    Ok(env.new_object(http_result_class, args.sig, &args.args)?)
+                })
+            }
+            Err(status) => {
+                let args = jni_args!((
+                    status.code as jshort => short,
+                ) -> void);
+                Ok(env.new_object(http_result_class, args.sig, &args.args)?)
+            }
+        }
+    }
+
     fn make_peek_info_object<'a>(
         &self,
         env: &mut JNIEnv<'a>,
@@ -2070,64 +2401,14 @@ impl sfu::Delegate for AndroidPlatform {
         };
         let jni_call_manager = self.jni_call_manager.as_obj();
 
-        let http_result_class = match self.class_cache.get_class(HTTP_RESULT_CLASS) {
+        let result_object = match self.make_peek_result_object(&mut env, peek_result) {
             Ok(v) => v,
             Err(error) => {
-                error!("http_result_class: {:?}", error);
+                error!("make_peek_result_object: {:?}", error);
                 return;
             }
         };
 
-        let result_object = match peek_result {
-            Ok(peek_info) => {
-                let joined_members = peek_info.unique_users();
-
-                // Set a frame capacity of min (5) + objects (5) + elements (N * 1 object per element).
-                let capacity = (10 + joined_members.len()) as i32;
-                let result = env.with_local_frame_returning_local(capacity, |env| -> Result<_> {
-                    let jni_peek_info = match self.make_peek_info_object(
-                        env,
-                        &peek_info,
-                        &mut joined_members.into_iter(),
-                    ) {
-                        Ok(value) => value,
-                        Err(e) => {
-                            error!("make_peek_info_object: {:?}", e);
-                            return Ok(JObject::null());
-                        }
-                    };
-
-                    let args = jni_args!((
-                        jni_peek_info => java.lang.Object,
-                    ) -> void);
-                    Ok(env.new_object(http_result_class, args.sig, &args.args)?)
-                });
-                match result {
-                    Ok(v) if !v.is_null() => v,
-                    Ok(_) => {
-                        // Already logged, so just bail out early.
-                        return;
-                    }
-                    Err(error) => {
-                        error!("new HttpResult(PeekInfo): {:?}", error);
-                        return;
-                    }
-                }
-            }
-            Err(status) => {
-                let args = jni_args!((
-                    status.code as jshort => short,
-                ) -> void);
-                match env.new_object(http_result_class, args.sig, &args.args) {
-                    Ok(v) => v,
-                    Err(error) => {
-                        error!("new HttpResult(short): {:?}", error);
-                        return;
-                    }
-                }
-            }
-        };
-
         let result = jni_call_method(
             &mut env,
             jni_call_manager,
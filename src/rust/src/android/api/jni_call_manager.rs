@@ -11,7 +11,7 @@
 use std::{borrow::Cow, time::Duration};
 
 use jni::{
-    objects::{JByteArray, JClass, JObject, JString},
+    objects::{JByteArray, JClass, JObject, JObjectArray, JString},
     strings::JavaStr,
     sys::{jboolean, jint, jlong, jobject},
     JNIEnv,
@@ -22,7 +22,10 @@ use crate::{
         android_platform::AndroidPlatform, call_manager, call_manager::AndroidCallManager, error,
     },
     common::{CallConfig, CallMediaType, DataMode, DeviceId},
-    core::{connection::Connection, group_call, signaling, util::try_scoped},
+    core::{
+        connection::Connection, group_call, incoming_call_policy::QuietHours, signaling,
+        util::try_scoped,
+    },
     webrtc,
 };
 
@@ -214,6 +217,169 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcHangup(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcSetOnHold(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    on_hold: jboolean,
+) {
+    match call_manager::set_on_hold(call_manager as *mut AndroidCallManager, on_hold != 0) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcEscalateToGroupCall(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+) {
+    match call_manager::escalate_to_group_call(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcTransferCall(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    remote_user_id: JByteArray,
+    target_device: jint,
+) {
+    match call_manager::transfer_call(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        remote_user_id,
+        target_device as DeviceId,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcAcceptCallTransfer(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    jni_remote: JObject,
+    call_id: jlong,
+    call_media_type: jint,
+    local_device: jint,
+) {
+    match call_manager::accept_call_transfer(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        jni_remote,
+        call_id,
+        CallMediaType::from_i32(call_media_type),
+        local_device as DeviceId,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcSetCallWaitingEnabled(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    enabled: jboolean,
+) {
+    match call_manager::set_call_waiting_enabled(
+        call_manager as *mut AndroidCallManager,
+        enabled != 0,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcSetDoNotDisturb(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    enabled: jboolean,
+    has_quiet_hours: jboolean,
+    start_minute: jint,
+    end_minute: jint,
+    utc_offset_minutes: jint,
+    allowed_remotes: JObjectArray,
+    allowed_uuids: JByteArray,
+    max_concurrent_group_rings: jint,
+) {
+    let quiet_hours = if has_quiet_hours != 0 {
+        Some(QuietHours {
+            start_minute: start_minute as u16,
+            end_minute: end_minute as u16,
+            utc_offset_minutes,
+        })
+    } else {
+        None
+    };
+    let max_concurrent_group_rings = if max_concurrent_group_rings < 0 {
+        None
+    } else {
+        Some(max_concurrent_group_rings as usize)
+    };
+
+    match call_manager::set_do_not_disturb(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        enabled != 0,
+        quiet_hours,
+        allowed_remotes,
+        allowed_uuids,
+        max_concurrent_group_rings,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcSwapCalls(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+) {
+    match call_manager::swap_calls(call_manager as *mut AndroidCallManager) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCancelGroupRing(
@@ -408,13 +574,15 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcReceivedHttp
     call_manager: jlong,
     request_id: jlong,
     status_code: jint,
+    headers: JObject,
     body: JByteArray,
 ) {
     match call_manager::received_http_response(
-        &env,
+        &mut env,
         call_manager as *mut AndroidCallManager,
         request_id,
         status_code,
+        headers,
         body,
     ) {
         Ok(v) => v,
@@ -765,6 +933,88 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcPeekCallLink
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcWatchGroupCallPeek(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    group_id: JByteArray,
+    sfu_url: JString,
+    membership_proof: JByteArray,
+    jni_serialized_group_members: JByteArray,
+) {
+    match call_manager::watch_group_call_peek(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        group_id,
+        sfu_url,
+        membership_proof,
+        jni_serialized_group_members,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcWatchCallLinkPeek(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    sfu_url: JString,
+    auth_credential_presentation: JByteArray,
+    root_key: JByteArray,
+) {
+    match call_manager::watch_call_link_peek(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        sfu_url,
+        auth_credential_presentation,
+        root_key,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcUnwatchPeek(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    id: JByteArray,
+) {
+    match call_manager::unwatch_peek(&mut env, call_manager as *mut AndroidCallManager, id) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcRefreshPeek(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    id: JByteArray,
+) {
+    match call_manager::refresh_peek(&mut env, call_manager as *mut AndroidCallManager, id) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcCreateGroupCallClient(
@@ -1231,6 +1481,27 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRaiseHand(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRequestToPresent(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    take_over: bool,
+) {
+    match call_manager::request_to_present(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        take_over,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallId_ringrtcFromEraId(
@@ -5,10 +5,10 @@
 
 //! Android CallManager Interface.
 
-use std::{borrow::Cow, convert::TryFrom, panic, sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::HashMap, convert::TryFrom, panic, sync::Arc, time::Duration};
 
 use jni::{
-    objects::{GlobalRef, JByteArray, JClass, JObject, JString},
+    objects::{GlobalRef, JByteArray, JClass, JObject, JObjectArray, JString},
     sys::{jint, jlong},
     JNIEnv,
 };
@@ -26,7 +26,9 @@ use crate::{
     core::{
         call_manager::CallManager,
         connection::Connection,
-        group_call, signaling,
+        group_call,
+        incoming_call_policy::{DoNotDisturb, IncomingCallPolicy, QuietHours},
+        signaling,
         util::{ptr_as_box, ptr_as_mut},
     },
     error::RingRtcError,
@@ -224,6 +226,103 @@ pub fn hangup(call_manager: *mut AndroidCallManager) -> Result<()> {
     call_manager.hangup()
 }
 
+/// Application notification to put the active call on hold or resume it
+pub fn set_on_hold(call_manager: *mut AndroidCallManager, on_hold: bool) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_on_hold(on_hold)
+}
+
+/// Application notification to continue the active call as a group call
+pub fn escalate_to_group_call(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.escalate_to_group_call(client_id)
+}
+
+/// Application notification to hand the active call over to another of
+/// the user's devices
+pub fn transfer_call(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    remote_user_id: JByteArray,
+    target_device_id: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.transfer_call(env.convert_byte_array(remote_user_id)?, target_device_id)
+}
+
+/// Application notification to take over a call offered by another of
+/// the user's devices
+pub fn accept_call_transfer(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    jni_remote: JObject,
+    call_id: jlong,
+    call_media_type: CallMediaType,
+    local_device_id: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_id = CallId::from(call_id);
+    let app_remote_peer = env.new_global_ref(jni_remote)?;
+    call_manager.create_outgoing_call(app_remote_peer, call_id, call_media_type, local_device_id)
+}
+
+/// Application notification to enable or disable call waiting
+pub fn set_call_waiting_enabled(
+    call_manager: *mut AndroidCallManager,
+    enabled: bool,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_call_waiting_enabled(enabled)
+}
+
+/// Application notification to turn do not disturb on or off
+pub fn set_do_not_disturb(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    enabled: bool,
+    quiet_hours: Option<QuietHours>,
+    allowed_remotes: JObjectArray,
+    allowed_uuids: JByteArray,
+    max_concurrent_group_rings: Option<usize>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+
+    let policy: Option<Box<dyn IncomingCallPolicy<AndroidPlatform>>> = if enabled {
+        let mut allowed_peers = Vec::new();
+        for i in 0..env.get_array_length(&allowed_remotes)? {
+            let jni_remote = env.get_object_array_element(&allowed_remotes, i)?;
+            allowed_peers.push(env.new_global_ref(jni_remote)?);
+        }
+
+        // The allowed UUIDs are serialized as 16-byte chunks.
+        let allowed_users = env
+            .convert_byte_array(allowed_uuids)?
+            .chunks_exact(16)
+            .map(|uuid| uuid.to_vec())
+            .collect();
+
+        Some(Box::new(DoNotDisturb {
+            allowed_peers,
+            allowed_users,
+            quiet_hours,
+            max_concurrent_group_rings,
+            ..Default::default()
+        }))
+    } else {
+        None
+    };
+    call_manager.set_incoming_call_policy(policy)
+}
+
+/// Application notification to hold the active call and resume the held one
+pub fn swap_calls(call_manager: *mut AndroidCallManager) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.swap_calls()
+}
+
 /// Application notification cancelling a group call ring
 pub fn cancel_group_ring(
     env: &JNIEnv,
@@ -427,10 +526,11 @@ pub fn received_call_message(
 
 /// Application notification of received HTTP response.
 pub fn received_http_response(
-    env: &JNIEnv,
+    env: &mut JNIEnv,
     call_manager: *mut AndroidCallManager,
     request_id: jlong,
     status_code: jint,
+    jni_headers: JObject,
     body: JByteArray,
 ) -> Result<()> {
     let call_manager = unsafe { ptr_as_mut(call_manager)? };
@@ -442,8 +542,31 @@ pub fn received_http_response(
         env.convert_byte_array(body)?
     };
 
+    // Convert Java list of HttpHeader into a Rust HashMap.
+    let mut headers = HashMap::new();
+    if !jni_headers.is_null() {
+        let jni_header_list = env.get_list(&jni_headers)?;
+        let mut iterator = jni_header_list.iter(env)?;
+        while let Some(jni_header) = iterator.next(env)? {
+            const STRING_TYPE: &str = jni_signature!(java.lang.String);
+
+            const NAME_FIELD: &str = "name";
+            let name =
+                JString::from(jni_get_field(env, &jni_header, NAME_FIELD, STRING_TYPE)?.l()?);
+            let name: String = env.get_string(&name)?.into();
+
+            const VALUE_FIELD: &str = "value";
+            let value =
+                JString::from(jni_get_field(env, &jni_header, VALUE_FIELD, STRING_TYPE)?.l()?);
+            let value: String = env.get_string(&value)?.into();
+
+            headers.insert(name, value);
+        }
+    }
+
     let response = http::Response {
         status: (status_code as u16).into(),
+        headers,
         body,
     };
 
@@ -632,14 +755,9 @@ pub fn update_call_link(
     } else {
         Some(env.get_string(&new_name)?)
     };
-    let encrypted_name = new_name.map(|name| {
-        let name = Cow::from(&name);
-        if name.is_empty() {
-            vec![]
-        } else {
-            root_key.encrypt(name.as_bytes(), rand::rngs::OsRng)
-        }
-    });
+    let encrypted_name = new_name
+        .map(|name| call_links::encrypt_name(&root_key, &Cow::from(&name), rand::rngs::OsRng))
+        .transpose()?;
     let new_restrictions = jint_to_restrictions(new_restrictions);
     let new_revoked = match new_revoked {
         0 => Some(false),
@@ -659,6 +777,7 @@ pub fn update_call_link(
             encrypted_name: encrypted_name.as_deref(),
             restrictions: new_restrictions,
             revoked: new_revoked,
+            ..CallLinkUpdateRequest::default()
         },
         Box::new(move |result| {
             platform.handle_call_link_result(request_id as u32, result);
@@ -779,6 +898,66 @@ pub fn peek_call_link_call(
     Ok(())
 }
 
+pub fn watch_group_call_peek(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    group_id: JByteArray,
+    sfu_url: JString,
+    membership_proof: JByteArray,
+    jni_serialized_group_members: JByteArray,
+) -> Result<()> {
+    let group_id = env.convert_byte_array(group_id)?;
+
+    let sfu_url = env.get_string(&sfu_url)?.into();
+
+    let membership_proof = env.convert_byte_array(membership_proof)?;
+
+    let group_members =
+        deserialize_to_group_member_info(env.convert_byte_array(jni_serialized_group_members)?)?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.watch_group_call_peek(group_id, sfu_url, membership_proof, group_members)
+}
+
+pub fn watch_call_link_peek(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    sfu_url: JString,
+    auth_credential_presentation: JByteArray,
+    root_key: JByteArray,
+) -> Result<()> {
+    let sfu_url = env.get_string(&sfu_url)?.into();
+
+    let auth_credential_presentation = env.convert_byte_array(auth_credential_presentation)?;
+    let root_key =
+        call_links::CallLinkRootKey::try_from(env.convert_byte_array(root_key)?.as_slice())?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.watch_call_link_peek(sfu_url, &auth_credential_presentation, root_key)
+}
+
+pub fn unwatch_peek(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    id: JByteArray,
+) -> Result<()> {
+    let id = env.convert_byte_array(id)?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.unwatch_peek(&id)
+}
+
+pub fn refresh_peek(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    id: JByteArray,
+) -> Result<()> {
+    let id = env.convert_byte_array(id)?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.refresh_peek(&id)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_group_call_client(
     env: &mut JNIEnv,
@@ -1181,6 +1360,16 @@ pub fn raise_hand(
     Ok(())
 }
 
+pub fn request_to_present(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    take_over: bool,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.request_to_present(client_id, take_over);
+    Ok(())
+}
+
 fn jint_to_restrictions(raw_restrictions: jint) -> Option<CallLinkRestrictions> {
     match raw_restrictions {
         0 => Some(CallLinkRestrictions::None),
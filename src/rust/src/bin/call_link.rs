@@ -3,21 +3,38 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 //
 
+//! A scriptable tool for administering call links against a calling server, or a local stand-in.
+//!
+//! Credentials are issued locally from the server's secret zkparams, so this only works with
+//! servers you control (by default, the zkparams used for testing in Signal-Calling-Service).
+//!
+//! Exit codes:
+//! - 0: success
+//! - 1: the server responded with an error status
+//! - 2: invalid arguments or input files
+//! - 3: no valid response from the server
+
 use std::{
     collections::HashMap,
     hash::{Hash, Hasher},
-    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{mpsc, Arc},
     time::{Duration, SystemTime},
 };
 
 use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use rand::SeedableRng;
 use ringrtc::lite::{
     call_links::{
-        CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkUpdateRequest,
+        self, CallLinkDeleteRequest, CallLinkEpoch, CallLinkRestrictions, CallLinkRootKey,
+        CallLinkState, CallLinkUpdateRequest, CallLinkUrl,
     },
     http::{self, sim as sim_http, Client},
+    sfu::{self, PeekDeviceInfo, PeekInfo},
 };
+use serde_json::json;
 use uuid::Uuid;
 use zkgroup::call_links::CallLinkSecretParams;
 
@@ -27,12 +44,13 @@ static LOG: Log = Log;
 
 impl log::Log for Log {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        metadata.level() <= log::Level::Debug
+        metadata.level() <= log::max_level()
     }
 
     fn log(&self, record: &log::Record) {
         if self.enabled(record.metadata()) {
-            println!("{} - {}", record.level(), record.args());
+            // Logs go to stderr so that stdout can be parsed by scripts.
+            eprintln!("{} - {}", record.level(), record.args());
         }
     }
 
@@ -42,12 +60,203 @@ impl log::Log for Log {
 // These are the zkparams used for testing in Signal-Calling-Service.
 const DEFAULT_ZKPARAMS: &str = "AMJqvmQRYwEGlm0MSy6QFPIAvgOVsqRASNX1meQyCOYHJFqxO8lITPkow5kmhPrsNbu9JhVfKFwesVSKhdZaqQko3IZlJZMqP7DDw0DgTWpdnYzSt0XBWT50DM1cw1nCUXXBZUiijdaFs+JRlTKdh54M7sf43pFxyMHlS3URH50LOeR8jVQKaUHi1bDP2GR9ZXp3Ot9Fsp0pM4D/vjL5PwoOUuzNNdpIqUSFhKVrtazwuHNn9ecHMsFsN0QPzByiDA8nhKcGpdzyWUvGjEDBvpKkBtqjo8QuXWjyS3jSl2oJ/Z4Fh3o2N1YfD2aWV/K88o+TN2/j2/k+KbaIZgmiWwppLU+SYGwthxdDfZgnbaaGT/vMYX9P5JlUWSuP3xIxDzPzxBEFho67BP0Pvux+0a5nEOEVEpfRSs61MMvwNXEKZtzkO0QFbOrFYrPntyb7ToqNi66OQNyTfl/J7kqFZg2MTm3CKjHTAIvVMFAGCIamsrT9sWXOtuNeMS94xazxDA==";
 
-const USER_ID: [u8; 16] = [0; 16]; // null UUID
-const ADMIN_PASSKEY: &[u8] = &[1, 2, 3, 4, 5];
+const DEFAULT_ADMIN_PASSKEY: &[u8] = &[1, 2, 3, 4, 5];
+
+#[derive(Debug, Parser)]
+#[command(about = "Administers call links on a calling server", long_about = None)]
+struct Cli {
+    /// The calling server (or local stand-in) to talk to.
+    #[arg(long, default_value = "http://localhost:8090")]
+    url: String,
+
+    /// The server's GenericServerSecretParams (not public!), in base64, used to issue
+    /// credentials. Defaults to the zkparams used for testing.
+    #[arg(long, conflicts_with = "zkparams_file")]
+    zkparams: Option<String>,
+
+    /// A file containing the value for --zkparams.
+    #[arg(long)]
+    zkparams_file: Option<PathBuf>,
+
+    /// The user to issue credentials for.
+    #[arg(long, default_value_t = Uuid::nil())]
+    user_id: Uuid,
+
+    /// Print results as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// Log requests and other details to stderr.
+    #[arg(long, short)]
+    verbose: bool,
+
+    /// Save every request and response to this file, for replaying in tests (see
+    /// `http::ReplayingClient`). Authorization headers are redacted.
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct LinkArgs {
+    /// Any word you want; it is hashed to produce a root key (for testing).
+    #[arg(long)]
+    id: Option<String>,
+
+    /// A root key as printed by `root-key`, or a whole call link URL (which may include an
+    /// epoch).
+    #[arg(long)]
+    root_key: Option<String>,
+
+    /// A file containing the value for --root-key.
+    #[arg(long)]
+    root_key_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct AdminArgs {
+    /// The link's admin passkey, in base64. Defaults to the testing passkey [1, 2, 3, 4, 5].
+    #[arg(long, conflicts_with = "admin_passkey_file")]
+    admin_passkey: Option<String>,
+
+    /// A file containing the value for --admin-passkey.
+    #[arg(long)]
+    admin_passkey_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Restrictions {
+    None,
+    AdminApproval,
+}
+
+impl From<Restrictions> for CallLinkRestrictions {
+    fn from(value: Restrictions) -> Self {
+        match value {
+            Restrictions::None => Self::None,
+            Restrictions::AdminApproval => Self::AdminApproval,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create a new link.
+    Create {
+        #[command(flatten)]
+        link: LinkArgs,
+        #[command(flatten)]
+        admin: AdminArgs,
+        #[arg(long)]
+        restrictions: Option<Restrictions>,
+    },
+    /// Fetch the current state of a link.
+    Read {
+        #[command(flatten)]
+        link: LinkArgs,
+    },
+    /// Change any of a link's properties. An empty name or description clears it.
+    Update {
+        #[command(flatten)]
+        link: LinkArgs,
+        #[command(flatten)]
+        admin: AdminArgs,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        restrictions: Option<Restrictions>,
+        #[arg(long)]
+        revoked: Option<bool>,
+    },
+    /// Delete a link.
+    Delete {
+        #[command(flatten)]
+        link: LinkArgs,
+        #[command(flatten)]
+        admin: AdminArgs,
+    },
+    /// Move a link to a new epoch, so that only the new URL works.
+    ///
+    /// Anyone with the root key can still find the new epoch; create a new link to shut them out.
+    Reset {
+        #[command(flatten)]
+        link: LinkArgs,
+        #[command(flatten)]
+        admin: AdminArgs,
+    },
+    /// Show who is in a link's call, and who is waiting to be approved.
+    Peek {
+        #[command(flatten)]
+        link: LinkArgs,
+    },
+    /// Print a link's root key and URL.
+    RootKey {
+        #[command(flatten)]
+        link: LinkArgs,
+    },
+    /// Print a new random root key.
+    GenerateRootKey,
+    /// Print a new random admin passkey, in base64.
+    GenerateAdminPasskey,
+    /// Reset a link's list of approved users (if the server has this enabled).
+    ResetApprovals {
+        #[command(flatten)]
+        link: LinkArgs,
+    },
+    /// Reset a link's expiration (if the server has this enabled).
+    ResetExpiration {
+        #[command(flatten)]
+        link: LinkArgs,
+    },
+}
+
+#[derive(Debug)]
+enum Failure {
+    InvalidInput(String),
+    Status(http::ResponseStatus),
+}
+
+impl Failure {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::InvalidInput(_) => ExitCode::from(2),
+            Failure::Status(status) => match status.r#type() {
+                http::ResponseStatusType::RequestError
+                | http::ResponseStatusType::ResponseError => ExitCode::from(3),
+                _ => ExitCode::from(1),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::InvalidInput(message) => write!(f, "{message}"),
+            Failure::Status(status) => write!(f, "failed: {status}"),
+        }
+    }
+}
+
+impl From<http::ResponseStatus> for Failure {
+    fn from(status: http::ResponseStatus) -> Self {
+        Self::Status(status)
+    }
+}
+
+fn invalid_input(message: impl std::fmt::Display) -> Failure {
+    Failure::InvalidInput(message.to_string())
+}
 
-fn prompt(s: &str) {
-    std::io::stdout().write_all(s.as_bytes()).unwrap();
-    std::io::stdout().flush().unwrap();
+fn read_trimmed(path: &Path) -> Result<String, Failure> {
+    std::fs::read_to_string(path)
+        .map(|contents| contents.trim().to_string())
+        .map_err(|e| invalid_input(format!("cannot read {}: {e}", path.display())))
 }
 
 fn root_key_from_id(id: &str) -> CallLinkRootKey {
@@ -57,6 +266,35 @@ fn root_key_from_id(id: &str) -> CallLinkRootKey {
     CallLinkRootKey::generate(prng)
 }
 
+impl LinkArgs {
+    fn root_key(&self) -> Result<CallLinkRootKey, Failure> {
+        let root_key = match (&self.id, &self.root_key, &self.root_key_file) {
+            (Some(id), _, _) => return Ok(root_key_from_id(id)),
+            (_, Some(root_key), _) => root_key.clone(),
+            (_, _, Some(path)) => read_trimmed(path)?,
+            (None, None, None) => unreachable!("required by clap"),
+        };
+        if let Ok(url) = CallLinkUrl::parse(&root_key) {
+            return Ok(url.root_key);
+        }
+        CallLinkRootKey::try_from(root_key.as_str())
+            .map_err(|_| invalid_input("invalid root key or call link URL"))
+    }
+}
+
+impl AdminArgs {
+    fn admin_passkey(&self) -> Result<Vec<u8>, Failure> {
+        let admin_passkey = match (&self.admin_passkey, &self.admin_passkey_file) {
+            (Some(admin_passkey), _) => admin_passkey.clone(),
+            (_, Some(path)) => read_trimmed(path)?,
+            (None, None) => return Ok(DEFAULT_ADMIN_PASSKEY.to_vec()),
+        };
+        base64
+            .decode(admin_passkey)
+            .map_err(|_| invalid_input("admin passkey should be base64"))
+    }
+}
+
 fn start_of_today_in_epoch_seconds() -> zkgroup::Timestamp {
     let now: Duration = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -65,296 +303,434 @@ fn start_of_today_in_epoch_seconds() -> zkgroup::Timestamp {
     zkgroup::Timestamp::from_epoch_seconds(now.as_secs() - remainder)
 }
 
-fn issue_and_present_auth_credential(
-    server_zkparams: &zkgroup::generic_server_params::GenericServerSecretParams,
-    public_zkparams: &zkgroup::generic_server_params::GenericServerPublicParams,
-    root_key: &CallLinkRootKey,
-) -> zkgroup::call_links::CallLinkAuthCredentialPresentation {
-    let timestamp = start_of_today_in_epoch_seconds();
-    let user_id = Uuid::from_bytes(USER_ID).into();
-    let auth_credential = zkgroup::call_links::CallLinkAuthCredentialResponse::issue_credential(
-        user_id,
-        timestamp,
-        server_zkparams,
-        rand::random(),
-    )
-    .receive(user_id, timestamp, public_zkparams)
-    .unwrap();
-    let call_link_zkparams = CallLinkSecretParams::derive_from_root_key(&root_key.bytes());
-    auth_credential.present(
-        user_id,
-        timestamp,
-        public_zkparams,
-        &call_link_zkparams,
-        rand::random(),
-    )
+struct Credentials {
+    user_id: Uuid,
+    server_zkparams: zkgroup::generic_server_params::GenericServerSecretParams,
+    public_zkparams: zkgroup::generic_server_params::GenericServerPublicParams,
+}
+
+impl Credentials {
+    fn new(cli: &Cli) -> Result<Self, Failure> {
+        let zkparams_base64 = match (&cli.zkparams, &cli.zkparams_file) {
+            (Some(zkparams), _) => zkparams.clone(),
+            (_, Some(path)) => read_trimmed(path)?,
+            (None, None) => DEFAULT_ZKPARAMS.to_string(),
+        };
+        let server_zkparams: zkgroup::generic_server_params::GenericServerSecretParams =
+            bincode::deserialize(
+                &base64
+                    .decode(zkparams_base64)
+                    .map_err(|_| invalid_input("zkparams should be valid base64"))?,
+            )
+            .map_err(|_| {
+                invalid_input("zkparams should be a valid GenericServerSecretParams (not public!)")
+            })?;
+        Ok(Self {
+            user_id: cli.user_id,
+            public_zkparams: server_zkparams.get_public_params(),
+            server_zkparams,
+        })
+    }
+
+    fn auth_presentation(&self, root_key: &CallLinkRootKey) -> Vec<u8> {
+        let user_id = self.user_id.into();
+        let timestamp = start_of_today_in_epoch_seconds();
+        let auth_credential =
+            zkgroup::call_links::CallLinkAuthCredentialResponse::issue_credential(
+                user_id,
+                timestamp,
+                &self.server_zkparams,
+                rand::random(),
+            )
+            .receive(user_id, timestamp, &self.public_zkparams)
+            .expect("credential issued with matching params");
+        let call_link_zkparams = CallLinkSecretParams::derive_from_root_key(&root_key.bytes());
+        let presentation = auth_credential.present(
+            user_id,
+            timestamp,
+            &self.public_zkparams,
+            &call_link_zkparams,
+            rand::random(),
+        );
+        bincode::serialize(&presentation).expect("cannot fail to serialize")
+    }
+
+    fn create_presentation(&self, root_key: &CallLinkRootKey) -> Vec<u8> {
+        let user_id = self.user_id.into();
+        let room_id = root_key.derive_room_id();
+        let request_context = zkgroup::call_links::CreateCallLinkCredentialRequestContext::new(
+            &room_id,
+            rand::random(),
+        );
+        let response = request_context.get_request().issue(
+            user_id,
+            start_of_today_in_epoch_seconds(),
+            &self.server_zkparams,
+            rand::random(),
+        );
+        let credential = request_context
+            .receive(response, user_id, &self.public_zkparams)
+            .expect("credential issued with matching params");
+        let call_link_zkparams = CallLinkSecretParams::derive_from_root_key(&root_key.bytes());
+        let presentation = credential.present(
+            &room_id,
+            user_id,
+            &self.public_zkparams,
+            &call_link_zkparams,
+            rand::random(),
+        );
+        bincode::serialize(&presentation).expect("cannot fail to serialize")
+    }
+}
+
+/// Runs one request to completion, since the HTTP client reports results via callbacks.
+fn wait_for<T: Send + 'static>(send_request: impl FnOnce(Box<dyn FnOnce(T) + Send>)) -> T {
+    let (sender, receiver) = mpsc::channel();
+    send_request(Box::new(move |result| {
+        let _ = sender.send(result);
+    }));
+    receiver.recv().expect("callback is always called")
 }
 
 fn make_testing_request(
-    id: &str,
-    server_zkparams: &zkgroup::generic_server_params::GenericServerSecretParams,
-    public_zkparams: &zkgroup::generic_server_params::GenericServerPublicParams,
-    http_client: &sim_http::HttpClient,
-    url: &'static str,
+    http_client: &dyn Client,
+    url: &str,
+    credentials: &Credentials,
+    root_key: CallLinkRootKey,
     method: http::Method,
     path: &str,
-) {
-    let root_key = root_key_from_id(id);
-    let auth_credential_presentation =
-        issue_and_present_auth_credential(server_zkparams, public_zkparams, &root_key);
-    let http_client_inner = http_client.clone();
-    http_client.send_request(
-        http::Request {
-            method,
-            url: format!("{url}{path}"),
-            headers: HashMap::from_iter([
-                (
-                    "Authorization".to_string(),
-                    ringrtc::lite::call_links::auth_header_from_auth_credential(
-                        &bincode::serialize(&auth_credential_presentation).unwrap(),
+) -> Result<CallLinkState, Failure> {
+    let auth_presentation = credentials.auth_presentation(&root_key);
+    let response = wait_for(|callback| {
+        http_client.send_request(
+            http::Request {
+                method,
+                url: format!("{}{path}", url.trim_end_matches('/')),
+                headers: HashMap::from_iter([
+                    (
+                        "Authorization".to_string(),
+                        call_links::auth_header_from_auth_credential(&auth_presentation),
+                    ),
+                    (
+                        "X-Room-Id".to_string(),
+                        hex::encode(root_key.derive_room_id()),
                     ),
-                ),
-                (
-                    "X-Room-Id".to_string(),
-                    hex::encode(root_key.derive_room_id()),
-                ),
-            ]),
-            body: None,
+                ]),
+                body: None,
+            },
+            callback,
+        )
+    });
+    match response {
+        Some(response) if response.status.is_success() => {
+            // Do a regular read to show the update.
+            // zkgroup sin: we're reusing a presentation.
+            // But this is a testing client only.
+            Ok(wait_for(|callback| {
+                call_links::read_call_link(http_client, url, root_key, &auth_presentation, callback)
+            })?)
+        }
+        Some(response) => Err(response.status.into()),
+        None => Err(http::ResponseStatus::REQUEST_FAILED.into()),
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn call_link_state_to_json(state: &CallLinkState) -> serde_json::Value {
+    json!({
+        "name": state.name,
+        "description": state.description,
+        "restrictions": match state.restrictions {
+            CallLinkRestrictions::None => "none",
+            CallLinkRestrictions::AdminApproval => "adminApproval",
+            CallLinkRestrictions::Unknown => "unknown",
         },
-        Box::new(move |response| match response {
-            Some(response) if response.status.is_success() => {
-                // Do a regular read to show the update.
-                // zkgroup sin: we're reusing a presentation.
-                // But this is a testing client only.
-                ringrtc::lite::call_links::read_call_link(
-                    &http_client_inner,
-                    url,
-                    root_key,
-                    &bincode::serialize(&auth_credential_presentation).unwrap(),
-                    Box::new(show_result),
-                )
-            }
-            Some(response) => {
-                println!("failed: {}", response.status);
-                prompt("\n> ");
-            }
-            None => {
-                println!("request failed");
-                prompt("\n> ");
-            }
-        }),
-    )
-}
-
-fn show_result<T: std::fmt::Debug>(result: Result<T, http::ResponseStatus>) {
-    match result {
-        Ok(state) => println!("{state:#?}"),
-        Err(status) => println!("failed: {status}"),
+        "revoked": state.revoked,
+        "expiration": unix_seconds(state.expiration),
+        "scheduledStart": state.scheduled_start.map(unix_seconds),
+    })
+}
+
+fn user_id_to_json(user_id: &[u8]) -> String {
+    Uuid::from_slice(user_id)
+        .map(|uuid| uuid.to_string())
+        .unwrap_or_else(|_| hex::encode(user_id))
+}
+
+fn devices_to_json(devices: &[PeekDeviceInfo]) -> serde_json::Value {
+    devices
+        .iter()
+        .map(|device| {
+            json!({
+                "demuxId": device.demux_id,
+                "userId": device.user_id.as_deref().map(user_id_to_json),
+            })
+        })
+        .collect()
+}
+
+fn peek_info_to_json(peek_info: &PeekInfo) -> serde_json::Value {
+    json!({
+        "eraId": peek_info.era_id,
+        "creator": peek_info.creator.as_deref().map(user_id_to_json),
+        "maxDevices": peek_info.max_devices,
+        "devices": devices_to_json(&peek_info.devices),
+        "pendingDevices": devices_to_json(&peek_info.pending_devices),
+        "callLinkState": peek_info.call_link_state.as_ref().map(call_link_state_to_json),
+    })
+}
+
+fn root_key_to_json(root_key: &CallLinkRootKey) -> serde_json::Value {
+    json!({
+        "rootKey": root_key.to_formatted_string(),
+        "epoch": root_key.epoch().map(CallLinkEpoch::to_formatted_string),
+        "roomId": hex::encode(root_key.derive_room_id()),
+        "url": CallLinkUrl::new(root_key.clone()).to_url_string(),
+    })
+}
+
+fn print_output(json: bool, as_json: serde_json::Value, as_text: impl FnOnce() -> String) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&as_json).expect("cannot fail to serialize")
+        );
+    } else {
+        println!("{}", as_text());
     }
-    prompt("\n> ");
-}
-
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let url: &'static str = args
-        .get(1)
-        .map(|s| &*Box::leak(s.clone().into_boxed_str()))
-        .unwrap_or("http://localhost:8090");
-    let zkparams_base64 = args.get(2).map(String::as_str).unwrap_or(DEFAULT_ZKPARAMS);
-    let server_zkparams: zkgroup::generic_server_params::GenericServerSecretParams =
-        bincode::deserialize(
-            &base64
-                .decode(zkparams_base64)
-                .expect("zkparams should be valid base64"),
-        )
-        .expect("zkparams should be a valid GenericServerSecretParams (not public!)");
-    let public_zkparams = server_zkparams.get_public_params();
-    let user_id = Uuid::from_bytes(USER_ID).into();
+}
 
-    log::set_logger(&LOG).expect("set logger");
-    log::set_max_level(log::LevelFilter::Info);
-
-    let http_client = sim_http::HttpClient::start();
-
-    prompt("> ");
-    for line in std::io::stdin().lines() {
-        let words: Vec<&str> = line.as_ref().unwrap().split_ascii_whitespace().collect();
-        match &words[..] {
-            [] => {
-                prompt("> ");
-            }
-            ["help"] => {
-                println!(
-                    "
-Available commands are:
-
-help                         - show this message
-create <id>                  - create a new link
-read <id>                    - fetch the current state of a link
-set-title <id> <new-title>   - change the title of a link
-admin-approval <id> (on|off) - turn on/off admin approval for a link
-reset-approvals <id>         - reset a link's list of approved users (if the server has this enabled)
-reset-expiration <id>        - reset a link's expiration (if the server has this enabled)
-root-key <id>                - print the root key for a link
-exit                         - quit
-
-<id> can be any word you want; it is hashed to produce a root key.
-The admin passkey for any created links is a constant {ADMIN_PASSKEY:?}.
-"
-                );
-                prompt("> ");
-            }
-            ["create", id] => {
-                let root_key = root_key_from_id(id);
-                let room_id = root_key.derive_room_id();
-                let create_credential_request_context =
-                    zkgroup::call_links::CreateCallLinkCredentialRequestContext::new(
-                        &room_id,
-                        rand::random(),
-                    );
-                let create_credential_response =
-                    create_credential_request_context.get_request().issue(
-                        user_id,
-                        start_of_today_in_epoch_seconds(),
-                        &server_zkparams,
-                        rand::random(),
-                    );
-                let create_credential = create_credential_request_context
-                    .receive(create_credential_response, user_id, &public_zkparams)
-                    .unwrap();
-                let call_link_zkparams =
-                    CallLinkSecretParams::derive_from_root_key(&root_key.bytes());
-                let create_credential_presentation = create_credential.present(
-                    &room_id,
-                    user_id,
-                    &public_zkparams,
-                    &call_link_zkparams,
-                    rand::random(),
-                );
-                ringrtc::lite::call_links::create_call_link(
-                    &http_client,
-                    url,
-                    root_key,
-                    &bincode::serialize(&create_credential_presentation).unwrap(),
-                    ADMIN_PASSKEY,
-                    &bincode::serialize(&call_link_zkparams.get_public_params()).unwrap(),
-                    None,
-                    Box::new(show_result),
-                );
-            }
-            ["read", id] => {
-                let root_key = root_key_from_id(id);
-                let auth_credential_presentation = issue_and_present_auth_credential(
-                    &server_zkparams,
-                    &public_zkparams,
-                    &root_key,
-                );
-                ringrtc::lite::call_links::read_call_link(
-                    &http_client,
-                    url,
-                    root_key,
-                    &bincode::serialize(&auth_credential_presentation).unwrap(),
-                    Box::new(show_result),
-                );
-            }
-            ["delete", id] => {
-                let root_key = root_key_from_id(id);
-                let auth_credential_presentation = issue_and_present_auth_credential(
-                    &server_zkparams,
-                    &public_zkparams,
-                    &root_key,
-                );
-                ringrtc::lite::call_links::delete_call_link(
-                    &http_client,
+fn print_state(json: bool, state: &CallLinkState) {
+    print_output(json, call_link_state_to_json(state), || {
+        format!("{state:#?}")
+    });
+}
+
+fn run(cli: Cli) -> Result<(), Failure> {
+    let credentials = Credentials::new(&cli)?;
+    let http_client = http::RecordingClient::new(sim_http::HttpClient::start());
+    let result = run_command(&cli, &http_client, &credentials);
+    if let Some(path) = &cli.record {
+        http_client
+            .save(path)
+            .map_err(|e| invalid_input(format!("cannot write {}: {e}", path.display())))?;
+    }
+    result
+}
+
+fn run_command(
+    cli: &Cli,
+    http_client: &dyn Client,
+    credentials: &Credentials,
+) -> Result<(), Failure> {
+    let url = cli.url.as_str();
+
+    match &cli.command {
+        Command::Create {
+            link,
+            admin,
+            restrictions,
+        } => {
+            let root_key = link.root_key()?;
+            let admin_passkey = admin.admin_passkey()?;
+            let create_presentation = credentials.create_presentation(&root_key);
+            let call_link_zkparams = CallLinkSecretParams::derive_from_root_key(&root_key.bytes());
+            let state = wait_for(|callback| {
+                call_links::create_call_link(
+                    http_client,
                     url,
                     root_key,
-                    &bincode::serialize(&auth_credential_presentation).unwrap(),
-                    &CallLinkDeleteRequest {
-                        admin_passkey: ADMIN_PASSKEY,
-                    },
-                    Box::new(show_result),
-                );
-            }
-            ["set-title", id, new_title] => {
-                let root_key = root_key_from_id(id);
-                let encrypted_name = root_key.encrypt(new_title.as_bytes(), rand::thread_rng());
-                let auth_credential_presentation = issue_and_present_auth_credential(
-                    &server_zkparams,
-                    &public_zkparams,
-                    &root_key,
-                );
-                ringrtc::lite::call_links::update_call_link(
-                    &http_client,
+                    &create_presentation,
+                    &admin_passkey,
+                    &bincode::serialize(&call_link_zkparams.get_public_params())
+                        .expect("cannot fail to serialize"),
+                    restrictions.map(CallLinkRestrictions::from),
+                    callback,
+                )
+            })?;
+            print_state(cli.json, &state);
+        }
+        Command::Read { link } => {
+            let root_key = link.root_key()?;
+            let auth_presentation = credentials.auth_presentation(&root_key);
+            let state = wait_for(|callback| {
+                call_links::read_call_link(http_client, url, root_key, &auth_presentation, callback)
+            })?;
+            print_state(cli.json, &state);
+        }
+        Command::Update {
+            link,
+            admin,
+            name,
+            description,
+            restrictions,
+            revoked,
+        } => {
+            let root_key = link.root_key()?;
+            let admin_passkey = admin.admin_passkey()?;
+            let encrypted_name = name
+                .as_deref()
+                .map(|name| call_links::encrypt_name(&root_key, name, rand::thread_rng()))
+                .transpose()
+                .map_err(invalid_input)?;
+            let encrypted_description = description
+                .as_deref()
+                .map(|description| {
+                    call_links::encrypt_description(&root_key, description, rand::thread_rng())
+                })
+                .transpose()
+                .map_err(invalid_input)?;
+            let auth_presentation = credentials.auth_presentation(&root_key);
+            let state = wait_for(|callback| {
+                call_links::update_call_link(
+                    http_client,
                     url,
                     root_key,
-                    &bincode::serialize(&auth_credential_presentation).unwrap(),
+                    &auth_presentation,
                     &CallLinkUpdateRequest {
-                        admin_passkey: ADMIN_PASSKEY,
-                        encrypted_name: Some(&encrypted_name),
+                        admin_passkey: &admin_passkey,
+                        encrypted_name: encrypted_name.as_deref(),
+                        encrypted_description: encrypted_description.as_deref(),
+                        restrictions: restrictions.map(CallLinkRestrictions::from),
+                        revoked: *revoked,
                         ..CallLinkUpdateRequest::default()
                     },
-                    Box::new(show_result),
-                );
-            }
-            ["admin-approval", id, on_or_off @ ("on" | "off")] => {
-                let root_key = root_key_from_id(id);
-                let auth_credential_presentation = issue_and_present_auth_credential(
-                    &server_zkparams,
-                    &public_zkparams,
-                    &root_key,
-                );
-                let restrictions = if *on_or_off == "on" {
-                    CallLinkRestrictions::AdminApproval
-                } else {
-                    CallLinkRestrictions::None
-                };
-                ringrtc::lite::call_links::update_call_link(
-                    &http_client,
+                    callback,
+                )
+            })?;
+            print_state(cli.json, &state);
+        }
+        Command::Delete { link, admin } => {
+            let root_key = link.root_key()?;
+            let admin_passkey = admin.admin_passkey()?;
+            let auth_presentation = credentials.auth_presentation(&root_key);
+            wait_for(|callback| {
+                call_links::delete_call_link(
+                    http_client,
                     url,
                     root_key,
-                    &bincode::serialize(&auth_credential_presentation).unwrap(),
-                    &CallLinkUpdateRequest {
-                        admin_passkey: ADMIN_PASSKEY,
-                        restrictions: Some(restrictions),
-                        ..CallLinkUpdateRequest::default()
+                    &auth_presentation,
+                    &CallLinkDeleteRequest {
+                        admin_passkey: &admin_passkey,
                     },
-                    Box::new(show_result),
-                );
-            }
-            ["reset-approvals", id] => {
-                make_testing_request(
-                    id,
-                    &server_zkparams,
-                    &public_zkparams,
-                    &http_client,
+                    callback,
+                )
+            })?;
+            print_output(cli.json, json!({ "deleted": true }), || {
+                "deleted".to_string()
+            });
+        }
+        Command::Reset { link, admin } => {
+            let root_key = link.root_key()?;
+            let admin_passkey = admin.admin_passkey()?;
+            let auth_presentation = credentials.auth_presentation(&root_key);
+            let new_epoch = CallLinkEpoch::generate(rand::thread_rng());
+            let new_root_key = root_key.clone().with_epoch(Some(new_epoch));
+            let state = wait_for(|callback| {
+                call_links::reset_call_link(
+                    http_client,
                     url,
-                    http::Method::Delete,
-                    "/v1/call-link/approvals",
-                );
-            }
-            ["reset-expiration", id] => {
-                make_testing_request(
-                    id,
-                    &server_zkparams,
-                    &public_zkparams,
-                    &http_client,
+                    root_key,
+                    new_epoch,
+                    &auth_presentation,
+                    &admin_passkey,
+                    callback,
+                )
+            })?;
+            let new_url = CallLinkUrl::new(new_root_key).to_url_string();
+            print_output(
+                cli.json,
+                json!({ "url": new_url, "state": call_link_state_to_json(&state) }),
+                || format!("{new_url}\n{state:#?}"),
+            );
+        }
+        Command::Peek { link } => {
+            let root_key = link.root_key()?;
+            let auth_presentation = credentials.auth_presentation(&root_key);
+            let peek_info = wait_for(|callback| {
+                sfu::peek(
+                    http_client,
                     url,
-                    http::Method::Post,
-                    "/v1/call-link/reset-expiration",
-                );
-            }
-            ["root-key", id] => {
-                let root_key = root_key_from_id(id);
-                println!("{}\n", root_key.to_formatted_string());
-                prompt("> ");
-            }
-            ["exit" | "quit"] => {
-                break;
-            }
-            _ => {
-                println!("Couldn't parse that.\n");
-                prompt("> ");
-            }
+                    Some(hex::encode(root_key.derive_room_id())),
+                    call_links::auth_header_from_auth_credential(&auth_presentation),
+                    Arc::new(call_links::CallLinkMemberResolver::from(&root_key)),
+                    Some(root_key),
+                    callback,
+                )
+            })?;
+            print_output(cli.json, peek_info_to_json(&peek_info), || {
+                format!("{peek_info:#?}")
+            });
+        }
+        Command::RootKey { link } => {
+            let root_key = link.root_key()?;
+            print_output(cli.json, root_key_to_json(&root_key), || {
+                format!(
+                    "{}\n{}",
+                    root_key.to_formatted_string(),
+                    CallLinkUrl::new(root_key.clone()).to_url_string()
+                )
+            });
+        }
+        Command::GenerateRootKey => {
+            let root_key = CallLinkRootKey::generate(rand::rngs::OsRng);
+            print_output(cli.json, root_key_to_json(&root_key), || {
+                root_key.to_formatted_string()
+            });
+        }
+        Command::GenerateAdminPasskey => {
+            let admin_passkey =
+                base64.encode(CallLinkRootKey::generate_admin_passkey(rand::rngs::OsRng));
+            print_output(cli.json, json!({ "adminPasskey": admin_passkey }), || {
+                admin_passkey.clone()
+            });
+        }
+        Command::ResetApprovals { link } => {
+            let state = make_testing_request(
+                http_client,
+                url,
+                credentials,
+                link.root_key()?,
+                http::Method::Delete,
+                "/v1/call-link/approvals",
+            )?;
+            print_state(cli.json, &state);
+        }
+        Command::ResetExpiration { link } => {
+            let state = make_testing_request(
+                http_client,
+                url,
+                credentials,
+                link.root_key()?,
+                http::Method::Post,
+                "/v1/call-link/reset-expiration",
+            )?;
+            print_state(cli.json, &state);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    log::set_logger(&LOG).expect("set logger");
+    log::set_max_level(if cli.verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Warn
+    });
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("{failure}");
+            failure.exit_code()
         }
     }
 }
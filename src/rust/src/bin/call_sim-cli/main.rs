@@ -324,6 +324,7 @@ fn main() -> Result<()> {
         },
         audio_rtcp_report_interval_ms: args.audio_rtcp_report_interval_ms,
         enable_vp9: args.vp9,
+        ice_restart_config: Default::default(),
     };
 
     let mut scenario = ScenarioManager::new()?;
@@ -18,7 +18,7 @@ use ringrtc::{
         units::DataRate,
         CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result,
     },
-    core::{call_manager::CallManager, group_call, signaling},
+    core::{call_manager::CallManager, call_record::CallRecord, group_call, signaling},
     lite::{
         http,
         sfu::{DemuxId, UserId},
@@ -677,6 +677,48 @@ impl CallStateHandler for CallEndpoint {
         );
         Ok(())
     }
+
+    fn handle_remote_hold(&self, remote_peer_id: &str, on_hold: bool) -> Result<()> {
+        info!(
+            "On Hold for {} => {}: {}",
+            self.peer_id, remote_peer_id, on_hold
+        );
+        Ok(())
+    }
+
+    fn handle_call_waiting(&self, remote_peer_id: &str, call_id: CallId) -> Result<()> {
+        info!(
+            "Call waiting for {} => {}: {}",
+            self.peer_id, remote_peer_id, call_id
+        );
+        Ok(())
+    }
+
+    fn handle_group_call_invitation(
+        &self,
+        remote_peer_id: &str,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        info!(
+            "Group call invitation for {} => {}: {} {:?}",
+            self.peer_id, remote_peer_id, call_id, invitation
+        );
+        Ok(())
+    }
+
+    fn handle_call_transfer_offered(&self, _remote_user_id: &[u8], call_id: CallId) -> Result<()> {
+        info!("Call transfer offered to {}: {}", self.peer_id, call_id);
+        Ok(())
+    }
+
+    fn handle_call_record(&self, remote_peer_id: &str, record: CallRecord) -> Result<()> {
+        info!(
+            "Call record for {} from {}: {:?}",
+            self.peer_id, remote_peer_id, record
+        );
+        Ok(())
+    }
 }
 
 impl GroupUpdateHandler for CallEndpoint {
@@ -14,12 +14,12 @@ use ringrtc::{
     core::{
         call_mutex::CallMutex,
         group_call::{
-            self, ClientId, ConnectionState, EndReason, HttpSfuClient, JoinState, Reaction,
-            RemoteDeviceState, RemoteDevicesChangedReason, SpeechEvent,
+            self, CallSummary, ClientId, ConnectionState, EndReason, HttpSfuClient, JoinState,
+            Reaction, RemoteDeviceState, RemoteDevicesChangedReason, SpeechEvent,
         },
     },
     lite::{
-        http::sim as sim_http,
+        http::{self, sim as sim_http},
         sfu::{DemuxId, MemberMap, ObfuscatedResolver, PeekInfo, UserId},
     },
     protobuf,
@@ -111,8 +111,9 @@ impl group_call::Observer for Observer {
         info!("Got a video track for {}", sender_demux_id);
     }
 
-    fn handle_ended(&self, _client_id: ClientId, reason: EndReason) {
+    fn handle_ended(&self, _client_id: ClientId, reason: EndReason, summary: CallSummary) {
         info!("Ended with reason {:?}", reason);
+        info!("Call summary: {:?}", summary);
     }
 
     fn handle_network_route_changed(
@@ -148,6 +149,10 @@ impl group_call::Observer for Observer {
         info!("Raised hands changed to {:?}", raised_hands);
     }
 
+    fn handle_presenter_changed(&self, _client_id: ClientId, current_presenter: Option<DemuxId>) {
+        info!("Presenter changed to {:?}", current_presenter);
+    }
+
     fn handle_rtc_stats_report(&self, _report_json: String) {
         // ignore
     }
@@ -201,7 +206,7 @@ fn main() {
     ringrtc::webrtc::logging::set_logger(log::LevelFilter::Info);
 
     let group_id = b"Test Group".to_vec();
-    let http_client = sim_http::HttpClient::start();
+    let http_client = http::RetryingClient::start(sim_http::HttpClient::start()).unwrap();
     let sfu_client = Box::new(HttpSfuClient::new(
         Box::new(http_client),
         url.to_string(),
@@ -10,7 +10,7 @@ use std::{
         atomic,
         atomic::AtomicBool,
         mpsc::{channel, RecvError, RecvTimeoutError, Sender},
-        Arc, Mutex,
+        Arc, Mutex, OnceLock,
     },
     thread,
     time::{Duration, Instant},
@@ -21,6 +21,8 @@ use crate::{common::Result, error::RingRtcError};
 pub struct Actor<State> {
     sender: Sender<Task<State>>,
     stopper: Stopper,
+    /// Set by the Actor's thread once it starts running.
+    thread_id: Arc<OnceLock<thread::ThreadId>>,
 }
 
 impl<State: 'static> Actor<State> {
@@ -39,11 +41,16 @@ impl<State: 'static> Actor<State> {
         let stopped = Arc::new(AtomicBool::new(false));
         let stopped_to_register = stopped.clone();
 
-        let actor = Self { sender, stopper };
+        let actor = Self {
+            sender,
+            stopper,
+            thread_id: Arc::new(OnceLock::new()),
+        };
         let actor_to_register = actor.clone();
         let actor_to_return = actor.clone();
         // Moves in actor and stopped
         let join_handle = thread::Builder::new().name(name.into()).spawn(move || {
+            let _ = actor.thread_id.set(thread::current().id());
             let mut state = match gen_state(actor) {
                 Ok(state) => state,
                 Err(e) => {
@@ -112,6 +119,12 @@ impl<State: 'static> Actor<State> {
     pub fn stopper(&self) -> &Stopper {
         &self.stopper
     }
+
+    /// Returns true if called from within one of this Actor's tasks, where
+    /// blocking on another task would deadlock.
+    pub fn is_current_thread(&self) -> bool {
+        self.thread_id.get() == Some(&thread::current().id())
+    }
 }
 
 // This doesn't #[derive] for some reason.
@@ -120,6 +133,7 @@ impl<State> Clone for Actor<State> {
         Self {
             sender: self.sender.clone(),
             stopper: self.stopper.clone(),
+            thread_id: self.thread_id.clone(),
         }
     }
 }
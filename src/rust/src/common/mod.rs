@@ -9,7 +9,7 @@ pub mod actor;
 pub mod jni_signature;
 pub mod units;
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use crate::webrtc::{
     media::AudioEncoderConfig,
@@ -401,6 +401,33 @@ pub enum ApplicationEvent {
     /// Received an offer while already handling an active call and glare
     /// was detected.
     ReceivedOfferWithGlare,
+
+    /// Received an offer while in an accepted call, and call waiting is
+    /// enabled. The new call waits to be accepted (putting the active call
+    /// on hold) or dropped instead of being rejected as busy.
+    ReceivedCallWaiting,
+
+    /// The remote side put the call on hold; media stops flowing until it resumes.
+    RemoteHold,
+
+    /// The remote side took the call off hold.
+    RemoteResume,
+
+    /// The call ended because it continues as a group call, which has now
+    /// been joined.
+    EndedEscalatedToGroupCall,
+
+    /// The call ended because another of the user's devices took it over.
+    EndedTransferred,
+
+    /// Received an offer that the incoming call policy declined without
+    /// ringing. The caller was told the call was declined, busy, or needs
+    /// permission, depending on the policy's decision.
+    ///
+    /// See [`CallManager::set_incoming_call_policy`][].
+    ///
+    /// [`CallManager::set_incoming_call_policy`]: crate::core::call_manager::CallManager::set_incoming_call_policy
+    ReceivedOfferDeclinedByPolicy,
 }
 
 impl fmt::Display for ApplicationEvent {
@@ -762,6 +789,26 @@ impl DataMode {
     }
 }
 
+/// How a 1:1 connection restarts ICE after it fails or the local
+/// network changes.
+#[derive(Clone, Debug)]
+pub struct IceRestartConfig {
+    /// The number of restarts to try in a row before giving up on the
+    /// connection. Zero disables ICE restarts.
+    pub max_attempts: u32,
+    /// How long to wait for a restart to reconnect before trying again.
+    pub attempt_timeout: Duration,
+}
+
+impl Default for IceRestartConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            attempt_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
 /// Low-level media configuration.
 #[derive(Clone, Debug)]
 pub struct CallConfig {
@@ -777,6 +824,8 @@ pub struct CallConfig {
     pub audio_rtcp_report_interval_ms: i32,
 
     pub enable_vp9: bool,
+
+    pub ice_restart_config: IceRestartConfig,
 }
 
 impl Default for CallConfig {
@@ -791,6 +840,7 @@ impl Default for CallConfig {
             audio_jitter_buffer_config: Default::default(),
             audio_rtcp_report_interval_ms: 5000,
             enable_vp9: true,
+            ice_restart_config: Default::default(),
         }
     }
 }
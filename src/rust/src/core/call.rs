@@ -29,6 +29,7 @@ use crate::{
         call_fsm::{CallEvent, CallStateMachine},
         call_manager::CallManager,
         call_mutex::CallMutex,
+        call_record::{CallHistory, CallMilestone, CallRecord},
         connection::{Connection, ConnectionObserverEvent, ConnectionType},
         platform::Platform,
         signaling,
@@ -87,6 +88,12 @@ where
     state: Arc<CallMutex<CallState>>,
     /// The actively connected connection.
     active_device_id: Arc<CallMutex<Option<DeviceId>>>,
+    /// The remote device taking over the call from the active one, if the
+    /// remote peer is transferring the call between its devices.
+    transfer_device_id: Arc<CallMutex<Option<DeviceId>>>,
+    /// The remote device that the active one has announced will take over
+    /// the call. Only an offer from this device is treated as a transfer.
+    announced_transfer_device_id: Arc<CallMutex<Option<DeviceId>>>,
     /// Pending remote offer and associated data.  Incoming calls only.
     pending_call: Arc<CallMutex<Option<PendingCall>>>,
     /// Injects events into the [CallStateMachine](../call_fsm/struct.CallStateMachine.html).
@@ -103,10 +110,15 @@ where
     /// It's a little ugly, but we need to handle the case where we get ConnectionState::ConnectedBeforeAccepted
     /// before a ConnectionState::ConnectingAfterAccepted and not notify the application twice.
     did_notify_application_of_remote_ringing: Arc<AtomicBool>,
+    /// Whether either side has invited the other to continue this call as a
+    /// group call, so that a normal hangup means the remote has joined it.
+    escalating: Arc<AtomicBool>,
     /// When doing call forking, the parent that must be kept alive to keep
     /// ICE candidates and signaling alive.
     /// And we also need to keep around that parent's offer that it created.
     forking: Arc<CallMutex<Option<ForkingState<T>>>>,
+    /// What's needed to report how the call went once it concludes.
+    history: Arc<CallMutex<CallHistory>>,
 }
 
 impl<T> fmt::Display for Call<T>
@@ -146,10 +158,13 @@ where
         if self.ref_count() == 1 {
             info!("Call: Dropping last reference: {}", self.call_id);
 
-            // This is the last call reference, so let the application
-            // release the the remote object.
+            // This is the last call reference, so report how the call went
+            // and let the application release the the remote object.
             if let Ok(call_manager) = self.call_manager() {
                 if let Ok(remote_peer) = self.remote_peer() {
+                    if let Ok(record) = self.call_record() {
+                        let _ = call_manager.notify_call_record(&remote_peer, record);
+                    }
                     let _ = call_manager.notify_call_concluded(&remote_peer, self.call_id);
                 }
             }
@@ -178,6 +193,8 @@ where
             app_call_context: Arc::clone(&self.app_call_context),
             state: Arc::clone(&self.state),
             active_device_id: Arc::clone(&self.active_device_id),
+            transfer_device_id: Arc::clone(&self.transfer_device_id),
+            announced_transfer_device_id: Arc::clone(&self.announced_transfer_device_id),
             pending_call: Arc::clone(&self.pending_call),
             fsm_sender: self.fsm_sender.clone(),
             timeout_stopper: self.timeout_stopper.clone(),
@@ -187,7 +204,9 @@ where
             did_notify_application_of_remote_ringing: Arc::clone(
                 &self.did_notify_application_of_remote_ringing,
             ),
+            escalating: Arc::clone(&self.escalating),
             forking: Arc::clone(&self.forking),
+            history: Arc::clone(&self.history),
         }
     }
 }
@@ -224,6 +243,11 @@ where
             app_call_context: Arc::new(CallMutex::new(None, "app_call_context")),
             state: Arc::new(CallMutex::new(CallState::NotYetStarted, "state")),
             active_device_id: Arc::new(CallMutex::new(None, "active_device_id")),
+            transfer_device_id: Arc::new(CallMutex::new(None, "transfer_device_id")),
+            announced_transfer_device_id: Arc::new(CallMutex::new(
+                None,
+                "announced_transfer_device_id",
+            )),
             pending_call: Arc::new(CallMutex::new(None, "pending_call")),
             fsm_sender,
             timeout_stopper: Stopper::new(),
@@ -231,7 +255,9 @@ where
             terminate_condvar: Arc::new((Mutex::new(false), Condvar::new())),
             did_send_offer: Arc::new(AtomicBool::new(false)),
             did_notify_application_of_remote_ringing: Arc::new(AtomicBool::new(false)),
+            escalating: Arc::new(AtomicBool::new(false)),
             forking: Arc::new(CallMutex::new(None, "forking")),
+            history: Arc::new(CallMutex::new(CallHistory::new(), "history")),
         };
 
         Ok(call)
@@ -264,6 +290,38 @@ where
         self.direction
     }
 
+    /// Remember that the call ended, as reported to the application with `event`.
+    pub fn record_ended(&self, event: ApplicationEvent) -> Result<()> {
+        self.history.lock()?.ended(event);
+        Ok(())
+    }
+
+    /// Remember that another of the user's devices accepted the incoming call.
+    pub fn record_answered_elsewhere(&self, device_id: DeviceId) -> Result<()> {
+        self.history.lock()?.answered_elsewhere(device_id);
+        Ok(())
+    }
+
+    /// Remember that the incoming call's offer took `age` to arrive.
+    pub fn record_offer_age(&self, age: Duration) -> Result<()> {
+        self.history.lock()?.offer_age(age);
+        Ok(())
+    }
+
+    /// Remember that the call reached `milestone` while being set up.
+    pub fn record_milestone(&self, milestone: CallMilestone) -> Result<()> {
+        self.history.lock()?.reached(milestone);
+        Ok(())
+    }
+
+    /// Return how the call has gone so far.
+    pub fn call_record(&self) -> Result<CallRecord> {
+        Ok(self
+            .history
+            .lock()?
+            .to_record(self.call_id, self.direction, self.media_type))
+    }
+
     /// Return the current Call state.
     pub fn state(&self) -> Result<CallState> {
         let state = self.state.lock()?;
@@ -307,6 +365,22 @@ where
         }
     }
 
+    /// Return the remote device taking over the call, if any.
+    pub fn transfer_device_id(&self) -> Result<Option<DeviceId>> {
+        Ok(*self.transfer_device_id.lock()?)
+    }
+
+    /// Return the remote device announced as taking over the call, if any.
+    pub fn announced_transfer_device_id(&self) -> Result<Option<DeviceId>> {
+        Ok(*self.announced_transfer_device_id.lock()?)
+    }
+
+    /// Record the remote device announced as taking over the call.
+    pub fn set_announced_transfer_device_id(&self, remote_device_id: DeviceId) -> Result<()> {
+        *self.announced_transfer_device_id.lock()? = Some(remote_device_id);
+        Ok(())
+    }
+
     /// For an incoming call, create a PendingCall structure for
     /// holding the offer and ICE candidates sent by the remote side
     /// *before* the application has formally decided to accept the
@@ -398,6 +472,11 @@ where
         connection.inject_accept()?;
         connection.enable_media()?;
         connection.start_tick()?;
+        {
+            let mut history = self.history.lock()?;
+            history.answered(self.local_device_id);
+            history.reached(CallMilestone::Accepted);
+        }
         self.notify_application(ApplicationEvent::LocalAccepted)?;
         Ok(())
     }
@@ -407,6 +486,12 @@ where
         let connection = self.active_connection()?;
         connection.enable_media()?;
         connection.start_tick()?;
+        let active_device_id = self.active_device_id()?;
+        {
+            let mut history = self.history.lock()?;
+            history.answered(active_device_id);
+            history.reached(CallMilestone::Accepted);
+        }
         self.notify_application(ApplicationEvent::RemoteAccepted)?;
         // Now that we've picked a connection, we can notify the app of the
         // network route.
@@ -414,6 +499,35 @@ where
         Ok(())
     }
 
+    /// Put the call on hold, or resume it, via the active connection.
+    pub fn set_on_hold(&self, on_hold: bool) -> Result<()> {
+        let mut connection = self.active_connection()?;
+        connection.set_on_hold(on_hold)
+    }
+
+    /// Invite the remote peer, via the active connection, to continue
+    /// the call as a group call.
+    pub fn send_group_call_invitation(
+        &self,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        self.escalating.store(true, Ordering::Release);
+        self.active_connection()?
+            .send_group_call_invitation(invitation)
+    }
+
+    /// Tell the remote peer, via the active connection, which of the local
+    /// user's devices is taking over the call.
+    pub fn send_transfer(&self, target_device_id: DeviceId) -> Result<()> {
+        self.active_connection()?.send_transfer(target_device_id)
+    }
+
+    /// Whether either side has invited the other to continue the call as a
+    /// group call.
+    pub fn escalating(&self) -> bool {
+        self.escalating.load(Ordering::Acquire)
+    }
+
     /// Notify application of an event.
     ///
     /// This is a pass through to the CallManager.
@@ -444,6 +558,20 @@ where
         call_manager.notify_network_route_changed(&remote_peer, network_route)
     }
 
+    /// Notify application of an invitation to continue the call as a group call.
+    ///
+    /// This is a pass through to the CallManager.
+    pub fn notify_group_call_invitation(
+        &self,
+        invitation: signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        self.escalating.store(true, Ordering::Release);
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_group_call_invitation(&remote_peer, self.call_id, invitation)
+    }
+
     /// Notify application of audio levels
     ///
     /// This is a pass through to the CallManager.
@@ -662,6 +790,96 @@ where
         Ok(())
     }
 
+    /// Answer an offer from another of the remote peer's devices, which is
+    /// taking over the call from the active device.
+    ///
+    /// The new connection is kept alongside the active one until it
+    /// connects, at which point it replaces it.
+    pub fn start_transfer_connection(&self, received: signaling::ReceivedOffer) -> Result<()> {
+        let remote_device_id = received.sender_device_id;
+        info!(
+            "start_transfer_connection(): id: {}",
+            self.call_id().format(remote_device_id)
+        );
+
+        if self.transfer_device_id()?.is_some()
+            || self.connection_map.lock()?.contains_key(&remote_device_id)
+        {
+            info!("start_transfer_connection(): already transferring, so ignore");
+            return Ok(());
+        }
+
+        let active_connection = self.active_connection()?;
+        let call_config = active_connection.call_config().clone();
+        let audio_levels_interval = active_connection.audio_levels_interval();
+
+        let mut call_manager = self.call_manager()?;
+        let mut connection = call_manager.create_connection(
+            self,
+            remote_device_id,
+            ConnectionType::Incoming,
+            received.offer.latest_version(),
+            call_config,
+            audio_levels_interval,
+        )?;
+        let answer = connection.start_incoming(received, Vec::new())?;
+        call_manager.send_answer(
+            self.clone(),
+            connection.clone(),
+            signaling::SendAnswer {
+                receiver_device_id: remote_device_id,
+                answer,
+            },
+        )?;
+
+        self.connection_map
+            .lock()?
+            .insert(remote_device_id, connection);
+        *self.transfer_device_id.lock()? = Some(remote_device_id);
+        Ok(())
+    }
+
+    /// Make the connection to the device taking over the call the active
+    /// one, and drop the connection to the device it took over from.
+    pub fn activate_transfer(&mut self, remote_device_id: DeviceId) -> Result<()> {
+        info!(
+            "activate_transfer(): id: {}",
+            self.call_id().format(remote_device_id)
+        );
+
+        if self.transfer_device_id.lock()?.take() != Some(remote_device_id) {
+            return Ok(());
+        }
+        *self.announced_transfer_device_id.lock()? = None;
+        *self.active_device_id.lock()? = Some(remote_device_id);
+        // This blocks.
+        self.terminate_connections_except_accepted(remote_device_id)?;
+
+        let mut connection = self.active_connection()?;
+        connection.inject_accept()?;
+        connection.enable_media()?;
+        connection.start_tick()?;
+        self.notify_network_route_changed(connection.network_route()?)
+    }
+
+    /// Drop the connection to a device that failed to take over the call.
+    pub fn abandon_transfer(&mut self, remote_device_id: DeviceId) -> Result<()> {
+        info!(
+            "abandon_transfer(): id: {}",
+            self.call_id().format(remote_device_id)
+        );
+
+        if self.transfer_device_id.lock()?.take() != Some(remote_device_id) {
+            return Ok(());
+        }
+        let connection = self.connection_map.lock()?.remove(&remote_device_id);
+        if let Some(mut connection) = connection {
+            // This blocks.
+            connection.terminate()?;
+        }
+        Ok(())
+    }
+
     /// Handle the received ICE candidates.
     pub fn received_ice(&self, mut received: signaling::ReceivedIce) -> Result<()> {
         info!(
@@ -783,6 +1001,13 @@ where
     pub fn handle_ice_failed(&mut self, remote_device: DeviceId) -> Result<()> {
         info!("ice_failed(): id: {}", self.call_id().format(remote_device));
 
+        if self.transfer_device_id()? == Some(remote_device) {
+            // The device taking over the call couldn't connect, so keep
+            // the call going with the active device.
+            info!("ice_failed(): transfer connection");
+            return self.abandon_transfer(remote_device);
+        }
+
         if let Ok(active_device_id) = self.active_device_id() {
             // There is an active connection.
             if active_device_id == remote_device {
@@ -1029,6 +1254,11 @@ where
         self.inject_event(CallEvent::ReceivedIce(received))
     }
 
+    /// Inject a `ReceivedTransfer` event into the FSM
+    pub fn inject_received_transfer(&mut self, received: signaling::ReceivedOffer) -> Result<()> {
+        self.inject_event(CallEvent::ReceivedTransfer(received))
+    }
+
     /// Inject a `ReceivedHangup` event into the FSM
     pub fn inject_received_hangup(&mut self, received: signaling::ReceivedHangup) -> Result<()> {
         self.inject_event(CallEvent::ReceivedHangup(received))
@@ -37,6 +37,7 @@
 //! ## Signaling events from client application
 //! - ReceivedAnswer
 //! - ReceivedIce
+//! - ReceivedTransfer
 //!
 //! ## Internally-generated events
 //!
@@ -57,6 +58,7 @@ use crate::{
     },
     core::{
         call::{Call, EventStream},
+        call_record::CallMilestone,
         connection::ConnectionObserverEvent,
         platform::Platform,
         signaling,
@@ -90,6 +92,9 @@ pub enum CallEvent {
     ReceivedIce(signaling::ReceivedIce),
     /// Received hangup signal message from remote peer.
     ReceivedHangup(signaling::ReceivedHangup),
+    /// Received offer from another of the remote peer's devices, which is
+    /// taking over the call.
+    ReceivedTransfer(signaling::ReceivedOffer),
 
     /// Connection observer event
     ConnectionObserverEvent(ConnectionObserverEvent, DeviceId),
@@ -133,6 +138,9 @@ impl fmt::Display for CallEvent {
                 "ReceivedHangup, device: {} hangup: {}",
                 received.sender_device_id, received.hangup
             ),
+            CallEvent::ReceivedTransfer(received) => {
+                format!("ReceivedTransfer, device: {}", received.sender_device_id)
+            }
             CallEvent::ConnectionObserverEvent(e, d) => {
                 format!("ConnectionObserverEvent, event: {}, device: {}", e, d)
             }
@@ -390,6 +398,9 @@ where
             CallEvent::ReceivedHangup(received) => {
                 self.handle_received_hangup(call, state, received)
             }
+            CallEvent::ReceivedTransfer(received) => {
+                self.handle_received_transfer(call, state, received)
+            }
             CallEvent::ConnectionObserverEvent(event, remote_device_id) => {
                 self.handle_connection_observer_event(call, state, event, remote_device_id)
             }
@@ -435,6 +446,25 @@ where
         });
     }
 
+    fn notify_group_call_invitation(
+        &mut self,
+        mut call: Call<T>,
+        invitation: signaling::GroupCallInvitation,
+    ) {
+        self.notify_spawn(move || {
+            let result = try_scoped(|| {
+                if call.terminating()? {
+                    Ok(())
+                } else {
+                    call.notify_group_call_invitation(invitation)
+                }
+            });
+            if let Err(err) = result {
+                call.inject_internal_error(err, "Notify Group Call Invitation failed");
+            }
+        });
+    }
+
     fn notify_audio_levels(
         &mut self,
         mut call: Call<T>,
@@ -533,6 +563,9 @@ where
         received: signaling::ReceivedIce,
     ) -> Result<()> {
         if state.can_receive_ice_candidates() {
+            if !received.ice.candidates.is_empty() {
+                call.record_milestone(CallMilestone::FirstIceCandidateReceived)?;
+            }
             self.schedule_work_until_terminating(call, "Handle Received Ice failed", move |call| {
                 call.received_ice(received)
             });
@@ -542,6 +575,24 @@ where
         Ok(())
     }
 
+    fn handle_received_transfer(
+        &mut self,
+        call: Call<T>,
+        state: CallState,
+        received: signaling::ReceivedOffer,
+    ) -> Result<()> {
+        if state.active() {
+            self.schedule_work_until_terminating(
+                call,
+                "Handle Received Transfer failed",
+                move |call| call.start_transfer_connection(received),
+            );
+        } else {
+            self.unexpected_state(state, "HandleReceivedTransfer");
+        }
+        Ok(())
+    }
+
     fn handle_received_hangup(
         &mut self,
         call: Call<T>,
@@ -565,6 +616,16 @@ where
             return Ok(());
         }
 
+        // If the device taking over the call gave up, keep the call going
+        // with the active device.
+        if call.transfer_device_id()? == Some(sender_device_id) {
+            info!("handle_received_hangup(): Abandoning transfer");
+            self.schedule_work_until_terminating(call, "Abandon transfer failed", move |call| {
+                call.abandon_transfer(sender_device_id)
+            });
+            return Ok(());
+        }
+
         // If already connected to device A, ignore hangup messages from device B.
         if let Ok(active_device_id) = call.active_device_id() {
             if sender_device_id != active_device_id {
@@ -586,6 +647,12 @@ where
         // - hangup_to_propagate: If a caller, the hangup to send to other callees
         // - app_event_override: The event, if any, to return to the UX to override the default
         let (expected, hangup_to_propagate, app_event_override) = match (hangup_type, direction) {
+            // Either side gets Normal after a group call invitation: the remote has
+            // joined the group call, so this one carries on there.
+            (signaling::HangupType::Normal, _) if call.escalating() => {
+                app_event_without_propagation(ApplicationEvent::EndedEscalatedToGroupCall)
+            }
+
             // Caller gets NeedsPermission: propagate it with specific app event.
             (signaling::HangupType::NeedPermission, CallDirection::Outgoing) => {
                 propagate_with_app_event(
@@ -608,6 +675,9 @@ where
 
             // Callee gets propagated hangup: use specific app event.
             (signaling::HangupType::AcceptedOnAnotherDevice, CallDirection::Incoming) => {
+                if let Some(hangup_device_id) = hangup_device_id {
+                    call.record_answered_elsewhere(hangup_device_id)?;
+                }
                 app_event_without_propagation(ApplicationEvent::EndedRemoteHangupAccepted)
             }
             (signaling::HangupType::DeclinedOnAnotherDevice, CallDirection::Incoming) => {
@@ -707,7 +777,32 @@ where
         let direction = call.direction();
 
         match event {
+            ConnectionObserverEvent::StateChanged(ConnectionState::ConnectedBeforeAccepted)
+                if state.active() && call.transfer_device_id()? == Some(remote_device_id) =>
+            {
+                info!(
+                    "handle_connection_observer_event(): Transfer connected from {}",
+                    remote_device_id
+                );
+                if state == CallState::ReconnectingAfterAccepted {
+                    call.set_state(CallState::ConnectedAndAccepted)?;
+                    self.notify_application(call.clone(), ApplicationEvent::Reconnected);
+                }
+                self.schedule_work_until_terminating(
+                    call,
+                    "Activate transfer failed",
+                    move |call| call.activate_transfer(remote_device_id),
+                );
+                Ok(())
+            }
             ConnectionObserverEvent::StateChanged(connection_state) => {
+                if matches!(
+                    connection_state,
+                    ConnectionState::ConnectedBeforeAccepted
+                        | ConnectionState::ConnectedAndAccepted
+                ) {
+                    call.record_milestone(CallMilestone::IceConnected)?;
+                }
                 match (direction, state, connection_state) {
                     (
                         CallDirection::Incoming,
@@ -946,6 +1041,13 @@ where
                             )
                         }
                     }
+                    if let Some(on_hold) = status.on_hold {
+                        if on_hold {
+                            self.notify_application(call.clone(), ApplicationEvent::RemoteHold)
+                        } else {
+                            self.notify_application(call.clone(), ApplicationEvent::RemoteResume)
+                        }
+                    }
                     if let Some(audio_enabled) = status.audio_enabled {
                         if audio_enabled {
                             self.notify_application(call, ApplicationEvent::RemoteAudioEnable)
@@ -961,6 +1063,28 @@ where
                 }
                 Ok(())
             }
+            ConnectionObserverEvent::ReceivedGroupCallInvitation(invitation) => {
+                if state.active() && call.active_device_id()? == remote_device_id {
+                    self.notify_group_call_invitation(call, invitation);
+                } else {
+                    info!(
+                        "call_id: {} remote_device_id: {} Ignoring group call invitation from inactive connection.",
+                        call_id, remote_device_id
+                    );
+                }
+                Ok(())
+            }
+            ConnectionObserverEvent::ReceivedTransfer(transfer_device_id) => {
+                if state.active() && call.active_device_id()? == remote_device_id {
+                    call.set_announced_transfer_device_id(transfer_device_id)?;
+                } else {
+                    info!(
+                        "call_id: {} remote_device_id: {} Ignoring transfer from inactive connection.",
+                        call_id, remote_device_id
+                    );
+                }
+                Ok(())
+            }
             ConnectionObserverEvent::IceNetworkRouteChanged(network_route) => {
                 match call.active_device_id() {
                     Err(_) => {
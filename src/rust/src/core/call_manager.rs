@@ -29,9 +29,11 @@ use crate::{
     core::{
         call::Call,
         call_mutex::CallMutex,
+        call_record::{CallMilestone, CallRecord, GroupRingOutcome, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         group_call::{Client, ClientStartParams, GroupCallKind, HttpSfuClient, Observer, Reaction},
+        incoming_call_policy::{IncomingCallDecision, IncomingCallPolicy},
         platform::Platform,
         signaling,
         signaling::ReceivedOffer,
@@ -43,7 +45,7 @@ use crate::{
         http,
         sfu::{
             self, DemuxId, GroupMember, MemberMap, MembershipProof, ObfuscatedResolver, PeekInfo,
-            UserId,
+            PeekWatchId, PeekWatchTarget, PeekWatcher, PeekWatcherConfig, UserId,
         },
     },
     protobuf,
@@ -155,10 +157,44 @@ where
     call_id: CallId,
     /// The type of message the item corresponds to.
     message_type: signaling::MessageType,
+    /// The remote device of the Connection sending the message, if the
+    /// message is sent on behalf of a single Connection.
+    remote_device_id: Option<DeviceId>,
     /// The closure to be called which will send the message.
     message_closure: MessageClosure<T>,
 }
 
+impl<T> SignalingMessageItem<T>
+where
+    T: Platform,
+{
+    /// Messages with a higher priority are sent before those with a lower
+    /// priority, regardless of when they were queued. Hangup and Busy come
+    /// first since they end calls, and ICE candidates come last since they
+    /// are only useful after the offer and answer.
+    fn priority(&self) -> u8 {
+        match self.message_type {
+            signaling::MessageType::Hangup | signaling::MessageType::Busy => 3,
+            signaling::MessageType::Answer => 2,
+            signaling::MessageType::Offer => 1,
+            signaling::MessageType::Ice => 0,
+        }
+    }
+
+    /// Hangup and Busy messages are still sent after their call has
+    /// terminated, so that the remote side can end the call too.
+    fn outlives_call(&self) -> bool {
+        matches!(
+            self.message_type,
+            signaling::MessageType::Hangup | signaling::MessageType::Busy
+        )
+    }
+}
+
+/// How many terminated calls the message queue remembers, in order to drop
+/// messages queued for them after they were trimmed.
+const MAX_TERMINATED_CALL_IDS: usize = 8;
+
 /// A structure implementing a message queue used to control the
 /// timing of sending Signaling messages. This helps ensure that
 /// messages are sent with the same cadence that they can actually
@@ -167,8 +203,12 @@ pub struct SignalingMessageQueue<T>
 where
     T: Platform,
 {
-    /// The message queue.
+    /// The message queue, ordered by priority and then by when each
+    /// message was queued.
     queue: VecDeque<SignalingMessageItem<T>>,
+    /// The most recently terminated calls, whose messages are no longer
+    /// queued unless they outlive the call.
+    terminated_call_ids: VecDeque<CallId>,
     /// The type of the last message sent from the message queue.
     last_sent_message_type: Option<signaling::MessageType>,
     /// Whether or not a message is still being handled by the
@@ -186,17 +226,73 @@ where
     pub fn new() -> Result<Self> {
         Ok(Self {
             queue: VecDeque::new(),
+            terminated_call_ids: VecDeque::new(),
             last_sent_message_type: None,
             messages_in_flight: false,
         })
     }
+
+    /// Queue a message behind any others of the same or higher priority.
+    ///
+    /// Messages for terminated calls are dropped, unless they outlive the
+    /// call. ICE messages are dropped if they immediately follow another ICE
+    /// message from the same Connection, since the queued message will take
+    /// all the buffered candidates and send them in one go.
+    fn push(&mut self, message_item: SignalingMessageItem<T>) {
+        if !message_item.outlives_call() && self.terminated_call_ids.contains(&message_item.call_id)
+        {
+            info!(
+                "push(): dropping {:?} for terminated call_id: {}",
+                message_item.message_type, message_item.call_id
+            );
+            return;
+        }
+
+        if message_item.message_type == signaling::MessageType::Ice
+            && self.queue.back().is_some_and(|last| {
+                last.message_type == signaling::MessageType::Ice
+                    && last.call_id == message_item.call_id
+                    && last.remote_device_id == message_item.remote_device_id
+            })
+        {
+            info!(
+                "push(): coalescing ICE for call_id: {}",
+                message_item.call_id
+            );
+            return;
+        }
+
+        let priority = message_item.priority();
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| queued.priority() < priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, message_item);
+    }
+
+    /// Remove all messages queued for the call, except those that outlive
+    /// it, and drop any that are queued for it later.
+    fn trim(&mut self, call_id: CallId) {
+        self.queue
+            .retain(|queued| queued.call_id != call_id || queued.outlives_call());
+
+        if !self.terminated_call_ids.contains(&call_id) {
+            if self.terminated_call_ids.len() == MAX_TERMINATED_CALL_IDS {
+                self.terminated_call_ids.pop_front();
+            }
+            self.terminated_call_ids.push_back(call_id);
+        }
+    }
 }
 
 /// Information about a received group ring that hasn't yet been accepted or cancelled.
 #[derive(Debug)]
 struct OutstandingGroupRing {
     ring_id: group_call::RingId,
+    sender_uuid: UserId,
     received: Instant,
+    received_at: SystemTime,
 }
 
 impl OutstandingGroupRing {
@@ -211,6 +307,9 @@ enum ReceivedOfferCollision {
     None,
     /// An active call with a different user, so act busy
     Busy,
+    /// An accepted call with a different user, but call waiting is enabled,
+    /// so start the incoming call without disturbing the active one
+    Waiting,
     /// An active call with the same user, but we win so ignore the incoming call
     GlareWinner,
     /// An active call with the same user, but we lose so drop our call
@@ -220,6 +319,9 @@ enum ReceivedOfferCollision {
     /// An active call with the same user, but we were already connected but they
     /// are recalling us, so drop our call, no need to send hangup, they already ended
     ReCall,
+    /// An accepted call with the same user, and the device they announced is taking
+    /// it over with the same call_id, so add a connection to that device
+    Transfer,
 }
 
 /// Management of 1:1 call messages that arrive before the offer for a particular call.
@@ -365,6 +467,32 @@ struct GroupCallClient {
     /// clients can exist, but only one of those can be active at a time. A client is
     /// considered `active` until the UI initiates a `disconnect`.
     active: bool,
+    /// What the peer of a 1:1 call needs to join this call, if the 1:1 call
+    /// is escalated into it.
+    invitation: signaling::GroupCallInvitation,
+}
+
+/// A 1:1 call that is being continued as a group call.
+#[derive(Clone, Copy, Debug)]
+struct Escalation {
+    /// The 1:1 call, which ends once the group call has been joined.
+    call_id: CallId,
+    /// The group call or call link client that takes its place.
+    client_id: group_call::ClientId,
+}
+
+/// A 1:1 call being handed over between two of the local user's devices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Transfer {
+    /// This device offered the active call to another of the user's devices,
+    /// and ends it once that device has taken over.
+    Offered { call_id: CallId },
+    /// Another of the user's devices offered its call to this one, which
+    /// calls the remote peer with the same call_id.
+    Received {
+        call_id: CallId,
+        sender_device_id: DeviceId,
+    },
 }
 
 pub struct CallManager<T>
@@ -379,6 +507,23 @@ where
     call_by_call_id: Arc<CallMutex<HashMap<CallId, Call<T>>>>,
     /// CallId of the active call.
     active_call_id: Arc<CallMutex<Option<CallId>>>,
+    /// CallId of a 1:1 call on hold while another call is active.
+    held_call_id: Arc<CallMutex<Option<CallId>>>,
+    /// CallId of an incoming 1:1 call that arrived during the active call
+    /// and hasn't been accepted yet.
+    waiting_call_id: Arc<CallMutex<Option<CallId>>>,
+    /// Whether an offer received during an accepted call should wait
+    /// instead of being rejected as busy.
+    call_waiting_enabled: Arc<CallMutex<bool>>,
+    /// Decides whether new incoming calls and group rings may ring, if set.
+    incoming_call_policy: Arc<CallMutex<Option<Box<dyn IncomingCallPolicy<T>>>>>,
+    /// The group call that the active call is being escalated into, if any.
+    escalation: Arc<CallMutex<Option<Escalation>>>,
+    /// The 1:1 call that ended because the remote joined the group call it was
+    /// being escalated into first, so the local side can still follow along.
+    remote_escalation_call_id: Arc<CallMutex<Option<CallId>>>,
+    /// The 1:1 call being handed over to or from another of the user's devices.
+    transfer: Arc<CallMutex<Option<Transfer>>>,
     /// 1:1 call messages that arrived before the Offer for a particular call.
     pending_call_messages: Arc<CallMutex<PendingCallMessages>>,
     /// Map of all group calls.
@@ -395,6 +540,10 @@ where
     message_queue: Arc<CallMutex<SignalingMessageQueue<T>>>,
     /// How to make HTTP requests to the SFU for group calls.
     http_client: http::DelegatingClient,
+    /// Wraps http_client with deadlines and retries; shared by all group calls.
+    retrying_http_client: Arc<http::RetryingClient>,
+    /// Keeps calls the application is interested in peeked. Started on first use.
+    peek_watcher: Arc<CallMutex<Option<PeekWatcher>>>,
 }
 
 impl<T> fmt::Display for CallManager<T>
@@ -452,6 +601,13 @@ where
             self_uuid: Arc::clone(&self.self_uuid),
             call_by_call_id: Arc::clone(&self.call_by_call_id),
             active_call_id: Arc::clone(&self.active_call_id),
+            held_call_id: Arc::clone(&self.held_call_id),
+            waiting_call_id: Arc::clone(&self.waiting_call_id),
+            call_waiting_enabled: Arc::clone(&self.call_waiting_enabled),
+            incoming_call_policy: Arc::clone(&self.incoming_call_policy),
+            escalation: Arc::clone(&self.escalation),
+            remote_escalation_call_id: Arc::clone(&self.remote_escalation_call_id),
+            transfer: Arc::clone(&self.transfer),
             pending_call_messages: Arc::clone(&self.pending_call_messages),
             group_call_by_client_id: Arc::clone(&self.group_call_by_client_id),
             next_group_call_client_id: Arc::clone(&self.next_group_call_client_id),
@@ -460,6 +616,8 @@ where
             worker: self.worker.clone(),
             message_queue: Arc::clone(&self.message_queue),
             http_client: self.http_client.clone(),
+            retrying_http_client: Arc::clone(&self.retrying_http_client),
+            peek_watcher: Arc::clone(&self.peek_watcher),
         }
     }
 }
@@ -481,12 +639,20 @@ where
         );
 
         let worker_stopper = Stopper::new();
+        let retrying_http_client = Arc::new(http::RetryingClient::start(http_client.clone())?);
 
         Ok(Self {
             platform: Arc::new(CallMutex::new(platform, "platform")),
             self_uuid: Arc::new(CallMutex::new(None, "self_uuid")),
             call_by_call_id: Arc::new(CallMutex::new(HashMap::new(), "call_by_call_id")),
             active_call_id: Arc::new(CallMutex::new(None, "active_call_id")),
+            held_call_id: Arc::new(CallMutex::new(None, "held_call_id")),
+            waiting_call_id: Arc::new(CallMutex::new(None, "waiting_call_id")),
+            call_waiting_enabled: Arc::new(CallMutex::new(false, "call_waiting_enabled")),
+            incoming_call_policy: Arc::new(CallMutex::new(None, "incoming_call_policy")),
+            escalation: Arc::new(CallMutex::new(None, "escalation")),
+            remote_escalation_call_id: Arc::new(CallMutex::new(None, "remote_escalation_call_id")),
+            transfer: Arc::new(CallMutex::new(None, "transfer")),
             pending_call_messages: Arc::new(CallMutex::new(
                 PendingCallMessages::None,
                 "pending_individual_call_messages",
@@ -507,6 +673,8 @@ where
                 "message_queue",
             )),
             http_client,
+            retrying_http_client,
+            peek_watcher: Arc::new(CallMutex::new(None, "peek_watcher")),
         })
     }
 
@@ -514,6 +682,12 @@ where
         &self.http_client
     }
 
+    /// What the group calls' HTTP requests to the SFU have needed so far, such as retries and
+    /// timeouts.
+    pub fn http_retry_metrics(&self) -> http::RetryMetrics {
+        self.retrying_http_client.metrics()
+    }
+
     /// Updates the current user's UUID.
     pub fn set_self_uuid(&mut self, uuid: UserId) -> Result<()> {
         info!("set_self_uuid():");
@@ -521,6 +695,34 @@ where
         Ok(())
     }
 
+    /// Enables or disables call waiting.
+    ///
+    /// When enabled, an offer from someone else that arrives during an
+    /// accepted 1:1 call is reported with [`ApplicationEvent::ReceivedCallWaiting`]
+    /// and started like any other incoming call, instead of being rejected
+    /// as busy. Accepting it puts the active call on hold.
+    pub fn set_call_waiting_enabled(&mut self, enabled: bool) -> Result<()> {
+        info!("set_call_waiting_enabled(): {}", enabled);
+        *self.call_waiting_enabled.lock()? = enabled;
+        Ok(())
+    }
+
+    /// Sets or clears the policy deciding whether incoming calls may ring.
+    ///
+    /// Offers the policy doesn't allow are answered with a hangup or busy
+    /// message, as the policy decides, and reported with
+    /// [`ApplicationEvent::ReceivedOfferDeclinedByPolicy`]. Group rings it
+    /// doesn't allow are declined on all of the user's devices and reported
+    /// with [`group_call::RingUpdate::DeclinedByPolicy`].
+    pub fn set_incoming_call_policy(
+        &mut self,
+        policy: Option<Box<dyn IncomingCallPolicy<T>>>,
+    ) -> Result<()> {
+        info!("set_incoming_call_policy(): {}", policy.is_some());
+        *self.incoming_call_policy.lock()? = policy;
+        Ok(())
+    }
+
     /// Create an outgoing call.
     pub fn call(
         &mut self,
@@ -598,16 +800,80 @@ where
         handle_active_call_api!(self, CallManager::handle_hangup)
     }
 
+    /// Put the active call on hold and resume the held call.
+    ///
+    /// If there is no active call (for example, because it ended while
+    /// the other call was on hold), the held call is simply resumed.
+    pub fn swap_calls(&mut self) -> Result<()> {
+        handle_api!(self, CallManager::handle_swap_calls)
+    }
+
+    /// Continue the active call as a group call.
+    ///
+    /// The application first creates the group call or call link client
+    /// with `create_group_call_client()` or `create_call_link_call_client()`.
+    /// The peer is invited via RTP data, the client joins, and the 1:1 call
+    /// ends with `EndedEscalatedToGroupCall` once the group call has been
+    /// joined, so media keeps flowing in the meantime.
+    ///
+    /// The peer calls this too, with its own client, when it's told about
+    /// the invitation via `Platform::on_group_call_invitation()`. If the
+    /// other side joins first, the 1:1 call ends with
+    /// `EndedEscalatedToGroupCall` right away, and this just joins.
+    pub fn escalate_to_group_call(&mut self, client_id: group_call::ClientId) -> Result<()> {
+        handle_api!(self, CallManager::handle_escalate_to_group_call, client_id)
+    }
+
+    /// Hand the active call over to another of the user's devices.
+    ///
+    /// The target device is told via a call message sent to the user's own
+    /// devices, and is expected to call `remote_user_id` with the same
+    /// call_id. The remote peer adds that device to the call, and once it
+    /// is connected, this device's call ends with `EndedTransferred`.
+    pub fn transfer_call(
+        &mut self,
+        remote_user_id: UserId,
+        target_device_id: DeviceId,
+    ) -> Result<()> {
+        handle_api!(
+            self,
+            CallManager::handle_transfer_call,
+            remote_user_id,
+            target_device_id
+        )
+    }
+
+    /// Put the active call on hold, or resume it.
+    ///
+    /// Media stops flowing in both directions while the call is on hold,
+    /// but ICE stays connected. The remote side is told via RTP data.
+    pub fn set_on_hold(&mut self, on_hold: bool) -> Result<()> {
+        handle_api!(self, CallManager::handle_set_on_hold, on_hold)
+    }
+
+    /// Removes the ring if it's still outstanding, reporting how it ended.
     fn remove_outstanding_group_ring(
         &mut self,
         group_id: group_call::GroupIdRef,
         ring_id: group_call::RingId,
+        outcome: GroupRingOutcome,
     ) -> Result<()> {
-        let mut outstanding_group_rings = self.outstanding_group_rings.lock()?;
-        if let Some(ring) = outstanding_group_rings.get(group_id) {
-            if ring.ring_id == ring_id {
-                outstanding_group_rings.remove(group_id);
+        let removed = {
+            let mut outstanding_group_rings = self.outstanding_group_rings.lock()?;
+            match outstanding_group_rings.get(group_id) {
+                Some(ring) if ring.ring_id == ring_id => outstanding_group_rings.remove(group_id),
+                _ => None,
             }
+        };
+        if let Some(ring) = removed {
+            self.notify_group_ring_record(GroupRingRecord {
+                group_id: group_id.to_vec(),
+                ring_id,
+                sender_id: ring.sender_uuid,
+                received: ring.received_at,
+                ended: SystemTime::now(),
+                outcome,
+            })?;
         }
         Ok(())
     }
@@ -621,7 +887,11 @@ where
     ) -> Result<()> {
         info!("cancel_group_ring(): ring_id: {}", ring_id);
 
-        self.remove_outstanding_group_ring(&group_id, ring_id)?;
+        let outcome = match reason {
+            Some(group_call::RingCancelReason::Busy) => GroupRingOutcome::Busy,
+            Some(group_call::RingCancelReason::DeclinedByUser) | None => GroupRingOutcome::Declined,
+        };
+        self.remove_outstanding_group_ring(&group_id, ring_id, outcome)?;
 
         if let Some(reason) = reason {
             let self_uuid = self
@@ -787,6 +1057,43 @@ where
         }
     }
 
+    /// Returns the call on hold, if any.
+    pub fn held_call(&self) -> Result<Option<Call<T>>> {
+        self.call_in_slot(&self.held_call_id)
+    }
+
+    /// Returns the waiting call, if any.
+    pub fn waiting_call(&self) -> Result<Option<Call<T>>> {
+        self.call_in_slot(&self.waiting_call_id)
+    }
+
+    fn call_in_slot(&self, slot: &CallMutex<Option<CallId>>) -> Result<Option<Call<T>>> {
+        let call_id = *slot.lock()?;
+        match call_id {
+            Some(call_id) => Ok(self.call_by_call_id.lock()?.get(&call_id).cloned()),
+            None => Ok(None),
+        }
+    }
+
+    fn waiting_call_with_id(&self, call_id: CallId) -> Result<Option<Call<T>>> {
+        Ok(self
+            .waiting_call()?
+            .filter(|call| call.call_id() == call_id))
+    }
+
+    /// Returns the held or waiting call with the given call_id, if any.
+    fn background_call(&self, call_id: CallId) -> Result<Option<Call<T>>> {
+        Ok([self.held_call()?, self.waiting_call()?]
+            .into_iter()
+            .flatten()
+            .find(|call| call.call_id() == call_id))
+    }
+
+    /// Checks if there is a held or waiting call.
+    fn has_background_call(&self) -> Result<bool> {
+        Ok(self.held_call_id.lock()?.is_some() || self.waiting_call_id.lock()?.is_some())
+    }
+
     /// Return active connection object.
     pub fn active_connection(&self) -> Result<Connection<T>> {
         info!("active_connection():");
@@ -934,10 +1241,17 @@ where
         Ok(())
     }
 
-    /// Releases busy so another call can begin
+    /// Releases busy so another call can begin, unless there is still a
+    /// held or waiting call.
     fn release_busy(&mut self) -> Result<()> {
+        if self.escalation.lock()?.is_some() {
+            // The group call the active call was escalated into has
+            // (or is about to) take over the busy state.
+            return Ok(());
+        }
+        let still_busy = self.has_background_call()?;
         let mut busy = self.busy.lock()?;
-        *busy = false;
+        *busy = still_busy;
 
         Ok(())
     }
@@ -983,6 +1297,7 @@ where
         let message_item = SignalingMessageItem {
             call_id,
             message_type: signaling::MessageType::Hangup,
+            remote_device_id: None,
             message_closure: hangup_closure,
         };
 
@@ -1010,6 +1325,7 @@ where
         self.trim_messages(call_id)?;
 
         if let Some(event) = event {
+            call.record_ended(event)?;
             let remote_peer = call.remote_peer()?;
             self.notify_application(&remote_peer, call_id, event)?;
         }
@@ -1044,6 +1360,45 @@ where
         })
     }
 
+    /// Makes the waiting call, if any, the active call, so that it carries
+    /// on like any other incoming call once the active call has ended.
+    fn promote_waiting_call(&mut self) -> Result<()> {
+        if let Some(call_id) = self.waiting_call_id.lock()?.take() {
+            info!("promote_waiting_call(): call_id: {}", call_id);
+            *self.active_call_id.lock()? = Some(call_id);
+        }
+        Ok(())
+    }
+
+    /// Terminates a held or waiting call.
+    fn terminate_background_call(
+        &mut self,
+        call: Call<T>,
+        send_hangup: bool,
+        event: ApplicationEvent,
+    ) -> Result<()> {
+        let call_id = call.call_id();
+        info!("terminate_background_call(): call_id: {}", call_id);
+
+        for slot in [&self.held_call_id, &self.waiting_call_id] {
+            let mut slot = slot.lock()?;
+            if *slot == Some(call_id) {
+                *slot = None;
+            }
+        }
+        if !self.call_active()? {
+            self.release_busy()?;
+        }
+
+        let hangup = if send_hangup {
+            Some(signaling::Hangup::Normal)
+        } else {
+            None
+        };
+
+        self.terminate_call(call, hangup, Some(event))
+    }
+
     /// Terminates the active call.
     fn terminate_active_call(&mut self, send_hangup: bool, event: ApplicationEvent) -> Result<()> {
         info!("terminate_active_call():");
@@ -1055,6 +1410,7 @@ where
 
         let call = self.active_call()?;
         self.clear_active_call()?;
+        self.promote_waiting_call()?;
         self.release_busy()?;
 
         let hangup = if send_hangup {
@@ -1124,6 +1480,10 @@ where
             format!("accept()\t{}", call_id)
         );
 
+        if let Some(waiting_call) = self.waiting_call_with_id(call_id)? {
+            return self.accept_waiting_call(waiting_call);
+        }
+
         let mut active_call = check_active_call!(self, "handle_accept_call");
         if active_call.call_id() != call_id {
             ringbenchx!(RingBench::Cm, RingBench::App, "inactive call_id");
@@ -1133,6 +1493,27 @@ where
         active_call.inject_accept_call()
     }
 
+    /// Accept the waiting call, putting the active call (if any) on hold.
+    fn accept_waiting_call(&mut self, mut waiting_call: Call<T>) -> Result<()> {
+        let call_id = waiting_call.call_id();
+        info!("accept_waiting_call(): call_id: {}", call_id);
+
+        if let Ok(active_call) = self.active_call() {
+            if let Err(err) = active_call.set_on_hold(true) {
+                warn!(
+                    "accept_waiting_call(): failed to hold call_id {}: {}",
+                    active_call.call_id(),
+                    err
+                );
+            }
+            *self.held_call_id.lock()? = Some(active_call.call_id());
+        }
+        *self.waiting_call_id.lock()? = None;
+        *self.active_call_id.lock()? = Some(call_id);
+
+        waiting_call.inject_accept_call()
+    }
+
     fn handle_terminate_active_call(
         &mut self,
         active_call: Call<T>,
@@ -1140,6 +1521,7 @@ where
         event: ApplicationEvent,
     ) -> Result<()> {
         self.clear_active_call()?;
+        self.promote_waiting_call()?;
         self.release_busy()?;
         self.terminate_call(active_call, hangup, Some(event))
     }
@@ -1152,6 +1534,15 @@ where
             format!("drop()\t{}", call_id)
         );
 
+        if let Some(waiting_call) = self.waiting_call_with_id(call_id)? {
+            // The caller has been ringing, so this is declining, not being busy.
+            return self.terminate_background_call(
+                waiting_call,
+                true,
+                ApplicationEvent::EndedAppDroppedCall,
+            );
+        }
+
         let active_call = check_active_call!(self, "handle_drop_call");
         if active_call.call_id() != call_id {
             ringbenchx!(RingBench::Cm, RingBench::App, "inactive call_id");
@@ -1175,6 +1566,11 @@ where
             format!("proceed()\t{}", call_id)
         );
 
+        if let Some(mut waiting_call) = self.waiting_call_with_id(call_id)? {
+            waiting_call.set_call_context(app_call_context)?;
+            return waiting_call.inject_proceed(call_config, audio_levels_interval);
+        }
+
         let mut active_call = check_active_call!(self, "handle_proceed");
         if active_call.call_id() != call_id {
             ringbenchx!(RingBench::Cm, RingBench::App, "inactive call_id");
@@ -1229,6 +1625,16 @@ where
                 );
 
                 let _ = self.terminate_active_call(true, ApplicationEvent::EndedSignalingFailure);
+            } else if let Ok(Some(call)) = self.background_call(call_id) {
+                info!(
+                    "handle_message_send_failure(): id: {}, concluding held or waiting call",
+                    call_id
+                );
+                self.terminate_background_call(
+                    call,
+                    true,
+                    ApplicationEvent::EndedSignalingFailure,
+                )?;
             } else {
                 // See if the associated call is in the call map.
                 let mut call = None;
@@ -1285,6 +1691,364 @@ where
         )
     }
 
+    /// Handle swap_calls() API from application.
+    fn handle_swap_calls(&mut self) -> Result<()> {
+        ringbench!(RingBench::App, RingBench::Cm, "swap_calls()");
+
+        let held_call = match self.held_call()? {
+            Some(held_call) => held_call,
+            None => {
+                ringbenchx!(RingBench::Cm, RingBench::App, "no held call");
+                return Ok(());
+            }
+        };
+
+        let active_call = self.active_call().ok();
+        if let Some(active_call) = &active_call {
+            if !active_call.state()?.active() {
+                ringbenchx!(RingBench::Cm, RingBench::App, "active call not accepted");
+                return Ok(());
+            }
+            active_call.set_on_hold(true)?;
+        }
+
+        *self.held_call_id.lock()? = active_call.map(|call| call.call_id());
+        *self.active_call_id.lock()? = Some(held_call.call_id());
+        held_call.set_on_hold(false)
+    }
+
+    /// Handle escalate_to_group_call() API from application.
+    fn handle_escalate_to_group_call(&mut self, client_id: group_call::ClientId) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::Cm,
+            format!("escalate_to_group_call()\t{}", client_id)
+        );
+
+        let active_call = match self.active_call() {
+            Ok(active_call) => active_call,
+            Err(_) => {
+                if let Some(call_id) = self.remote_escalation_call_id.lock()?.take() {
+                    // The remote joined first, which ended the 1:1 call, so just follow it.
+                    info!(
+                        "handle_escalate_to_group_call(): call_id: {} already escalated",
+                        call_id
+                    );
+                    if let Some(group_call) = self.group_call_by_client_id.lock()?.get(&client_id) {
+                        group_call.client.join();
+                    }
+                    return Ok(());
+                }
+                ringbenchx!(RingBench::Cm, RingBench::App, "inactive");
+                return Ok(());
+            }
+        };
+        info!(
+            "handle_escalate_to_group_call(): active call_id: {}",
+            active_call.call_id()
+        );
+        let _ = self.remote_escalation_call_id.lock()?.take();
+        if !active_call.state()?.active() {
+            ringbenchx!(RingBench::Cm, RingBench::App, "active call not accepted");
+            return Ok(());
+        }
+        if self.has_background_call()? {
+            ringbenchx!(RingBench::Cm, RingBench::App, "held or waiting call");
+            return Ok(());
+        }
+        if self.escalation.lock()?.is_some() {
+            ringbenchx!(RingBench::Cm, RingBench::App, "already escalating");
+            return Ok(());
+        }
+
+        let (client, invitation) = match self.group_call_by_client_id.lock()?.get(&client_id) {
+            Some(group_call) => (group_call.client.clone(), group_call.invitation.clone()),
+            None => {
+                warn!("Group Client not found for id: {}", client_id);
+                return Ok(());
+            }
+        };
+
+        active_call.send_group_call_invitation(&invitation)?;
+        *self.escalation.lock()? = Some(Escalation {
+            call_id: active_call.call_id(),
+            client_id,
+        });
+
+        // The group call client takes the busy state when it joins; until
+        // then, the active call keeps offers from starting other calls.
+        *self.busy.lock()? = false;
+        client.join();
+        Ok(())
+    }
+
+    /// Ends the escalated 1:1 call once its group call has been joined.
+    fn handle_group_call_joined(&mut self, client_id: group_call::ClientId) -> Result<()> {
+        let escalation = match self.take_escalation(client_id)? {
+            Some(escalation) => escalation,
+            None => return Ok(()),
+        };
+
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!("escalated()\t{}\t{}", escalation.call_id, client_id)
+        );
+
+        if !self.call_is_active(escalation.call_id)? {
+            info!("handle_group_call_joined(): 1:1 call already ended");
+            return Ok(());
+        }
+
+        // Busy now belongs to the group call, so it isn't released here.
+        let active_call = self.active_call()?;
+        self.clear_active_call()?;
+        self.terminate_call(
+            active_call,
+            Some(signaling::Hangup::Normal),
+            Some(ApplicationEvent::EndedEscalatedToGroupCall),
+        )
+    }
+
+    /// Gives up on an escalation whose group call ended before it was joined.
+    fn handle_group_call_ended(&mut self, client_id: group_call::ClientId) -> Result<()> {
+        let escalation = self.take_escalation(client_id)?;
+        if let Some(escalation) = escalation {
+            info!(
+                "handle_group_call_ended(): escalation of call_id: {} failed",
+                escalation.call_id
+            );
+            if self.call_is_active(escalation.call_id)? {
+                // The 1:1 call carries on, so it's still busy.
+                *self.busy.lock()? = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears the escalation if it is into the given group call, returning it.
+    fn take_escalation(&self, client_id: group_call::ClientId) -> Result<Option<Escalation>> {
+        let mut escalation = self.escalation.lock()?;
+        match *escalation {
+            Some(Escalation {
+                client_id: escalation_client_id,
+                ..
+            }) if escalation_client_id == client_id => Ok(escalation.take()),
+            _ => Ok(None),
+        }
+    }
+
+    /// Handle transfer_call() API from application.
+    fn handle_transfer_call(
+        &mut self,
+        remote_user_id: UserId,
+        target_device_id: DeviceId,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::Cm,
+            format!("transfer_call()\t{}", target_device_id)
+        );
+
+        let active_call = check_active_call!(self, "handle_transfer_call");
+        if !active_call.state()?.active() {
+            ringbenchx!(RingBench::Cm, RingBench::App, "active call not accepted");
+            return Ok(());
+        }
+        if active_call.local_device_id() == target_device_id {
+            ringbenchx!(RingBench::Cm, RingBench::App, "already on target device");
+            return Ok(());
+        }
+        if self.escalation.lock()?.is_some() {
+            ringbenchx!(RingBench::Cm, RingBench::App, "escalating");
+            return Ok(());
+        }
+
+        let self_uuid = match self.self_uuid.lock()?.clone() {
+            Some(self_uuid) => self_uuid,
+            None => {
+                error!("self UUID unknown; cannot transfer the call");
+                return Ok(());
+            }
+        };
+
+        // The remote peer only lets the announced device take over the call.
+        active_call.send_transfer(target_device_id)?;
+
+        let call_id = active_call.call_id();
+        *self.transfer.lock()? = Some(Transfer::Offered { call_id });
+
+        use protobuf::signaling::call_message::transfer::Type as TransferType;
+        let message = protobuf::signaling::CallMessage {
+            transfer: Some(protobuf::signaling::call_message::Transfer {
+                r#type: Some(TransferType::Offer.into()),
+                call_id: Some(call_id.as_u64()),
+                peer_id: Some(remote_user_id),
+                device_id: Some(target_device_id),
+            }),
+            ..Default::default()
+        };
+        self.send_signaling_message(
+            self_uuid,
+            message,
+            group_call::SignalingMessageUrgency::HandleImmediately,
+        );
+        Ok(())
+    }
+
+    /// Lets the device that offered a transferred call know that this
+    /// device has taken it over.
+    fn handle_transfer_accepted(&mut self, call_id: CallId) -> Result<()> {
+        let sender_device_id = {
+            let mut transfer = self.transfer.lock()?;
+            match *transfer {
+                Some(Transfer::Received {
+                    call_id: transfer_call_id,
+                    sender_device_id,
+                }) if transfer_call_id == call_id => {
+                    *transfer = None;
+                    sender_device_id
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!("transferred()\t{}", call_id)
+        );
+
+        let self_uuid = match self.self_uuid.lock()?.clone() {
+            Some(self_uuid) => self_uuid,
+            None => {
+                error!("self UUID unknown; cannot complete the transfer");
+                return Ok(());
+            }
+        };
+
+        use protobuf::signaling::call_message::transfer::Type as TransferType;
+        let message = protobuf::signaling::CallMessage {
+            transfer: Some(protobuf::signaling::call_message::Transfer {
+                r#type: Some(TransferType::Completed.into()),
+                call_id: Some(call_id.as_u64()),
+                peer_id: None,
+                device_id: Some(sender_device_id),
+            }),
+            ..Default::default()
+        };
+        self.send_signaling_message(
+            self_uuid,
+            message,
+            group_call::SignalingMessageUrgency::HandleImmediately,
+        );
+        Ok(())
+    }
+
+    /// Handles a transfer message from another of the user's devices.
+    fn handle_received_transfer(
+        &mut self,
+        sender_device_id: DeviceId,
+        local_device_id: DeviceId,
+        transfer: protobuf::signaling::call_message::Transfer,
+        message_age: Duration,
+    ) -> Result<()> {
+        use protobuf::signaling::call_message::transfer::Type as TransferType;
+        match (
+            transfer
+                .r#type
+                .and_then(|ty| TransferType::try_from(ty).ok()),
+            transfer.call_id,
+            transfer.device_id,
+        ) {
+            (Some(_), Some(_), Some(device_id)) if device_id != local_device_id => {
+                info!("Ignoring transfer for device {}", device_id);
+            }
+            (Some(TransferType::Offer), Some(call_id), Some(_)) => {
+                let call_id = CallId::new(call_id);
+                let remote_user_id = match transfer.peer_id {
+                    Some(peer_id) => peer_id,
+                    None => {
+                        warn!("Received transfer offer without a peer");
+                        return Ok(());
+                    }
+                };
+                if message_age > MAX_MESSAGE_AGE {
+                    info!("Ignoring expired transfer offer for call_id: {}", call_id);
+                    return Ok(());
+                }
+                if *self.busy.lock()? {
+                    info!(
+                        "Ignoring transfer offer while busy for call_id: {}",
+                        call_id
+                    );
+                    return Ok(());
+                }
+
+                ringbench!(
+                    RingBench::Cm,
+                    RingBench::App,
+                    format!("transfer_offered()\t{}\t{}", call_id, sender_device_id)
+                );
+
+                *self.transfer.lock()? = Some(Transfer::Received {
+                    call_id,
+                    sender_device_id,
+                });
+                self.platform
+                    .lock()?
+                    .on_call_transfer_offered(remote_user_id, call_id)?;
+            }
+            (Some(TransferType::Completed), Some(call_id), Some(_)) => {
+                let call_id = CallId::new(call_id);
+                if *self.transfer.lock()? != Some(Transfer::Offered { call_id }) {
+                    info!("Ignoring completed transfer for call_id: {}", call_id);
+                    return Ok(());
+                }
+                let _ = self.transfer.lock()?.take();
+
+                ringbench!(
+                    RingBench::Cm,
+                    RingBench::App,
+                    format!("transfer_completed()\t{}\t{}", call_id, sender_device_id)
+                );
+
+                if !self.call_is_active(call_id)? {
+                    info!("handle_received_transfer(): call already ended");
+                    return Ok(());
+                }
+                // The remote peer has already dropped this device in favor of
+                // the new one, so there's no need to hang up.
+                let active_call = self.active_call()?;
+                self.handle_terminate_active_call(
+                    active_call,
+                    None,
+                    ApplicationEvent::EndedTransferred,
+                )?;
+            }
+            _ => {
+                warn!("Received malformed Transfer: {:?}", transfer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle set_on_hold() API from application.
+    fn handle_set_on_hold(&mut self, on_hold: bool) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::Cm,
+            format!("set_on_hold({})", on_hold)
+        );
+
+        let active_call = check_active_call!(self, "handle_set_on_hold");
+        if !active_call.state()?.active() {
+            ringbenchx!(RingBench::Cm, RingBench::App, "active call not accepted");
+            return Ok(());
+        }
+        active_call.set_on_hold(on_hold)
+    }
+
     /// Handle received_offer() API from application.
     fn handle_received_offer(
         &mut self,
@@ -1309,6 +2073,14 @@ where
                 OfferValidationError::Expired => {
                     ringbenchx!(RingBench::Cm, RingBench::App, "offer expired");
                     self.notify_offer_expired(&remote_peer, incoming_call_id, received.age)?;
+                    self.notify_call_record(
+                        &remote_peer,
+                        CallRecord::expired_offer(
+                            incoming_call_id,
+                            received.offer.call_media_type,
+                            received.age,
+                        ),
+                    )?;
                 }
             }
             // Notify application we are completely done with this remote.
@@ -1333,21 +2105,10 @@ where
             }
         };
 
-        // Create the call object so that it will either be used as the
-        // active call or properly concluded if dropped.
-        let mut incoming_call = Call::new(
-            remote_peer.clone(),
-            incoming_call_id,
-            CallDirection::Incoming,
-            received.offer.call_media_type,
-            received.receiver_device_id,
-            self.clone(),
-        )?;
-
         let collision = match (active_call_id, &active_call, *busy) {
             (None, None, false) => ReceivedOfferCollision::None,
             (None, None, true) => {
-                info!("Group call exists, sending busy for received offer");
+                info!("Group call or held call exists, sending busy for received offer");
                 ReceivedOfferCollision::Busy
             }
             (_, Some(active_call), _) => {
@@ -1359,6 +2120,25 @@ where
             }
         };
 
+        if let (ReceivedOfferCollision::Transfer, Some(mut active_call)) =
+            (&collision, active_call.clone())
+        {
+            // The offer continues the active call rather than starting another one.
+            return active_call.inject_received_transfer(received);
+        }
+
+        // Create the call object so that it will either be used as the
+        // active call or properly concluded if dropped.
+        let mut incoming_call = Call::new(
+            remote_peer.clone(),
+            incoming_call_id,
+            CallDirection::Incoming,
+            received.offer.call_media_type,
+            received.receiver_device_id,
+            self.clone(),
+        )?;
+        incoming_call.record_offer_age(received.age)?;
+
         enum ActiveCallAction {
             DontTerminate,
             TerminateAndSendHangup(ApplicationEvent),
@@ -1368,9 +2148,17 @@ where
         enum IncomingCallAction {
             Ignore(ApplicationEvent),
             RejectAsBusy(ApplicationEvent),
+            Reject(signaling::Hangup),
             Start,
+            Wait,
         }
 
+        // Only offers that would otherwise ring are up to the policy.
+        let subject_to_policy = matches!(
+            collision,
+            ReceivedOfferCollision::None | ReceivedOfferCollision::Waiting
+        );
+
         let (active_call_action, incoming_call_action) = match collision {
             ReceivedOfferCollision::None => {
                 (ActiveCallAction::DontTerminate, IncomingCallAction::Start)
@@ -1379,6 +2167,9 @@ where
                 ActiveCallAction::DontTerminate,
                 IncomingCallAction::RejectAsBusy(ApplicationEvent::ReceivedOfferWhileActive),
             ),
+            ReceivedOfferCollision::Waiting => {
+                (ActiveCallAction::DontTerminate, IncomingCallAction::Wait)
+            }
             ReceivedOfferCollision::GlareWinner => (
                 ActiveCallAction::DontTerminate,
                 IncomingCallAction::Ignore(ApplicationEvent::ReceivedOfferWithGlare),
@@ -1397,13 +2188,34 @@ where
                 ),
                 IncomingCallAction::Start,
             ),
+            ReceivedOfferCollision::Transfer => unreachable!("handled above"),
+        };
+
+        let incoming_call_action = match incoming_call_action {
+            IncomingCallAction::Start | IncomingCallAction::Wait if subject_to_policy => {
+                let decision =
+                    self.check_direct_call_policy(&remote_peer, received.offer.call_media_type)?;
+                match decision {
+                    IncomingCallDecision::Allow => incoming_call_action,
+                    IncomingCallDecision::Decline => {
+                        IncomingCallAction::Reject(signaling::Hangup::Normal)
+                    }
+                    IncomingCallDecision::NeedPermission => IncomingCallAction::Reject(
+                        signaling::Hangup::NeedPermission(Some(received.receiver_device_id)),
+                    ),
+                    IncomingCallDecision::Busy => IncomingCallAction::RejectAsBusy(
+                        ApplicationEvent::ReceivedOfferDeclinedByPolicy,
+                    ),
+                }
+            }
+            action => action,
         };
 
         match active_call_action {
             ActiveCallAction::DontTerminate => {}
             ActiveCallAction::TerminateAndSendHangup(app_event) => {
                 self.clear_active_call()?;
-                *busy = false;
+                *busy = self.has_background_call()?;
                 self.terminate_call(
                     active_call.unwrap(),
                     Some(signaling::Hangup::Normal),
@@ -1412,19 +2224,35 @@ where
             }
             ActiveCallAction::TerminateWithoutSendingHangup(app_event) => {
                 self.clear_active_call()?;
-                *busy = false;
+                *busy = self.has_background_call()?;
                 self.terminate_call(active_call.unwrap(), None, Some(app_event))?;
             }
         }
 
         match incoming_call_action {
             IncomingCallAction::Ignore(app_event) => {
+                incoming_call.record_ended(app_event)?;
                 self.notify_application(&remote_peer, incoming_call_id, app_event)?;
             }
             IncomingCallAction::RejectAsBusy(app_event) => {
+                incoming_call.record_ended(app_event)?;
                 self.notify_application(&remote_peer, incoming_call_id, app_event)?;
                 self.send_busy(incoming_call)?;
             }
+            IncomingCallAction::Reject(hangup) => {
+                ringbenchx!(
+                    RingBench::Cm,
+                    RingBench::App,
+                    format!("declined by policy: {}", hangup)
+                );
+                incoming_call.record_ended(ApplicationEvent::ReceivedOfferDeclinedByPolicy)?;
+                self.notify_application(
+                    &remote_peer,
+                    incoming_call_id,
+                    ApplicationEvent::ReceivedOfferDeclinedByPolicy,
+                )?;
+                incoming_call.send_hangup_via_signaling_to_all(hangup)?;
+            }
             IncomingCallAction::Start => {
                 let mut active_call_id = self.active_call_id.lock()?;
                 if let Some(active_call_id) = *active_call_id {
@@ -1463,6 +2291,21 @@ where
                     }
                 }
             }
+            IncomingCallAction::Wait => {
+                *self.waiting_call_id.lock()? = Some(incoming_call_id);
+                self.call_by_call_id
+                    .lock()?
+                    .insert(incoming_call_id, incoming_call.clone());
+
+                self.notify_application(
+                    &remote_peer,
+                    incoming_call_id,
+                    ApplicationEvent::ReceivedCallWaiting,
+                )?;
+                incoming_call.start_timeout_timer(TIME_OUT_PERIOD)?;
+                incoming_call.handle_received_offer(received)?;
+                incoming_call.inject_start_call()?;
+            }
         }
         Ok(())
     }
@@ -1490,6 +2333,7 @@ where
             return Ok(());
         }
 
+        active_call.record_milestone(CallMilestone::AnswerReceived)?;
         active_call.inject_received_answer(received)
     }
 
@@ -1510,6 +2354,10 @@ where
             )
         );
 
+        if let Some(mut call) = self.background_call(call_id)? {
+            return call.inject_received_ice(received);
+        }
+
         match self.active_call() {
             Ok(mut active_call) if active_call.call_id() == call_id => {
                 active_call.inject_received_ice(received)?;
@@ -1552,6 +2400,10 @@ where
             )
         );
 
+        if let Some(mut call) = self.background_call(call_id)? {
+            return call.inject_received_hangup(received);
+        }
+
         match self.active_call() {
             Ok(mut active_call) if active_call.call_id() == call_id => {
                 active_call.inject_received_hangup(received)?;
@@ -1612,8 +2464,8 @@ where
     fn handle_received_call_message(
         &mut self,
         sender_uuid: Vec<u8>,
-        _sender_device_id: DeviceId,
-        _local_device_id: DeviceId,
+        sender_device_id: DeviceId,
+        local_device_id: DeviceId,
         message: Vec<u8>,
         message_age: Duration,
     ) -> Result<()> {
@@ -1649,6 +2501,16 @@ where
                                         Some(group_call::RingCancelReason::Busy),
                                     )?;
                                     group_call::RingUpdate::BusyLocally
+                                } else if let Some(reason) =
+                                    self.check_group_ring_policy(group_id, &sender_uuid)?
+                                {
+                                    // Answer for all of your devices.
+                                    self.cancel_group_ring(
+                                        group_id.clone(),
+                                        ring_id.into(),
+                                        Some(reason),
+                                    )?;
+                                    group_call::RingUpdate::DeclinedByPolicy
                                 } else {
                                     self.start_group_ring(
                                         group_id.clone(),
@@ -1659,11 +2521,38 @@ where
                                 }
                             }
                             IntentionType::Cancelled => {
-                                self.remove_outstanding_group_ring(group_id, ring_id.into())?;
+                                self.remove_outstanding_group_ring(
+                                    group_id,
+                                    ring_id.into(),
+                                    GroupRingOutcome::CancelledByRinger,
+                                )?;
                                 group_call::RingUpdate::CancelledByRinger
                             }
                         };
 
+                        // Rings that never rang are over as soon as they arrive.
+                        let outcome = match ring_update {
+                            group_call::RingUpdate::ExpiredRequest => {
+                                Some(GroupRingOutcome::Expired)
+                            }
+                            group_call::RingUpdate::BusyLocally => Some(GroupRingOutcome::Busy),
+                            group_call::RingUpdate::DeclinedByPolicy => {
+                                Some(GroupRingOutcome::DeclinedByPolicy)
+                            }
+                            _ => None,
+                        };
+                        if let Some(outcome) = outcome {
+                            let now = SystemTime::now();
+                            self.notify_group_ring_record(GroupRingRecord {
+                                group_id: group_id.clone(),
+                                ring_id: ring_id.into(),
+                                sender_id: sender_uuid.clone(),
+                                received: now,
+                                ended: now,
+                                outcome,
+                            })?;
+                        }
+
                         self.platform.lock()?.group_call_ring_update(
                             std::mem::take(group_id),
                             ring_id.into(),
@@ -1708,17 +2597,22 @@ where
                         warn!("should not be notified of our own other devices ringing");
                     }
                     (Some(group_id), Some(response_type), Some(ring_id)) => {
-                        let ring_update = match response_type {
-                            ResponseType::Accepted => {
-                                group_call::RingUpdate::AcceptedOnAnotherDevice
-                            }
-                            ResponseType::Busy => group_call::RingUpdate::BusyOnAnotherDevice,
-                            ResponseType::Declined => {
-                                group_call::RingUpdate::DeclinedOnAnotherDevice
-                            }
+                        let (ring_update, outcome) = match response_type {
+                            ResponseType::Accepted => (
+                                group_call::RingUpdate::AcceptedOnAnotherDevice,
+                                GroupRingOutcome::AcceptedOnAnotherDevice,
+                            ),
+                            ResponseType::Busy => (
+                                group_call::RingUpdate::BusyOnAnotherDevice,
+                                GroupRingOutcome::BusyOnAnotherDevice,
+                            ),
+                            ResponseType::Declined => (
+                                group_call::RingUpdate::DeclinedOnAnotherDevice,
+                                GroupRingOutcome::DeclinedOnAnotherDevice,
+                            ),
                             ResponseType::Ringing => unreachable!("handled above"),
                         };
-                        self.remove_outstanding_group_ring(group_id, ring_id.into())?;
+                        self.remove_outstanding_group_ring(group_id, ring_id.into(), outcome)?;
                         self.platform.lock()?.group_call_ring_update(
                             std::mem::take(group_id),
                             ring_id.into(),
@@ -1751,6 +2645,25 @@ where
                     };
                 }
             }
+            protobuf::signaling::CallMessage {
+                transfer: Some(transfer),
+                ..
+            } => {
+                let self_uuid = self.self_uuid.lock()?.clone();
+                if self_uuid.as_ref() != Some(&sender_uuid) {
+                    info!(
+                        "Discarding transfer from another user {}",
+                        uuid_to_string(&sender_uuid)
+                    );
+                    return Ok(());
+                }
+                self.handle_received_transfer(
+                    sender_device_id,
+                    local_device_id,
+                    transfer,
+                    message_age,
+                )?;
+            }
             _ => {
                 warn!("Received unknown CallMessage - ignoring");
             }
@@ -1758,6 +2671,52 @@ where
         Ok(())
     }
 
+    /// Consults the incoming call policy, if any, about an offer from `remote_peer`.
+    fn check_direct_call_policy(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        call_media_type: CallMediaType,
+    ) -> Result<IncomingCallDecision> {
+        let policy = self.incoming_call_policy.lock()?;
+        Ok(match policy.as_ref() {
+            None => IncomingCallDecision::Allow,
+            Some(policy) => {
+                let platform = self.platform.lock()?;
+                policy.check_direct_call(&platform, remote_peer, call_media_type)
+            }
+        })
+    }
+
+    /// Consults the incoming call policy, if any, about a group ring,
+    /// returning how to cancel the ring if it isn't allowed.
+    fn check_group_ring_policy(
+        &self,
+        group_id: group_call::GroupIdRef,
+        sender_uuid: &UserId,
+    ) -> Result<Option<group_call::RingCancelReason>> {
+        let incoming_call_policy = self.incoming_call_policy.lock()?;
+        let Some(policy) = incoming_call_policy.as_ref() else {
+            return Ok(None);
+        };
+        let other_rings = self
+            .outstanding_group_rings
+            .lock()?
+            .iter()
+            .filter(|(ring_group_id, ring)| {
+                ring_group_id.as_slice() != group_id && !ring.has_expired()
+            })
+            .count();
+        Ok(
+            match policy.check_group_ring(group_id, sender_uuid, other_rings) {
+                IncomingCallDecision::Allow => None,
+                IncomingCallDecision::Decline | IncomingCallDecision::NeedPermission => {
+                    Some(group_call::RingCancelReason::DeclinedByUser)
+                }
+                IncomingCallDecision::Busy => Some(group_call::RingCancelReason::Busy),
+            },
+        )
+    }
+
     fn start_group_ring(
         &mut self,
         group_id: group_call::GroupId,
@@ -1775,7 +2734,9 @@ where
                 group_id.clone(),
                 OutstandingGroupRing {
                     ring_id,
+                    sender_uuid: sender_uuid.clone(),
                     received: Instant::now(),
+                    received_at: SystemTime::now(),
                 },
             );
         }
@@ -1797,7 +2758,11 @@ where
         self.worker
             .send_delayed(*INCOMING_GROUP_CALL_RING_TIME, move |_| {
                 let result = try_scoped(|| {
-                    self_for_timeout.remove_outstanding_group_ring(&group_id, ring_id)?;
+                    self_for_timeout.remove_outstanding_group_ring(
+                        &group_id,
+                        ring_id,
+                        GroupRingOutcome::Expired,
+                    )?;
                     self_for_timeout.platform.lock()?.group_call_ring_update(
                         group_id,
                         ring_id,
@@ -1855,6 +2820,11 @@ where
             let _ = self.terminate_call(call, Some(signaling::Hangup::Normal), None);
         }
 
+        let _ = self.held_call_id.lock()?.take();
+        let _ = self.waiting_call_id.lock()?.take();
+        let _ = self.escalation.lock()?.take();
+        let _ = self.remote_escalation_call_id.lock()?.take();
+        let _ = self.transfer.lock()?.take();
         self.clear_active_call()?;
         self.release_busy()?;
 
@@ -1889,6 +2859,7 @@ where
         let message_item = SignalingMessageItem {
             call_id,
             message_type: signaling::MessageType::Busy,
+            remote_device_id: None,
             message_closure: busy_closure,
         };
 
@@ -1939,6 +2910,15 @@ where
                             info!("Glare, not yet accepted and peer devices match");
                             glare_tiebreaker()
                         }
+                    } else if *incoming_call_id == active_call.call_id()
+                        && active_call_state.active()
+                        && matches!(
+                            active_call.announced_transfer_device_id(),
+                            Ok(Some(device_id)) if device_id == received.sender_device_id
+                        )
+                    {
+                        info!("Transfer, peer's other device is taking over the call");
+                        ReceivedOfferCollision::Transfer
                     } else {
                         info!("Call from different device, sending busy for received offer");
                         ReceivedOfferCollision::Busy
@@ -1947,6 +2927,9 @@ where
                     info!("Glare, not yet connected so no active device");
                     glare_tiebreaker()
                 }
+            } else if self.can_start_waiting_call(active_call_state) {
+                info!("Active call exists, starting a waiting call for received offer");
+                ReceivedOfferCollision::Waiting
             } else {
                 info!("Active call exists, sending busy for received offer");
                 ReceivedOfferCollision::Busy
@@ -1957,6 +2940,27 @@ where
         }
     }
 
+    /// Checks if an offer from someone else can wait for the active call,
+    /// rather than being rejected as busy.
+    fn can_start_waiting_call(&self, active_call_state: CallState) -> bool {
+        let enabled = self
+            .call_waiting_enabled
+            .lock()
+            .map(|enabled| *enabled)
+            .unwrap_or(false);
+        let escalating = self
+            .escalation
+            .lock()
+            .map(|escalation| escalation.is_some())
+            .unwrap_or(true);
+        // Only one other call at a time, held or waiting, and not while the
+        // active call is turning into a group call.
+        enabled
+            && active_call_state.active()
+            && !escalating
+            && !self.has_background_call().unwrap_or(true)
+    }
+
     /// Check if the remote_peer matches the remote_peer in the active
     /// call.
     fn remote_peer_equals_active(
@@ -2042,7 +3046,7 @@ where
         if let Some(message_item) = message_item_option {
             match self.message_queue.lock() {
                 Ok(mut message_queue) => {
-                    message_queue.queue.push_back(message_item);
+                    message_queue.push(message_item);
                 }
                 Err(e) => {
                     return Err(e);
@@ -2126,7 +3130,8 @@ where
     /// messages as they might have been sent on behalf of the
     /// call before termination. Also ignore Hangup messages, since
     /// they should always be sent as backup for callees to end
-    /// their side of the call.
+    /// their side of the call. Other messages queued for the call
+    /// later on are dropped as well.
     fn trim_messages(&self, call_id: CallId) -> Result<()> {
         let mut message_queue = self.message_queue.lock()?;
         let mq = &mut *message_queue;
@@ -2136,11 +3141,7 @@ where
             call_id,
             mq.queue.len()
         );
-        mq.queue.retain(|x| {
-            (x.call_id != call_id)
-                || (x.message_type == signaling::MessageType::Busy)
-                || (x.message_type == signaling::MessageType::Hangup)
-        });
+        mq.trim(call_id);
         debug!("trim_messages(): end len: {}", mq.queue.len());
 
         Ok(())
@@ -2177,10 +3178,39 @@ where
     ) -> Result<()> {
         ringbench!(RingBench::Cm, RingBench::App, format!("event({})", event));
 
+        if event == ApplicationEvent::RemoteAccepted {
+            // If the call was transferred to this device, it has now taken over.
+            let mut call_manager = self.clone();
+            handle_api!(call_manager, CallManager::handle_transfer_accepted, call_id)?;
+        }
+
         let platform = self.platform.lock()?;
         platform.on_event(remote_peer, call_id, event)
     }
 
+    /// Notify application of an invitation to continue a call as a group call.
+    pub(super) fn notify_group_call_invitation(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        call_id: CallId,
+        invitation: signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!("group_call_invitation()\t{}\t{:?}", call_id, invitation)
+        );
+
+        if matches!(*self.escalation.lock()?, Some(escalation) if escalation.call_id == call_id) {
+            // We're already escalating this call, so this is the peer following along.
+            info!("notify_group_call_invitation(): already escalating");
+            return Ok(());
+        }
+
+        let platform = self.platform.lock()?;
+        platform.on_group_call_invitation(remote_peer, call_id, &invitation)
+    }
+
     /// Notify application that the network route changed
     pub(super) fn notify_network_route_changed(
         &self,
@@ -2281,10 +3311,18 @@ where
         info!("remote_hangup(): call_id: {}", call_id);
 
         if self.call_is_active(call_id)? {
+            if app_event_override == Some(ApplicationEvent::EndedEscalatedToGroupCall)
+                && self.escalation.lock()?.is_none()
+            {
+                *self.remote_escalation_call_id.lock()? = Some(call_id);
+            }
             match app_event_override {
                 Some(event) => self.terminate_active_call(false, event),
                 None => self.terminate_active_call(false, ApplicationEvent::EndedRemoteHangup),
             }
+        } else if let Some(call) = self.background_call(call_id)? {
+            let event = app_event_override.unwrap_or(ApplicationEvent::EndedRemoteHangup);
+            self.terminate_background_call(call, false, event)
         } else {
             info!("remote_hangup(): ignoring for inactive call");
             Ok(())
@@ -2308,6 +3346,39 @@ where
         platform.on_offer_expired(remote_peer, call_id, age)
     }
 
+    /// Notify application of how a 1:1 call went, once it has concluded.
+    pub(super) fn notify_call_record(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        record: CallRecord,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!("call_record()\t{}\t{:?}", record.call_id, record.outcome)
+        );
+
+        record.setup.log(record.call_id);
+
+        let platform = self.platform.lock()?;
+        platform.on_call_record(remote_peer, record)
+    }
+
+    /// Notify application of how a group ring went, once it's over.
+    fn notify_group_ring_record(&self, record: GroupRingRecord) -> Result<()> {
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!(
+                "group_ring_record()\t{}\t{:?}",
+                record.ring_id, record.outcome
+            )
+        );
+
+        let platform = self.platform.lock()?;
+        platform.on_group_ring_record(record)
+    }
+
     /// Notify application that the call is concluded.
     pub(super) fn notify_call_concluded(
         &self,
@@ -2330,6 +3401,8 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedTimeout)
+        } else if let Some(call) = self.background_call(call_id)? {
+            self.terminate_background_call(call, true, ApplicationEvent::EndedTimeout)
         } else {
             info!("timeout(): ignoring for inactive call");
             Ok(())
@@ -2342,6 +3415,8 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedConnectionFailure)
+        } else if let Some(call) = self.background_call(call_id)? {
+            self.terminate_background_call(call, true, ApplicationEvent::EndedConnectionFailure)
         } else {
             info!("call_failed(): ignoring for inactive call");
             Ok(())
@@ -2357,6 +3432,8 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedInternalFailure)
+        } else if let Some(call) = self.background_call(call_id)? {
+            self.terminate_background_call(call, true, ApplicationEvent::EndedInternalFailure)
         } else {
             info!("internal_error(): ignoring for inactive call");
             Ok(())
@@ -2373,6 +3450,7 @@ where
         let call_id = call.call_id();
         info!("send_offer(): call_id: {}", call_id);
 
+        let remote_device_id = connection.remote_device_id();
         let offer_closure = Box::new(move |cm: &CallManager<T>| {
             ringbench!(
                 RingBench::Cm,
@@ -2385,6 +3463,7 @@ where
             if connection.can_send_messages() {
                 let platform = cm.platform.lock()?;
                 platform.on_send_offer(&remote_peer, call_id, offer)?;
+                call.record_milestone(CallMilestone::OfferSent)?;
                 Ok(MessageSendResult::Sent)
             } else {
                 Ok(MessageSendResult::NotSent)
@@ -2394,6 +3473,7 @@ where
         let message_item = SignalingMessageItem {
             call_id,
             message_type: signaling::MessageType::Offer,
+            remote_device_id: Some(remote_device_id),
             message_closure: offer_closure,
         };
 
@@ -2410,6 +3490,7 @@ where
         let call_id = call.call_id();
         info!("send_answer(): call_id: {}", call_id);
 
+        let remote_device_id = connection.remote_device_id();
         let answer_closure = Box::new(move |cm: &CallManager<T>| {
             ringbench!(
                 RingBench::Cm,
@@ -2426,6 +3507,7 @@ where
             if connection.can_send_messages() {
                 let platform = cm.platform.lock()?;
                 platform.on_send_answer(&remote_peer, call_id, send)?;
+                call.record_milestone(CallMilestone::AnswerSent)?;
                 Ok(MessageSendResult::Sent)
             } else {
                 Ok(MessageSendResult::NotSent)
@@ -2435,6 +3517,7 @@ where
         let message_item = SignalingMessageItem {
             call_id,
             message_type: signaling::MessageType::Answer,
+            remote_device_id: Some(remote_device_id),
             message_closure: answer_closure,
         };
 
@@ -2451,6 +3534,7 @@ where
         let call_id = call.call_id();
         info!("send_ice_candidates(): call_id: {}", call_id);
 
+        let remote_device_id = connection.remote_device_id();
         let ice_closure = Box::new(move |cm: &CallManager<T>| {
             let local_candidates = connection.take_buffered_local_ice_candidates()?;
 
@@ -2485,12 +3569,14 @@ where
                     },
                 },
             )?;
+            call.record_milestone(CallMilestone::FirstIceCandidateSent)?;
             Ok(MessageSendResult::Sent)
         });
 
         let message_item = SignalingMessageItem {
             call_id,
             message_type: signaling::MessageType::Ice,
+            remote_device_id: Some(remote_device_id),
             message_closure: ice_closure,
         };
 
@@ -2562,6 +3648,17 @@ where
     ) {
         info!("handle_join_state_changed():");
         platform_handler!(self, handle_join_state_changed, client_id, join_state);
+
+        if let group_call::JoinState::Joined(_) = join_state {
+            let mut call_manager = self.clone();
+            if let Err(err) = handle_api!(
+                call_manager,
+                CallManager::handle_group_call_joined,
+                client_id
+            ) {
+                error!("handle_join_state_changed(): {}", err);
+            }
+        }
     }
 
     fn handle_remote_devices_changed(
@@ -2655,13 +3752,47 @@ where
         platform_handler!(self, handle_raised_hands, client_id, raised_hands);
     }
 
+    fn handle_presenter_changed(
+        &self,
+        client_id: group_call::ClientId,
+        current_presenter: Option<DemuxId>,
+    ) {
+        info!("handle_presenter_changed(): {:?}", current_presenter);
+        platform_handler!(self, handle_presenter_changed, client_id, current_presenter);
+    }
+
     fn handle_rtc_stats_report(&self, report_json: String) {
         platform_handler!(self, handle_rtc_stats_report, report_json);
     }
 
-    fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
+    fn handle_admin_action_results(
+        &self,
+        client_id: group_call::ClientId,
+        results: Vec<group_call::AdminActionResult>,
+    ) {
+        info!("handle_admin_action_results(): {} results", results.len());
+        platform_handler!(self, handle_admin_action_results, client_id, results);
+    }
+
+    fn handle_ended(
+        &self,
+        client_id: group_call::ClientId,
+        reason: group_call::EndReason,
+        summary: group_call::CallSummary,
+    ) {
         info!("handle_ended({:?}):", reason);
+        info!("  http retry metrics: {:?}", self.http_retry_metrics());
+        platform_handler!(self, handle_call_summary, client_id, summary);
         platform_handler!(self, handle_ended, client_id, reason);
+
+        let mut call_manager = self.clone();
+        if let Err(err) = handle_api!(
+            call_manager,
+            CallManager::handle_group_call_ended,
+            client_id
+        ) {
+            error!("handle_ended(): {}", err);
+        }
     }
 
     fn send_signaling_message(
@@ -2673,6 +3804,24 @@ where
         info!("send_signaling_message():");
         info!("  recipient: {}", uuid_to_string(&recipient_id));
 
+        // Joining a group call accepts its ring, if any, which is then over.
+        if let Some(protobuf::signaling::call_message::RingResponse {
+            group_id: Some(group_id),
+            ring_id: Some(ring_id),
+            r#type: Some(response_type),
+        }) = &call_message.ring_response
+        {
+            use protobuf::signaling::call_message::ring_response::Type as ResponseType;
+            if ResponseType::try_from(*response_type) == Ok(ResponseType::Accepted) {
+                self.remove_outstanding_group_ring(
+                    group_id,
+                    (*ring_id).into(),
+                    GroupRingOutcome::Accepted,
+                )
+                .unwrap_or_else(|e| error!("failed to remove accepted group ring: {}", e));
+            }
+        }
+
         let platform = self.platform.lock().expect("platform.lock()");
         let mut bytes = BytesMut::with_capacity(call_message.encoded_len());
         let result = call_message.encode(&mut bytes);
@@ -2756,6 +3905,91 @@ where
         }
     }
 
+    /// Keeps a call peeked in the background, reporting each change via
+    /// Platform::handle_watched_peek_changed, until unwatch_peek is called.
+    pub fn watch_peek(&self, id: PeekWatchId, target: PeekWatchTarget) -> Result<()> {
+        let mut peek_watcher = self.peek_watcher.lock()?;
+        let peek_watcher = match &mut *peek_watcher {
+            Some(peek_watcher) => peek_watcher,
+            None => {
+                let platform = Arc::clone(&self.platform);
+                peek_watcher.insert(PeekWatcher::start(
+                    Box::new(self.retrying_http_client.scoped()),
+                    PeekWatcherConfig::default(),
+                    Box::new(move |id, peek_result| match platform.lock() {
+                        Ok(platform) => {
+                            platform.handle_watched_peek_changed(id.clone(), peek_result.clone())
+                        }
+                        Err(error) => error!("{}", error),
+                    }),
+                ))
+            }
+        };
+        peek_watcher.watch(id, target);
+        Ok(())
+    }
+
+    /// Like peek_group_call, but keeps peeking. Changes are reported with the group ID.
+    pub fn watch_group_call_peek(
+        &self,
+        group_id: group_call::GroupId,
+        sfu_url: String,
+        membership_proof: MembershipProof,
+        group_members: Vec<GroupMember>,
+    ) -> Result<()> {
+        let auth_header = sfu::auth_header_from_membership_proof(&membership_proof)
+            .ok_or_else(|| anyhow::anyhow!(RingRtcError::InvalidMembershipProof))?;
+        self.watch_peek(
+            group_id,
+            PeekWatchTarget {
+                sfu_url,
+                room_id_header: None,
+                auth_header,
+                member_resolver: Arc::new(MemberMap::new(&group_members)),
+                call_link_root_key: None,
+            },
+        )
+    }
+
+    /// Like watch_group_call_peek, but for a call link. Changes are reported with the room ID
+    /// derived from the root key.
+    pub fn watch_call_link_peek(
+        &self,
+        sfu_url: String,
+        auth_credential_presentation: &[u8],
+        root_key: CallLinkRootKey,
+    ) -> Result<()> {
+        let room_id = root_key.derive_room_id();
+        self.watch_peek(
+            room_id.clone(),
+            PeekWatchTarget {
+                sfu_url,
+                room_id_header: Some(hex::encode(room_id)),
+                auth_header: call_links::auth_header_from_auth_credential(
+                    auth_credential_presentation,
+                ),
+                member_resolver: Arc::new(CallLinkMemberResolver::from(&root_key)),
+                call_link_root_key: Some(root_key),
+            },
+        )
+    }
+
+    /// Stops watching a call. A peek already in flight won't be reported.
+    pub fn unwatch_peek(&self, id: &PeekWatchId) -> Result<()> {
+        if let Some(peek_watcher) = &*self.peek_watcher.lock()? {
+            peek_watcher.unwatch(id);
+        }
+        Ok(())
+    }
+
+    /// Peeks a watched call as soon as possible, such as after receiving a group call update.
+    pub fn refresh_peek(&self, id: &PeekWatchId) -> Result<()> {
+        if let Some(peek_watcher) = &*self.peek_watcher.lock()? {
+            peek_watcher.refresh(id);
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn create_group_call_client(
         &mut self,
@@ -2807,7 +4041,7 @@ where
         };
 
         let sfu_client = HttpSfuClient::new(
-            Box::new(self.http_client.clone()),
+            Box::new(self.retrying_http_client.scoped()),
             sfu_url,
             None,
             None,
@@ -2815,6 +4049,9 @@ where
         );
 
         let obfuscated_resolver = ObfuscatedResolver::new(Arc::new(MemberMap::new(&[])), None);
+        let invitation = signaling::GroupCallInvitation::Group {
+            group_id: group_id.clone(),
+        };
 
         let client = Client::start(ClientStartParams {
             group_id,
@@ -2838,6 +4075,7 @@ where
             GroupCallClient {
                 client,
                 active: true,
+                invitation,
             },
         );
 
@@ -2894,7 +4132,7 @@ where
         let member_resolver = Arc::new(CallLinkMemberResolver::from(&root_key));
 
         let mut sfu_client = HttpSfuClient::new(
-            Box::new(self.http_client.clone()),
+            Box::new(self.retrying_http_client.scoped()),
             sfu_url,
             Some(&room_id),
             admin_passkey,
@@ -2905,6 +4143,10 @@ where
         ));
         sfu_client.set_member_resolver(member_resolver.clone());
 
+        let invitation = signaling::GroupCallInvitation::CallLink {
+            root_key: root_key.bytes().to_vec(),
+            epoch: root_key.epoch().map(u32::from),
+        };
         let obfuscated_resolver = ObfuscatedResolver::new(member_resolver, Some(root_key));
 
         let client = Client::start(ClientStartParams {
@@ -2929,6 +4171,7 @@ where
             GroupCallClient {
                 client,
                 active: true,
+                invitation,
             },
         );
 
@@ -3015,6 +4258,7 @@ where
     forward_group_call_api!(leave());
     forward_group_call_api!(react(value: String));
     forward_group_call_api!(raise_hand(raise: bool));
+    forward_group_call_api!(request_to_present(take_over: bool));
     forward_group_call_api!(group_ring => ring(recipient: Option<UserId>));
     forward_group_call_api!(set_outgoing_audio_muted(muted: bool));
     forward_group_call_api!(set_outgoing_video_muted(muted: bool));
@@ -3028,11 +4272,44 @@ where
     ), false);
     forward_group_call_api!(approve_user(user_id: UserId));
     forward_group_call_api!(deny_user(user_id: UserId));
+    forward_group_call_api!(approve_users(user_ids: Vec<UserId>));
+    forward_group_call_api!(deny_users(user_ids: Vec<UserId>));
+    forward_group_call_api!(approve_all_pending_users());
+    forward_group_call_api!(deny_all_pending_users());
     forward_group_call_api!(remove_client(other_client_id: DemuxId));
     forward_group_call_api!(block_client(other_client_id: DemuxId));
     forward_group_call_api!(set_group_members(members: Vec<GroupMember>));
     forward_group_call_api!(set_membership_proof(proof: Vec<u8>));
     forward_group_call_api!(set_rtc_stats_interval(interval: Duration));
+    forward_group_call_api!(set_heartbeat_interval(interval: Duration));
+    forward_group_call_api!(set_unresponsive_grace_period(grace_period: Duration));
+
+    /// Returns a consistent view of the group call client's state, or None if the client
+    /// can't be found or has already ended. Blocks until the client has processed all
+    /// previously queued operations, so it must be called from the application rather
+    /// than from a group call observer callback, where it returns None.
+    pub fn group_call_snapshot(
+        &self,
+        client_id: group_call::ClientId,
+    ) -> Option<group_call::ClientSnapshot> {
+        info!("group_call_snapshot(): id: {}", client_id);
+
+        // Clone the client so that the map isn't locked while waiting on the client's actor.
+        let client = match self.group_call_by_client_id.lock() {
+            Ok(group_call_map) => match group_call_map.get(&client_id) {
+                Some(group_call) => group_call.client.clone(),
+                None => {
+                    warn!("Group Client not found for id: {}", client_id);
+                    return None;
+                }
+            },
+            Err(error) => {
+                error!("{}", error);
+                return None;
+            }
+        };
+        client.snapshot()
+    }
 
     pub fn disconnect(&mut self, client_id: group_call::ClientId) {
         info!("disconnect(): id: {}", client_id);
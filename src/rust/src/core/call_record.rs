@@ -0,0 +1,417 @@
+//
+// Copyright 2024 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Records of how calls and group rings ended, for call history and
+//! setup-time metrics.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    common::{ApplicationEvent, CallDirection, CallId, CallMediaType, DeviceId},
+    core::group_call::{GroupId, RingId},
+    lite::sfu::UserId,
+};
+
+/// How a 1:1 call ended. The apps mirror these by ordinal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallOutcome {
+    /// The call was accepted, by this device for incoming calls or by the
+    /// callee for outgoing calls.
+    Accepted,
+    /// Incoming calls only: the caller gave up, or the call timed out,
+    /// before anyone answered.
+    Missed,
+    /// Outgoing calls only: the callee didn't answer in time.
+    Unanswered,
+    /// The call was declined, by this device for incoming calls or by the
+    /// callee for outgoing calls.
+    Declined,
+    /// Outgoing calls only: this device hung up before the callee answered.
+    Cancelled,
+    /// Incoming calls only: another of the user's devices answered.
+    AcceptedOnAnotherDevice,
+    /// Incoming calls only: another of the user's devices declined.
+    DeclinedOnAnotherDevice,
+    /// Incoming calls only: the incoming call policy declined the call.
+    DeclinedByPolicy,
+    /// The callee, or this device for incoming calls, was on another call.
+    Busy,
+    /// Outgoing calls only: the callee hasn't given permission to be called.
+    NeedPermission,
+    /// The call was replaced by another call with the same peer, because of
+    /// glare or a recall.
+    Superseded,
+    /// The call was accepted and then continued as a group call.
+    EscalatedToGroupCall,
+    /// The call was accepted and then continued on another of the user's devices.
+    Transferred,
+    /// The call failed before it was accepted.
+    Failed,
+}
+
+impl CallOutcome {
+    fn new(direction: CallDirection, answered: bool, ended_by: Option<ApplicationEvent>) -> Self {
+        use ApplicationEvent::*;
+        let incoming = direction == CallDirection::Incoming;
+        match ended_by {
+            Some(EndedEscalatedToGroupCall) => Self::EscalatedToGroupCall,
+            Some(EndedTransferred) => Self::Transferred,
+            _ if answered => Self::Accepted,
+            Some(EndedRemoteHangupAccepted) => Self::AcceptedOnAnotherDevice,
+            Some(EndedRemoteHangupDeclined) => Self::DeclinedOnAnotherDevice,
+            Some(ReceivedOfferDeclinedByPolicy) => Self::DeclinedByPolicy,
+            Some(EndedRemoteHangupBusy | EndedRemoteBusy | ReceivedOfferWhileActive) => Self::Busy,
+            Some(EndedRemoteHangupNeedPermission) => Self::NeedPermission,
+            Some(EndedRemoteGlare | EndedRemoteReCall | ReceivedOfferWithGlare) => Self::Superseded,
+            Some(EndedLocalHangup | EndedAppDroppedCall) if incoming => Self::Declined,
+            Some(EndedLocalHangup | EndedAppDroppedCall) => Self::Cancelled,
+            Some(EndedRemoteHangup) if incoming => Self::Missed,
+            Some(EndedRemoteHangup) => Self::Declined,
+            Some(EndedTimeout | ReceivedOfferExpired) if incoming => Self::Missed,
+            Some(EndedTimeout) => Self::Unanswered,
+            _ => Self::Failed,
+        }
+    }
+}
+
+/// A point reached while setting up a 1:1 call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallMilestone {
+    /// Outgoing calls only: the offer was handed to the application to send.
+    OfferSent,
+    /// Incoming calls only: the answer was handed to the application to send.
+    AnswerSent,
+    /// Outgoing calls only: an answer was received.
+    AnswerReceived,
+    /// The first local ICE candidates were handed to the application to send.
+    FirstIceCandidateSent,
+    /// The first remote ICE candidates were received.
+    FirstIceCandidateReceived,
+    /// ICE connected, whether or not the call had been accepted.
+    IceConnected,
+    /// The call was accepted, locally for incoming calls or via RTP data
+    /// from the callee for outgoing calls.
+    Accepted,
+    /// The remote peer's incoming media stream was first received.
+    FirstMedia,
+}
+
+/// How long setting up a 1:1 call took, as offsets from when this device
+/// started handling the call. Only the first time each milestone is reached
+/// counts, and milestones that weren't reached are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallSetupMetrics {
+    /// Incoming calls only: how long the offer took to arrive.
+    pub offer_age: Option<Duration>,
+    pub offer_sent: Option<Duration>,
+    pub answer_sent: Option<Duration>,
+    pub answer_received: Option<Duration>,
+    pub first_ice_candidate_sent: Option<Duration>,
+    pub first_ice_candidate_received: Option<Duration>,
+    pub ice_connected: Option<Duration>,
+    pub accepted: Option<Duration>,
+    pub first_media: Option<Duration>,
+}
+
+impl CallSetupMetrics {
+    fn reached(&mut self, milestone: CallMilestone, elapsed: Duration) {
+        let offset = match milestone {
+            CallMilestone::OfferSent => &mut self.offer_sent,
+            CallMilestone::AnswerSent => &mut self.answer_sent,
+            CallMilestone::AnswerReceived => &mut self.answer_received,
+            CallMilestone::FirstIceCandidateSent => &mut self.first_ice_candidate_sent,
+            CallMilestone::FirstIceCandidateReceived => &mut self.first_ice_candidate_received,
+            CallMilestone::IceConnected => &mut self.ice_connected,
+            CallMilestone::Accepted => &mut self.accepted,
+            CallMilestone::FirstMedia => &mut self.first_media,
+        };
+        offset.get_or_insert(elapsed);
+    }
+
+    /// The metrics in the order they're declared, for the application.
+    pub fn offsets(&self) -> [Option<Duration>; 9] {
+        [
+            self.offer_age,
+            self.offer_sent,
+            self.answer_sent,
+            self.answer_received,
+            self.first_ice_candidate_sent,
+            self.first_ice_candidate_received,
+            self.ice_connected,
+            self.accepted,
+            self.first_media,
+        ]
+    }
+
+    /// Writes the metrics to the stats log. Milestones that weren't reached
+    /// are left empty.
+    pub fn log(&self, call_id: CallId) {
+        let ms = |offset: Option<Duration>| {
+            offset.map_or_else(String::new, |offset| format!("{}ms", offset.as_millis()))
+        };
+        info!(
+            "ringrtc_stats!,setup,{},{},{},{},{},{},{},{},{},{}",
+            call_id,
+            ms(self.offer_age),
+            ms(self.offer_sent),
+            ms(self.answer_sent),
+            ms(self.answer_received),
+            ms(self.first_ice_candidate_sent),
+            ms(self.first_ice_candidate_received),
+            ms(self.ice_connected),
+            ms(self.accepted),
+            ms(self.first_media),
+        );
+    }
+}
+
+/// How a 1:1 call went, reported once the call has concluded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallRecord {
+    pub call_id: CallId,
+    pub direction: CallDirection,
+    pub media_type: CallMediaType,
+    /// When this device started handling the call.
+    pub started: SystemTime,
+    /// When the call was accepted, if it was.
+    pub answered: Option<SystemTime>,
+    /// When the call ended.
+    pub ended: SystemTime,
+    pub outcome: CallOutcome,
+    /// The device that answered, if any: the callee's device for outgoing
+    /// calls, and this or another of the user's devices for incoming calls.
+    pub answered_by_device_id: Option<DeviceId>,
+    /// How long each step of setting up the call took.
+    pub setup: CallSetupMetrics,
+}
+
+impl CallRecord {
+    /// A record for an incoming call that expired before it could ring,
+    /// having taken `age` to arrive.
+    pub(crate) fn expired_offer(call_id: CallId, media_type: CallMediaType, age: Duration) -> Self {
+        let now = SystemTime::now();
+        Self {
+            call_id,
+            direction: CallDirection::Incoming,
+            media_type,
+            started: now,
+            answered: None,
+            ended: now,
+            outcome: CallOutcome::Missed,
+            answered_by_device_id: None,
+            setup: CallSetupMetrics {
+                offer_age: Some(age),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// What a Call keeps track of to produce its [`CallRecord`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CallHistory {
+    started: SystemTime,
+    /// Setup metrics are measured against a monotonic clock.
+    started_instant: Instant,
+    setup: CallSetupMetrics,
+    answered: Option<SystemTime>,
+    answered_by_device_id: Option<DeviceId>,
+    ended: Option<(SystemTime, ApplicationEvent)>,
+}
+
+impl CallHistory {
+    pub fn new() -> Self {
+        Self {
+            started: SystemTime::now(),
+            started_instant: Instant::now(),
+            setup: CallSetupMetrics::default(),
+            answered: None,
+            answered_by_device_id: None,
+            ended: None,
+        }
+    }
+
+    /// The incoming call's offer took `age` to arrive.
+    pub fn offer_age(&mut self, age: Duration) {
+        self.setup.offer_age = Some(age);
+    }
+
+    /// The call reached `milestone`. Only the first time counts.
+    pub fn reached(&mut self, milestone: CallMilestone) {
+        self.setup
+            .reached(milestone, self.started_instant.elapsed());
+    }
+
+    /// The call was accepted, by `device_id`.
+    pub fn answered(&mut self, device_id: DeviceId) {
+        if self.answered.is_none() {
+            self.answered = Some(SystemTime::now());
+            self.answered_by_device_id = Some(device_id);
+        }
+    }
+
+    /// Another of the user's devices accepted the incoming call.
+    pub fn answered_elsewhere(&mut self, device_id: DeviceId) {
+        if self.answered.is_none() {
+            self.answered_by_device_id = Some(device_id);
+        }
+    }
+
+    /// The call ended, as reported to the application with `event`. Only
+    /// the first end counts.
+    pub fn ended(&mut self, event: ApplicationEvent) {
+        if self.ended.is_none() {
+            self.ended = Some((SystemTime::now(), event));
+        }
+    }
+
+    pub fn to_record(
+        &self,
+        call_id: CallId,
+        direction: CallDirection,
+        media_type: CallMediaType,
+    ) -> CallRecord {
+        let (ended, ended_by) = match self.ended {
+            Some((ended, event)) => (ended, Some(event)),
+            None => (SystemTime::now(), None),
+        };
+        CallRecord {
+            call_id,
+            direction,
+            media_type,
+            started: self.started,
+            answered: self.answered,
+            ended,
+            outcome: CallOutcome::new(direction, self.answered.is_some(), ended_by),
+            answered_by_device_id: self.answered_by_device_id,
+            setup: self.setup,
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch, for passing record times to the
+/// application.
+pub fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// How a ring for a group call ended. The apps mirror these by ordinal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupRingOutcome {
+    /// This device joined the call.
+    Accepted,
+    /// Another of the user's devices joined the call.
+    AcceptedOnAnotherDevice,
+    /// The user declined the ring on this device.
+    Declined,
+    /// The user declined the ring on another device.
+    DeclinedOnAnotherDevice,
+    /// The incoming call policy declined the ring.
+    DeclinedByPolicy,
+    /// This device was on another call.
+    Busy,
+    /// Another of the user's devices was on another call.
+    BusyOnAnotherDevice,
+    /// The sender cancelled the ring.
+    CancelledByRinger,
+    /// Nobody responded before the ring expired.
+    Expired,
+}
+
+/// How a ring for a group call went, reported once it's over.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupRingRecord {
+    pub group_id: GroupId,
+    pub ring_id: RingId,
+    pub sender_id: UserId,
+    /// When the ring was received.
+    pub received: SystemTime,
+    /// When the ring ended.
+    pub ended: SystemTime,
+    pub outcome: GroupRingOutcome,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_depends_on_direction() {
+        let outcome = |direction, event| CallOutcome::new(direction, false, Some(event));
+        assert_eq!(
+            CallOutcome::Declined,
+            outcome(CallDirection::Incoming, ApplicationEvent::EndedLocalHangup)
+        );
+        assert_eq!(
+            CallOutcome::Cancelled,
+            outcome(CallDirection::Outgoing, ApplicationEvent::EndedLocalHangup)
+        );
+        assert_eq!(
+            CallOutcome::Missed,
+            outcome(CallDirection::Incoming, ApplicationEvent::EndedRemoteHangup)
+        );
+        assert_eq!(
+            CallOutcome::Declined,
+            outcome(CallDirection::Outgoing, ApplicationEvent::EndedRemoteHangup)
+        );
+        assert_eq!(
+            CallOutcome::Missed,
+            outcome(CallDirection::Incoming, ApplicationEvent::EndedTimeout)
+        );
+        assert_eq!(
+            CallOutcome::Unanswered,
+            outcome(CallDirection::Outgoing, ApplicationEvent::EndedTimeout)
+        );
+    }
+
+    #[test]
+    fn answered_calls_are_accepted() {
+        for event in [
+            ApplicationEvent::EndedLocalHangup,
+            ApplicationEvent::EndedRemoteHangup,
+            ApplicationEvent::EndedConnectionFailure,
+        ] {
+            assert_eq!(
+                CallOutcome::Accepted,
+                CallOutcome::new(CallDirection::Incoming, true, Some(event))
+            );
+        }
+        assert_eq!(
+            CallOutcome::Transferred,
+            CallOutcome::new(
+                CallDirection::Outgoing,
+                true,
+                Some(ApplicationEvent::EndedTransferred)
+            )
+        );
+    }
+
+    #[test]
+    fn only_first_end_counts() {
+        let mut history = CallHistory::new();
+        history.ended(ApplicationEvent::EndedRemoteBusy);
+        history.ended(ApplicationEvent::EndedInternalFailure);
+        let record = history.to_record(
+            CallId::new(1),
+            CallDirection::Outgoing,
+            CallMediaType::Audio,
+        );
+        assert_eq!(CallOutcome::Busy, record.outcome);
+        assert_eq!(None, record.answered);
+    }
+
+    #[test]
+    fn only_first_milestone_counts() {
+        let mut setup = CallSetupMetrics::default();
+        setup.reached(CallMilestone::FirstMedia, Duration::from_millis(500));
+        setup.reached(CallMilestone::FirstMedia, Duration::from_millis(900));
+        setup.reached(CallMilestone::IceConnected, Duration::from_millis(300));
+        assert_eq!(Some(Duration::from_millis(500)), setup.first_media);
+        assert_eq!(Some(Duration::from_millis(300)), setup.ice_connected);
+        assert_eq!(None, setup.accepted);
+    }
+}
@@ -13,7 +13,7 @@ use std::{
         Arc, Condvar, Mutex, MutexGuard,
     },
     thread,
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 
 use bytes::{BufMut, BytesMut};
@@ -33,10 +33,11 @@ use crate::{
     core::{
         call::Call,
         call_mutex::CallMutex,
+        call_record::CallMilestone,
         connection_fsm::{ConnectionEvent, ConnectionStateMachine},
         platform::Platform,
         signaling,
-        util::{ptr_as_box, redact_string},
+        util::{ptr_as_box, random_alphanumeric, redact_string},
     },
     error::RingRtcError,
     lite::sfu::DemuxId,
@@ -89,7 +90,7 @@ pub const NEW_RTP_DATA_SSRC: rtp::Ssrc = 0xD;
 
 /// Connection observer status notification types
 /// Sent from the Connection to the parent Call object
-#[derive(Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ConnectionObserverEvent {
     StateChanged(ConnectionState),
 
@@ -101,6 +102,14 @@ pub enum ConnectionObserverEvent {
     /// or via signaling.
     ReceivedHangup(signaling::Hangup),
 
+    /// The remote side sent an invitation to continue the call as a
+    /// group call via RTP data.
+    ReceivedGroupCallInvitation(signaling::GroupCallInvitation),
+
+    /// The remote side announced via RTP data which of its devices is
+    /// taking over the call.
+    ReceivedTransfer(DeviceId),
+
     /// The ICE network route changed
     IceNetworkRouteChanged(NetworkRoute),
 
@@ -121,12 +130,6 @@ impl ConnectionObserverEvent {
     }
 }
 
-impl Clone for ConnectionObserverEvent {
-    fn clone(&self) -> Self {
-        *self
-    }
-}
-
 impl fmt::Display for ConnectionObserverEvent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -340,6 +343,37 @@ enum BweCallbackState {
     Done,
 }
 
+/// What a connection needs to restart ICE once it is set up.
+///
+/// A restart keeps everything that was negotiated in the offer and answer
+/// except for the ICE credentials, which both sides replace. The offerer
+/// always performs the restart; the answerer can only ask for one.
+#[derive(Default)]
+struct IceRestartState {
+    /// The offer and answer the connection was started with.
+    v4_offer: Option<protobuf::signaling::ConnectionParametersV4>,
+    v4_answer: Option<protobuf::signaling::ConnectionParametersV4>,
+    /// The SRTP keys negotiated along with them, if any.
+    offer_key: Option<SrtpKey>,
+    answer_key: Option<SrtpKey>,
+    /// The generation of the latest restart, sent or received.
+    generation: u32,
+    /// Restarts tried since the connection last (re)connected.
+    attempts: u32,
+    /// When the latest restart started, while it is still in progress.
+    started: Option<Instant>,
+}
+
+impl IceRestartState {
+    /// Older clients drop restarts, so both the offer and the answer have
+    /// to say they can handle them.
+    fn supported(&self) -> bool {
+        [&self.v4_offer, &self.v4_answer]
+            .iter()
+            .all(|v4| matches!(v4, Some(v4) if v4.supports_ice_restart == Some(true)))
+    }
+}
+
 /// Represents the connection between a local client and one remote peer.
 ///
 /// This object is thread-safe.
@@ -389,6 +423,8 @@ where
     incoming_video_sink: Option<Box<dyn VideoSink>>,
     /// Tracks when to send `ConnectionObserverEvent::LowBandwidthForVideo`.
     bwe_callback_state: BweCallbackState,
+    /// The negotiated parameters and progress of ICE restarts.
+    ice_restart: Arc<CallMutex<IceRestartState>>,
 }
 
 impl<T> fmt::Display for Connection<T>
@@ -474,6 +510,7 @@ where
             last_received_rtp_data_timestamp: Arc::clone(&self.last_received_rtp_data_timestamp),
             incoming_video_sink: self.incoming_video_sink.clone(),
             bwe_callback_state: self.bwe_callback_state,
+            ice_restart: Arc::clone(&self.ice_restart),
         }
     }
 }
@@ -495,7 +532,14 @@ where
         let (fsm_sender, fsm_receiver) = std::sync::mpsc::sync_channel(256);
 
         let call_id = call.call_id();
-        let direction = call.direction();
+        // Usually the same as the call's direction, but a call transferred
+        // to another of the peer's devices answers that device's offer.
+        let direction = match connection_type {
+            ConnectionType::Incoming => CallDirection::Incoming,
+            ConnectionType::OutgoingParent | ConnectionType::OutgoingChild => {
+                CallDirection::Outgoing
+            }
+        };
 
         let webrtc = WebRtcData {
             peer_connection: None,
@@ -561,6 +605,7 @@ where
             bwe_callback_state: BweCallbackState::CheckIfLow {
                 delayed_check_tick: 0,
             },
+            ice_restart: Arc::new(CallMutex::new(Default::default(), "ice_restart")),
         };
 
         connection.init_connection_ptr()?;
@@ -682,6 +727,7 @@ where
             peer_connection.create_offer(observer.as_ref());
             let _ = observer.get_result()?;
 
+            let mut ice_restart = IceRestartState::default();
             let (mut offer, mut answer, remote_public_key) =
                 if let (Some(v4_offer), Some(v4_answer)) = (offer.to_v4(), received.answer.to_v4())
                 {
@@ -697,7 +743,10 @@ where
                     v4_answer.receive_video_codecs, v4_answer.max_bitrate_bps, bandwidth_controller
                 );
 
-                    (offer, answer, v4_answer.public_key)
+                    let remote_public_key = v4_answer.public_key.clone();
+                    ice_restart.v4_offer = Some(v4_offer);
+                    ice_restart.v4_answer = Some(v4_answer);
+                    (offer, answer, remote_public_key)
                 } else {
                     return Err(RingRtcError::UnknownSignaledProtocolVersion.into());
                 };
@@ -716,6 +765,8 @@ where
                 )?;
                 offer.disable_dtls_and_set_srtp_key(&offer_key)?;
                 answer.disable_dtls_and_set_srtp_key(&answer_key)?;
+                ice_restart.offer_key = Some(offer_key);
+                ice_restart.answer_key = Some(answer_key);
             }
 
             let observer = create_ssd_observer();
@@ -740,6 +791,8 @@ where
 
             self.apply_bandwidth_controller(&mut bandwidth_controller, &mut webrtc)?;
 
+            *self.ice_restart.lock()? = ice_restart;
+
             self.set_state(ConnectionState::ConnectingBeforeAccepted)?;
             Ok(())
         })();
@@ -794,6 +847,7 @@ where
                 return Err(RingRtcError::UnknownSignaledProtocolVersion.into());
             };
 
+            let mut ice_restart = IceRestartState::default();
             let (local_secret, local_public_key) = generate_local_secret_and_public_key()?;
             let answer_key = match remote_public_key {
                 None => None,
@@ -810,6 +864,7 @@ where
                         callee_identity_key,
                     )?;
                     offer.disable_dtls_and_set_srtp_key(&offer_key)?;
+                    ice_restart.offer_key = Some(offer_key);
                     Some(answer_key)
                 }
             };
@@ -845,6 +900,9 @@ where
                 if let Some(answer_key) = &answer_key {
                     answer.disable_dtls_and_set_srtp_key(answer_key)?;
                 }
+                ice_restart.v4_offer = v4_offer.clone();
+                ice_restart.v4_answer = Some(v4_answer.clone());
+                ice_restart.answer_key = answer_key;
                 signaling::Answer::from_v4(v4_answer)?
             } else {
                 return Err(RingRtcError::UnknownSignaledProtocolVersion.into());
@@ -875,6 +933,8 @@ where
             let peer_connection = webrtc.peer_connection()?;
             self.add_and_remove_remote_ice_candidates(peer_connection, &remote_ice_candidates)?;
 
+            *self.ice_restart.lock()? = ice_restart;
+
             self.set_state(ConnectionState::ConnectingBeforeAccepted)?;
             Ok(answer_to_send)
         })();
@@ -1139,6 +1199,11 @@ where
     }
 
     pub fn tick(&mut self, ticks_elapsed: u64) -> Result<()> {
+        if self.ice_restart_timed_out()? {
+            // Treat it like another failure, which retries or gives up.
+            self.inject_ice_failed()?;
+        }
+
         let mut webrtc = self.webrtc.lock()?;
 
         if ticks_elapsed % SEND_RTP_DATA_MESSAGE_INTERVAL_TICKS == 0 {
@@ -1285,6 +1350,16 @@ where
     }
 
     pub fn handle_received_ice(&self, ice: signaling::Ice) -> Result<()> {
+        // Apply restarts first so that the candidates which follow them
+        // are paired with the new credentials.
+        for restart in ice
+            .candidates
+            .iter()
+            .filter_map(|candidate| candidate.ice_restart())
+        {
+            self.handle_received_ice_restart(restart)?;
+        }
+
         let webrtc = self.webrtc.lock()?;
         let pc = webrtc.peer_connection()?;
 
@@ -1301,6 +1376,10 @@ where
         let mut removed_addresses = vec![];
         let mut removed_ports = vec![];
         for candidate in remote_ice_candidates {
+            if candidate.ice_restart().is_some() {
+                // Handled by handle_received_ice(); the added candidate is only a dummy.
+                continue;
+            }
             if let Some(removed_address) = candidate.removed_address() {
                 removed_ports.push(removed_address.port());
                 removed_addresses.push(removed_address);
@@ -1335,6 +1414,255 @@ where
         Ok(())
     }
 
+    /// Returns true if the remote peer supports ICE restarts and another
+    /// one may be tried. A restart for a new local network isn't a retry,
+    /// so it doesn't need any attempts left.
+    pub fn can_restart_ice(&self, new_network: bool) -> Result<bool> {
+        let ice_restart = self.ice_restart.lock()?;
+        Ok(ice_restart.supported()
+            && (new_network
+                || ice_restart.attempts < self.call_config.ice_restart_config.max_attempts))
+    }
+
+    /// Restart ICE with new local credentials. The answerer can't do that
+    /// by itself, so it asks the offerer to restart instead.
+    ///
+    /// A restart for a new local network isn't a retry, so `new_network`
+    /// forgets about earlier attempts rather than counting as one.
+    pub fn restart_ice(&self, new_network: bool) -> Result<()> {
+        let (generation, v4_offer, offer_key) = {
+            let mut ice_restart = self.ice_restart.lock()?;
+            if new_network {
+                ice_restart.attempts = 0;
+            } else {
+                ice_restart.attempts += 1;
+            }
+            ice_restart.started = Some(Instant::now());
+
+            if self.direction == CallDirection::Incoming {
+                info!(
+                    "restart_ice(): asking the offerer to restart, attempt: {}, generation: {}",
+                    ice_restart.attempts, ice_restart.generation
+                );
+                let restart = signaling::IceRestart {
+                    generation: ice_restart.generation,
+                    credentials: None,
+                };
+                drop(ice_restart);
+                return self.send_ice_restart(restart);
+            }
+
+            ice_restart.generation += 1;
+            info!(
+                "restart_ice(): attempt: {}, generation: {}",
+                ice_restart.attempts, ice_restart.generation
+            );
+            (
+                ice_restart.generation,
+                ice_restart.v4_offer.clone(),
+                ice_restart.offer_key.clone(),
+            )
+        };
+
+        let mut v4_offer = v4_offer.ok_or_else(|| {
+            RingRtcError::OptionValueNotSet("restart_ice".to_string(), "v4_offer".to_string())
+        })?;
+        let (ice_ufrag, ice_pwd) = generate_ice_credentials();
+        v4_offer.ice_ufrag = Some(ice_ufrag.clone());
+        v4_offer.ice_pwd = Some(ice_pwd.clone());
+
+        let mut offer = SessionDescription::offer_from_v4(&v4_offer, &self.call_config)?;
+        if let Some(offer_key) = &offer_key {
+            offer.disable_dtls_and_set_srtp_key(offer_key)?;
+        }
+
+        // Send the credentials before gathering starts so that they reach
+        // the remote peer ahead of any candidates using them.
+        self.send_ice_restart(signaling::IceRestart {
+            generation,
+            credentials: Some((ice_ufrag, ice_pwd)),
+        })?;
+
+        let webrtc = self.webrtc.lock()?;
+        let observer = create_ssd_observer();
+        webrtc
+            .peer_connection()?
+            .set_local_description(observer.as_ref(), offer);
+        observer.get_result()?;
+        Ok(())
+    }
+
+    /// Forget about earlier ICE restarts once the connection is back.
+    pub fn ice_restart_completed(&self) -> Result<()> {
+        let mut ice_restart = self.ice_restart.lock()?;
+        if ice_restart.attempts > 0 {
+            info!(
+                "ice_restart_completed(): attempts: {}, generation: {}",
+                ice_restart.attempts, ice_restart.generation
+            );
+        }
+        ice_restart.attempts = 0;
+        ice_restart.started = None;
+        Ok(())
+    }
+
+    /// Returns true (once) when the latest ICE restart didn't reconnect in time.
+    fn ice_restart_timed_out(&self) -> Result<bool> {
+        let mut ice_restart = self.ice_restart.lock()?;
+        match ice_restart.started {
+            Some(started)
+                if started.elapsed() >= self.call_config.ice_restart_config.attempt_timeout =>
+            {
+                ice_restart.started = None;
+                // A restart while still connected (after a network change)
+                // doesn't need to reconnect, and is done with by now.
+                if self.state()? == ConnectionState::ReconnectingAfterAccepted {
+                    Ok(true)
+                } else {
+                    ice_restart.attempts = 0;
+                    Ok(false)
+                }
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn send_ice_restart(&self, restart: signaling::IceRestart) -> Result<()> {
+        info!(
+            "send_ice_restart(): generation: {}, with credentials: {}",
+            restart.generation,
+            restart.credentials.is_some()
+        );
+        self.buffer_local_ice_candidates(vec![signaling::IceCandidate::from_ice_restart(restart)?])
+    }
+
+    fn handle_received_ice_restart(&self, restart: signaling::IceRestart) -> Result<()> {
+        let signaling::IceRestart {
+            generation,
+            credentials,
+        } = restart;
+        info!(
+            "handle_received_ice_restart(): generation: {}, with credentials: {}",
+            generation,
+            credentials.is_some()
+        );
+
+        match (self.direction, credentials) {
+            (CallDirection::Outgoing, None) => {
+                // The answerer wants a restart, unless we've started one since.
+                if generation != self.ice_restart.lock()?.generation {
+                    info!("Ignoring request for an outdated ICE restart");
+                    return Ok(());
+                }
+                if !self.can_restart_ice(false)? {
+                    info!("Ignoring request for an ICE restart; unsupported or no attempts left");
+                    return Ok(());
+                }
+                self.restart_ice(false)
+            }
+            (CallDirection::Outgoing, Some((ice_ufrag, ice_pwd))) => {
+                // The answerer's reply to our restart.
+                let (v4_answer, answer_key) = {
+                    let ice_restart = self.ice_restart.lock()?;
+                    if generation != ice_restart.generation {
+                        info!("Ignoring reply to an outdated ICE restart");
+                        return Ok(());
+                    }
+                    (
+                        ice_restart.v4_answer.clone(),
+                        ice_restart.answer_key.clone(),
+                    )
+                };
+
+                let mut v4_answer = v4_answer.ok_or_else(|| {
+                    RingRtcError::OptionValueNotSet(
+                        "handle_received_ice_restart".to_string(),
+                        "v4_answer".to_string(),
+                    )
+                })?;
+                v4_answer.ice_ufrag = Some(ice_ufrag);
+                v4_answer.ice_pwd = Some(ice_pwd);
+
+                let mut answer = SessionDescription::answer_from_v4(&v4_answer, &self.call_config)?;
+                if let Some(answer_key) = &answer_key {
+                    answer.disable_dtls_and_set_srtp_key(answer_key)?;
+                }
+
+                let webrtc = self.webrtc.lock()?;
+                let observer = create_ssd_observer();
+                webrtc
+                    .peer_connection()?
+                    .set_remote_description(observer.as_ref(), answer);
+                observer.get_result()?;
+                Ok(())
+            }
+            (CallDirection::Incoming, None) => {
+                warn!("Ignoring request for an ICE restart from the offerer");
+                Ok(())
+            }
+            (CallDirection::Incoming, Some((ice_ufrag, ice_pwd))) => {
+                // The offerer restarted, so answer with our own new credentials.
+                let (v4_offer, offer_key, v4_answer, answer_key) = {
+                    let mut ice_restart = self.ice_restart.lock()?;
+                    if generation <= ice_restart.generation {
+                        info!("Ignoring outdated ICE restart");
+                        return Ok(());
+                    }
+                    ice_restart.generation = generation;
+                    ice_restart.started = Some(Instant::now());
+                    (
+                        ice_restart.v4_offer.clone(),
+                        ice_restart.offer_key.clone(),
+                        ice_restart.v4_answer.clone(),
+                        ice_restart.answer_key.clone(),
+                    )
+                };
+
+                let (mut v4_offer, mut v4_answer) = match (v4_offer, v4_answer) {
+                    (Some(v4_offer), Some(v4_answer)) => (v4_offer, v4_answer),
+                    _ => {
+                        return Err(RingRtcError::OptionValueNotSet(
+                            "handle_received_ice_restart".to_string(),
+                            "v4_offer".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                v4_offer.ice_ufrag = Some(ice_ufrag);
+                v4_offer.ice_pwd = Some(ice_pwd);
+                let (local_ice_ufrag, local_ice_pwd) = generate_ice_credentials();
+                v4_answer.ice_ufrag = Some(local_ice_ufrag.clone());
+                v4_answer.ice_pwd = Some(local_ice_pwd.clone());
+
+                let mut offer = SessionDescription::offer_from_v4(&v4_offer, &self.call_config)?;
+                if let Some(offer_key) = &offer_key {
+                    offer.disable_dtls_and_set_srtp_key(offer_key)?;
+                }
+                let mut answer = SessionDescription::answer_from_v4(&v4_answer, &self.call_config)?;
+                if let Some(answer_key) = &answer_key {
+                    answer.disable_dtls_and_set_srtp_key(answer_key)?;
+                }
+
+                self.send_ice_restart(signaling::IceRestart {
+                    generation,
+                    credentials: Some((local_ice_ufrag, local_ice_pwd)),
+                })?;
+
+                let webrtc = self.webrtc.lock()?;
+                let peer_connection = webrtc.peer_connection()?;
+
+                let observer = create_ssd_observer();
+                peer_connection.set_remote_description(observer.as_ref(), offer);
+                observer.get_result()?;
+
+                let observer = create_ssd_observer();
+                peer_connection.set_local_description(observer.as_ref(), answer);
+                observer.get_result()?;
+                Ok(())
+            }
+        }
+    }
+
     /// Send a hangup message to the remote peer via RTP data.
     pub fn send_hangup_via_rtp_data(&self, hangup: signaling::Hangup) -> Result<()> {
         ringbench!(
@@ -1373,6 +1701,65 @@ where
         })
     }
 
+    /// Stop or resume sending and receiving media, and tell the remote peer
+    /// via the sender status. ICE stays connected so that a held call can be
+    /// resumed.
+    pub fn set_on_hold(&mut self, on_hold: bool) -> Result<()> {
+        ringbench!(
+            RingBench::Conn,
+            RingBench::WebRtc,
+            format!("hold({})\t{}", on_hold, self.connection_id)
+        );
+
+        {
+            let webrtc = self.webrtc.lock()?;
+            let pc = webrtc.peer_connection()?;
+            pc.set_outgoing_media_enabled(!on_hold);
+            pc.set_incoming_media_enabled(!on_hold);
+        }
+        self.update_sender_status(signaling::SenderStatus {
+            on_hold: Some(on_hold),
+            ..Default::default()
+        })
+    }
+
+    /// Invite the remote peer to continue the call as a group call.
+    pub fn send_group_call_invitation(
+        &self,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::Conn,
+            RingBench::WebRtc,
+            format!("dc(group_call_invitation)\t{}", self.connection_id)
+        );
+
+        let invitation = invitation.to_proto(self.call_id);
+        let mut webrtc = self.webrtc.lock()?;
+        self.update_and_send_rtp_data_message(&mut webrtc, move |data| {
+            data.group_call_invitation = Some(invitation)
+        })
+    }
+
+    /// Tell the remote peer which of the local user's devices is taking over
+    /// the call.
+    pub fn send_transfer(&self, target_device_id: DeviceId) -> Result<()> {
+        ringbench!(
+            RingBench::Conn,
+            RingBench::WebRtc,
+            format!("dc(transfer)\t{}\t{}", self.connection_id, target_device_id)
+        );
+
+        let transfer = protobuf::rtp_data::Transfer {
+            id: Some(u64::from(self.call_id)),
+            device_id: Some(target_device_id),
+        };
+        let mut webrtc = self.webrtc.lock()?;
+        self.update_and_send_rtp_data_message(&mut webrtc, move |data| {
+            data.transfer = Some(transfer)
+        })
+    }
+
     fn update_bandwidth_controller(
         &self,
         update: impl FnOnce(&mut BandwidthController) -> bool,
@@ -1422,11 +1809,13 @@ where
                 previous.and_then(|sender_status| sender_status.sharing_screen);
             let previous_audio_enabled =
                 previous.and_then(|sender_status| sender_status.audio_enabled);
+            let previous_on_hold = previous.and_then(|sender_status| sender_status.on_hold);
             data.sender_status = Some(protobuf::rtp_data::SenderStatus {
                 id: Some(u64::from(self.call_id)),
                 video_enabled: updated.video_enabled.or(previous_video_enabled),
                 sharing_screen: updated.sharing_screen.or(previous_sharing_screen),
                 audio_enabled: updated.audio_enabled.or(previous_audio_enabled),
+                on_hold: updated.on_hold.or(previous_on_hold),
             });
         })
     }
@@ -1538,7 +1927,9 @@ where
             // - iOS: The RTCMediaStream level of wrapping is passed to the app via onConnectMedia, which adds a sink to the first video track.
             // - Android: The JavaMediaStream level of wrapping is passed to the app via onConnectMedia, which adds a sink to the first video track.
             // - Desktop: Uses the PeerConnectionObserver for video sinks rather than adding its own.
-            call.create_incoming_media(self, stream)?
+            let incoming_media = call.create_incoming_media(self, stream)?;
+            call.record_milestone(CallMilestone::FirstMedia)?;
+            incoming_media
         };
         self.set_incoming_media(incoming_media)
     }
@@ -1873,6 +2264,7 @@ where
                     video_enabled: sender_status.video_enabled,
                     sharing_screen: sender_status.sharing_screen,
                     audio_enabled: sender_status.audio_enabled,
+                    on_hold: sender_status.on_hold,
                 },
                 seqnum,
             )
@@ -1888,6 +2280,27 @@ where
             .unwrap_or_else(|e| warn!("unable to inject remote receiver status event: {}", e));
             message_handled = true;
         };
+        if let Some(invitation) = message.group_call_invitation {
+            let call_id = CallId::new(invitation.id());
+            match signaling::GroupCallInvitation::from_proto(invitation) {
+                Some(invitation) => self
+                    .inject_received_group_call_invitation_via_rtp_data(call_id, invitation)
+                    .unwrap_or_else(|e| {
+                        warn!("unable to inject remote group call invitation event: {}", e)
+                    }),
+                None => warn!("Ignoring group call invitation without a group or call link"),
+            }
+            message_handled = true;
+        };
+        if let Some(transfer) = message.transfer {
+            match transfer.device_id {
+                Some(device_id) => self
+                    .inject_received_transfer_via_rtp_data(CallId::new(transfer.id()), device_id)
+                    .unwrap_or_else(|e| warn!("unable to inject remote transfer event: {}", e)),
+                None => warn!("Ignoring transfer without a device"),
+            }
+            message_handled = true;
+        };
         if !message_handled {
             info!("Unhandled RTP data message: {:?}", message);
         }
@@ -1904,6 +2317,42 @@ where
         self.inject_event(ConnectionEvent::ReceivedAcceptedViaRtpData(call_id))
     }
 
+    /// Inject a `ReceivedGroupCallInvitationViaRtpData` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `call_id` - Call ID from the remote peer.
+    /// * `invitation` - The group call to continue the call in.
+    pub fn inject_received_group_call_invitation_via_rtp_data(
+        &mut self,
+        call_id: CallId,
+        invitation: signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        self.inject_event(ConnectionEvent::ReceivedGroupCallInvitationViaRtpData(
+            call_id, invitation,
+        ))
+    }
+
+    /// Inject a `ReceivedTransferViaRtpData` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `call_id` - Call ID from the remote peer.
+    /// * `device_id` - The remote device taking over the call.
+    pub fn inject_received_transfer_via_rtp_data(
+        &mut self,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        self.inject_event(ConnectionEvent::ReceivedTransferViaRtpData(
+            call_id, device_id,
+        ))
+    }
+
     /// Inject a `ReceivedHangup` event into the FSM.
     ///
     /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
@@ -2082,6 +2531,31 @@ where
             .unwrap()
             .sender_status
     }
+
+    #[cfg(feature = "sim")]
+    pub fn last_sent_group_call_invitation(
+        &self,
+    ) -> Option<protobuf::rtp_data::GroupCallInvitation> {
+        self.accumulated_rtp_data_message
+            .lock()
+            .unwrap()
+            .group_call_invitation
+            .clone()
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn last_sent_transfer(&self) -> Option<protobuf::rtp_data::Transfer> {
+        self.accumulated_rtp_data_message.lock().unwrap().transfer
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn last_sent_hold(&self) -> Option<bool> {
+        self.accumulated_rtp_data_message
+            .lock()
+            .unwrap()
+            .sender_status
+            .and_then(|sender_status| sender_status.on_hold)
+    }
 }
 
 #[cfg(feature = "sim")]
@@ -2201,6 +2675,11 @@ fn generate_local_secret_and_public_key() -> Result<(StaticSecret, PublicKey)> {
     Ok((secret, public))
 }
 
+/// Generates a new ICE ufrag and pwd, as short as ICE allows.
+fn generate_ice_credentials() -> (String, String) {
+    (random_alphanumeric(4), random_alphanumeric(22))
+}
+
 struct NegotiatedSrtpKeys {
     pub offer_key: SrtpKey,
     pub answer_key: SrtpKey,
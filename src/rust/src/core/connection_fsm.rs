@@ -35,6 +35,8 @@
 //! - ReceivedAcceptedViaRtpData
 //! - ReceivedSenderStatusViaRtpData
 //! - ReceivedReceiverStatusViaRtpData
+//! - ReceivedGroupCallInvitationViaRtpData
+//! - ReceivedTransferViaRtpData
 //! - ReceivedHangup
 //!
 //! # Asynchronous Outputs:
@@ -55,7 +57,7 @@ use crate::{
     common::{
         actor::{Actor, Stopper},
         units::DataRate,
-        CallDirection, CallId, ConnectionState, DataMode, Result, RingBench,
+        CallDirection, CallId, ConnectionState, DataMode, DeviceId, Result, RingBench,
     },
     core::{
         connection::{Connection, ConnectionObserverEvent, EventStream},
@@ -64,7 +66,10 @@ use crate::{
         util::try_scoped,
     },
     error::RingRtcError,
-    webrtc::{media::MediaStream, peer_connection_observer::NetworkRoute},
+    webrtc::{
+        media::MediaStream,
+        peer_connection_observer::{NetworkAdapterType, NetworkRoute},
+    },
 };
 
 /// The different types of Connection Events.
@@ -97,6 +102,14 @@ pub enum ConnectionEvent {
     /// Source: RTP data
     /// Action: Make adjustments in connection if necessary.
     ReceivedReceiverStatusViaRtpData(CallId, DataRate, u64),
+    /// Receive an invitation to continue the call as a group call.
+    /// Source: RTP data
+    /// Action: Bubble up to the CallManager and then the app, which should join.
+    ReceivedGroupCallInvitationViaRtpData(CallId, signaling::GroupCallInvitation),
+    /// Receive the remote device that is taking over the call.
+    /// Source: RTP data
+    /// Action: Bubble up to the Call, which then accepts an offer from that device.
+    ReceivedTransferViaRtpData(CallId, DeviceId),
     /// Send sender status message via RTP data
     /// Source: app (user action)
     /// Action: Accumulate and send a sender status message via RTP data.
@@ -165,6 +178,18 @@ impl fmt::Display for ConnectionEvent {
                     id, max_bitrate, seqnum
                 )
             }
+            ConnectionEvent::ReceivedGroupCallInvitationViaRtpData(id, invitation) => {
+                format!(
+                    "ReceivedGroupCallInvitationViaRtpData, call_id: {}, invitation: {:?}",
+                    id, invitation
+                )
+            }
+            ConnectionEvent::ReceivedTransferViaRtpData(id, device_id) => {
+                format!(
+                    "ReceivedTransferViaRtpData, call_id: {}, device_id: {}",
+                    id, device_id
+                )
+            }
             ConnectionEvent::ReceivedIce(_) => "RemoteIceCandidates".to_string(),
             ConnectionEvent::SendHangupViaRtpData(hangup) => {
                 format!("SendHangupViaRtpData, hangup: {}", hangup)
@@ -230,6 +255,13 @@ where
     /// We process remote receiver status messages larger than the seqnum
     /// and use the bitrate when it changes.
     last_remote_receiver_status: Option<(u64, DataRate)>,
+    /// The last received group call invitation. The invitation is
+    /// repeated with every RTP data message, so we only pass it on when
+    /// it changes.
+    last_remote_group_call_invitation: Option<signaling::GroupCallInvitation>,
+    /// The last received device taking over the call, which is also
+    /// repeated with every RTP data message.
+    last_remote_transfer_device_id: Option<DeviceId>,
 }
 
 impl<T> fmt::Display for ConnectionStateMachine<T>
@@ -253,6 +285,8 @@ where
             notify_thread: Actor::start("connection-fsm-notify", Stopper::new(), |_| Ok(()))?,
             last_remote_sender_status: None,
             last_remote_receiver_status: None,
+            last_remote_group_call_invitation: None,
+            last_remote_transfer_device_id: None,
         })
     }
 
@@ -277,6 +311,14 @@ where
                 | (
                     ConnectionState::ConnectedAndAccepted,
                     ConnectionEvent::ReceivedAcceptedViaRtpData(_),
+                )
+                | (
+                    ConnectionState::ConnectedAndAccepted,
+                    ConnectionEvent::ReceivedGroupCallInvitationViaRtpData(_, _),
+                )
+                | (
+                    ConnectionState::ConnectedAndAccepted,
+                    ConnectionEvent::ReceivedTransferViaRtpData(_, _),
                 ) => {
                     // Don't log periodic, ignored events at high verbosity
                     debug!("state: {}, event: {}", state, event)
@@ -375,6 +417,13 @@ where
                     max_bitrate,
                     seqnum,
                 ),
+            ConnectionEvent::ReceivedGroupCallInvitationViaRtpData(id, invitation) => self
+                .handle_received_group_call_invitation_via_rtp_data(
+                    connection, state, id, invitation,
+                ),
+            ConnectionEvent::ReceivedTransferViaRtpData(id, device_id) => {
+                self.handle_received_transfer_via_rtp_data(connection, state, id, device_id)
+            }
             ConnectionEvent::ReceivedIce(ice) => self.handle_received_ice(connection, state, ice),
             ConnectionEvent::UpdateSenderStatus(status) => {
                 self.handle_update_sender_status(connection, state, status)
@@ -389,7 +438,7 @@ where
             ConnectionEvent::IceFailed => self.handle_ice_failed(connection, state),
             ConnectionEvent::IceDisconnected => self.handle_ice_disconnected(connection, state),
             ConnectionEvent::IceNetworkRouteChanged(network_route) => {
-                self.handle_ice_network_route_changed(connection, network_route)
+                self.handle_ice_network_route_changed(connection, state, network_route)
             }
             ConnectionEvent::InternalError(error) => self.handle_internal_error(connection, error),
             ConnectionEvent::ReceivedIncomingMedia(stream) => {
@@ -564,6 +613,9 @@ where
             return Ok(());
         }
 
+        let previous_on_hold = self
+            .last_remote_sender_status
+            .and_then(|(_, last_status)| last_status.on_hold);
         let changed = match self.last_remote_sender_status {
             // This is the first sequence number
             None => true,
@@ -588,6 +640,12 @@ where
         match state {
             ConnectionState::ConnectedAndAccepted | ConnectionState::ReconnectingAfterAccepted => {
                 if changed {
+                    let mut status = status;
+                    // Hold is repeated in every status once it's set, so only pass it on
+                    // when it changes, or the app would see RemoteHold for every video toggle.
+                    if status.on_hold == previous_on_hold {
+                        status.on_hold = None;
+                    }
                     Self::handle_remote_sender_status_changed(&connection, status)?;
                 }
             }
@@ -672,6 +730,66 @@ where
         Ok(())
     }
 
+    fn handle_received_group_call_invitation_via_rtp_data(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        call_id: CallId,
+        invitation: signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        if connection.call_id() != call_id {
+            warn!("Remote group call invitation for non-active call");
+            return Ok(());
+        }
+
+        if state != ConnectionState::ConnectedAndAccepted {
+            // Only an accepted call can be escalated; the invitation is
+            // repeated, so it will be seen again if the call is accepted.
+            self.unexpected_state(state, "ReceivedGroupCallInvitationViaRtpData");
+            return Ok(());
+        }
+
+        if self.last_remote_group_call_invitation.as_ref() == Some(&invitation) {
+            return Ok(());
+        }
+        self.last_remote_group_call_invitation = Some(invitation.clone());
+        self.notify_observer(
+            connection,
+            ConnectionObserverEvent::ReceivedGroupCallInvitation(invitation),
+        );
+        Ok(())
+    }
+
+    fn handle_received_transfer_via_rtp_data(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        if connection.call_id() != call_id {
+            warn!("Remote transfer for non-active call");
+            return Ok(());
+        }
+
+        if state != ConnectionState::ConnectedAndAccepted {
+            // Only an accepted call can be transferred; the transfer is
+            // repeated, so it will be seen again if the call is accepted.
+            self.unexpected_state(state, "ReceivedTransferViaRtpData");
+            return Ok(());
+        }
+
+        if self.last_remote_transfer_device_id == Some(device_id) {
+            return Ok(());
+        }
+        self.last_remote_transfer_device_id = Some(device_id);
+        self.notify_observer(
+            connection,
+            ConnectionObserverEvent::ReceivedTransfer(device_id),
+        );
+        Ok(())
+    }
+
     fn handle_received_ice(
         &mut self,
         connection: Connection<T>,
@@ -824,6 +942,7 @@ where
                 // ICE has reconnected after the call was
                 // previously accepted (and connected).  Return to that state
                 // now.
+                connection.ice_restart_completed()?;
                 connection.set_state(ConnectionState::ConnectedAndAccepted)?;
             }
             ConnectionState::IceFailed
@@ -841,7 +960,16 @@ where
         connection: Connection<T>,
         state: ConnectionState,
     ) -> Result<()> {
-        if state.connecting_or_connected() {
+        if matches!(
+            state,
+            ConnectionState::ConnectedAndAccepted | ConnectionState::ReconnectingAfterAccepted
+        ) && connection.can_restart_ice(false)?
+        {
+            // The call was up, so try to get it back with new ICE
+            // credentials before giving up on it.
+            connection.set_state(ConnectionState::ReconnectingAfterAccepted)?;
+            self.restart_ice(connection, false);
+        } else if state.connecting_or_connected() {
             // For callee -- the call was disconnected while answering/local_ringing
             // For caller -- the recipient was unreachable
             connection.set_state(ConnectionState::IceFailed)?;
@@ -851,6 +979,20 @@ where
         Ok(())
     }
 
+    fn restart_ice(&mut self, mut connection: Connection<T>, new_network: bool) {
+        self.worker_spawn(move || {
+            let result = try_scoped(|| {
+                if connection.terminating()? {
+                    return Ok(());
+                }
+                connection.restart_ice(new_network)
+            });
+            if let Err(err) = result {
+                connection.inject_internal_error(err, "ICE restart failed");
+            }
+        });
+    }
+
     fn handle_ice_disconnected(
         &mut self,
         connection: Connection<T>,
@@ -885,11 +1027,11 @@ where
     fn handle_ice_network_route_changed(
         &mut self,
         connection: Connection<T>,
+        state: ConnectionState,
         network_route: NetworkRoute,
     ) -> Result<()> {
-        if network_route.local_adapter_type
-            == crate::webrtc::peer_connection_observer::NetworkAdapterType::Vpn
-        {
+        let previous_adapter_type = connection.network_route()?.local_adapter_type;
+        if network_route.local_adapter_type == NetworkAdapterType::Vpn {
             info!(
                 "Local ICE network adapter type changed to {:?} going through a VPN",
                 network_route.local_adapter_type_under_vpn
@@ -901,6 +1043,21 @@ where
             );
         }
         connection.set_network_route(network_route)?;
+
+        // When the local network changes (such as from Wi-Fi to cellular),
+        // restart ICE so both sides gather candidates on the new network
+        // instead of waiting for the old ones to fail.
+        if previous_adapter_type != NetworkAdapterType::Unknown
+            && previous_adapter_type != network_route.local_adapter_type
+            && matches!(
+                state,
+                ConnectionState::ConnectedAndAccepted | ConnectionState::ReconnectingAfterAccepted
+            )
+            && connection.can_restart_ice(true)?
+        {
+            self.restart_ice(connection.clone(), true);
+        }
+
         self.notify_observer(
             connection,
             ConnectionObserverEvent::IceNetworkRouteChanged(network_route),
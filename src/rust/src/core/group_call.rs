@@ -4,7 +4,7 @@
 //
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     convert::TryInto,
     hash::{Hash, Hasher},
     iter::FromIterator,
@@ -20,7 +20,7 @@ use hkdf::Hkdf;
 use mrp::{MrpReceiveError, MrpSendError, MrpStream};
 use num_enum::TryFromPrimitive;
 use prost::Message;
-use rand::{rngs::OsRng, Rng};
+use rand::rngs::OsRng;
 use sha2::{Digest, Sha256};
 use x25519_dalek::{EphemeralSecret, PublicKey};
 
@@ -30,13 +30,17 @@ use crate::{
         units::DataRate,
         CallId, DataMode, Result,
     },
-    core::{call_mutex::CallMutex, crypto as frame_crypto, signaling, util::uuid_to_string},
+    core::{
+        call_mutex::CallMutex,
+        crypto as frame_crypto, signaling,
+        util::{random_alphanumeric, uuid_to_string},
+    },
     error::RingRtcError,
     lite::{
         http, sfu,
         sfu::{
             ClientStatus, DemuxId, GroupMember, MemberMap, MembershipProof, ObfuscatedResolver,
-            PeekInfo, PeekResult, PeekResultCallback, UserId,
+            PeekInfo, PeekResult, PeekResultCallback, SfuError, UserId,
         },
     },
     protobuf,
@@ -55,7 +59,7 @@ use crate::{
         sdp_observer::{
             create_csd_observer, create_ssd_observer, SessionDescription, SrtpCryptoSuite, SrtpKey,
         },
-        stats_observer::{create_stats_observer, StatsObserver},
+        stats_observer::{create_stats_observer, MediaSummary, ReceiverSample, StatsObserver},
     },
 };
 
@@ -127,6 +131,8 @@ pub enum RingUpdate {
     BusyOnAnotherDevice,
     /// The sender cancelled the ring request.
     CancelledByRinger,
+    /// The incoming call policy declined the ring without ringing.
+    DeclinedByPolicy,
 }
 
 /// Describes why a ring was cancelled.
@@ -214,6 +220,81 @@ impl SpeechEvent {
     }
 }
 
+/// How well media from a remote device is reaching us, so that the UI can answer
+/// "is it me or them?". Ordered from best to worst.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ConnectionQuality {
+    Good,
+    Degraded,
+    Poor,
+}
+
+impl ConnectionQuality {
+    pub fn ordinal(&self) -> i32 {
+        // Must be kept in sync with the TypeScript enum.
+        match self {
+            ConnectionQuality::Good => 0,
+            ConnectionQuality::Degraded => 1,
+            ConnectionQuality::Poor => 2,
+        }
+    }
+
+    fn from_receiver_sample(sample: &ReceiverSample) -> Self {
+        // Too few frames to go by means the sender's frame rate is low anyway.
+        let frames_decoded_ratio = if sample.frames_expected >= MIN_FRAMES_EXPECTED {
+            sample.frames_decoded as f32 / sample.frames_expected as f32
+        } else {
+            1.0
+        };
+        if sample.packets_lost_pct >= POOR_PACKET_LOSS_PCT
+            || sample.jitter >= POOR_JITTER.as_secs_f64()
+            || sample.freeze_duration >= POOR_FREEZE_DURATION
+            || frames_decoded_ratio < POOR_FRAMES_DECODED_RATIO
+        {
+            ConnectionQuality::Poor
+        } else if sample.packets_lost_pct >= DEGRADED_PACKET_LOSS_PCT
+            || sample.jitter >= DEGRADED_JITTER.as_secs_f64()
+            || sample.freeze_duration >= DEGRADED_FREEZE_DURATION
+            || frames_decoded_ratio < DEGRADED_FRAMES_DECODED_RATIO
+        {
+            ConnectionQuality::Degraded
+        } else {
+            ConnectionQuality::Good
+        }
+    }
+
+    fn from_time_since_heartbeat(
+        time_since_heartbeat: Duration,
+        heartbeat_interval: Duration,
+    ) -> Self {
+        if time_since_heartbeat >= heartbeat_interval.mul_f64(POOR_HEARTBEAT_GAP_INTERVALS) {
+            ConnectionQuality::Poor
+        } else if time_since_heartbeat
+            >= heartbeat_interval.mul_f64(DEGRADED_HEARTBEAT_GAP_INTERVALS)
+        {
+            ConnectionQuality::Degraded
+        } else {
+            ConnectionQuality::Good
+        }
+    }
+}
+
+// Thresholds used to compute the ConnectionQuality of remote devices from receiver stats.
+const DEGRADED_PACKET_LOSS_PCT: f32 = 3.0;
+const POOR_PACKET_LOSS_PCT: f32 = 10.0;
+const DEGRADED_JITTER: Duration = Duration::from_millis(50);
+const POOR_JITTER: Duration = Duration::from_millis(100);
+const DEGRADED_FREEZE_DURATION: Duration = Duration::from_millis(500);
+const POOR_FREEZE_DURATION: Duration = Duration::from_secs(2);
+// Compared to the frames expected from the recent frame rate of a video stream.
+const MIN_FRAMES_EXPECTED: u32 = 10;
+const DEGRADED_FRAMES_DECODED_RATIO: f32 = 0.8;
+const POOR_FRAMES_DECODED_RATIO: f32 = 0.5;
+// Heartbeats are sent every heartbeat interval, so a gap of a few intervals means
+// data from that device isn't getting through.
+const DEGRADED_HEARTBEAT_GAP_INTERVALS: f64 = 2.5;
+const POOR_HEARTBEAT_GAP_INTERVALS: f64 = 5.0;
+
 #[derive(Debug)]
 pub enum RemoteDevicesChangedReason {
     DemuxIdsChanged,
@@ -222,6 +303,8 @@ pub enum RemoteDevicesChangedReason {
     HeartbeatStateChanged(DemuxId),
     ForwardedVideosChanged,
     HigherResolutionPendingChanged,
+    ConnectionQualityChanged,
+    UnresponsiveChanged(DemuxId),
 }
 
 // The callbacks from the Call to the Observer of the call.
@@ -306,11 +389,19 @@ pub trait Observer {
 
     fn handle_raised_hands(&self, client_id: ClientId, raised_hands: Vec<DemuxId>);
 
+    // Notifies the observer that a different device (possibly the local one) is now the
+    // presenter, or that nobody is presenting anymore.
+    fn handle_presenter_changed(&self, client_id: ClientId, current_presenter: Option<DemuxId>);
+
     fn handle_rtc_stats_report(&self, report_json: String);
 
+    // Reports what happened to each user in a batch from approve_users, deny_users,
+    // approve_all_pending_users, or deny_all_pending_users, once the whole batch has been handled.
+    fn handle_admin_action_results(&self, _client_id: ClientId, _results: Vec<AdminActionResult>) {}
+
     // This will be the last callback.
     // The observer can assume the Call is completely shut down and can be deleted.
-    fn handle_ended(&self, client_id: ClientId, reason: EndReason);
+    fn handle_ended(&self, client_id: ClientId, reason: EndReason, summary: CallSummary);
 }
 
 // The connection states of a device connecting to a group call.
@@ -509,10 +600,200 @@ pub enum EndReason {
     IceFailedAfterConnected,
     ServerChangedDemuxId,
     HasMaxDevices,
+    CallLinkExpired,
+    CallLinkInvalid,
+    CallLinkRevoked,
+    AuthExpired,
+    RateLimited,
+    SfuUnavailable,
+    SfuMalformedResponse,
+}
+
+impl From<&SfuError> for EndReason {
+    fn from(err: &SfuError) -> Self {
+        match err {
+            SfuError::MaxDevicesReached => Self::HasMaxDevices,
+            SfuError::CallLinkExpired => Self::CallLinkExpired,
+            SfuError::CallLinkInvalid => Self::CallLinkInvalid,
+            SfuError::CallLinkRevoked => Self::CallLinkRevoked,
+            SfuError::ApprovalDenied => Self::DeniedRequestToJoinCall,
+            SfuError::AuthExpired => Self::AuthExpired,
+            SfuError::RateLimited { .. } => Self::RateLimited,
+            SfuError::ServerUnavailable => Self::SfuUnavailable,
+            SfuError::MalformedResponse => Self::SfuMalformedResponse,
+            SfuError::RequestFailed | SfuError::UnexpectedStatus(_) => Self::SfuClientFailedToJoin,
+        }
+    }
 }
 
 const ADMIN_LOG_TAG: &str = "AdminAction";
 
+// Bulk approvals and denials are spread out over ticks so that hundreds of pending users don't
+// overflow the reliable stream to the SFU (see RELIABLE_RTP_BUFFER_SIZE).
+const MAX_ADMIN_ACTIONS_PER_TICK: usize = 8;
+// If the reliable stream stays full for this many ticks in a row (10 seconds), the SFU isn't
+// acking, so the rest of the batch is reported as failed rather than waiting forever.
+const MAX_ADMIN_ACTION_WINDOW_FULL_TICKS: u32 = 50;
+
+/// What happened to a single user's approval or denial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AdminActionOutcome {
+    /// The action was sent to the SFU (reliably, so it will be retried if lost).
+    Sent,
+    /// The user was already in the call, so there was nothing to approve or deny.
+    AlreadyJoined,
+    /// The user wasn't pending. They may have left or been handled by another admin.
+    NotPending,
+    /// The action couldn't be sent, such as because the call has been left.
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AdminActionResult {
+    pub user_id: UserId,
+    pub approved: bool,
+    pub outcome: AdminActionOutcome,
+}
+
+/// A batch of approvals or denials that hasn't been completely sent yet.
+#[derive(Debug)]
+struct PendingAdminActions {
+    approved: bool,
+    user_ids: VecDeque<UserId>,
+    results: Vec<AdminActionResult>,
+    // Consecutive ticks on which nothing could be sent because the send window was full.
+    window_full_ticks: u32,
+}
+
+/// Statistics about a whole group call, delivered along with [`Observer::handle_ended`].
+#[derive(Clone, Debug, Default)]
+pub struct CallSummary {
+    /// From connect() until the call ended.
+    pub call_duration: Duration,
+    /// The total time spent joined, across any rejoins.
+    pub join_duration: Duration,
+    /// The most devices (including pending devices) in the call at once, according to the SFU.
+    pub peak_device_count: usize,
+    /// The number of distinct users that were in the call at any point.
+    pub unique_user_count: usize,
+    /// How long each remote user was the primary speaker.
+    pub speaking_time: HashMap<UserId, Duration>,
+    pub reactions_sent: u32,
+    pub reactions_received: u32,
+    /// The number of times a hand (including the local one) was raised.
+    pub raised_hand_count: u32,
+    pub media: MediaSummary,
+    /// The number of times the connection to the SFU had to be reestablished.
+    pub reconnect_count: u32,
+}
+
+// What's needed to build a CallSummary when the call ends.
+#[derive(Default)]
+struct CallSummaryState {
+    connect_time: Option<Instant>,
+    // Set while joined.
+    join_time: Option<Instant>,
+    join_duration: Duration,
+    peak_device_count: usize,
+    unique_users: HashSet<UserId>,
+    current_speaker: Option<CurrentSpeaker>,
+    speaking_time: HashMap<UserId, Duration>,
+    reactions_sent: u32,
+    reactions_received: u32,
+    raised_hand_count: u32,
+    reconnect_count: u32,
+}
+
+// The remote device the SFU last reported as the primary speaker.
+struct CurrentSpeaker {
+    demux_id: DemuxId,
+    user_id: UserId,
+    // Unset once they've been silent for STOPPED_SPEAKING_DURATION, until they speak again.
+    speaking_since: Option<Instant>,
+    silence_started: Option<Instant>,
+}
+
+impl CallSummaryState {
+    fn set_joined(&mut self, joined: bool, now: Instant) {
+        match (joined, self.join_time) {
+            (true, None) => self.join_time = Some(now),
+            (false, Some(join_time)) => {
+                self.join_duration += now.saturating_duration_since(join_time);
+                self.join_time = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn set_current_speaker(&mut self, speaker: Option<(DemuxId, UserId)>, now: Instant) {
+        self.stop_current_speaker(now);
+        self.current_speaker = speaker.map(|(demux_id, user_id)| CurrentSpeaker {
+            demux_id,
+            user_id,
+            speaking_since: Some(now),
+            silence_started: None,
+        });
+    }
+
+    // Adds the current speaker's time up to when they went silent (or now, if they haven't).
+    fn stop_current_speaker(&mut self, now: Instant) {
+        if let Some(speaker) = &mut self.current_speaker {
+            if let Some(speaking_since) = speaker.speaking_since.take() {
+                let speaking_until = speaker.silence_started.unwrap_or(now);
+                *self
+                    .speaking_time
+                    .entry(speaker.user_id.clone())
+                    .or_default() += speaking_until.saturating_duration_since(speaking_since);
+            }
+        }
+    }
+
+    // The SFU only reports when the primary speaker changes, so use audio levels to tell
+    // when the current one has stopped (or started again).
+    fn update_current_speaker_level(
+        &mut self,
+        received_levels: &[ReceivedAudioLevel],
+        now: Instant,
+    ) {
+        let Some(speaker) = &mut self.current_speaker else {
+            return;
+        };
+        let level = received_levels
+            .iter()
+            .find(|received| received.demux_id == speaker.demux_id)
+            .map_or(0, |received| received.level);
+        if level > MIN_NON_SILENT_LEVEL {
+            speaker.silence_started = None;
+            speaker.speaking_since.get_or_insert(now);
+        } else {
+            let silence_started = *speaker.silence_started.get_or_insert(now);
+            if now.saturating_duration_since(silence_started) >= STOPPED_SPEAKING_DURATION {
+                self.stop_current_speaker(now);
+            }
+        }
+    }
+
+    fn finish(mut self, media: MediaSummary, now: Instant) -> CallSummary {
+        self.set_joined(false, now);
+        self.set_current_speaker(None, now);
+        CallSummary {
+            call_duration: self
+                .connect_time
+                .map(|connect_time| now.saturating_duration_since(connect_time))
+                .unwrap_or_default(),
+            join_duration: self.join_duration,
+            peak_device_count: self.peak_device_count,
+            unique_user_count: self.unique_users.len(),
+            speaking_time: self.speaking_time,
+            reactions_sent: self.reactions_sent,
+            reactions_received: self.reactions_received,
+            raised_hand_count: self.raised_hand_count,
+            media,
+            reconnect_count: self.reconnect_count,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Debug)]
 pub struct Reaction {
@@ -529,6 +810,9 @@ pub trait SfuClient {
     // Notifies the client of the new membership proof.
     fn set_membership_proof(&mut self, proof: MembershipProof);
     fn set_group_members(&mut self, members: Vec<GroupMember>);
+
+    // Drops any outstanding peeks or joins, because the call has ended.
+    fn cancel_requests(&mut self) {}
 }
 
 pub struct Joined {
@@ -631,15 +915,7 @@ impl HttpSfuClient {
                             }
                         },
                     }),
-                    Err(http_status) if http_status == http::ResponseStatus::REQUEST_FAILED => {
-                        Err(RingRtcError::SfuClientRequestFailed.into())
-                    }
-                    Err(http_status) if http_status == http::ResponseStatus::GROUP_CALL_FULL => {
-                        Err(RingRtcError::GroupCallFull.into())
-                    }
-                    Err(http_status) => {
-                        Err(RingRtcError::UnexpectedResponseCodeFromSFu(http_status.code).into())
-                    }
+                    Err(sfu_error) => Err(sfu_error.into()),
                 };
                 client.on_sfu_client_join_attempt_completed(join_result);
             }),
@@ -694,6 +970,11 @@ impl SfuClient for HttpSfuClient {
         info!("SfuClient set_group_members: {} members", members.len());
         self.set_member_resolver(Arc::new(sfu::MemberMap::new(&members)));
     }
+
+    fn cancel_requests(&mut self) {
+        self.deferred_join = None;
+        self.http_client.cancel_pending_requests();
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -702,6 +983,11 @@ pub struct HeartbeatState {
     pub video_muted: Option<bool>,
     pub presenting: Option<bool>,
     pub sharing_screen: Option<bool>,
+    // Set when presenting because of Client::request_to_present.
+    // Presenters without a claim are ordered as if their claim were 0.
+    pub presentation_claim: Option<u64>,
+    // Whether the claim was made with take over.
+    pub presentation_take_over: Option<bool>,
 }
 
 impl From<protobuf::group_call::device_to_device::Heartbeat> for HeartbeatState {
@@ -711,6 +997,8 @@ impl From<protobuf::group_call::device_to_device::Heartbeat> for HeartbeatState
             video_muted: proto.video_muted,
             presenting: proto.presenting,
             sharing_screen: proto.sharing_screen,
+            presentation_claim: proto.presentation_claim,
+            presentation_take_over: proto.presentation_take_over,
         }
     }
 }
@@ -737,6 +1025,21 @@ pub struct RemoteDeviceState {
     pub server_allocated_height: u16,
     pub client_decoded_height: Option<u32>,
     pub is_higher_resolution_pending: bool,
+    // None until we have received enough stats or heartbeats to tell.
+    pub connection_quality: Option<ConnectionQuality>,
+    // True if we've received heartbeats from this device before but none within the
+    // unresponsive grace period. Cleared as soon as another heartbeat arrives.
+    pub is_unresponsive: bool,
+    // The worst quality computed from the most recent receiver stats.
+    stats_connection_quality: Option<ConnectionQuality>,
+    // The time at which we last received a new heartbeat.
+    heartbeat_received_time: Option<Instant>,
+    // How often this device says it sends heartbeats, if it has said.
+    heartbeat_interval: Option<Duration>,
+}
+
+fn demux_id_from_ssrc(ssrc: rtp::Ssrc) -> DemuxId {
+    ssrc & DEMUX_ID_SSRC_MASK
 }
 
 fn as_unix_millis(t: Option<SystemTime>) -> u64 {
@@ -767,9 +1070,21 @@ impl RemoteDeviceState {
             server_allocated_height: 0,
             client_decoded_height: None,
             is_higher_resolution_pending: false,
+            connection_quality: None,
+            is_unresponsive: false,
+            stats_connection_quality: None,
+            heartbeat_received_time: None,
+            heartbeat_interval: None,
         }
     }
 
+    // Gaps between heartbeats are judged against the interval the device advertises, since
+    // each device can change its own. Devices that don't advertise one use the default.
+    fn expected_heartbeat_interval(&self) -> Duration {
+        self.heartbeat_interval
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL)
+    }
+
     pub fn speaker_time_as_unix_millis(&self) -> u64 {
         as_unix_millis(self.speaker_time)
     }
@@ -803,10 +1118,34 @@ pub struct VideoRequest {
     pub framerate: Option<u16>,
 }
 
+/// A consistent view of the state of a [`Client`] at a single point in time.
+///
+/// Everything in here is also delivered incrementally through the [`Observer`], but a snapshot
+/// lets an app (re)build its view of the call without mirroring every callback.
+#[derive(Clone, Debug)]
+pub struct ClientSnapshot {
+    pub client_id: ClientId,
+    pub connection_state: ConnectionState,
+    pub join_state: JoinState,
+    pub remote_devices: Vec<RemoteDeviceState>,
+    pub peek_info: Option<PeekInfo>,
+    pub joined_members: HashSet<UserId>,
+    pub raised_hands: Vec<DemuxId>,
+    // Demux IDs where video is being forwarded from, mapped to the server allocated height.
+    pub forwarding_videos: HashMap<DemuxId, u16>,
+    pub send_rates: SendRates,
+    pub data_mode: DataMode,
+    // The local mute/presenting/sharing flags, as last set by the app and sent in heartbeats.
+    pub outgoing_heartbeat_state: HeartbeatState,
+    pub current_presenter: Option<DemuxId>,
+}
+
 // This must stay in sync with the data PT in SfuClient.
 const RTP_DATA_PAYLOAD_TYPE: rtp::PayloadType = 101;
 // This must stay in sync with the data SSRC offset in SfuClient.
 const RTP_DATA_THROUGH_SFU_SSRC_OFFSET: rtp::Ssrc = 0xD;
+// SSRCs are derived from demux IDs, which leave a gap of 16 between them.
+const DEMUX_ID_SSRC_MASK: rtp::Ssrc = !0xF;
 const RTP_DATA_TO_SFU_SSRC: rtp::Ssrc = 1;
 
 // If the local device is the only device, tell WebRTC to send as little
@@ -1023,6 +1362,10 @@ struct State {
     rtp_data_to_sfu_next_seqnum: u32,
     rtp_data_through_sfu_next_seqnum: u32,
     next_heartbeat_time: Option<Instant>,
+    heartbeat_interval: Duration,
+    // How long a remote device can go without sending a heartbeat before it's
+    // considered unresponsive.
+    unresponsive_grace_period: Duration,
     /// The remote demux IDs are in the order of the corresponding transceivers
     /// in peer_connection. Each demux ID is associated with two transceivers
     /// (audio and video). None represents an unused transceiver pair.
@@ -1100,6 +1443,13 @@ struct State {
     raised_hands: Vec<DemuxId>,
     raise_hand_state: RaiseHandState,
 
+    pending_admin_actions: VecDeque<PendingAdminActions>,
+
+    // The single device (possibly the local one) that wins among all devices presenting.
+    current_presenter: Option<DemuxId>,
+
+    call_summary_state: CallSummaryState,
+
     sfu_reliable_stream: MrpStream<Vec<u8>, (rtp::Header, SfuToDevice)>,
     actor: Actor<State>,
 }
@@ -1158,8 +1508,17 @@ impl RemoteDevices {
 // membership list from the SfuClient
 const TICK_INTERVAL: Duration = Duration::from_millis(200);
 
-// How often to send RTP data messages and video requests.
-const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+// How often to send RTP data messages and video requests, unless overridden
+// with set_heartbeat_interval.
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+// How long to wait for a heartbeat from a remote device before marking it as
+// unresponsive, unless overridden with set_unresponsive_grace_period.
+const DEFAULT_UNRESPONSIVE_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+// However short the grace period, a remote device isn't marked as unresponsive
+// until it has missed this many of its own heartbeat intervals.
+const MIN_UNRESPONSIVE_HEARTBEAT_GAP_INTERVALS: u32 = 3;
 
 // How often to get and log stats.
 const DEFAULT_STATS_INTERVAL: Duration = Duration::from_secs(10);
@@ -1178,6 +1537,9 @@ const DELAYED_BWE_CHECK: Duration = Duration::from_secs(10);
 
 const REACTION_STRING_MAX_SIZE: usize = 256;
 
+/// How long Client::snapshot will wait for the actor before giving up.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct ClientStartParams {
     pub group_id: GroupId,
     pub client_id: ClientId,
@@ -1238,6 +1600,7 @@ impl Client {
                             observer.handle_ended(
                                 client_id,
                                 EndReason::FailedToCreatePeerConnectionFactory,
+                                CallSummary::default(),
                             );
                             return Err(err);
                         }
@@ -1264,7 +1627,11 @@ impl Client {
                         outgoing_video_track,
                     )
                     .inspect_err(|_| {
-                        observer.handle_ended(client_id, EndReason::FailedToCreatePeerConnection);
+                        observer.handle_ended(
+                            client_id,
+                            EndReason::FailedToCreatePeerConnection,
+                            CallSummary::default(),
+                        );
                     })?;
                 let call_id_for_stats = CallId::from(client_id as u64);
                 info!(
@@ -1315,6 +1682,8 @@ impl Client {
                     rtp_data_through_sfu_next_seqnum: 1,
 
                     next_heartbeat_time: None,
+                    heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+                    unresponsive_grace_period: DEFAULT_UNRESPONSIVE_GRACE_PERIOD,
 
                     next_stats_time: None,
                     get_stats_interval: DEFAULT_STATS_INTERVAL,
@@ -1358,7 +1727,10 @@ impl Client {
 
                     reactions: Vec::new(),
                     raised_hands: Vec::new(),
+                    current_presenter: None,
+                    call_summary_state: CallSummaryState::default(),
                     raise_hand_state: RaiseHandState::default(),
+                    pending_admin_actions: VecDeque::new(),
 
                     sfu_reliable_stream: MrpStream::with_capacity_limit(RELIABLE_RTP_BUFFER_SIZE),
 
@@ -1430,7 +1802,10 @@ impl Client {
                 // Also send video requests at the same rate as the heartbeat.
                 Self::send_video_requests_to_sfu(state);
                 state.on_demand_video_request_sent_since_last_heartbeat = false;
-                state.next_heartbeat_time = Some(now + HEARTBEAT_INTERVAL)
+                // And check whether remote heartbeats are still arriving.
+                Self::update_connection_quality(state, now);
+                Self::check_for_unresponsive_devices(state, now);
+                state.next_heartbeat_time = Some(now + state.heartbeat_interval)
             }
         }
 
@@ -1444,11 +1819,18 @@ impl Client {
             if let Some(report_json) = state.stats_observer.take_stats_report() {
                 state.observer.handle_rtc_stats_report(report_json)
             }
+            let receiver_samples = state.stats_observer.take_receiver_samples();
+            if !receiver_samples.is_empty() {
+                Self::handle_receiver_samples(state, &receiver_samples, now);
+            }
         }
 
         if let Some(next_speaking_audio_levels_time) = state.next_speaking_audio_levels_time {
             if now >= next_speaking_audio_levels_time {
-                let (captured_level, _) = state.peer_connection.get_audio_levels();
+                let (captured_level, received_levels) = state.peer_connection.get_audio_levels();
+                state
+                    .call_summary_state
+                    .update_current_speaker_level(&received_levels, now);
                 let mut time_silent = Duration::from_secs(0);
                 state.started_speaking = if captured_level > MIN_NON_SILENT_LEVEL
                     && !state.outgoing_heartbeat_state.audio_muted.unwrap_or(true)
@@ -1567,6 +1949,10 @@ impl Client {
                 .handle_reactions(state.client_id, std::mem::take(&mut state.reactions));
         }
 
+        if !state.pending_admin_actions.is_empty() {
+            Self::send_pending_admin_actions(state);
+        }
+
         if let Some(next_raise_hand_time) = state.next_raise_hand_time {
             if now >= next_raise_hand_time && state.raise_hand_state.outstanding {
                 state.next_raise_hand_time = Some(now + RAISE_HAND_INTERVAL);
@@ -1710,6 +2096,7 @@ impl Client {
                     );
 
                     let now = Instant::now();
+                    state.call_summary_state.connect_time = Some(now);
 
                     // Start heartbeats, audio levels, and raise hand right away.
                     state.next_heartbeat_time = Some(now);
@@ -1743,6 +2130,11 @@ impl Client {
             state.client_id
         );
 
+        if connection_state == ConnectionState::Reconnecting
+            && state.connection_state != ConnectionState::Reconnecting
+        {
+            state.call_summary_state.reconnect_count += 1;
+        }
         state.connection_state = connection_state;
         state
             .observer
@@ -1859,6 +2251,9 @@ impl Client {
             join_state
         );
         state.join_state = join_state;
+        state
+            .call_summary_state
+            .set_joined(matches!(join_state, JoinState::Joined(_)), Instant::now());
         state
             .observer
             .handle_join_state_changed(state.client_id, join_state);
@@ -1880,6 +2275,7 @@ impl Client {
         );
 
         Self::cancel_full_group_ring_if_needed(state);
+        Self::fail_pending_admin_actions(state);
 
         match state.join_state {
             JoinState::NotJoined(_) => {
@@ -2047,15 +2443,157 @@ impl Client {
                 state.client_id, presenting
             );
             state.outgoing_heartbeat_state.presenting = Some(presenting);
+            if !presenting {
+                state.outgoing_heartbeat_state.presentation_claim = None;
+                state.outgoing_heartbeat_state.presentation_take_over = None;
+            }
             if let Err(err) = Self::send_heartbeat(state) {
                 warn!(
                     "Failed to send heartbeat after updating presenting state: {:?}",
                     err
                 );
             }
+            Self::update_current_presenter(state);
+        });
+    }
+
+    /// Asks to become the one presenter in the call.
+    ///
+    /// Without `take_over`, the request is dropped if another device is already the current
+    /// presenter. Otherwise, the local device becomes the presenter everywhere (unless someone
+    /// else asks at the same time and wins the tie) and the previous presenter stops presenting.
+    ///
+    /// A resulting change of presenter is reported through
+    /// [`Observer::handle_presenter_changed`]. A dropped request, or one made before joining, is
+    /// only logged, so callers shouldn't wait for a report to find out whether it was denied.
+    pub fn request_to_present(&self, take_over: bool) {
+        debug!(
+            "group_call::Client(outer)::request_to_present(client_id: {}, take_over: {})",
+            self.client_id, take_over
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::request_to_present(client_id: {}, take_over: {})",
+                state.client_id, take_over
+            );
+            let JoinState::Joined(local_demux_id) = state.join_state else {
+                warn!("request_to_present(): not joined");
+                return;
+            };
+            if let Some(current_presenter) = state.current_presenter {
+                if current_presenter == local_demux_id {
+                    info!("request_to_present(): already the presenter");
+                    return;
+                }
+                if !take_over {
+                    info!(
+                        "request_to_present(): denied because {} is presenting",
+                        current_presenter
+                    );
+                    return;
+                }
+            }
+
+            let claim = state
+                .remote_devices
+                .iter()
+                .filter_map(|device| device.heartbeat_state.presentation_claim)
+                .chain(state.outgoing_heartbeat_state.presentation_claim)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            state.outgoing_heartbeat_state.presenting = Some(true);
+            state.outgoing_heartbeat_state.presentation_claim = Some(claim);
+            state.outgoing_heartbeat_state.presentation_take_over = Some(take_over);
+            if let Err(err) = Self::send_present_request(state, claim, take_over) {
+                warn!("Failed to send present request: {:?}", err);
+            }
+            if let Err(err) = Self::send_heartbeat(state) {
+                warn!(
+                    "Failed to send heartbeat after requesting to present: {:?}",
+                    err
+                );
+            }
+            Self::update_current_presenter(state);
         });
     }
 
+    // Picks the presenter with the highest claim, breaking ties with the lowest demux ID, so
+    // that every device in the call agrees once it has seen the same heartbeats.
+    //
+    // A claim made without take over doesn't count if someone else has a lower claim, since
+    // they were already presenting when it was made. This is enforced here, on every device,
+    // rather than only by the requester, and depends only on the claims and not on the order
+    // they arrived in.
+    fn compute_current_presenter(state: &State) -> Option<DemuxId> {
+        let local_presenter = match state.join_state {
+            JoinState::Joined(local_demux_id)
+                if state.outgoing_heartbeat_state.presenting == Some(true) =>
+            {
+                Some((&state.outgoing_heartbeat_state, local_demux_id))
+            }
+            _ => None,
+        };
+        let presenters: Vec<(u64, bool, DemuxId)> = state
+            .remote_devices
+            .iter()
+            .filter(|device| device.heartbeat_state.presenting == Some(true))
+            .map(|device| (&device.heartbeat_state, device.demux_id))
+            .chain(local_presenter)
+            .map(|(heartbeat_state, demux_id)| {
+                (
+                    heartbeat_state.presentation_claim.unwrap_or(0),
+                    heartbeat_state.presentation_take_over.unwrap_or(false),
+                    demux_id,
+                )
+            })
+            .collect();
+        presenters
+            .iter()
+            .filter(|(claim, take_over, demux_id)| {
+                *take_over
+                    || !presenters.iter().any(|(other_claim, _, other_demux_id)| {
+                        other_demux_id != demux_id && other_claim < claim
+                    })
+            })
+            .max_by(|(claim1, _, demux_id1), (claim2, _, demux_id2)| {
+                claim1.cmp(claim2).then(demux_id2.cmp(demux_id1))
+            })
+            .map(|(_, _, demux_id)| *demux_id)
+    }
+
+    fn update_current_presenter(state: &mut State) {
+        let current_presenter = Self::compute_current_presenter(state);
+        if current_presenter == state.current_presenter {
+            return;
+        }
+
+        info!(
+            "group_call::Client(inner)::update_current_presenter(client_id: {}): {:?} => {:?}",
+            state.client_id, state.current_presenter, current_presenter
+        );
+        let previous_presenter = std::mem::replace(&mut state.current_presenter, current_presenter);
+
+        // If someone took over from the local device, stop presenting so that other devices
+        // don't have to keep arbitrating against us.
+        if let JoinState::Joined(local_demux_id) = state.join_state {
+            if previous_presenter == Some(local_demux_id)
+                && state.outgoing_heartbeat_state.presentation_claim.is_some()
+            {
+                state.outgoing_heartbeat_state.presenting = Some(false);
+                state.outgoing_heartbeat_state.presentation_claim = None;
+                state.outgoing_heartbeat_state.presentation_take_over = None;
+                if let Err(err) = Self::send_heartbeat(state) {
+                    warn!("Failed to send heartbeat after losing presenter: {:?}", err);
+                }
+            }
+        }
+
+        state
+            .observer
+            .handle_presenter_changed(state.client_id, current_presenter);
+    }
+
     pub fn set_sharing_screen(&self, sharing_screen: bool) {
         debug!(
             "group_call::Client(outer)::set_sharing_screen(client_id: {}, sharing_screen: {})",
@@ -2265,14 +2803,18 @@ impl Client {
         }
     }
 
-    fn approve_or_deny_user(state: &mut State, user_id: UserId, approved: bool) {
+    fn approve_or_deny_user(
+        state: &mut State,
+        user_id: &UserId,
+        approved: bool,
+    ) -> std::result::Result<AdminActionOutcome, MrpSendError> {
         use protobuf::group_call::device_to_sfu::{AdminAction, GenericAdminAction};
 
         // Approval is implemented by demux ID (because we don't put user IDs in RTP messages).
         // So we have to find a corresponding demux ID in the pending users list.
         let Some(peek_info) = state.last_peek_info.as_ref() else {
             error!("{ADMIN_LOG_TAG}: Cannot approve users without peek info");
-            return;
+            return Ok(AdminActionOutcome::Failed);
         };
 
         let action_to_log = if approved { "approval" } else { "denial" };
@@ -2280,7 +2822,7 @@ impl Client {
         if let Some(demux_id) = peek_info
             .pending_devices
             .iter()
-            .find(|device| device.user_id.as_ref() == Some(&user_id))
+            .find(|device| device.user_id.as_ref() == Some(user_id))
             .map(|device| device.demux_id)
         {
             let action = if approved {
@@ -2299,18 +2841,22 @@ impl Client {
                 warn!(
                     "{ADMIN_LOG_TAG}: Failed to send {action_to_log} for demux {demux_id}: {e:?}"
                 );
+                Err(e)
             } else {
                 info!("{ADMIN_LOG_TAG}: Sent {action_to_log} for {demux_id}");
+                Ok(AdminActionOutcome::Sent)
             }
         } else if let Some(demux_id) = peek_info
             .devices
             .iter()
-            .find(|device| device.user_id.as_ref() == Some(&user_id))
+            .find(|device| device.user_id.as_ref() == Some(user_id))
             .map(|device| device.demux_id)
         {
             info!("{ADMIN_LOG_TAG}: User has already been added to call with demux ID {demux_id}");
+            Ok(AdminActionOutcome::AlreadyJoined)
         } else {
             warn!("{ADMIN_LOG_TAG}: Failed to find user for {action_to_log}. They may have left or been denied by another admin.");
+            Ok(AdminActionOutcome::NotPending)
         }
     }
 
@@ -2324,7 +2870,7 @@ impl Client {
                 "group_call::Client(inner)::approve_user(client_id: {})",
                 state.client_id
             );
-            Self::approve_or_deny_user(state, user_id, true);
+            let _ = Self::approve_or_deny_user(state, &user_id, true);
         });
     }
 
@@ -2338,7 +2884,178 @@ impl Client {
                 "group_call::Client(inner)::deny_user(client_id: {})",
                 state.client_id
             );
-            Self::approve_or_deny_user(state, user_id, false);
+            let _ = Self::approve_or_deny_user(state, &user_id, false);
+        });
+    }
+
+    /// Queues approvals or denials for a batch of users, which will be sent a few at a time.
+    ///
+    /// If `user_ids` is None, the batch is everyone pending according to the latest peek info.
+    /// The results are reported via [`Observer::handle_admin_action_results`].
+    fn queue_admin_actions(state: &mut State, user_ids: Option<Vec<UserId>>, approved: bool) {
+        let user_ids = user_ids.unwrap_or_else(|| {
+            state
+                .last_peek_info
+                .as_ref()
+                .map(|peek_info| {
+                    peek_info
+                        .unique_pending_users()
+                        .into_iter()
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        let mut seen = HashSet::new();
+        let user_ids: VecDeque<UserId> = user_ids
+            .into_iter()
+            .filter(|user_id| seen.insert(user_id.clone()))
+            .collect();
+        info!(
+            "{ADMIN_LOG_TAG}: Queueing {} for {} users",
+            if approved { "approval" } else { "denial" },
+            user_ids.len()
+        );
+
+        state.pending_admin_actions.push_back(PendingAdminActions {
+            approved,
+            results: Vec::with_capacity(user_ids.len()),
+            user_ids,
+            window_full_ticks: 0,
+        });
+        Self::send_pending_admin_actions(state);
+    }
+
+    fn send_pending_admin_actions(state: &mut State) {
+        let mut sent_count = 0;
+        while let Some(mut batch) = state.pending_admin_actions.pop_front() {
+            while sent_count < MAX_ADMIN_ACTIONS_PER_TICK {
+                let Some(user_id) = batch.user_ids.pop_front() else {
+                    break;
+                };
+                let outcome = match Self::approve_or_deny_user(state, &user_id, batch.approved) {
+                    Ok(outcome) => outcome,
+                    Err(MrpSendError::SendWindowFull) => {
+                        batch.user_ids.push_front(user_id);
+                        batch.window_full_ticks += 1;
+                        if batch.window_full_ticks < MAX_ADMIN_ACTION_WINDOW_FULL_TICKS {
+                            // Try again on the next tick, once the SFU has acked some messages.
+                            state.pending_admin_actions.push_front(batch);
+                        } else {
+                            warn!(
+                                "{ADMIN_LOG_TAG}: Send window still full after {} ticks; failing {} remaining",
+                                batch.window_full_ticks,
+                                batch.user_ids.len()
+                            );
+                            Self::fail_admin_actions(state, batch);
+                        }
+                        return;
+                    }
+                    Err(MrpSendError::InnerSendFailed(_)) => AdminActionOutcome::Failed,
+                };
+                if outcome == AdminActionOutcome::Sent {
+                    sent_count += 1;
+                    batch.window_full_ticks = 0;
+                }
+                batch.results.push(AdminActionResult {
+                    user_id,
+                    approved: batch.approved,
+                    outcome,
+                });
+            }
+
+            if !batch.user_ids.is_empty() {
+                state.pending_admin_actions.push_front(batch);
+                return;
+            }
+            state
+                .observer
+                .handle_admin_action_results(state.client_id, batch.results);
+        }
+    }
+
+    /// Reports every user in `batch` that hasn't been handled yet as
+    /// [`AdminActionOutcome::Failed`], along with the results so far.
+    fn fail_admin_actions(state: &mut State, mut batch: PendingAdminActions) {
+        let approved = batch.approved;
+        batch
+            .results
+            .extend(batch.user_ids.drain(..).map(|user_id| AdminActionResult {
+                user_id,
+                approved,
+                outcome: AdminActionOutcome::Failed,
+            }));
+        state
+            .observer
+            .handle_admin_action_results(state.client_id, batch.results);
+    }
+
+    /// Fails all queued and partially-sent batches, such as when leaving the call.
+    fn fail_pending_admin_actions(state: &mut State) {
+        for batch in std::mem::take(&mut state.pending_admin_actions) {
+            Self::fail_admin_actions(state, batch);
+        }
+    }
+
+    /// Approves each of `user_ids` that is pending, a few at a time.
+    pub fn approve_users(&self, user_ids: Vec<UserId>) {
+        debug!(
+            "group_call::Client(outer)::approve_users(client_id: {}, count: {})",
+            self.client_id,
+            user_ids.len()
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::approve_users(client_id: {})",
+                state.client_id
+            );
+            Self::queue_admin_actions(state, Some(user_ids), true);
+        });
+    }
+
+    /// Denies each of `user_ids` that is pending, a few at a time.
+    pub fn deny_users(&self, user_ids: Vec<UserId>) {
+        debug!(
+            "group_call::Client(outer)::deny_users(client_id: {}, count: {})",
+            self.client_id,
+            user_ids.len()
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::deny_users(client_id: {})",
+                state.client_id
+            );
+            Self::queue_admin_actions(state, Some(user_ids), false);
+        });
+    }
+
+    /// Approves everyone who is pending according to the latest peek info.
+    pub fn approve_all_pending_users(&self) {
+        debug!(
+            "group_call::Client(outer)::approve_all_pending_users(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::approve_all_pending_users(client_id: {})",
+                state.client_id
+            );
+            Self::queue_admin_actions(state, None, true);
+        });
+    }
+
+    /// Denies everyone who is pending according to the latest peek info.
+    pub fn deny_all_pending_users(&self) {
+        debug!(
+            "group_call::Client(outer)::deny_all_pending_users(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::deny_all_pending_users(client_id: {})",
+                state.client_id
+            );
+            Self::queue_admin_actions(state, None, false);
         });
     }
 
@@ -2469,6 +3186,8 @@ impl Client {
                 );
                 if let Err(err) = Self::send_reaction(state, value) {
                     warn!("Failed to send reaction: {:?}", err);
+                } else {
+                    state.call_summary_state.reactions_sent += 1;
                 }
             });
         }
@@ -2501,6 +3220,9 @@ impl Client {
             state.client_id
         );
 
+        state.sfu_client.cancel_requests();
+        Self::fail_pending_admin_actions(state);
+
         match state.join_state {
             JoinState::NotJoined(_) => {
                 // Nothing to do.
@@ -2524,7 +3246,15 @@ impl Client {
                     ConnectionState::NotConnected,
                 );
                 let _join_handles = state.actor.stopper().stop_all_without_joining();
-                state.observer.handle_ended(state.client_id, reason);
+                let summary = std::mem::take(&mut state.call_summary_state)
+                    .finish(state.stats_observer.media_summary(), Instant::now());
+                info!(
+                    "group_call::Client(inner)::end(client_id: {}) summary: {:?}",
+                    state.client_id, summary
+                );
+                state
+                    .observer
+                    .handle_ended(state.client_id, reason, summary);
             }
         }
     }
@@ -2611,6 +3341,10 @@ impl Client {
                 // device in it already (possible if the peek raced with the join request).
                 // In that case, set_peek_info_inner will have notified the observer about
                 // the join state change already.
+                state.call_summary_state.set_joined(
+                    matches!(state.join_state, JoinState::Joined(_)),
+                    Instant::now(),
+                );
                 state
                     .observer
                     .handle_join_state_changed(state.client_id, state.join_state);
@@ -2656,8 +3390,14 @@ impl Client {
     }
 
     fn on_sfu_client_join_failure(state: &mut State, err: anyhow::Error) {
-        // Map the error to an appropriate end reason.
-        let end_reason = err.downcast_ref::<RingRtcError>().map_or_else(
+        if let Some(sfu_error) = err.downcast_ref::<SfuError>() {
+            warn!("Failed to join: {}", sfu_error);
+            Self::end(state, sfu_error.into());
+            return;
+        }
+
+        // Map the error to an appropriate end reason.
+        let end_reason = err.downcast_ref::<RingRtcError>().map_or_else(
             || {
                 error!("Unexpected error: {}", err);
                 EndReason::SfuClientFailedToJoin
@@ -2882,6 +3622,45 @@ impl Client {
         });
     }
 
+    /// Changes how often heartbeats (and video requests) are sent. A zero interval
+    /// restores the default.
+    pub fn set_heartbeat_interval(&self, interval: Duration) {
+        info!(
+            "group_call::Client(outer)::set_heartbeat_interval: {}, interval: {:?})",
+            self.client_id, interval
+        );
+
+        self.actor.send(move |state| {
+            let old_heartbeat_interval = state.heartbeat_interval;
+            state.heartbeat_interval = if interval.is_zero() {
+                DEFAULT_HEARTBEAT_INTERVAL
+            } else {
+                interval
+            };
+
+            state.next_heartbeat_time = state.next_heartbeat_time.map(|heartbeat_time| {
+                heartbeat_time - old_heartbeat_interval + state.heartbeat_interval
+            });
+        });
+    }
+
+    /// Changes how long a remote device can go without sending a heartbeat before
+    /// it's marked as unresponsive. A zero grace period restores the default.
+    pub fn set_unresponsive_grace_period(&self, grace_period: Duration) {
+        info!(
+            "group_call::Client(outer)::set_unresponsive_grace_period: {}, grace_period: {:?})",
+            self.client_id, grace_period
+        );
+
+        self.actor.send(move |state| {
+            state.unresponsive_grace_period = if grace_period.is_zero() {
+                DEFAULT_UNRESPONSIVE_GRACE_PERIOD
+            } else {
+                grace_period
+            };
+        });
+    }
+
     // Most of the logic moved to inner method so this can be called by both
     // set_peek_result() and as a callback to SfuClient::request_remote_devices.
     fn set_peek_result_inner(state: &mut State, result: PeekResult) {
@@ -2898,6 +3677,15 @@ impl Client {
         }
         let peek_info = result.unwrap();
 
+        state.call_summary_state.peak_device_count = state
+            .call_summary_state
+            .peak_device_count
+            .max(peek_info.device_count_including_pending_devices());
+        state
+            .call_summary_state
+            .unique_users
+            .extend(peek_info.unique_users().into_iter().cloned());
+
         let is_first_peek_info = state.last_peek_info.is_none();
         let should_request_again = matches!(
             state.remote_devices_request_state,
@@ -3093,6 +3881,8 @@ impl Client {
                     &state.remote_devices,
                     RemoteDevicesChangedReason::DemuxIdsChanged,
                 );
+                // The presenter may have left.
+                Self::update_current_presenter(state);
             }
             // Make sure not to notify for the updated join state until the remote devices have been
             // updated.
@@ -3640,6 +4430,15 @@ impl Client {
                     video_muted: state.outgoing_heartbeat_state.video_muted,
                     presenting: state.outgoing_heartbeat_state.presenting,
                     sharing_screen: state.outgoing_heartbeat_state.sharing_screen,
+                    presentation_claim: state.outgoing_heartbeat_state.presentation_claim,
+                    presentation_take_over: state.outgoing_heartbeat_state.presentation_take_over,
+                    interval_millis: Some(
+                        state
+                            .heartbeat_interval
+                            .as_millis()
+                            .try_into()
+                            .unwrap_or(u32::MAX),
+                    ),
                 })
             },
             ..Default::default()
@@ -3647,6 +4446,19 @@ impl Client {
         Self::broadcast_data_through_sfu(state, &heartbeat_msg.encode_to_vec())
     }
 
+    fn send_present_request(state: &mut State, claim: u64, take_over: bool) -> Result<()> {
+        let present_request_msg = protobuf::group_call::DeviceToDevice {
+            present_request: {
+                Some(protobuf::group_call::device_to_device::PresentRequest {
+                    claim: Some(claim),
+                    take_over: Some(take_over),
+                })
+            },
+            ..Default::default()
+        };
+        Self::broadcast_data_through_sfu(state, &present_request_msg.encode_to_vec())
+    }
+
     fn send_reaction(state: &mut State, value: String) -> Result<()> {
         let react_msg = protobuf::group_call::DeviceToDevice {
             reaction: {
@@ -3922,6 +4734,13 @@ impl Client {
                         if let Some(reaction) = msg.reaction {
                             self.handle_reaction(demux_id, reaction);
                         }
+                        if let Some(present_request) = msg.present_request {
+                            self.handle_present_request_received(
+                                demux_id,
+                                header.timestamp,
+                                present_request,
+                            );
+                        }
                     } else {
                         warn!(
                             "Ignoring received RTP data because decoding failed. demux_id: {}",
@@ -4118,6 +4937,10 @@ impl Client {
                     speaker_device.demux_id, speaker_device.speaker_time
                 );
                 let demux_id = speaker_device.demux_id;
+                let user_id = speaker_device.user_id.clone();
+                state
+                    .call_summary_state
+                    .set_current_speaker(Some((demux_id, user_id)), Instant::now());
                 state.observer.handle_remote_devices_changed(
                     state.client_id,
                     &state.remote_devices,
@@ -4129,6 +4952,9 @@ impl Client {
                     demux_id
                 );
                 // Unknown speaker device. It's probably the local device.
+                state
+                    .call_summary_state
+                    .set_current_speaker(None, Instant::now());
             }
         });
     }
@@ -4191,6 +5017,13 @@ impl Client {
                     // Record this even if nothing changed.  Otherwise an old packet could override
                     // a new packet.
                     remote_device.heartbeat_rtp_timestamp = Some(timestamp);
+                    remote_device.heartbeat_received_time = Some(Instant::now());
+                    remote_device.heartbeat_interval = heartbeat
+                        .interval_millis
+                        .filter(|interval_millis| *interval_millis > 0)
+                        .map(|interval_millis| Duration::from_millis(interval_millis.into()));
+                    let was_unresponsive =
+                        std::mem::replace(&mut remote_device.is_unresponsive, false);
                     let heartbeat_state = HeartbeatState::from(heartbeat);
                     if remote_device.heartbeat_state != heartbeat_state {
                         if heartbeat_state.video_muted == Some(true) {
@@ -4205,6 +5038,19 @@ impl Client {
                             &state.remote_devices,
                             RemoteDevicesChangedReason::HeartbeatStateChanged(demux_id),
                         );
+                        Self::update_current_presenter(state);
+                    }
+
+                    if was_unresponsive {
+                        info!(
+                            "group_call::Client(inner)::handle_heartbeat_received(client_id: {}): demux_id {} is responsive again",
+                            state.client_id, demux_id
+                        );
+                        state.observer.handle_remote_devices_changed(
+                            state.client_id,
+                            &state.remote_devices,
+                            RemoteDevicesChangedReason::UnresponsiveChanged(demux_id),
+                        );
                     }
                 }
             } else {
@@ -4216,6 +5062,128 @@ impl Client {
         });
     }
 
+    fn handle_receiver_samples(state: &mut State, samples: &[ReceiverSample], now: Instant) {
+        for remote_device in state.remote_devices.iter_mut() {
+            remote_device.stats_connection_quality = samples
+                .iter()
+                .filter(|sample| demux_id_from_ssrc(sample.ssrc) == remote_device.demux_id)
+                .map(ConnectionQuality::from_receiver_sample)
+                .max();
+        }
+        Self::update_connection_quality(state, now);
+    }
+
+    // Combines the latest receiver stats with how recently each remote device's heartbeat
+    // arrived, and notifies the observer if the quality of any remote device changed.
+    fn update_connection_quality(state: &mut State, now: Instant) {
+        let mut changed = false;
+        for remote_device in state.remote_devices.iter_mut() {
+            let heartbeat_connection_quality =
+                remote_device
+                    .heartbeat_received_time
+                    .map(|heartbeat_received_time| {
+                        ConnectionQuality::from_time_since_heartbeat(
+                            now.saturating_duration_since(heartbeat_received_time),
+                            remote_device.expected_heartbeat_interval(),
+                        )
+                    });
+            let connection_quality = remote_device
+                .stats_connection_quality
+                .max(heartbeat_connection_quality);
+            if remote_device.connection_quality != connection_quality {
+                debug!(
+                    "group_call::Client(inner)::update_connection_quality(client_id: {}, demux_id: {}, connection_quality: {:?})",
+                    state.client_id, remote_device.demux_id, connection_quality
+                );
+                remote_device.connection_quality = connection_quality;
+                changed = true;
+            }
+        }
+        if changed {
+            state.observer.handle_remote_devices_changed(
+                state.client_id,
+                &state.remote_devices,
+                RemoteDevicesChangedReason::ConnectionQualityChanged,
+            );
+        }
+    }
+
+    fn handle_present_request_received(
+        &self,
+        demux_id: DemuxId,
+        timestamp: u32,
+        present_request: protobuf::group_call::device_to_device::PresentRequest,
+    ) {
+        self.actor.send(move |state| {
+            let Some(remote_device) = state.remote_devices.find_by_demux_id_mut(demux_id) else {
+                warn!(
+                    "Ignoring received present request for unknown demux_id {}",
+                    demux_id
+                );
+                return;
+            };
+            // Present requests and heartbeats share the same timestamps, so make sure an
+            // older heartbeat doesn't override a newer request or vice versa.
+            if timestamp <= remote_device.heartbeat_rtp_timestamp.unwrap_or(0) {
+                return;
+            }
+            info!(
+                "group_call::Client(inner)::handle_present_request_received(client_id: {}, demux_id: {}, claim: {:?}, take_over: {:?})",
+                state.client_id, demux_id, present_request.claim, present_request.take_over
+            );
+            remote_device.heartbeat_rtp_timestamp = Some(timestamp);
+            remote_device.heartbeat_state.presenting = Some(true);
+            remote_device.heartbeat_state.presentation_claim = present_request.claim;
+            remote_device.heartbeat_state.presentation_take_over = present_request.take_over;
+            state.observer.handle_remote_devices_changed(
+                state.client_id,
+                &state.remote_devices,
+                RemoteDevicesChangedReason::HeartbeatStateChanged(demux_id),
+            );
+            Self::update_current_presenter(state);
+        });
+    }
+
+    fn check_for_unresponsive_devices(state: &mut State, now: Instant) {
+        let unresponsive_grace_period = state.unresponsive_grace_period;
+        let newly_unresponsive_demux_ids: Vec<DemuxId> = state
+            .remote_devices
+            .iter_mut()
+            .filter(|remote_device| !remote_device.is_unresponsive)
+            .filter_map(|remote_device| {
+                let heartbeat_received_time = remote_device.heartbeat_received_time?;
+                let grace_period = unresponsive_grace_period.max(
+                    remote_device.expected_heartbeat_interval()
+                        * MIN_UNRESPONSIVE_HEARTBEAT_GAP_INTERVALS,
+                );
+                if now.saturating_duration_since(heartbeat_received_time) >= grace_period {
+                    remote_device.is_unresponsive = true;
+                    Some(remote_device.demux_id)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if newly_unresponsive_demux_ids.is_empty() {
+            return;
+        }
+
+        for demux_id in newly_unresponsive_demux_ids {
+            warn!(
+                "group_call::Client(inner)::check_for_unresponsive_devices(client_id: {}): no heartbeat from demux_id {} in {:?}",
+                state.client_id, demux_id, unresponsive_grace_period
+            );
+            state.observer.handle_remote_devices_changed(
+                state.client_id,
+                &state.remote_devices,
+                RemoteDevicesChangedReason::UnresponsiveChanged(demux_id),
+            );
+        }
+        // The device may have left without us noticing, so check with the SFU.
+        Self::request_remote_devices_as_soon_as_possible(state);
+    }
+
     fn handle_leaving_received(state: &mut State, demux_id: DemuxId) {
         // It's likely we haven't received an update from the SFU about this demux_id leaving.
         debug!(
@@ -4259,10 +5227,19 @@ impl Client {
         } else {
             self.actor.send(move |state| {
                 state.reactions.push(Reaction { demux_id, value });
+                state.call_summary_state.reactions_received += 1;
             });
         }
     }
 
+    fn count_newly_raised_hands(state: &mut State, raised_hands: &[DemuxId]) {
+        let newly_raised_hand_count = raised_hands
+            .iter()
+            .filter(|demux_id| !state.raised_hands.contains(demux_id))
+            .count();
+        state.call_summary_state.raised_hand_count += newly_raised_hand_count as u32;
+    }
+
     fn handle_raised_hands(actor: &Actor<State>, raised_hands: Vec<DemuxId>, server_seqnum: u32) {
         actor.send(move |state| {
             // The server has previously received a hand raise request from the client or admin
@@ -4277,6 +5254,7 @@ impl Client {
                     // "unlock" the UI state even if the raised hand list is the same as before.
                     if state.raise_hand_state.outstanding {
                         state.raise_hand_state.outstanding = false;
+                        Self::count_newly_raised_hands(state, &raised_hands);
                         state.raised_hands = raised_hands;
 
                         info!(
@@ -4304,6 +5282,7 @@ impl Client {
                             state.raise_hand_state.raise,
                             state.raise_hand_state.outstanding
                         );
+                        Self::count_newly_raised_hands(state, &raised_hands);
                         state.raised_hands = raised_hands;
                         state
                             .observer
@@ -4315,6 +5294,7 @@ impl Client {
                 // Issue a callback if the client has never raised their hand and the server
                 // list is different than before.
                 if state.raise_hand_state.seqnum == 0 && state.raised_hands != raised_hands {
+                    Self::count_newly_raised_hands(state, &raised_hands);
                     state.raised_hands = raised_hands;
                     state
                         .observer
@@ -4324,6 +5304,58 @@ impl Client {
         });
     }
 
+    /// Returns a consistent view of the client's state, read through the actor so that it
+    /// reflects every operation queued before this call.
+    ///
+    /// This blocks the calling thread for up to 5 seconds. Observer callbacks run on the
+    /// actor thread, so calling this from one would wait on itself; in that case it returns
+    /// None right away instead. Also returns None if the client has already ended or the
+    /// actor didn't respond in time.
+    pub fn snapshot(&self) -> Option<ClientSnapshot> {
+        debug!(
+            "group_call::Client(outer)::snapshot(client_id: {})",
+            self.client_id
+        );
+        if self.actor.is_current_thread() {
+            warn!(
+                "group_call::Client(outer)::snapshot(client_id: {}) called on the actor thread",
+                self.client_id
+            );
+            return None;
+        }
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        self.actor.send(move |state| {
+            let _ = sender.send(Self::snapshot_inner(state));
+        });
+        match receiver.recv_timeout(SNAPSHOT_TIMEOUT) {
+            Ok(snapshot) => Some(snapshot),
+            Err(err) => {
+                warn!(
+                    "group_call::Client(outer)::snapshot(client_id: {}) failed: {:?}",
+                    self.client_id, err
+                );
+                None
+            }
+        }
+    }
+
+    fn snapshot_inner(state: &State) -> ClientSnapshot {
+        ClientSnapshot {
+            client_id: state.client_id,
+            connection_state: state.connection_state,
+            join_state: state.join_state,
+            remote_devices: state.remote_devices.to_vec(),
+            peek_info: state.last_peek_info.clone(),
+            joined_members: state.joined_members.clone(),
+            raised_hands: state.raised_hands.clone(),
+            forwarding_videos: state.forwarding_videos.clone(),
+            send_rates: state.send_rates.clone(),
+            data_mode: state.data_mode,
+            outgoing_heartbeat_state: state.outgoing_heartbeat_state.clone(),
+            current_presenter: state.current_presenter,
+        }
+    }
+
     #[cfg(feature = "sim")]
     pub fn synchronize(&self) {
         let barrier = Arc::new(std::sync::Barrier::new(2));
@@ -4613,14 +5645,6 @@ impl PeerConnectionObserverTrait for PeerConnectionObserverImpl {
     }
 }
 
-fn random_alphanumeric(len: usize) -> String {
-    std::iter::repeat(())
-        .map(|()| rand::rngs::OsRng.sample(rand::distributions::Alphanumeric))
-        .take(len)
-        .map(char::from)
-        .collect()
-}
-
 // Should this go in some util class?
 struct Writer<'buf> {
     buf: &'buf mut [u8],
@@ -4808,7 +5832,7 @@ mod tests {
                 if counter.fetch_sub(1, Ordering::SeqCst) <= 0 {
                     // No more joins allowed. Simulate a "group full" condition.
                     client.on_sfu_client_join_attempt_completed(Err(
-                        RingRtcError::GroupCallFull.into()
+                        SfuError::MaxDevicesReached.into()
                     ));
                     return;
                 }
@@ -4909,6 +5933,9 @@ mod tests {
         send_rates: Arc<CallMutex<Option<SendRates>>>,
         ended: Waitable<EndReason>,
         reactions: Arc<CallMutex<Vec<Reaction>>>,
+        current_presenter: Arc<CallMutex<Option<DemuxId>>>,
+        summary: Arc<CallMutex<Option<CallSummary>>>,
+        admin_action_results: Waitable<Vec<AdminActionResult>>,
 
         request_membership_proof_invocation_count: Arc<AtomicU64>,
         request_group_members_invocation_count: Arc<AtomicU64>,
@@ -4952,6 +5979,9 @@ mod tests {
                 send_rates: Arc::new(CallMutex::new(None, "FakeObserver send rates")),
                 ended: Waitable::default(),
                 reactions: Arc::new(CallMutex::new(Default::default(), "FakeObserver reactions")),
+                current_presenter: Arc::new(CallMutex::new(None, "FakeObserver current presenter")),
+                summary: Arc::new(CallMutex::new(None, "FakeObserver summary")),
+                admin_action_results: Waitable::default(),
                 request_membership_proof_invocation_count: Default::default(),
                 request_group_members_invocation_count: Default::default(),
                 handle_remote_devices_changed_invocation_count: Default::default(),
@@ -5020,6 +6050,20 @@ mod tests {
             send_rates.clone()
         }
 
+        fn summary(&self) -> Option<CallSummary> {
+            self.summary
+                .lock()
+                .expect("Lock summary to read it")
+                .clone()
+        }
+
+        fn current_presenter(&self) -> Option<DemuxId> {
+            *self
+                .current_presenter
+                .lock()
+                .expect("Lock current presenter to read it")
+        }
+
         fn reactions(&self) -> Vec<Reaction> {
             let reactions = self.reactions.lock().expect("Lock reactions to read it");
             reactions.clone()
@@ -5164,6 +6208,18 @@ mod tests {
 
         fn handle_raised_hands(&self, _client_id: ClientId, _raised_hands: Vec<DemuxId>) {}
 
+        fn handle_presenter_changed(
+            &self,
+            _client_id: ClientId,
+            current_presenter: Option<DemuxId>,
+        ) {
+            let mut owned_current_presenter = self
+                .current_presenter
+                .lock()
+                .expect("Lock current presenter to handle update");
+            *owned_current_presenter = current_presenter;
+        }
+
         fn handle_rtc_stats_report(&self, _report_json: String) {}
 
         fn handle_peek_changed(
@@ -5308,7 +6364,18 @@ mod tests {
         ) {
         }
 
-        fn handle_ended(&self, _client_id: ClientId, reason: EndReason) {
+        fn handle_admin_action_results(
+            &self,
+            _client_id: ClientId,
+            results: Vec<AdminActionResult>,
+        ) {
+            self.admin_action_results.set(results);
+        }
+
+        fn handle_ended(&self, _client_id: ClientId, reason: EndReason, summary: CallSummary) {
+            let mut owned_summary = self.summary.lock().expect("Lock summary to handle ended");
+            *owned_summary = Some(summary);
+            drop(owned_summary);
             self.ended.set(reason);
         }
     }
@@ -6168,6 +7235,355 @@ mod tests {
         assert_eq!(1, client2.observer.reactions()[0].demux_id)
     }
 
+    #[test]
+    fn call_summary() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        client1.client.react("hello".to_string());
+        assert!(client2
+            .observer
+            .reactions_called
+            .wait(Duration::from_secs(5)));
+        client2.wait_for_client_to_process();
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+
+        let summary1 = client1
+            .observer
+            .summary()
+            .expect("client1 ended with a summary");
+        assert_eq!(1, summary1.reactions_sent);
+        assert_eq!(0, summary1.reactions_received);
+        assert_eq!(2, summary1.peak_device_count);
+        assert_eq!(2, summary1.unique_user_count);
+        assert_eq!(0, summary1.reconnect_count);
+        assert!(summary1.join_duration > Duration::ZERO);
+        assert!(summary1.call_duration >= summary1.join_duration);
+
+        let summary2 = client2
+            .observer
+            .summary()
+            .expect("client2 ended with a summary");
+        assert_eq!(0, summary2.reactions_sent);
+        assert_eq!(1, summary2.reactions_received);
+    }
+
+    #[test]
+    fn call_summary_speaking_time() {
+        let user_id = b"speaker".to_vec();
+        let levels = |level| {
+            vec![ReceivedAudioLevel {
+                demux_id: 32,
+                level,
+            }]
+        };
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut summary_state = CallSummaryState::default();
+        summary_state.set_current_speaker(Some((32, user_id.clone())), at(0));
+        summary_state.update_current_speaker_level(&levels(5000), at(1));
+        // Silent from 2s on; only counted as stopped after STOPPED_SPEAKING_DURATION.
+        summary_state.update_current_speaker_level(&levels(0), at(2));
+        summary_state.update_current_speaker_level(&levels(0), at(4));
+        summary_state.update_current_speaker_level(&levels(0), at(5));
+        summary_state.update_current_speaker_level(&levels(0), at(10));
+        // Speaking again without the SFU reporting a new speaker.
+        summary_state.update_current_speaker_level(&levels(5000), at(20));
+        // Someone else (e.g. the local device) becomes the speaker.
+        summary_state.set_current_speaker(None, at(23));
+
+        let summary = summary_state.finish(MediaSummary::default(), at(60));
+        assert_eq!(
+            Some(&Duration::from_secs(5)),
+            summary.speaking_time.get(&user_id)
+        );
+    }
+
+    #[test]
+    fn request_to_present() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        let client3 = TestClient::new(vec![3], 3);
+        client3.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2, &client3]);
+
+        let wait_for_all_clients = || {
+            for client in [&client1, &client2, &client3] {
+                client.wait_for_client_to_process();
+            }
+            for client in [&client1, &client2, &client3] {
+                client.wait_for_client_to_process();
+            }
+        };
+
+        client2.client.request_to_present(false);
+        wait_for_all_clients();
+        for client in [&client1, &client2, &client3] {
+            assert_eq!(Some(client2.demux_id), client.observer.current_presenter());
+        }
+
+        // Without take over, someone else presenting wins.
+        client3.client.request_to_present(false);
+        wait_for_all_clients();
+        for client in [&client1, &client2, &client3] {
+            assert_eq!(Some(client2.demux_id), client.observer.current_presenter());
+        }
+        assert_eq!(
+            client3
+                .client
+                .snapshot()
+                .unwrap()
+                .outgoing_heartbeat_state
+                .presenting,
+            None
+        );
+
+        // With take over, the previous presenter stops presenting.
+        client3.client.request_to_present(true);
+        wait_for_all_clients();
+        for client in [&client1, &client2, &client3] {
+            assert_eq!(Some(client3.demux_id), client.observer.current_presenter());
+        }
+        assert_eq!(
+            client2
+                .client
+                .snapshot()
+                .unwrap()
+                .outgoing_heartbeat_state
+                .presenting,
+            Some(false)
+        );
+
+        client3.client.set_presenting(false);
+        wait_for_all_clients();
+        for client in [&client1, &client2, &client3] {
+            assert_eq!(None, client.observer.current_presenter());
+        }
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+        client3.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn request_to_present_without_take_over_enforced_by_receivers() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        let client3 = TestClient::new(vec![3], 3);
+        client3.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2, &client3]);
+
+        let wait_for_all_clients = || {
+            for client in [&client1, &client2, &client3] {
+                client.wait_for_client_to_process();
+            }
+            for client in [&client1, &client2, &client3] {
+                client.wait_for_client_to_process();
+            }
+        };
+
+        client2.client.request_to_present(false);
+        wait_for_all_clients();
+
+        // Skip client3's own check and claim the presentation without take over.
+        client3.client.actor.send(|state| {
+            Client::send_present_request(state, 100, false).unwrap();
+        });
+        wait_for_all_clients();
+        for client in [&client1, &client2] {
+            assert_eq!(Some(client2.demux_id), client.observer.current_presenter());
+        }
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+        client3.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn unresponsive_remote_device() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2
+            .client
+            .set_unresponsive_grace_period(Duration::from_millis(500));
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        client1.client.set_outgoing_audio_muted(true);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        let remote_devices2 = client2.observer.remote_devices();
+        assert_eq!(1, remote_devices2.len());
+        assert_eq!(Some(true), remote_devices2[0].heartbeat_state.audio_muted);
+        assert!(!remote_devices2[0].is_unresponsive);
+
+        // A short grace period doesn't count against a device that has only missed
+        // one of its (default) heartbeat intervals...
+        let heartbeat_received_time = Instant::now();
+        client2.client.actor.send(move |state| {
+            Client::check_for_unresponsive_devices(
+                state,
+                heartbeat_received_time + Duration::from_millis(1500),
+            );
+        });
+        client2.wait_for_client_to_process();
+        assert!(!client2.observer.remote_devices()[0].is_unresponsive);
+
+        // ...but missing several does.
+        client2.client.actor.send(move |state| {
+            Client::check_for_unresponsive_devices(
+                state,
+                heartbeat_received_time + Duration::from_secs(5),
+            );
+        });
+        client2.wait_for_client_to_process();
+        assert!(client2.observer.remote_devices()[0].is_unresponsive);
+
+        // Any heartbeat makes it responsive again.
+        client1.client.set_outgoing_video_muted(false);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+        let remote_devices2 = client2.observer.remote_devices();
+        assert_eq!(1, remote_devices2.len());
+        assert!(!remote_devices2[0].is_unresponsive);
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn heartbeat_gaps_judged_by_remote_interval() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1
+            .client
+            .set_heartbeat_interval(Duration::from_secs(5));
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        // A short local interval doesn't change what's expected of others.
+        client2
+            .client
+            .set_heartbeat_interval(Duration::from_millis(100));
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        client1.client.set_outgoing_audio_muted(true);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        let connection_quality_after = |gap: Duration| {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let now = Instant::now() + gap;
+            client2.client.actor.send(move |state| {
+                Client::update_connection_quality(state, now);
+                let _ = sender.send(state.remote_devices[0].connection_quality);
+            });
+            receiver.recv().expect("connection quality")
+        };
+        // client1 advertised 5s, so a few seconds' gap is fine.
+        assert_eq!(
+            Some(ConnectionQuality::Good),
+            connection_quality_after(Duration::from_secs(3))
+        );
+        assert_eq!(
+            Some(ConnectionQuality::Degraded),
+            connection_quality_after(Duration::from_secs(15))
+        );
+        assert_eq!(
+            Some(ConnectionQuality::Poor),
+            connection_quality_after(Duration::from_secs(30))
+        );
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn snapshot() {
+        let client1 = TestClient::new(vec![1], 1);
+        let snapshot = client1
+            .client
+            .snapshot()
+            .expect("snapshot before connecting");
+        assert_eq!(ConnectionState::NotConnected, snapshot.connection_state);
+        assert_eq!(JoinState::NotJoined(None), snapshot.join_state);
+        assert!(snapshot.remote_devices.is_empty());
+        assert_eq!(None, snapshot.peek_info);
+        assert_eq!(HeartbeatState::default(), snapshot.outgoing_heartbeat_state);
+
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        client1.client.set_outgoing_audio_muted(true);
+        client1.client.set_data_mode(DataMode::Low);
+        // No need to wait; the snapshot is queued behind the changes above.
+        let snapshot = client1.client.snapshot().expect("snapshot after joining");
+        assert_eq!(JoinState::Joined(1), snapshot.join_state);
+        assert_eq!(
+            vec![2],
+            snapshot
+                .remote_devices
+                .iter()
+                .map(|device| device.demux_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(hash_set([vec![1], vec![2]]), snapshot.joined_members);
+        assert_eq!(
+            2,
+            snapshot
+                .peek_info
+                .expect("peek info after joining")
+                .devices
+                .len()
+        );
+        assert_eq!(Some(true), snapshot.outgoing_heartbeat_state.audio_muted);
+        assert_eq!(None, snapshot.outgoing_heartbeat_state.video_muted);
+        assert_eq!(DataMode::Low, snapshot.data_mode);
+
+        client1.disconnect_and_wait_until_ended();
+        assert!(client1.client.snapshot().is_none());
+    }
+
+    #[test]
+    fn snapshot_from_actor_thread() {
+        let client1 = TestClient::new(vec![1], 1);
+        let client = client1.client.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        client1.client.actor.send(move |_state| {
+            let _ = sender.send(client.snapshot().is_none());
+        });
+        // Well under SNAPSHOT_TIMEOUT, since it shouldn't wait at all.
+        assert_eq!(Ok(true), receiver.recv_timeout(Duration::from_secs(1)));
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn ignore_devices_that_arent_members() {
         let client = TestClient::new(vec![1], 1);
@@ -6839,6 +8255,187 @@ mod tests {
         client1.disconnect_and_wait_until_ended();
     }
 
+    fn admin_action_target_demux_ids(
+        receiver: &mpsc::Receiver<(rtp::Header, Vec<u8>)>,
+        count: usize,
+    ) -> HashSet<DemuxId> {
+        use protobuf::group_call::{
+            device_to_sfu::{AdminAction, GenericAdminAction},
+            DeviceToSfu,
+        };
+
+        let mut target_demux_ids = HashSet::new();
+        while target_demux_ids.len() < count {
+            let (_header, payload) = receiver
+                .recv_timeout(Duration::from_secs(1))
+                .expect("Get RTP packet to SFU");
+            match DeviceToSfu::decode(&payload[..]).unwrap().admin_action {
+                Some(
+                    AdminAction::Approve(GenericAdminAction { target_demux_id })
+                    | AdminAction::Deny(GenericAdminAction { target_demux_id }),
+                ) => {
+                    assert!(
+                        target_demux_ids.insert(target_demux_id.expect("has a target")),
+                        "sent twice"
+                    );
+                }
+                Some(other) => panic!("unexpected admin action {other:?}"),
+                // Something else, like a video request.
+                None => {}
+            }
+        }
+        target_demux_ids
+    }
+
+    #[test]
+    fn approve_all_pending_users() {
+        let mut client1 = TestClient::new(vec![1], 1);
+
+        let remote1 = TestClient::new(vec![11], 16);
+        let remote2a = TestClient::new(vec![22], 32);
+        let remote2b = TestClient::new(vec![22], 48);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_pending_clients_and_wait_until_applied(&[&remote1, &remote2a, &remote2b]);
+        client1.client.approve_all_pending_users();
+
+        // One approval per user, not per device.
+        assert_eq!(
+            hash_set([16, 32]),
+            admin_action_target_demux_ids(&receiver, 2)
+        );
+        let results = client1
+            .observer
+            .admin_action_results
+            .wait(Duration::from_secs(1))
+            .expect("results reported");
+        assert_eq!(
+            hash_set([
+                AdminActionResult {
+                    user_id: vec![11],
+                    approved: true,
+                    outcome: AdminActionOutcome::Sent,
+                },
+                AdminActionResult {
+                    user_id: vec![22],
+                    approved: true,
+                    outcome: AdminActionOutcome::Sent,
+                },
+            ]),
+            hash_set(results)
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn deny_users_reports_each_outcome() {
+        let mut client1 = TestClient::new(vec![1], 1);
+
+        let remote1 = TestClient::new(vec![11], 16);
+        let remote2 = TestClient::new(vec![22], 32);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.default_peek_info.devices.push(PeekDeviceInfo {
+            demux_id: remote1.demux_id,
+            user_id: Some(remote1.user_id.clone()),
+        });
+        client1.connect_join_and_wait_until_joined();
+        client1.set_pending_clients_and_wait_until_applied(&[&remote2]);
+        client1
+            .client
+            .deny_users(vec![vec![22], vec![11], vec![33], vec![22]]);
+
+        assert_eq!(hash_set([32]), admin_action_target_demux_ids(&receiver, 1));
+        let results = client1
+            .observer
+            .admin_action_results
+            .wait(Duration::from_secs(1))
+            .expect("results reported");
+        assert_eq!(
+            vec![
+                (vec![22], AdminActionOutcome::Sent),
+                (vec![11], AdminActionOutcome::AlreadyJoined),
+                (vec![33], AdminActionOutcome::NotPending),
+            ],
+            results
+                .into_iter()
+                .map(|result| (result.user_id, result.outcome))
+                .collect::<Vec<_>>()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn bulk_approvals_are_spread_over_ticks() {
+        let mut client1 = TestClient::new(vec![1], 1);
+
+        let count = MAX_ADMIN_ACTIONS_PER_TICK * 2 + 1;
+        let remotes: Vec<TestClient> = (0..count)
+            .map(|i| TestClient::new(vec![100 + i as u8], 16 * (i as DemuxId + 1)))
+            .collect();
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_pending_clients_and_wait_until_applied(&remotes.iter().collect::<Vec<_>>());
+        client1.client.approve_all_pending_users();
+        client1.wait_for_client_to_process();
+
+        // Only the first few go out right away, so the batch can't be finished yet.
+        assert_eq!(
+            None,
+            client1.observer.admin_action_results.wait(Duration::ZERO)
+        );
+        assert_eq!(count, admin_action_target_demux_ids(&receiver, count).len());
+        let results = client1
+            .observer
+            .admin_action_results
+            .wait(Duration::from_secs(2))
+            .expect("results reported");
+        assert_eq!(count, results.len());
+        assert!(results
+            .iter()
+            .all(|result| result.outcome == AdminActionOutcome::Sent));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn leaving_fails_pending_admin_actions() {
+        let mut client1 = TestClient::new(vec![1], 1);
+
+        let count = MAX_ADMIN_ACTIONS_PER_TICK * 2 + 1;
+        let remotes: Vec<TestClient> = (0..count)
+            .map(|i| TestClient::new(vec![100 + i as u8], 16 * (i as DemuxId + 1)))
+            .collect();
+
+        client1.connect_join_and_wait_until_joined();
+        client1.set_pending_clients_and_wait_until_applied(&remotes.iter().collect::<Vec<_>>());
+        client1.client.approve_all_pending_users();
+        client1.client.leave();
+        client1.wait_for_client_to_process();
+
+        let results = client1
+            .observer
+            .admin_action_results
+            .wait(Duration::ZERO)
+            .expect("results reported on leave");
+        assert_eq!(count, results.len());
+        assert!(results[..MAX_ADMIN_ACTIONS_PER_TICK]
+            .iter()
+            .all(|result| result.outcome == AdminActionOutcome::Sent));
+        assert!(results[MAX_ADMIN_ACTIONS_PER_TICK..]
+            .iter()
+            .all(|result| result.outcome == AdminActionOutcome::Failed));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn carry_over_devices_from_peeking_to_joined() {
         let client1 = TestClient::new(vec![1], 1);
@@ -7990,6 +9587,154 @@ mod remote_devices_tests {
         );
     }
 
+    #[test]
+    fn demux_id_from_ssrcs() {
+        assert_eq!(16, demux_id_from_ssrc(16));
+        assert_eq!(16, demux_id_from_ssrc(17));
+        assert_eq!(
+            16,
+            demux_id_from_ssrc(16 + RTP_DATA_THROUGH_SFU_SSRC_OFFSET)
+        );
+        assert_eq!(32, demux_id_from_ssrc(32 + 0xF));
+    }
+
+    #[test]
+    fn connection_quality_from_receiver_sample() {
+        let good = ReceiverSample {
+            ssrc: 16,
+            is_video: false,
+            packets_received: 50,
+            packets_lost_pct: 0.5,
+            jitter: 0.01,
+            frames_decoded: 0,
+            frames_expected: 0,
+            freeze_duration: Duration::ZERO,
+        };
+        assert_eq!(
+            ConnectionQuality::Good,
+            ConnectionQuality::from_receiver_sample(&good)
+        );
+
+        let lossy = ReceiverSample {
+            packets_lost_pct: 5.0,
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Degraded,
+            ConnectionQuality::from_receiver_sample(&lossy)
+        );
+
+        let very_lossy = ReceiverSample {
+            packets_lost_pct: 20.0,
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Poor,
+            ConnectionQuality::from_receiver_sample(&very_lossy)
+        );
+
+        let jittery = ReceiverSample {
+            jitter: 0.07,
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Degraded,
+            ConnectionQuality::from_receiver_sample(&jittery)
+        );
+
+        let briefly_frozen_video = ReceiverSample {
+            is_video: true,
+            frames_decoded: 0,
+            freeze_duration: Duration::from_secs(1),
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Degraded,
+            ConnectionQuality::from_receiver_sample(&briefly_frozen_video)
+        );
+
+        let frozen_video = ReceiverSample {
+            freeze_duration: Duration::from_secs(3),
+            ..briefly_frozen_video.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Poor,
+            ConnectionQuality::from_receiver_sample(&frozen_video)
+        );
+
+        let choppy_video = ReceiverSample {
+            is_video: true,
+            frames_decoded: 20,
+            frames_expected: 30,
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Degraded,
+            ConnectionQuality::from_receiver_sample(&choppy_video)
+        );
+
+        let very_choppy_video = ReceiverSample {
+            frames_decoded: 10,
+            ..choppy_video.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Poor,
+            ConnectionQuality::from_receiver_sample(&very_choppy_video)
+        );
+
+        let low_framerate_video = ReceiverSample {
+            frames_decoded: 1,
+            frames_expected: 5,
+            ..choppy_video.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Good,
+            ConnectionQuality::from_receiver_sample(&low_framerate_video)
+        );
+
+        let idle_video = ReceiverSample {
+            is_video: true,
+            packets_received: 0,
+            ..good.clone()
+        };
+        assert_eq!(
+            ConnectionQuality::Good,
+            ConnectionQuality::from_receiver_sample(&idle_video)
+        );
+    }
+
+    #[test]
+    fn connection_quality_from_time_since_heartbeat() {
+        assert_eq!(
+            ConnectionQuality::Good,
+            ConnectionQuality::from_time_since_heartbeat(
+                DEFAULT_HEARTBEAT_INTERVAL,
+                DEFAULT_HEARTBEAT_INTERVAL
+            )
+        );
+        assert_eq!(
+            ConnectionQuality::Degraded,
+            ConnectionQuality::from_time_since_heartbeat(
+                Duration::from_secs(3),
+                DEFAULT_HEARTBEAT_INTERVAL
+            )
+        );
+        assert_eq!(
+            ConnectionQuality::Poor,
+            ConnectionQuality::from_time_since_heartbeat(
+                Duration::from_secs(10),
+                DEFAULT_HEARTBEAT_INTERVAL
+            )
+        );
+        assert_eq!(
+            ConnectionQuality::Good,
+            ConnectionQuality::from_time_since_heartbeat(
+                Duration::from_secs(10),
+                Duration::from_secs(5)
+            )
+        );
+    }
+
     fn time(timestamp: u64) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp)
     }
@@ -0,0 +1,262 @@
+//
+// Copyright 2024 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Policies deciding whether an incoming call is allowed to ring.
+
+use std::{
+    collections::HashSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    common::CallMediaType,
+    core::{group_call, platform::Platform},
+    lite::sfu::UserId,
+};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// What to do with an incoming 1:1 call or group ring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IncomingCallDecision {
+    /// Ring as usual.
+    Allow,
+    /// Decline without ringing, as if the user had declined the call.
+    Decline,
+    /// Decline without ringing, telling the caller that they need the
+    /// user's permission to call. Group rings are declined normally.
+    NeedPermission,
+    /// Reject without ringing, as if this device were on another call.
+    Busy,
+}
+
+/// Decides whether incoming calls are allowed to ring.
+///
+/// Consulted by the CallManager for every new 1:1 offer and group ring,
+/// after any collisions with the active call have been handled. Calls
+/// that aren't allowed are answered automatically and reported to the
+/// application with [`ApplicationEvent::ReceivedOfferDeclinedByPolicy`][]
+/// or [`RingUpdate::DeclinedByPolicy`][].
+///
+/// [`ApplicationEvent::ReceivedOfferDeclinedByPolicy`]: crate::common::ApplicationEvent::ReceivedOfferDeclinedByPolicy
+/// [`RingUpdate::DeclinedByPolicy`]: group_call::RingUpdate::DeclinedByPolicy
+pub trait IncomingCallPolicy<T: Platform>: Send {
+    /// Checks an offer for a 1:1 call from `remote_peer`.
+    fn check_direct_call(
+        &self,
+        platform: &T,
+        remote_peer: &T::AppRemotePeer,
+        call_media_type: CallMediaType,
+    ) -> IncomingCallDecision;
+
+    /// Checks a ring from `sender` for the group call in `group_id`.
+    ///
+    /// `other_rings` is the number of rings for other groups that are
+    /// still outstanding.
+    fn check_group_ring(
+        &self,
+        group_id: group_call::GroupIdRef,
+        sender: &UserId,
+        other_rings: usize,
+    ) -> IncomingCallDecision;
+}
+
+/// A daily period during which calls don't ring, in local time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    /// Minutes after midnight when quiet hours start.
+    pub start_minute: u16,
+    /// Minutes after midnight when quiet hours end. If this is before
+    /// `start_minute`, quiet hours continue past midnight; if it's equal,
+    /// they last all day.
+    pub end_minute: u16,
+    /// The offset of local time from UTC, in minutes.
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietHours {
+    /// Returns true if `time` falls within quiet hours.
+    pub fn contains(&self, time: SystemTime) -> bool {
+        let utc_minutes = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => (since_epoch.as_secs() / 60) as i64,
+            Err(_) => return false,
+        };
+        let minute = (utc_minutes + self.utc_offset_minutes as i64).rem_euclid(MINUTES_PER_DAY);
+        let start = self.start_minute as i64;
+        let end = self.end_minute as i64;
+        match start.cmp(&end) {
+            std::cmp::Ordering::Less => start <= minute && minute < end,
+            std::cmp::Ordering::Greater => minute >= start || minute < end,
+            std::cmp::Ordering::Equal => true,
+        }
+    }
+}
+
+/// The built-in "do not disturb" policy.
+///
+/// Checks are applied in this order:
+/// - group rings beyond `max_concurrent_group_rings` are rejected as busy
+/// - callers in the allow-list always ring
+/// - with `contacts_only`, callers who aren't contacts need permission
+/// - during quiet hours, everyone else is declined
+pub struct DoNotDisturb<T: Platform> {
+    /// 1:1 callers that always ring.
+    pub allowed_peers: Vec<T::AppRemotePeer>,
+    /// Group ring senders that always ring.
+    pub allowed_users: HashSet<UserId>,
+    /// Whether only contacts may ring.
+    pub contacts_only: bool,
+    /// 1:1 callers that are contacts.
+    pub contact_peers: Vec<T::AppRemotePeer>,
+    /// Group ring senders that are contacts.
+    pub contact_users: HashSet<UserId>,
+    /// When calls don't ring at all, if ever.
+    pub quiet_hours: Option<QuietHours>,
+    /// How many group rings can be outstanding at once, if limited.
+    pub max_concurrent_group_rings: Option<usize>,
+}
+
+impl<T: Platform> Default for DoNotDisturb<T> {
+    fn default() -> Self {
+        Self {
+            allowed_peers: Vec::new(),
+            allowed_users: HashSet::new(),
+            contacts_only: false,
+            contact_peers: Vec::new(),
+            contact_users: HashSet::new(),
+            quiet_hours: None,
+            max_concurrent_group_rings: None,
+        }
+    }
+}
+
+impl<T: Platform> DoNotDisturb<T> {
+    fn is_quiet(&self) -> bool {
+        self.quiet_hours
+            .is_some_and(|quiet_hours| quiet_hours.contains(SystemTime::now()))
+    }
+
+    fn contains_peer(
+        platform: &T,
+        peers: &[T::AppRemotePeer],
+        remote_peer: &T::AppRemotePeer,
+    ) -> bool {
+        peers.iter().any(|peer| {
+            platform
+                .compare_remotes(peer, remote_peer)
+                .unwrap_or_else(|e| {
+                    warn!("compare_remotes() failed: {}", e);
+                    false
+                })
+        })
+    }
+}
+
+impl<T: Platform> IncomingCallPolicy<T> for DoNotDisturb<T> {
+    fn check_direct_call(
+        &self,
+        platform: &T,
+        remote_peer: &T::AppRemotePeer,
+        _call_media_type: CallMediaType,
+    ) -> IncomingCallDecision {
+        if Self::contains_peer(platform, &self.allowed_peers, remote_peer) {
+            IncomingCallDecision::Allow
+        } else if self.contacts_only
+            && !Self::contains_peer(platform, &self.contact_peers, remote_peer)
+        {
+            IncomingCallDecision::NeedPermission
+        } else if self.is_quiet() {
+            IncomingCallDecision::Decline
+        } else {
+            IncomingCallDecision::Allow
+        }
+    }
+
+    fn check_group_ring(
+        &self,
+        _group_id: group_call::GroupIdRef,
+        sender: &UserId,
+        other_rings: usize,
+    ) -> IncomingCallDecision {
+        if self
+            .max_concurrent_group_rings
+            .is_some_and(|max| other_rings >= max)
+        {
+            IncomingCallDecision::Busy
+        } else if self.allowed_users.contains(sender) {
+            IncomingCallDecision::Allow
+        } else if self.contacts_only && !self.contact_users.contains(sender) {
+            IncomingCallDecision::NeedPermission
+        } else if self.is_quiet() {
+            IncomingCallDecision::Decline
+        } else {
+            IncomingCallDecision::Allow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn at(hour: u64, minute: u64) -> SystemTime {
+        // 2024-01-01T00:00:00Z
+        UNIX_EPOCH + Duration::from_secs(1_704_067_200 + hour * 3600 + minute * 60)
+    }
+
+    #[test]
+    fn quiet_hours_within_a_day() {
+        let quiet_hours = QuietHours {
+            start_minute: 9 * 60,
+            end_minute: 17 * 60,
+            utc_offset_minutes: 0,
+        };
+        assert!(!quiet_hours.contains(at(8, 59)));
+        assert!(quiet_hours.contains(at(9, 0)));
+        assert!(quiet_hours.contains(at(16, 59)));
+        assert!(!quiet_hours.contains(at(17, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_past_midnight() {
+        let quiet_hours = QuietHours {
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+            utc_offset_minutes: 0,
+        };
+        assert!(quiet_hours.contains(at(23, 30)));
+        assert!(quiet_hours.contains(at(0, 0)));
+        assert!(quiet_hours.contains(at(6, 59)));
+        assert!(!quiet_hours.contains(at(7, 0)));
+        assert!(!quiet_hours.contains(at(21, 59)));
+    }
+
+    #[test]
+    fn quiet_hours_with_utc_offset() {
+        // 22:00-07:00 at UTC-5 is 03:00-12:00 UTC.
+        let quiet_hours = QuietHours {
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+            utc_offset_minutes: -5 * 60,
+        };
+        assert!(!quiet_hours.contains(at(2, 59)));
+        assert!(quiet_hours.contains(at(3, 0)));
+        assert!(quiet_hours.contains(at(11, 59)));
+        assert!(!quiet_hours.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_all_day() {
+        let quiet_hours = QuietHours {
+            start_minute: 0,
+            end_minute: 0,
+            utc_offset_minutes: 0,
+        };
+        assert!(quiet_hours.contains(at(0, 0)));
+        assert!(quiet_hours.contains(at(12, 0)));
+    }
+}
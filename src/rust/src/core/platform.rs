@@ -14,6 +14,7 @@ use crate::{
     },
     core::{
         call::Call,
+        call_record::{CallRecord, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         group_call::Reaction,
@@ -21,7 +22,7 @@ use crate::{
     },
     lite::{
         sfu,
-        sfu::{DemuxId, PeekInfo, UserId},
+        sfu::{DemuxId, PeekInfo, PeekResult, PeekWatchId, UserId},
     },
     webrtc::{
         media::{MediaStream, VideoTrack},
@@ -199,6 +200,36 @@ pub trait Platform: sfu::Delegate + fmt::Debug + fmt::Display + Send + Sized + '
     /// Notify the application that the call is completely concluded
     fn on_call_concluded(&self, remote_peer: &Self::AppRemotePeer, call_id: CallId) -> Result<()>;
 
+    /// Notify the application of how a 1:1 call went, for its call history.
+    /// Sent once per call, just before `on_call_concluded()`.
+    fn on_call_record(&self, remote_peer: &Self::AppRemotePeer, record: CallRecord) -> Result<()>;
+
+    /// Notify the application of how a ring for a group call went, for its
+    /// call history. Sent once the ring is over, whether or not it was
+    /// accepted.
+    fn on_group_ring_record(&self, record: GroupRingRecord) -> Result<()>;
+
+    /// Notify the application that the remote peer wants to continue the
+    /// call as a group call. To go along, the application should create a
+    /// group call or call link client for the invitation and pass it to
+    /// `CallManager::escalate_to_group_call()`.
+    fn on_group_call_invitation(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        _call_id: CallId,
+        _invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notify the application that another of the user's devices wants to
+    /// hand over its call with `remote_user_id`. To take the call, the
+    /// application should start an outgoing call to that user with the same
+    /// `call_id`.
+    fn on_call_transfer_offered(&self, _remote_user_id: UserId, _call_id: CallId) -> Result<()> {
+        Ok(())
+    }
+
     /// Return true if you want a CallManager to always assume you called
     /// message_sent() for every signaling message.
     fn assume_messages_sent(&self) -> bool {
@@ -279,7 +310,32 @@ pub trait Platform: sfu::Delegate + fmt::Debug + fmt::Display + Send + Sized + '
 
     fn handle_raised_hands(&self, client_id: group_call::ClientId, raised_hands: Vec<DemuxId>);
 
+    fn handle_presenter_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _current_presenter: Option<DemuxId>,
+    ) {
+    }
+
     fn handle_rtc_stats_report(&self, _report_json: String) {}
 
+    fn handle_admin_action_results(
+        &self,
+        _client_id: group_call::ClientId,
+        _results: Vec<group_call::AdminActionResult>,
+    ) {
+    }
+
+    fn handle_call_summary(
+        &self,
+        _client_id: group_call::ClientId,
+        _summary: group_call::CallSummary,
+    ) {
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason);
+
+    /// Called whenever the peek result of a call watched via CallManager::watch_peek changes,
+    /// including the first time it's peeked.
+    fn handle_watched_peek_changed(&self, _id: PeekWatchId, _peek_result: PeekResult) {}
 }
@@ -15,7 +15,7 @@ use bytes::{Bytes, BytesMut};
 use prost::Message as _;
 
 use crate::{
-    common::{CallMediaType, DeviceId, Result},
+    common::{CallId, CallMediaType, DeviceId, Result},
     protobuf,
 };
 
@@ -269,6 +269,7 @@ impl IceCandidate {
         let ice_candidate_proto = protobuf::signaling::IceCandidate {
             added_v3: Some(ice_candidate_proto_v3),
             removed: None,
+            restart: None,
         };
 
         let mut opaque = Vec::with_capacity(ice_candidate_proto.encoded_len());
@@ -280,6 +281,7 @@ impl IceCandidate {
     pub fn from_removed_address(removed_address: SocketAddr) -> Result<Self> {
         let ice_candidate_proto = protobuf::signaling::IceCandidate {
             removed: Some(removed_address.into()),
+            restart: None,
             // Old clients blow up if they don't find an added candidate,
             // so we need to put something here.
             // It must pass WebRTC's ParseCandidate, VerifyCandidate,
@@ -314,6 +316,27 @@ impl IceCandidate {
         Ok(Self::new(opaque))
     }
 
+    pub fn from_ice_restart(restart: IceRestart) -> Result<Self> {
+        let (ice_ufrag, ice_pwd) = restart.credentials.unzip();
+        let ice_candidate_proto = protobuf::signaling::IceCandidate {
+            restart: Some(protobuf::signaling::IceRestart {
+                ice_ufrag,
+                ice_pwd,
+                generation: Some(restart.generation),
+            }),
+            removed: None,
+            // See from_removed_address for why old clients need this.
+            added_v3: Some(protobuf::signaling::IceCandidateV3 {
+                sdp: Some("candidate:FAKE 1 tcp 0 127.0.0.1 0 typ host".to_owned()),
+            }),
+        };
+
+        let mut opaque = Vec::with_capacity(ice_candidate_proto.encoded_len());
+        ice_candidate_proto.encode(&mut opaque)?;
+
+        Ok(Self::new(opaque))
+    }
+
     // ICE candidates are the same for V2 and V3 and V4.
     pub fn v3_sdp(&self) -> Option<String> {
         match protobuf::signaling::IceCandidate::decode(Bytes::from(self.opaque.clone())).ok()? {
@@ -335,11 +358,39 @@ impl IceCandidate {
         }
     }
 
+    pub fn ice_restart(&self) -> Option<IceRestart> {
+        match protobuf::signaling::IceCandidate::decode(Bytes::from(self.opaque.clone())).ok()? {
+            protobuf::signaling::IceCandidate {
+                restart:
+                    Some(protobuf::signaling::IceRestart {
+                        ice_ufrag,
+                        ice_pwd,
+                        generation: Some(generation),
+                    }),
+                ..
+            } => Some(IceRestart {
+                generation,
+                credentials: ice_ufrag.zip(ice_pwd),
+            }),
+            _ => None,
+        }
+    }
+
     pub fn to_info_string(&self) -> String {
         format!("opaque.len={}", self.opaque.len())
     }
 }
 
+/// Sent in an Ice message to restart ICE on an established connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IceRestart {
+    /// Increases with every restart so replies can be matched up.
+    pub generation: u32,
+    /// The sender's new (ufrag, pwd), or None if the sender is only
+    /// asking the offerer to restart.
+    pub credentials: Option<(String, String)>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Hangup {
     Normal, // on this device
@@ -486,4 +537,56 @@ pub struct SenderStatus {
     pub video_enabled: Option<bool>,
     pub sharing_screen: Option<bool>,
     pub audio_enabled: Option<bool>,
+    pub on_hold: Option<bool>,
+}
+
+/// An invitation to continue a 1:1 call as a group call, sent via RTP data.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum GroupCallInvitation {
+    /// A Signal group call.
+    Group { group_id: Vec<u8> },
+    /// A call link call. The root key is needed to join, so it's sent along.
+    CallLink {
+        root_key: Vec<u8>,
+        epoch: Option<u32>,
+    },
+}
+
+impl GroupCallInvitation {
+    pub fn to_proto(&self, call_id: CallId) -> protobuf::rtp_data::GroupCallInvitation {
+        let mut proto = protobuf::rtp_data::GroupCallInvitation {
+            id: Some(u64::from(call_id)),
+            ..Default::default()
+        };
+        match self {
+            Self::Group { group_id } => proto.group_id = Some(group_id.clone()),
+            Self::CallLink { root_key, epoch } => {
+                proto.call_link_root_key = Some(root_key.clone());
+                proto.call_link_epoch = *epoch;
+            }
+        }
+        proto
+    }
+
+    /// Returns None if the invitation doesn't say which call to join.
+    pub fn from_proto(proto: protobuf::rtp_data::GroupCallInvitation) -> Option<Self> {
+        match (proto.group_id, proto.call_link_root_key) {
+            (_, Some(root_key)) => Some(Self::CallLink {
+                root_key,
+                epoch: proto.call_link_epoch,
+            }),
+            (Some(group_id), None) => Some(Self::Group { group_id }),
+            (None, None) => None,
+        }
+    }
+}
+
+// Not derived so that the call link root key doesn't end up in the logs.
+impl fmt::Debug for GroupCallInvitation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Group { .. } => write!(f, "Group"),
+            Self::CallLink { epoch, .. } => write!(f, "CallLink(epoch: {:?})", epoch),
+        }
+    }
 }
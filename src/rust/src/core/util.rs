@@ -12,6 +12,8 @@ use std::{
     sync::{mpsc::Receiver, Arc, Condvar, Mutex},
 };
 
+use rand::Rng;
+
 use crate::{common::Result, error::RingRtcError};
 
 /// Generic Mutex/Condvar pair for signaling async event completion.
@@ -277,6 +279,15 @@ pub fn uuid_to_string(bytes: &[u8]) -> String {
     }
 }
 
+/// Generates a random string of ASCII letters and digits, such as an ICE ufrag or pwd.
+pub fn random_alphanumeric(len: usize) -> String {
+    std::iter::repeat(())
+        .map(|()| rand::rngs::OsRng.sample(rand::distributions::Alphanumeric))
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
 // Allows using `?` syntax in a scope and collecting failures in a `Result`.
 pub fn try_scoped<T>(call: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
     call()
@@ -22,18 +22,20 @@ use neon::{
 };
 
 use crate::{
-    common::{CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result},
+    common::{CallConfig, CallDirection, CallId, CallMediaType, DataMode, DeviceId, Result},
     core::{
         call_manager::CallManager,
+        call_record::{self, CallRecord, GroupRingRecord},
         group_call,
         group_call::{GroupId, SignalingMessageUrgency},
+        incoming_call_policy::{DoNotDisturb, IncomingCallPolicy, QuietHours},
         signaling,
         util::minmax,
     },
     lite::{
         call_links::{
-            self, CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkState,
-            CallLinkUpdateRequest, Empty,
+            self, CallLinkDeleteRequest, CallLinkEpoch, CallLinkRestrictions, CallLinkRootKey,
+            CallLinkState, CallLinkUpdateRequest, CallLinkUrl, Empty,
         },
         http, sfu,
         sfu::{DemuxId, GroupMember, PeekInfo, UserId},
@@ -159,6 +161,18 @@ pub enum Event {
     // Whether the remote is sharing its screen or not changed.
     // Like call state, we ID the call by PeerId and assume there is only one.
     RemoteSharingScreenChange(PeerId, bool),
+    // Whether the remote has put the call on hold or not changed.
+    // Like call state, we ID the call by PeerId and assume there is only one.
+    RemoteHoldChange(PeerId, bool),
+    // An incoming call arrived while another call is active.
+    // It's started right after this like any other incoming call.
+    CallWaiting(PeerId, CallId),
+    // The remote peer wants to continue the call as a group call.
+    GroupCallInvitation(PeerId, CallId, signaling::GroupCallInvitation),
+    // Another of the user's devices wants to hand over its call with the given user.
+    CallTransferOffered(UserId, CallId),
+    // How a call went, sent once it has concluded.
+    CallRecord(PeerId, CallRecord),
     // The group call has an update.
     GroupUpdate(GroupUpdate),
     // A call link request has completed.
@@ -306,6 +320,35 @@ impl CallStateHandler for EventReporter {
         ))
     }
 
+    fn handle_remote_hold(&self, remote_peer_id: &str, on_hold: bool) -> Result<()> {
+        self.send(Event::RemoteHoldChange(remote_peer_id.to_string(), on_hold))
+    }
+
+    fn handle_call_waiting(&self, remote_peer_id: &str, call_id: CallId) -> Result<()> {
+        self.send(Event::CallWaiting(remote_peer_id.to_string(), call_id))
+    }
+
+    fn handle_group_call_invitation(
+        &self,
+        remote_peer_id: &str,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        self.send(Event::GroupCallInvitation(
+            remote_peer_id.to_string(),
+            call_id,
+            invitation.clone(),
+        ))
+    }
+
+    fn handle_call_transfer_offered(&self, remote_user_id: &[u8], call_id: CallId) -> Result<()> {
+        self.send(Event::CallTransferOffered(remote_user_id.to_vec(), call_id))
+    }
+
+    fn handle_call_record(&self, remote_peer_id: &str, record: CallRecord) -> Result<()> {
+        self.send(Event::CallRecord(remote_peer_id.to_string(), record))
+    }
+
     fn handle_audio_levels(
         &self,
         remote_peer_id: &str,
@@ -635,6 +678,196 @@ fn to_js_peek_info<'a>(
     Ok(js_info)
 }
 
+fn to_js_remote_device_states<'a>(
+    cx: &mut FunctionContext<'a>,
+    remote_device_states: &[group_call::RemoteDeviceState],
+) -> JsResult<'a, JsArray> {
+    let js_remote_device_states = JsArray::new(cx, remote_device_states.len());
+    for (i, remote_device_state) in remote_device_states.iter().enumerate() {
+        let demux_id = cx.number(remote_device_state.demux_id);
+        let user_id = to_js_buffer(cx, &remote_device_state.user_id);
+        let media_keys_received = cx.boolean(remote_device_state.media_keys_received);
+        let audio_muted: neon::handle::Handle<JsValue> =
+            match remote_device_state.heartbeat_state.audio_muted {
+                None => cx.undefined().upcast(),
+                Some(muted) => cx.boolean(muted).upcast(),
+            };
+        let video_muted: neon::handle::Handle<JsValue> =
+            match remote_device_state.heartbeat_state.video_muted {
+                None => cx.undefined().upcast(),
+                Some(muted) => cx.boolean(muted).upcast(),
+            };
+        let presenting: neon::handle::Handle<JsValue> =
+            match remote_device_state.heartbeat_state.presenting {
+                None => cx.undefined().upcast(),
+                Some(muted) => cx.boolean(muted).upcast(),
+            };
+        let sharing_screen: neon::handle::Handle<JsValue> =
+            match remote_device_state.heartbeat_state.sharing_screen {
+                None => cx.undefined().upcast(),
+                Some(muted) => cx.boolean(muted).upcast(),
+            };
+        // These are strings because we can't safely convert a u64 to a JavaScript-compatible number. We'll convert them to numeric types on the other side.
+        let added_time: neon::handle::Handle<JsValue> = cx
+            .string(remote_device_state.added_time_as_unix_millis().to_string())
+            .upcast();
+        let speaker_time: neon::handle::Handle<JsValue> = cx
+            .string(
+                remote_device_state
+                    .speaker_time_as_unix_millis()
+                    .to_string(),
+            )
+            .upcast();
+        let forwarding_video: neon::handle::Handle<JsValue> =
+            match remote_device_state.forwarding_video {
+                None => cx.undefined().upcast(),
+                Some(forwarding_video) => cx.boolean(forwarding_video).upcast(),
+            };
+        let is_higher_resolution_pending =
+            cx.boolean(remote_device_state.is_higher_resolution_pending);
+        let connection_quality: neon::handle::Handle<JsValue> =
+            match remote_device_state.connection_quality {
+                None => cx.undefined().upcast(),
+                Some(connection_quality) => cx.number(connection_quality.ordinal()).upcast(),
+            };
+
+        let js_remote_device_state = cx.empty_object();
+        js_remote_device_state.set(cx, "demuxId", demux_id)?;
+        js_remote_device_state.set(cx, "userId", user_id)?;
+        js_remote_device_state.set(cx, "mediaKeysReceived", media_keys_received)?;
+        js_remote_device_state.set(cx, "audioMuted", audio_muted)?;
+        js_remote_device_state.set(cx, "videoMuted", video_muted)?;
+        js_remote_device_state.set(cx, "presenting", presenting)?;
+        js_remote_device_state.set(cx, "sharingScreen", sharing_screen)?;
+        js_remote_device_state.set(cx, "addedTime", added_time)?;
+        js_remote_device_state.set(cx, "speakerTime", speaker_time)?;
+        js_remote_device_state.set(cx, "forwardingVideo", forwarding_video)?;
+        js_remote_device_state.set(
+            cx,
+            "isHigherResolutionPending",
+            is_higher_resolution_pending,
+        )?;
+        js_remote_device_state.set(cx, "connectionQuality", connection_quality)?;
+        let is_unresponsive = cx.boolean(remote_device_state.is_unresponsive);
+        js_remote_device_state.set(cx, "isUnresponsive", is_unresponsive)?;
+
+        js_remote_device_states.set(cx, i as u32, js_remote_device_state)?;
+    }
+    Ok(js_remote_device_states)
+}
+
+fn to_js_call_summary<'a>(
+    cx: &mut FunctionContext<'a>,
+    summary: &group_call::CallSummary,
+) -> JsResult<'a, JsObject> {
+    let js_summary = cx.empty_object();
+    let call_duration = cx.number(summary.call_duration.as_millis() as f64);
+    js_summary.set(cx, "callDurationMillis", call_duration)?;
+    let join_duration = cx.number(summary.join_duration.as_millis() as f64);
+    js_summary.set(cx, "joinDurationMillis", join_duration)?;
+    let peak_device_count = cx.number(summary.peak_device_count as u32);
+    js_summary.set(cx, "peakDeviceCount", peak_device_count)?;
+    let unique_user_count = cx.number(summary.unique_user_count as u32);
+    js_summary.set(cx, "uniqueUserCount", unique_user_count)?;
+
+    let js_speaking_times = JsArray::new(cx, summary.speaking_time.len());
+    for (i, (user_id, speaking_time)) in summary.speaking_time.iter().enumerate() {
+        let js_speaking_time = cx.empty_object();
+        let user_id = to_js_buffer(cx, user_id);
+        js_speaking_time.set(cx, "userId", user_id)?;
+        let millis = cx.number(speaking_time.as_millis() as f64);
+        js_speaking_time.set(cx, "speakingTimeMillis", millis)?;
+        js_speaking_times.set(cx, i as u32, js_speaking_time)?;
+    }
+    js_summary.set(cx, "speakingTimes", js_speaking_times)?;
+
+    let reactions_sent = cx.number(summary.reactions_sent);
+    js_summary.set(cx, "reactionsSent", reactions_sent)?;
+    let reactions_received = cx.number(summary.reactions_received);
+    js_summary.set(cx, "reactionsReceived", reactions_received)?;
+    let raised_hand_count = cx.number(summary.raised_hand_count);
+    js_summary.set(cx, "raisedHandCount", raised_hand_count)?;
+    let average_send_rate = cx.number(summary.media.average_send_rate.as_bps() as f64);
+    js_summary.set(cx, "averageSendBps", average_send_rate)?;
+    let average_receive_rate = cx.number(summary.media.average_receive_rate.as_bps() as f64);
+    js_summary.set(cx, "averageReceiveBps", average_receive_rate)?;
+    let send_packets_lost_pct = cx.number(summary.media.send_packets_lost_pct);
+    js_summary.set(cx, "sendPacketsLostPct", send_packets_lost_pct)?;
+    let receive_packets_lost_pct = cx.number(summary.media.receive_packets_lost_pct);
+    js_summary.set(cx, "receivePacketsLostPct", receive_packets_lost_pct)?;
+    let reconnect_count = cx.number(summary.reconnect_count);
+    js_summary.set(cx, "reconnectCount", reconnect_count)?;
+    Ok(js_summary)
+}
+
+fn to_js_call_record<'a>(
+    cx: &mut FunctionContext<'a>,
+    record: &CallRecord,
+) -> JsResult<'a, JsObject> {
+    let js_record = cx.empty_object();
+    let call_id = create_id_arg(cx, record.call_id.as_u64());
+    js_record.set(cx, "callId", call_id)?;
+    let is_incoming = cx.boolean(record.direction == CallDirection::Incoming);
+    js_record.set(cx, "isIncoming", is_incoming)?;
+    let is_video_call = cx.boolean(record.media_type == CallMediaType::Video);
+    js_record.set(cx, "isVideoCall", is_video_call)?;
+    let started = cx.number(call_record::millis_since_epoch(record.started) as f64);
+    js_record.set(cx, "startedMillis", started)?;
+    if let Some(answered) = record.answered {
+        let answered = cx.number(call_record::millis_since_epoch(answered) as f64);
+        js_record.set(cx, "answeredMillis", answered)?;
+    }
+    let ended = cx.number(call_record::millis_since_epoch(record.ended) as f64);
+    js_record.set(cx, "endedMillis", ended)?;
+    let outcome = cx.string(format!("{:?}", record.outcome));
+    js_record.set(cx, "outcome", outcome)?;
+    if let Some(device_id) = record.answered_by_device_id {
+        let device_id = cx.number(device_id);
+        js_record.set(cx, "answeredByDeviceId", device_id)?;
+    }
+
+    let js_setup = cx.empty_object();
+    let names = [
+        "offerAgeMillis",
+        "offerSentMillis",
+        "answerSentMillis",
+        "answerReceivedMillis",
+        "firstIceCandidateSentMillis",
+        "firstIceCandidateReceivedMillis",
+        "iceConnectedMillis",
+        "acceptedMillis",
+        "firstMediaMillis",
+    ];
+    for (name, offset) in names.iter().zip(record.setup.offsets()) {
+        if let Some(offset) = offset {
+            let millis = cx.number(offset.as_millis() as f64);
+            js_setup.set(cx, *name, millis)?;
+        }
+    }
+    js_record.set(cx, "setup", js_setup)?;
+    Ok(js_record)
+}
+
+fn to_js_group_ring_record<'a>(
+    cx: &mut FunctionContext<'a>,
+    record: &GroupRingRecord,
+) -> JsResult<'a, JsObject> {
+    let js_record = cx.empty_object();
+    let group_id = to_js_buffer(cx, &record.group_id);
+    js_record.set(cx, "groupId", group_id)?;
+    let ring_id = JsBigInt::from_i64(cx, record.ring_id.into());
+    js_record.set(cx, "ringId", ring_id)?;
+    let sender_id = to_js_buffer(cx, &record.sender_id);
+    js_record.set(cx, "senderId", sender_id)?;
+    let received = cx.number(call_record::millis_since_epoch(record.received) as f64);
+    js_record.set(cx, "receivedMillis", received)?;
+    let ended = cx.number(call_record::millis_since_epoch(record.ended) as f64);
+    js_record.set(cx, "endedMillis", ended)?;
+    let outcome = cx.string(format!("{:?}", record.outcome));
+    js_record.set(cx, "outcome", outcome)?;
+    Ok(js_record)
+}
+
 fn to_js_call_link_state<'a>(
     cx: &mut FunctionContext<'a>,
     state: Option<&CallLinkState>,
@@ -662,6 +895,21 @@ fn to_js_call_link_state<'a>(
                 )
                 .or_else(|e| cx.throw_range_error(e.to_string()))?;
             state_object.set(cx, "expiration", js_expiration)?;
+            let js_description = cx.string(&state.description);
+            state_object.set(cx, "description", js_description)?;
+            let js_scheduled_start = match state.scheduled_start {
+                Some(scheduled_start) => cx
+                    .date(
+                        scheduled_start
+                            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_millis() as f64,
+                    )
+                    .or_else(|e| cx.throw_range_error(e.to_string()))?
+                    .upcast(),
+                None => cx.undefined().upcast::<JsValue>(),
+            };
+            state_object.set(cx, "scheduledStart", js_scheduled_start)?;
             Ok(state_object.upcast())
         }
         None => Ok(cx.undefined().upcast()),
@@ -913,6 +1161,154 @@ fn hangup(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn setOnHold(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let on_hold = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+    debug!("JsCallManager.setOnHold({})", on_hold);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.set_on_hold(on_hold)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn escalateToGroupCall(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    debug!("JsCallManager.escalateToGroupCall({})", client_id);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.escalate_to_group_call(client_id)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn transferCall(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let remote_user_id = cx.argument::<JsBuffer>(0)?;
+    let remote_user_id = remote_user_id.as_slice(&cx).to_vec();
+    let target_device_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as DeviceId;
+    debug!("JsCallManager.transferCall({})", target_device_id);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .transfer_call(remote_user_id, target_device_id)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn acceptCallTransfer(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let peer_id = cx.argument::<JsString>(0)?.value(&mut cx) as PeerId;
+    let call_id = CallId::new(get_id_arg(&mut cx, 1));
+    let video_enabled = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let local_device_id = cx.argument::<JsNumber>(3)?.value(&mut cx) as DeviceId;
+
+    let media_type = if video_enabled {
+        CallMediaType::Video
+    } else {
+        CallMediaType::Audio
+    };
+
+    debug!(
+        "JsCallManager.acceptCallTransfer({}, {}, {}, {})",
+        peer_id, call_id, media_type, local_device_id
+    );
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.create_outgoing_call(
+            peer_id,
+            call_id,
+            media_type,
+            local_device_id,
+        )?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn setDoNotDisturb(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let enabled = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+    debug!("JsCallManager.setDoNotDisturb({})", enabled);
+
+    let policy: Option<Box<dyn IncomingCallPolicy<NativePlatform>>> = if enabled {
+        let quiet_hours = match (
+            cx.argument::<JsValue>(1)?.downcast::<JsNumber, _>(&mut cx),
+            cx.argument::<JsValue>(2)?.downcast::<JsNumber, _>(&mut cx),
+            cx.argument::<JsValue>(3)?.downcast::<JsNumber, _>(&mut cx),
+        ) {
+            (Ok(start_minute), Ok(end_minute), Ok(utc_offset_minutes)) => Some(QuietHours {
+                start_minute: start_minute.value(&mut cx) as u16,
+                end_minute: end_minute.value(&mut cx) as u16,
+                utc_offset_minutes: utc_offset_minutes.value(&mut cx) as i32,
+            }),
+            _ => None,
+        };
+        let max_concurrent_group_rings = cx
+            .argument::<JsValue>(4)?
+            .downcast::<JsNumber, _>(&mut cx)
+            .ok()
+            .map(|max| max.value(&mut cx) as usize);
+        let allowed_users = user_ids_from_js_array(&mut cx, 5)?;
+        let js_allowed_peers = cx.argument::<JsArray>(6)?;
+        let mut allowed_peers = Vec::with_capacity(js_allowed_peers.len(&mut cx) as usize);
+        for i in 0..js_allowed_peers.len(&mut cx) {
+            let js_peer_id = js_allowed_peers.get::<JsString, _, _>(&mut cx, i)?;
+            allowed_peers.push(js_peer_id.value(&mut cx) as PeerId);
+        }
+        Some(Box::new(DoNotDisturb {
+            allowed_peers,
+            allowed_users: allowed_users.into_iter().collect(),
+            quiet_hours,
+            max_concurrent_group_rings,
+            ..Default::default()
+        }))
+    } else {
+        None
+    };
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.set_incoming_call_policy(policy)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn setCallWaitingEnabled(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let enabled = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+    debug!("JsCallManager.setCallWaitingEnabled({})", enabled);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.set_call_waiting_enabled(enabled)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn swapCalls(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.swapCalls()");
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.swap_calls()?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn signalingMessageSent(mut cx: FunctionContext) -> JsResult<JsValue> {
     let call_id = CallId::new(get_id_arg(&mut cx, 0));
@@ -1153,8 +1549,26 @@ fn receivedHttpResponse(mut cx: FunctionContext) -> JsResult<JsValue> {
     let status_code = cx.argument::<JsNumber>(1)?.value(&mut cx) as u16;
     let body = cx.argument::<JsBuffer>(2)?;
     let body = body.as_slice(&cx).to_vec();
+    let mut headers = HashMap::new();
+    if let Some(js_headers) = cx.argument_opt(3) {
+        if let Ok(js_headers) = js_headers.downcast::<JsObject, _>(&mut cx) {
+            for js_name in js_headers
+                .get_own_property_names(&mut cx)?
+                .to_vec(&mut cx)?
+            {
+                let name = js_name
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx);
+                let value = js_headers
+                    .get::<JsString, _, _>(&mut cx, name.as_str())?
+                    .value(&mut cx);
+                headers.insert(name, value);
+            }
+        }
+    }
     let response = http::Response {
         status: status_code.into(),
+        headers,
         body,
     };
 
@@ -1411,9 +1825,7 @@ fn createCallLinkCallClient(mut cx: FunctionContext) -> JsResult<JsValue> {
     let auth_presentation = cx.argument::<JsBuffer>(1)?;
     let auth_presentation = auth_presentation.as_slice(&cx).to_vec();
 
-    let root_key_bytes = cx.argument::<JsBuffer>(2)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 2, 6)?;
 
     let admin_passkey = cx.argument::<JsValue>(3)?;
     let admin_passkey = if admin_passkey.is_a::<JsUndefined, _>(&mut cx) {
@@ -1656,6 +2068,21 @@ fn groupRaiseHand(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn groupRequestToPresent(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let take_over = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .request_to_present(client_id, take_over);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn resendMediaKeys(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1754,6 +2181,66 @@ fn denyUser(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+fn user_ids_from_js_array(cx: &mut FunctionContext, index: usize) -> NeonResult<Vec<UserId>> {
+    let js_user_ids = cx.argument::<JsArray>(index)?;
+    let mut user_ids = Vec::with_capacity(js_user_ids.len(cx) as usize);
+    for i in 0..js_user_ids.len(cx) {
+        let js_user_id = js_user_ids.get::<JsBuffer, _, _>(cx, i)?;
+        user_ids.push(js_user_id.as_slice(cx).to_vec());
+    }
+    Ok(user_ids)
+}
+
+#[allow(non_snake_case)]
+fn approveUsers(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let user_ids = user_ids_from_js_array(&mut cx, 1)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.approve_users(client_id, user_ids);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn denyUsers(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let user_ids = user_ids_from_js_array(&mut cx, 1)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.deny_users(client_id, user_ids);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn approveAllPendingUsers(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.approve_all_pending_users(client_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn denyAllPendingUsers(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.deny_all_pending_users(client_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn removeClient(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1846,17 +2333,33 @@ fn peekGroupCall(mut cx: FunctionContext) -> JsResult<JsValue> {
     let membership_proof = cx.argument::<JsBuffer>(2)?;
     let membership_proof = membership_proof.as_slice(&cx).to_vec();
 
-    let js_members = cx.argument::<JsArray>(3)?;
-    let mut members = Vec::with_capacity(js_members.len(&mut cx) as usize);
-    for i in 0..js_members.len(&mut cx) {
-        let js_member = js_members.get::<JsObject, _, _>(&mut cx, i)?;
+    let members = group_members_from_js_args(&mut cx, 3)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .peek_group_call(request_id, sfu_url, membership_proof, members);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+fn group_members_from_js_args(
+    cx: &mut FunctionContext,
+    members_index: usize,
+) -> NeonResult<Vec<GroupMember>> {
+    let js_members = cx.argument::<JsArray>(members_index)?;
+    let mut members = Vec::with_capacity(js_members.len(cx) as usize);
+    for i in 0..js_members.len(cx) {
+        let js_member = js_members.get::<JsObject, _, _>(cx, i)?;
         let user_id = js_member
-            .get_opt::<JsBuffer, _, _>(&mut cx, "userId")?
-            .map(|handle| handle.as_slice(&cx).to_vec());
+            .get_opt::<JsBuffer, _, _>(cx, "userId")?
+            .map(|handle| handle.as_slice(cx).to_vec());
 
         let member_id = js_member
-            .get_opt::<JsBuffer, _, _>(&mut cx, "userIdCipherText")?
-            .map(|handle| handle.as_slice(&cx).to_vec());
+            .get_opt::<JsBuffer, _, _>(cx, "userIdCipherText")?
+            .map(|handle| handle.as_slice(cx).to_vec());
 
         match (user_id, member_id) {
             (Some(user_id), Some(member_id)) => {
@@ -1867,17 +2370,58 @@ fn peekGroupCall(mut cx: FunctionContext) -> JsResult<JsValue> {
             }
         };
     }
+    Ok(members)
+}
+
+#[allow(non_snake_case)]
+fn watchGroupCallPeek(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let group_id = cx.argument::<JsBuffer>(0)?.as_slice(&cx).to_vec();
+    let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
+    let membership_proof = cx.argument::<JsBuffer>(2)?.as_slice(&cx).to_vec();
+    let members = group_members_from_js_args(&mut cx, 3)?;
 
     with_call_endpoint(&mut cx, |endpoint| {
         endpoint
             .call_manager
-            .peek_group_call(request_id, sfu_url, membership_proof, members);
-        Ok(())
+            .watch_group_call_peek(group_id, sfu_url, membership_proof, members)
     })
     .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn watchCallLinkPeek(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let sfu_url = cx.argument::<JsString>(0)?.value(&mut cx);
+    let auth_presentation = cx.argument::<JsBuffer>(1)?.as_slice(&cx).to_vec();
+    let root_key = root_key_from_js_args(&mut cx, 2, 3)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .watch_call_link_peek(sfu_url, &auth_presentation, root_key)
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn unwatchPeek(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let id = cx.argument::<JsBuffer>(0)?.as_slice(&cx).to_vec();
+
+    with_call_endpoint(&mut cx, |endpoint| endpoint.call_manager.unwatch_peek(&id))
+        .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn refreshPeek(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let id = cx.argument::<JsBuffer>(0)?.as_slice(&cx).to_vec();
+
+    with_call_endpoint(&mut cx, |endpoint| endpoint.call_manager.refresh_peek(&id))
+        .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn peekCallLinkCall(mut cx: FunctionContext) -> JsResult<JsValue> {
     let request_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
@@ -1887,9 +2431,7 @@ fn peekCallLinkCall(mut cx: FunctionContext) -> JsResult<JsValue> {
     let auth_presentation = cx.argument::<JsBuffer>(2)?;
     let auth_presentation = auth_presentation.as_slice(&cx).to_vec();
 
-    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 3, 4)?;
 
     with_call_endpoint(&mut cx, |endpoint| {
         let event_reporter = endpoint.event_reporter.clone();
@@ -1918,9 +2460,7 @@ fn readCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
     let auth_presentation = cx.argument::<JsBuffer>(2)?;
     let auth_presentation = auth_presentation.as_slice(&cx).to_vec();
-    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 3, 4)?;
 
     with_call_endpoint(&mut cx, |endpoint| {
         let event_reporter = endpoint.event_reporter.clone();
@@ -1940,6 +2480,24 @@ fn readCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+/// Reads a call link root key, along with its epoch from an optional later argument.
+fn root_key_from_js_args(
+    cx: &mut FunctionContext,
+    key_index: usize,
+    epoch_index: usize,
+) -> NeonResult<CallLinkRootKey> {
+    let root_key_bytes = cx.argument::<JsBuffer>(key_index)?;
+    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(cx))
+        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let epoch = match cx.argument_opt(epoch_index) {
+        Some(epoch) if !epoch.is_a::<JsUndefined, _>(cx) => Some(CallLinkEpoch::from(
+            epoch.downcast_or_throw::<JsNumber, _>(cx)?.value(cx) as u32,
+        )),
+        _ => None,
+    };
+    Ok(root_key.with_epoch(epoch))
+}
+
 fn jsvalue_to_restrictions(
     raw_restrictions: Handle<'_, JsValue>,
     cx: &mut FunctionContext,
@@ -1964,9 +2522,7 @@ fn createCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
     let create_presentation = cx.argument::<JsBuffer>(2)?;
     let create_presentation = create_presentation.as_slice(&cx).to_vec();
-    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 3, 7)?;
     let admin_passkey = cx.argument::<JsBuffer>(4)?;
     let admin_passkey = admin_passkey.as_slice(&cx).to_vec();
     let public_zkparams = cx.argument::<JsBuffer>(5)?;
@@ -2001,9 +2557,7 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
     let create_presentation = cx.argument::<JsBuffer>(2)?;
     let create_presentation = create_presentation.as_slice(&cx).to_vec();
-    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 3, 8)?;
     let admin_passkey = cx.argument::<JsBuffer>(4)?;
     let admin_passkey = admin_passkey.as_slice(&cx).to_vec();
 
@@ -2011,14 +2565,11 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let new_name = if new_name.is_a::<JsUndefined, _>(&mut cx) {
         None
     } else {
-        let name = new_name
-            .downcast_or_throw::<JsString, _>(&mut cx)?
-            .value(&mut cx);
-        Some(if name.is_empty() {
-            vec![]
-        } else {
-            root_key.encrypt(name.as_bytes(), rand::rngs::OsRng)
-        })
+        Some(
+            new_name
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx),
+        )
     };
 
     let new_restrictions = cx.argument::<JsValue>(6)?;
@@ -2035,8 +2586,60 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
         )
     };
 
+    let new_description = match cx.argument_opt(9) {
+        Some(description) if !description.is_a::<JsUndefined, _>(&mut cx) => Some(
+            description
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx),
+        ),
+        _ => None,
+    };
+
+    // undefined leaves the scheduled start alone; null clears it.
+    let new_scheduled_start = match cx.argument_opt(10) {
+        Some(scheduled_start) if scheduled_start.is_a::<JsNull, _>(&mut cx) => Some(
+            call_links::encrypt_scheduled_start(&root_key, None, rand::rngs::OsRng),
+        ),
+        Some(scheduled_start) if !scheduled_start.is_a::<JsUndefined, _>(&mut cx) => {
+            let millis = scheduled_start
+                .downcast_or_throw::<JsDate, _>(&mut cx)?
+                .value(&mut cx);
+            let scheduled_start =
+                std::time::SystemTime::UNIX_EPOCH + Duration::from_millis(millis.max(0.0) as u64);
+            Some(call_links::encrypt_scheduled_start(
+                &root_key,
+                Some(scheduled_start),
+                rand::rngs::OsRng,
+            ))
+        }
+        _ => None,
+    };
+
+    let encrypted_text = new_name
+        .map(|name| call_links::encrypt_name(&root_key, &name, rand::rngs::OsRng))
+        .transpose()
+        .and_then(|encrypted_name| {
+            let encrypted_description = new_description
+                .map(|description| {
+                    call_links::encrypt_description(&root_key, &description, rand::rngs::OsRng)
+                })
+                .transpose()?;
+            Ok((encrypted_name, encrypted_description))
+        });
+
     with_call_endpoint(&mut cx, |endpoint| {
         let event_reporter = endpoint.event_reporter.clone();
+        let (new_name, new_description) = match encrypted_text {
+            Ok(encrypted_text) => encrypted_text,
+            Err(e) => {
+                error!("updateCallLink: {}", e);
+                let _ = event_reporter.send(Event::CallLinkResponse {
+                    request_id,
+                    result: Err(http::ResponseStatus::INVALID_REQUEST),
+                });
+                return Ok(());
+            }
+        };
         call_links::update_call_link(
             endpoint.call_manager.http_client(),
             &sfu_url,
@@ -2047,6 +2650,8 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
                 encrypted_name: new_name.as_deref(),
                 restrictions: new_restrictions,
                 revoked: new_revoked,
+                encrypted_description: new_description.as_deref(),
+                encrypted_scheduled_start: new_scheduled_start.as_deref(),
             },
             Box::new(move |result| {
                 // Ignore errors, that can only mean we're shutting down.
@@ -2059,15 +2664,44 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn resetCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let request_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
+    let auth_presentation = cx.argument::<JsBuffer>(2)?;
+    let auth_presentation = auth_presentation.as_slice(&cx).to_vec();
+    let root_key = root_key_from_js_args(&mut cx, 3, 6)?;
+    let admin_passkey = cx.argument::<JsBuffer>(4)?;
+    let admin_passkey = admin_passkey.as_slice(&cx).to_vec();
+    let new_epoch = CallLinkEpoch::from(cx.argument::<JsNumber>(5)?.value(&mut cx) as u32);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        let event_reporter = endpoint.event_reporter.clone();
+        call_links::reset_call_link(
+            endpoint.call_manager.http_client(),
+            &sfu_url,
+            root_key,
+            new_epoch,
+            &auth_presentation,
+            &admin_passkey,
+            Box::new(move |result| {
+                // Ignore errors, that can only mean we're shutting down.
+                let _ = event_reporter.send(Event::CallLinkResponse { request_id, result });
+            }),
+        );
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn deleteCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let request_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
     let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
     let auth_presentation = cx.argument::<JsBuffer>(2)?;
     let auth_presentation = auth_presentation.as_slice(&cx).to_vec();
-    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
-    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
-        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let root_key = root_key_from_js_args(&mut cx, 3, 5)?;
     let admin_passkey = cx.argument::<JsBuffer>(4)?;
     let admin_passkey = admin_passkey.as_slice(&cx).to_vec();
 
@@ -2187,6 +2821,72 @@ fn setRtcStatsInterval(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn getGroupCallSnapshot(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+
+    let snapshot = with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.group_call_snapshot(client_id)
+    });
+    let snapshot = match snapshot {
+        Some(snapshot) => snapshot,
+        None => return Ok(cx.undefined().upcast()),
+    };
+
+    let js_snapshot = cx.empty_object();
+    let js_connection_state = cx.number(snapshot.connection_state.ordinal());
+    js_snapshot.set(&mut cx, "connectionState", js_connection_state)?;
+    let js_join_state = cx.number(snapshot.join_state.ordinal());
+    js_snapshot.set(&mut cx, "joinState", js_join_state)?;
+    let js_demux_id: Handle<JsValue> = match snapshot.join_state {
+        group_call::JoinState::Pending(demux_id) | group_call::JoinState::Joined(demux_id) => {
+            cx.number(demux_id).upcast()
+        }
+        _ => cx.null().upcast(),
+    };
+    js_snapshot.set(&mut cx, "demuxId", js_demux_id)?;
+
+    let heartbeat_state = &snapshot.outgoing_heartbeat_state;
+    let js_local_device_state = cx.empty_object();
+    for (key, value) in [
+        ("audioMuted", heartbeat_state.audio_muted),
+        ("videoMuted", heartbeat_state.video_muted),
+        ("presenting", heartbeat_state.presenting),
+        ("sharingScreen", heartbeat_state.sharing_screen),
+    ] {
+        let js_value: Handle<JsValue> = match value {
+            None => cx.undefined().upcast(),
+            Some(value) => cx.boolean(value).upcast(),
+        };
+        js_local_device_state.set(&mut cx, key, js_value)?;
+    }
+    js_snapshot.set(&mut cx, "localDeviceState", js_local_device_state)?;
+
+    let js_remote_device_states = to_js_remote_device_states(&mut cx, &snapshot.remote_devices)?;
+    js_snapshot.set(&mut cx, "remoteDeviceStates", js_remote_device_states)?;
+
+    let js_peek_info: Handle<JsValue> = match snapshot.peek_info {
+        Some(peek_info) => to_js_peek_info(&mut cx, peek_info)?.upcast(),
+        None => cx.undefined().upcast(),
+    };
+    js_snapshot.set(&mut cx, "peekInfo", js_peek_info)?;
+
+    let js_raised_hands = JsArray::new(&mut cx, snapshot.raised_hands.len());
+    for (i, demux_id) in snapshot.raised_hands.iter().enumerate() {
+        let js_demux_id = cx.number(*demux_id);
+        js_raised_hands.set(&mut cx, i as u32, js_demux_id)?;
+    }
+    js_snapshot.set(&mut cx, "raisedHands", js_raised_hands)?;
+
+    let js_current_presenter: Handle<JsValue> = match snapshot.current_presenter {
+        Some(demux_id) => cx.number(demux_id).upcast(),
+        None => cx.null().upcast(),
+    };
+    js_snapshot.set(&mut cx, "currentPresenter", js_current_presenter)?;
+
+    Ok(js_snapshot.upcast())
+}
+
 #[allow(non_snake_case)]
 fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
     let this = cx.this::<JsObject>()?;
@@ -2329,6 +3029,7 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                     EndReason::ReceivedOfferExpired { .. } => "ReceivedOfferExpired",
                     EndReason::ReceivedOfferWhileActive => "ReceivedOfferWhileActive",
                     EndReason::ReceivedOfferWithGlare => "ReceivedOfferWithGlare",
+                    EndReason::ReceivedOfferDeclinedByPolicy => "ReceivedOfferDeclinedByPolicy",
                     EndReason::SignalingFailure => "SignalingFailure",
                     EndReason::GlareFailure => "GlareFailure",
                     EndReason::ConnectionFailure => "ConnectionFailure",
@@ -2337,6 +3038,8 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                     EndReason::AcceptedOnAnotherDevice => "AcceptedOnAnotherDevice",
                     EndReason::DeclinedOnAnotherDevice => "DeclinedOnAnotherDevice",
                     EndReason::BusyOnAnotherDevice => "BusyOnAnotherDevice",
+                    EndReason::EscalatedToGroupCall => "EscalatedToGroupCall",
+                    EndReason::Transferred => "Transferred",
                 };
                 let age = match reason {
                     EndReason::ReceivedOfferExpired { age } => age,
@@ -2422,6 +3125,72 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 method.call(&mut cx, observer, args)?;
             }
 
+            Event::RemoteHoldChange(peer_id, on_hold) => {
+                let method_name = "onRemoteHold";
+                let args = [cx.string(peer_id).upcast(), cx.boolean(on_hold).upcast()];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::CallTransferOffered(remote_user_id, call_id) => {
+                let method_name = "onCallTransferOffered";
+                let args = [
+                    to_js_buffer(&mut cx, &remote_user_id),
+                    create_id_arg(&mut cx, call_id.as_u64()),
+                ];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::CallRecord(peer_id, record) => {
+                let method_name = "onCallRecord";
+                let args = [
+                    cx.string(peer_id).upcast(),
+                    to_js_call_record(&mut cx, &record)?.upcast(),
+                ];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::CallWaiting(peer_id, call_id) => {
+                let method_name = "onCallWaiting";
+                let args = [
+                    cx.string(peer_id).upcast(),
+                    create_id_arg(&mut cx, call_id.as_u64()),
+                ];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupCallInvitation(peer_id, call_id, invitation) => {
+                let method_name = "onGroupCallInvitation";
+                let (group_id, root_key, epoch) = match invitation {
+                    signaling::GroupCallInvitation::Group { group_id } => (
+                        to_js_buffer(&mut cx, &group_id),
+                        cx.undefined().upcast(),
+                        None,
+                    ),
+                    signaling::GroupCallInvitation::CallLink { root_key, epoch } => (
+                        cx.undefined().upcast(),
+                        to_js_buffer(&mut cx, &root_key),
+                        epoch,
+                    ),
+                };
+                let epoch: Handle<JsValue> = match epoch {
+                    Some(epoch) => cx.number(epoch).upcast(),
+                    None => cx.undefined().upcast(),
+                };
+                let args = [
+                    cx.string(peer_id).upcast(),
+                    create_id_arg(&mut cx, call_id.as_u64()),
+                    group_id,
+                    root_key,
+                    epoch,
+                ];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
             Event::AudioLevels {
                 peer_id,
                 captured_level,
@@ -2639,73 +3408,8 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
             )) => {
                 let method_name = "handleRemoteDevicesChanged";
 
-                let js_remote_device_states = JsArray::new(&mut cx, remote_device_states.len());
-                for (i, remote_device_state) in remote_device_states.iter().enumerate() {
-                    let demux_id = cx.number(remote_device_state.demux_id);
-                    let user_id = to_js_buffer(&mut cx, &remote_device_state.user_id);
-                    let media_keys_received = cx.boolean(remote_device_state.media_keys_received);
-                    let audio_muted: neon::handle::Handle<JsValue> =
-                        match remote_device_state.heartbeat_state.audio_muted {
-                            None => cx.undefined().upcast(),
-                            Some(muted) => cx.boolean(muted).upcast(),
-                        };
-                    let video_muted: neon::handle::Handle<JsValue> =
-                        match remote_device_state.heartbeat_state.video_muted {
-                            None => cx.undefined().upcast(),
-                            Some(muted) => cx.boolean(muted).upcast(),
-                        };
-                    let presenting: neon::handle::Handle<JsValue> =
-                        match remote_device_state.heartbeat_state.presenting {
-                            None => cx.undefined().upcast(),
-                            Some(muted) => cx.boolean(muted).upcast(),
-                        };
-                    let sharing_screen: neon::handle::Handle<JsValue> =
-                        match remote_device_state.heartbeat_state.sharing_screen {
-                            None => cx.undefined().upcast(),
-                            Some(muted) => cx.boolean(muted).upcast(),
-                        };
-                    // These are strings because we can't safely convert a u64 to a JavaScript-compatible number. We'll convert them to numeric types on the other side.
-                    let added_time: neon::handle::Handle<JsValue> = cx
-                        .string(remote_device_state.added_time_as_unix_millis().to_string())
-                        .upcast();
-                    let speaker_time: neon::handle::Handle<JsValue> = cx
-                        .string(
-                            remote_device_state
-                                .speaker_time_as_unix_millis()
-                                .to_string(),
-                        )
-                        .upcast();
-                    let forwarding_video: neon::handle::Handle<JsValue> =
-                        match remote_device_state.forwarding_video {
-                            None => cx.undefined().upcast(),
-                            Some(forwarding_video) => cx.boolean(forwarding_video).upcast(),
-                        };
-                    let is_higher_resolution_pending =
-                        cx.boolean(remote_device_state.is_higher_resolution_pending);
-
-                    let js_remote_device_state = cx.empty_object();
-                    js_remote_device_state.set(&mut cx, "demuxId", demux_id)?;
-                    js_remote_device_state.set(&mut cx, "userId", user_id)?;
-                    js_remote_device_state.set(
-                        &mut cx,
-                        "mediaKeysReceived",
-                        media_keys_received,
-                    )?;
-                    js_remote_device_state.set(&mut cx, "audioMuted", audio_muted)?;
-                    js_remote_device_state.set(&mut cx, "videoMuted", video_muted)?;
-                    js_remote_device_state.set(&mut cx, "presenting", presenting)?;
-                    js_remote_device_state.set(&mut cx, "sharingScreen", sharing_screen)?;
-                    js_remote_device_state.set(&mut cx, "addedTime", added_time)?;
-                    js_remote_device_state.set(&mut cx, "speakerTime", speaker_time)?;
-                    js_remote_device_state.set(&mut cx, "forwardingVideo", forwarding_video)?;
-                    js_remote_device_state.set(
-                        &mut cx,
-                        "isHigherResolutionPending",
-                        is_higher_resolution_pending,
-                    )?;
-
-                    js_remote_device_states.set(&mut cx, i as u32, js_remote_device_state)?;
-                }
+                let js_remote_device_states =
+                    to_js_remote_device_states(&mut cx, &remote_device_states)?;
 
                 let args = [
                     cx.number(client_id).upcast(),
@@ -2747,6 +3451,30 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 method.call(&mut cx, observer, args)?;
             }
 
+            Event::GroupUpdate(GroupUpdate::WatchedPeekChanged { id, peek_result }) => {
+                let (js_status, js_info) = match peek_result {
+                    Ok(peek_info) => {
+                        let js_info = to_js_peek_info(&mut cx, peek_info)?;
+                        (cx.number(200), js_info.upcast())
+                    }
+                    Err(status) => (cx.number(status.code), cx.undefined().upcast()),
+                };
+
+                let method_name = "handleWatchedPeekChanged";
+
+                let args = [to_js_buffer(&mut cx, &id), js_status.upcast(), js_info];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::CallSummary(client_id, summary)) => {
+                let method_name = "handleCallSummary";
+                let js_summary = to_js_call_summary(&mut cx, &summary)?;
+                let args = [cx.number(client_id).upcast(), js_summary.upcast()];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
             Event::GroupUpdate(GroupUpdate::Ended(client_id, reason)) => {
                 let method_name = "handleEnded";
                 let args = [
@@ -2780,6 +3508,13 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 method.call(&mut cx, observer, args)?;
             }
 
+            Event::GroupUpdate(GroupUpdate::RingRecord(record)) => {
+                let method_name = "onGroupRingRecord";
+                let args = [to_js_group_ring_record(&mut cx, &record)?.upcast::<JsValue>()];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
             Event::GroupUpdate(GroupUpdate::AudioLevels(
                 client_id,
                 captured_level,
@@ -2853,6 +3588,20 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 method.call(&mut cx, observer, args)?;
             }
 
+            Event::GroupUpdate(GroupUpdate::PresenterChanged(client_id, current_presenter)) => {
+                let method_name = "handlePresenterChanged";
+                let args = [
+                    cx.number(client_id).upcast(),
+                    match current_presenter {
+                        Some(demux_id) => cx.number(demux_id).upcast(),
+                        None => cx.null().upcast(),
+                    },
+                ];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
             Event::GroupUpdate(GroupUpdate::RtcStatsReportComplete { report_json }) => {
                 let method_name = "handleRtcStatsReportComplete";
                 let args = [cx.string(report_json).upcast()];
@@ -2868,6 +3617,24 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
                 method.call(&mut cx, observer, args)?;
             }
+            Event::GroupUpdate(GroupUpdate::AdminActionResults(client_id, results)) => {
+                let js_results = JsArray::new(&mut cx, results.len());
+                for (i, result) in results.into_iter().enumerate() {
+                    let js_result = cx.empty_object();
+                    let js_user_id = to_js_buffer(&mut cx, &result.user_id);
+                    js_result.set(&mut cx, "userId", js_user_id)?;
+                    let js_approved = cx.boolean(result.approved);
+                    js_result.set(&mut cx, "approved", js_approved)?;
+                    let js_outcome = cx.number(result.outcome as i32);
+                    js_result.set(&mut cx, "outcome", js_outcome)?;
+                    js_results.set(&mut cx, i as u32, js_result)?;
+                }
+
+                let method_name = "handleAdminActionResults";
+                let args = [cx.number(client_id).upcast(), js_results.upcast()];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
         }
     }
     Ok(cx.undefined().upcast())
@@ -2920,16 +3687,17 @@ fn CallLinkRootKey_generateAdminPasskey(mut cx: FunctionContext) -> JsResult<JsB
 
 #[allow(non_snake_case)]
 fn CallLinkRootKey_deriveRoomId(mut cx: FunctionContext) -> JsResult<JsBuffer> {
-    let bytes = cx.argument::<JsBuffer>(0)?;
-    match CallLinkRootKey::try_from(bytes.as_slice(&cx)) {
-        Ok(key) => {
-            let room_id = key.derive_room_id();
-            let mut buffer = cx.buffer(room_id.len())?;
-            buffer.as_mut_slice(&mut cx).copy_from_slice(&room_id);
-            Ok(buffer)
-        }
-        Err(e) => cx.throw_error(e.to_string()),
-    }
+    let key = root_key_from_js_args(&mut cx, 0, 1)?;
+    let room_id = key.derive_room_id();
+    let mut buffer = cx.buffer(room_id.len())?;
+    buffer.as_mut_slice(&mut cx).copy_from_slice(&room_id);
+    Ok(buffer)
+}
+
+#[allow(non_snake_case)]
+fn CallLinkEpoch_generate(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let epoch = CallLinkEpoch::generate(rand::rngs::OsRng);
+    Ok(cx.number(u32::from(epoch)))
 }
 
 #[allow(non_snake_case)]
@@ -2944,6 +3712,31 @@ fn CallLinkRootKey_toFormattedString(mut cx: FunctionContext) -> JsResult<JsStri
     }
 }
 
+#[allow(non_snake_case)]
+fn CallLinkUrl_parse(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let string = cx.argument::<JsString>(0)?.value(&mut cx);
+    match CallLinkUrl::parse(&string) {
+        Ok(url) => {
+            let js_url = cx.empty_object();
+            let root_key = to_js_buffer(&mut cx, &url.root_key.bytes());
+            js_url.set(&mut cx, "rootKey", root_key)?;
+            let epoch: Handle<JsValue> = match url.root_key.epoch() {
+                Some(epoch) => cx.number(u32::from(epoch)).upcast(),
+                None => cx.undefined().upcast(),
+            };
+            js_url.set(&mut cx, "epoch", epoch)?;
+            Ok(js_url)
+        }
+        Err(e) => cx.throw_error(e.to_string()),
+    }
+}
+
+#[allow(non_snake_case)]
+fn CallLinkUrl_format(mut cx: FunctionContext) -> JsResult<JsString> {
+    let root_key = root_key_from_js_args(&mut cx, 0, 1)?;
+    Ok(cx.string(CallLinkUrl::new(root_key).to_url_string()))
+}
+
 #[neon::main]
 fn register(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("createCallEndpoint", createCallEndpoint)?;
@@ -2961,6 +3754,9 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
         "CallLinkRootKey_toFormattedString",
         CallLinkRootKey_toFormattedString,
     )?;
+    cx.export_function("CallLinkEpoch_generate", CallLinkEpoch_generate)?;
+    cx.export_function("CallLinkUrl_parse", CallLinkUrl_parse)?;
+    cx.export_function("CallLinkUrl_format", CallLinkUrl_format)?;
 
     let js_property_key = cx.string(CALL_ENDPOINT_PROPERTY_KEY);
     cx.export_value("callEndpointPropertyKey", js_property_key)?;
@@ -2972,6 +3768,13 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("cm_accept", accept)?;
     cx.export_function("cm_ignore", ignore)?;
     cx.export_function("cm_hangup", hangup)?;
+    cx.export_function("cm_setOnHold", setOnHold)?;
+    cx.export_function("cm_escalateToGroupCall", escalateToGroupCall)?;
+    cx.export_function("cm_transferCall", transferCall)?;
+    cx.export_function("cm_setDoNotDisturb", setDoNotDisturb)?;
+    cx.export_function("cm_acceptCallTransfer", acceptCallTransfer)?;
+    cx.export_function("cm_setCallWaitingEnabled", setCallWaitingEnabled)?;
+    cx.export_function("cm_swapCalls", swapCalls)?;
     cx.export_function("cm_signalingMessageSent", signalingMessageSent)?;
     cx.export_function("cm_signalingMessageSendFailed", signalingMessageSendFailed)?;
     cx.export_function("cm_updateDataMode", updateDataMode)?;
@@ -3009,26 +3812,37 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("cm_groupRing", groupRing)?;
     cx.export_function("cm_groupReact", groupReact)?;
     cx.export_function("cm_groupRaiseHand", groupRaiseHand)?;
+    cx.export_function("cm_groupRequestToPresent", groupRequestToPresent)?;
     cx.export_function("cm_resendMediaKeys", resendMediaKeys)?;
     cx.export_function("cm_setDataMode", setDataMode)?;
     cx.export_function("cm_requestVideo", requestVideo)?;
     cx.export_function("cm_approveUser", approveUser)?;
     cx.export_function("cm_denyUser", denyUser)?;
+    cx.export_function("cm_approveUsers", approveUsers)?;
+    cx.export_function("cm_denyUsers", denyUsers)?;
+    cx.export_function("cm_approveAllPendingUsers", approveAllPendingUsers)?;
+    cx.export_function("cm_denyAllPendingUsers", denyAllPendingUsers)?;
     cx.export_function("cm_removeClient", removeClient)?;
     cx.export_function("cm_blockClient", blockClient)?;
     cx.export_function("cm_setGroupMembers", setGroupMembers)?;
     cx.export_function("cm_setMembershipProof", setMembershipProof)?;
     cx.export_function("cm_peekGroupCall", peekGroupCall)?;
     cx.export_function("cm_peekCallLinkCall", peekCallLinkCall)?;
+    cx.export_function("cm_watchGroupCallPeek", watchGroupCallPeek)?;
+    cx.export_function("cm_watchCallLinkPeek", watchCallLinkPeek)?;
+    cx.export_function("cm_unwatchPeek", unwatchPeek)?;
+    cx.export_function("cm_refreshPeek", refreshPeek)?;
     cx.export_function("cm_readCallLink", readCallLink)?;
     cx.export_function("cm_createCallLink", createCallLink)?;
     cx.export_function("cm_updateCallLink", updateCallLink)?;
+    cx.export_function("cm_resetCallLink", resetCallLink)?;
     cx.export_function("cm_deleteCallLink", deleteCallLink)?;
     cx.export_function("cm_getAudioInputs", getAudioInputs)?;
     cx.export_function("cm_setAudioInput", setAudioInput)?;
     cx.export_function("cm_getAudioOutputs", getAudioOutputs)?;
     cx.export_function("cm_setAudioOutput", setAudioOutput)?;
     cx.export_function("cm_setRtcStatsInterval", setRtcStatsInterval)?;
+    cx.export_function("cm_getGroupCallSnapshot", getGroupCallSnapshot)?;
     cx.export_function("cm_processEvents", processEvents)?;
     Ok(())
 }
@@ -113,6 +113,8 @@ pub enum RingRtcError {
     SfuClientRequestFailed,
     #[error("The maximum number of participants has been reached")]
     GroupCallFull,
+    #[error("Invalid membership proof")]
+    InvalidMembershipProof,
 
     // Frame encryption error codes
     #[error("Frame Counter too big")]
@@ -11,7 +11,7 @@ use libc::size_t;
 
 use crate::{
     common::{CallConfig, CallMediaType, DataMode, DeviceId},
-    core::{group_call, signaling},
+    core::{group_call, incoming_call_policy::QuietHours, signaling},
     ios::{call_manager, call_manager::IosCallManager},
     lite::{call_links::CallLinkRootKey, http, sfu, sfu::DemuxId},
     webrtc::{self, media, peer_connection::AudioLevel, peer_connection_factory as pcf},
@@ -102,6 +102,15 @@ pub struct AppOptionalUInt32 {
     pub valid: bool,
 }
 
+/// Structure for passing optional u64 values to/from Swift.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppOptionalUInt64 {
+    pub value: u64,
+    pub valid: bool,
+}
+
 /// Structure for passing optional bool values to/from Swift.
 #[repr(C)]
 #[derive(Debug)]
@@ -276,6 +285,84 @@ pub struct AppReactionsArray {
     pub count: size_t,
 }
 
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppSpeakingTime {
+    pub userId: AppByteSlice,
+    pub speakingTimeMillis: u64,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppSpeakingTimeArray {
+    pub speakingTimes: *const AppSpeakingTime,
+    pub count: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppCallSummary {
+    pub callDurationMillis: u64,
+    pub joinDurationMillis: u64,
+    pub peakDeviceCount: u32,
+    pub uniqueUserCount: u32,
+    pub speakingTimes: AppSpeakingTimeArray,
+    pub reactionsSent: u32,
+    pub reactionsReceived: u32,
+    pub raisedHandCount: u32,
+    pub averageSendBps: u64,
+    pub averageReceiveBps: u64,
+    pub sendPacketsLostPct: f32,
+    pub receivePacketsLostPct: f32,
+    pub reconnectCount: u32,
+}
+
+/// Offsets in milliseconds from when the call started being handled.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppCallSetupMetrics {
+    pub offerAge: AppOptionalUInt32,
+    pub offerSent: AppOptionalUInt32,
+    pub answerSent: AppOptionalUInt32,
+    pub answerReceived: AppOptionalUInt32,
+    pub firstIceCandidateSent: AppOptionalUInt32,
+    pub firstIceCandidateReceived: AppOptionalUInt32,
+    pub iceConnected: AppOptionalUInt32,
+    pub accepted: AppOptionalUInt32,
+    pub firstMedia: AppOptionalUInt32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppCallRecord {
+    pub callId: u64,
+    pub isIncoming: bool,
+    pub isVideo: bool,
+    pub startedMillis: u64,
+    pub answeredMillis: AppOptionalUInt64,
+    pub endedMillis: u64,
+    pub outcome: i32,
+    pub answeredByDeviceId: AppOptionalUInt32,
+    pub setup: AppCallSetupMetrics,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppGroupRingRecord {
+    pub groupId: AppByteSlice,
+    pub ringId: i64,
+    pub senderUuid: AppByteSlice,
+    pub receivedMillis: u64,
+    pub endedMillis: u64,
+    pub outcome: i32,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[allow(non_snake_case)]
@@ -429,6 +516,18 @@ pub struct AppInterface {
     pub onCompareRemotes:
         extern "C" fn(object: *mut c_void, remote1: *const c_void, remote2: *const c_void) -> bool,
     pub onCallConcluded: extern "C" fn(object: *mut c_void, remote: *const c_void),
+    pub onGroupCallInvitation: extern "C" fn(
+        object: *mut c_void,
+        remote: *const c_void,
+        callId: u64,
+        groupId: AppByteSlice,
+        callLinkRootKey: AppByteSlice,
+        callLinkEpoch: AppOptionalUInt32,
+    ),
+    pub onCallTransferOffered:
+        extern "C" fn(object: *mut c_void, remoteUserId: AppByteSlice, callId: u64),
+    pub onCallRecord:
+        extern "C" fn(object: *mut c_void, remote: *const c_void, record: AppCallRecord),
 
     // Group Calls
     pub groupCallRingUpdate: extern "C" fn(
@@ -438,6 +537,7 @@ pub struct AppInterface {
         senderUuid: AppByteSlice,
         ringUpdate: i32,
     ),
+    pub onGroupRingRecord: extern "C" fn(object: *mut c_void, record: AppGroupRingRecord),
     pub requestMembershipProof: extern "C" fn(object: *mut c_void, clientId: group_call::ClientId),
     pub requestGroupMembers: extern "C" fn(object: *mut c_void, clientId: group_call::ClientId),
     pub handleConnectionStateChanged:
@@ -465,6 +565,11 @@ pub struct AppInterface {
         clientId: group_call::ClientId,
         raisedHands: AppRaisedHandsArray,
     ),
+    pub handlePresenterChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        currentPresenter: AppOptionalUInt32,
+    ),
     pub handleJoinStateChanged: extern "C" fn(
         object: *mut c_void,
         clientId: group_call::ClientId,
@@ -493,6 +598,23 @@ pub struct AppInterface {
         deviceCountExcludingPendingDevices: u32,
         pendingUsers: AppUuidArray,
     ),
+    pub handleCallSummary:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, summary: AppCallSummary),
+    /// Reports a change in a call watched via ringrtcWatchGroupCallPeek or
+    /// ringrtcWatchCallLinkPeek. If statusCode isn't 200, the peek failed and the
+    /// remaining fields are empty.
+    pub handleWatchedPeekChanged: extern "C" fn(
+        object: *mut c_void,
+        id: AppByteSlice,
+        statusCode: u16,
+        joinedMembers: AppUuidArray,
+        creator: AppByteSlice,
+        eraId: AppByteSlice,
+        maxDevices: AppOptionalUInt32,
+        deviceCountIncludingPendingDevices: u32,
+        deviceCountExcludingPendingDevices: u32,
+        pendingUsers: AppUuidArray,
+    ),
     pub handleEnded:
         extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, reason: i32),
     pub handleSpeakingNotification:
@@ -640,6 +762,161 @@ pub extern "C" fn ringrtcHangup(callManager: *mut c_void) -> *mut c_void {
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetOnHold(callManager: *mut c_void, onHold: bool) -> *mut c_void {
+    match call_manager::set_on_hold(callManager as *mut IosCallManager, onHold) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcEscalateToGroupCall(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+) -> *mut c_void {
+    match call_manager::escalate_to_group_call(callManager as *mut IosCallManager, clientId) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcTransferCall(
+    callManager: *mut c_void,
+    remoteUserId: AppByteSlice,
+    targetDeviceId: u32,
+) -> *mut c_void {
+    let remote_user_id = match byte_vec_from_app_slice(&remoteUserId) {
+        Some(remote_user_id) => remote_user_id,
+        None => {
+            error!("Missing remote user ID");
+            return ptr::null_mut();
+        }
+    };
+    match call_manager::transfer_call(
+        callManager as *mut IosCallManager,
+        remote_user_id,
+        targetDeviceId as DeviceId,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcAcceptCallTransfer(
+    callManager: *mut c_void,
+    appRemote: *const c_void,
+    callId: u64,
+    callMediaType: i32,
+    appLocalDevice: u32,
+) -> *mut c_void {
+    match call_manager::accept_call_transfer(
+        callManager as *mut IosCallManager,
+        appRemote,
+        callId,
+        CallMediaType::from_i32(callMediaType),
+        appLocalDevice as DeviceId,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetCallWaitingEnabled(
+    callManager: *mut c_void,
+    enabled: bool,
+) -> *mut c_void {
+    match call_manager::set_call_waiting_enabled(callManager as *mut IosCallManager, enabled) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetDoNotDisturb(
+    callManager: *mut c_void,
+    enabled: bool,
+    hasQuietHours: bool,
+    startMinute: u16,
+    endMinute: u16,
+    utcOffsetMinutes: i32,
+    allowedUuids: AppByteSlice,
+    maxConcurrentGroupRings: AppOptionalUInt32,
+) -> *mut c_void {
+    let quiet_hours = if hasQuietHours {
+        Some(QuietHours {
+            start_minute: startMinute,
+            end_minute: endMinute,
+            utc_offset_minutes: utcOffsetMinutes,
+        })
+    } else {
+        None
+    };
+
+    // The allowed UUIDs are concatenated as 16-byte chunks.
+    let allowed_users = byte_vec_from_app_slice(&allowedUuids)
+        .unwrap_or_default()
+        .chunks_exact(16)
+        .map(|uuid| uuid.to_vec())
+        .collect();
+
+    let max_concurrent_group_rings = if maxConcurrentGroupRings.valid {
+        Some(maxConcurrentGroupRings.value as usize)
+    } else {
+        None
+    };
+
+    match call_manager::set_do_not_disturb(
+        callManager as *mut IosCallManager,
+        enabled,
+        quiet_hours,
+        allowed_users,
+        max_concurrent_group_rings,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSwapCalls(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::swap_calls(callManager as *mut IosCallManager) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcCancelGroupRing(
@@ -1343,6 +1620,21 @@ pub extern "C" fn ringrtcSetGroupMembers(
     clientId: group_call::ClientId,
     appGroupMemberInfoArray: *const AppGroupMemberInfoArray,
 ) {
+    let group_members = group_members_from_app_array(appGroupMemberInfoArray);
+
+    let result = call_manager::set_group_members(
+        callManager as *mut IosCallManager,
+        clientId,
+        group_members,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+fn group_members_from_app_array(
+    appGroupMemberInfoArray: *const AppGroupMemberInfoArray,
+) -> Vec<sfu::GroupMember> {
     let count = unsafe { (*appGroupMemberInfoArray).count };
     let app_group_members = unsafe { (*appGroupMemberInfoArray).members };
 
@@ -1368,14 +1660,7 @@ pub extern "C" fn ringrtcSetGroupMembers(
         })
     }
 
-    let result = call_manager::set_group_members(
-        callManager as *mut IosCallManager,
-        clientId,
-        group_members,
-    );
-    if result.is_err() {
-        error!("{:?}", result.err());
-    }
+    group_members
 }
 
 #[no_mangle]
@@ -1433,6 +1718,125 @@ pub extern "C" fn ringrtcRaiseHand(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRequestToPresent(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    takeOver: bool,
+) {
+    let result =
+        call_manager::request_to_present(callManager as *mut IosCallManager, clientId, takeOver);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcWatchGroupCallPeek(
+    callManager: *mut c_void,
+    groupId: AppByteSlice,
+    sfuUrl: AppByteSlice,
+    membershipProof: AppByteSlice,
+    appGroupMemberInfoArray: *const AppGroupMemberInfoArray,
+) {
+    let group_id = byte_vec_from_app_slice(&groupId);
+    if group_id.is_none() {
+        error!("Invalid groupId");
+        return;
+    }
+    let sfu_url = string_from_app_slice(&sfuUrl);
+    if sfu_url.is_none() {
+        error!("Invalid sfuUrl");
+        return;
+    }
+    let membership_proof = byte_vec_from_app_slice(&membershipProof);
+    if membership_proof.is_none() {
+        error!("Invalid membershipProof");
+        return;
+    }
+    let group_members = group_members_from_app_array(appGroupMemberInfoArray);
+
+    let result = call_manager::watch_group_call_peek(
+        callManager as *mut IosCallManager,
+        group_id.unwrap(),
+        sfu_url.unwrap(),
+        membership_proof.unwrap(),
+        group_members,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcWatchCallLinkPeek(
+    callManager: *mut c_void,
+    sfuUrl: AppByteSlice,
+    authCredentialPresentation: AppByteSlice,
+    rootKeyBytes: AppByteSlice,
+) {
+    let sfu_url = string_from_app_slice(&sfuUrl);
+    if sfu_url.is_none() {
+        error!("Invalid sfuUrl");
+        return;
+    }
+    let auth_presentation = byte_vec_from_app_slice(&authCredentialPresentation);
+    if auth_presentation.is_none() {
+        error!("Invalid authCredentialPresentation");
+        return;
+    }
+    let root_key = rootKeyBytes
+        .as_slice()
+        .and_then(|bytes| CallLinkRootKey::try_from(bytes).ok());
+    if root_key.is_none() {
+        error!("Invalid rootKey");
+        return;
+    }
+
+    let result = call_manager::watch_call_link_peek(
+        callManager as *mut IosCallManager,
+        sfu_url.unwrap(),
+        auth_presentation.unwrap(),
+        root_key.unwrap(),
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcUnwatchPeek(callManager: *mut c_void, id: AppByteSlice) {
+    let id = byte_vec_from_app_slice(&id);
+    if id.is_none() {
+        error!("Invalid id");
+        return;
+    }
+
+    let result = call_manager::unwatch_peek(callManager as *mut IosCallManager, id.unwrap());
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRefreshPeek(callManager: *mut c_void, id: AppByteSlice) {
+    let id = byte_vec_from_app_slice(&id);
+    if id.is_none() {
+        error!("Invalid id");
+        return;
+    }
+
+    let result = call_manager::refresh_peek(callManager as *mut IosCallManager, id.unwrap());
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcIsValidOffer(
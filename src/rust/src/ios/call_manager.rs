@@ -14,7 +14,9 @@ use crate::{
     core::{
         call_manager,
         call_manager::CallManager,
-        group_call, signaling,
+        group_call,
+        incoming_call_policy::{DoNotDisturb, IncomingCallPolicy, QuietHours},
+        signaling,
         util::{ptr_as_box, ptr_as_mut},
     },
     error::RingRtcError,
@@ -104,6 +106,85 @@ pub fn hangup(call_manager: *mut IosCallManager) -> Result<()> {
     call_manager.hangup()
 }
 
+/// Application notification to put the active call on hold or resume it.
+pub fn set_on_hold(call_manager: *mut IosCallManager, on_hold: bool) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_on_hold(on_hold)
+}
+
+/// Application notification to continue the active call as a group call.
+pub fn escalate_to_group_call(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.escalate_to_group_call(client_id)
+}
+
+/// Application notification to hand the active call over to another of the
+/// user's devices.
+pub fn transfer_call(
+    call_manager: *mut IosCallManager,
+    remote_user_id: UserId,
+    target_device_id: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.transfer_call(remote_user_id, target_device_id)
+}
+
+/// Application notification to take over a call offered by another of the
+/// user's devices.
+pub fn accept_call_transfer(
+    call_manager: *mut IosCallManager,
+    app_remote: *const c_void,
+    call_id: u64,
+    call_media_type: CallMediaType,
+    app_local_device: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.create_outgoing_call(
+        AppObject::from(app_remote),
+        CallId::from(call_id),
+        call_media_type,
+        app_local_device,
+    )
+}
+
+/// Application notification to enable or disable call waiting.
+pub fn set_call_waiting_enabled(call_manager: *mut IosCallManager, enabled: bool) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_call_waiting_enabled(enabled)
+}
+
+/// Application notification to turn do not disturb on or off.
+pub fn set_do_not_disturb(
+    call_manager: *mut IosCallManager,
+    enabled: bool,
+    quiet_hours: Option<QuietHours>,
+    allowed_users: Vec<UserId>,
+    max_concurrent_group_rings: Option<usize>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+
+    let policy: Option<Box<dyn IncomingCallPolicy<IosPlatform>>> = if enabled {
+        Some(Box::new(DoNotDisturb {
+            allowed_users: allowed_users.into_iter().collect(),
+            quiet_hours,
+            max_concurrent_group_rings,
+            ..Default::default()
+        }))
+    } else {
+        None
+    };
+    call_manager.set_incoming_call_policy(policy)
+}
+
+/// Application notification to hold the active call and resume the held one.
+pub fn swap_calls(call_manager: *mut IosCallManager) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.swap_calls()
+}
+
 /// Application notification cancelling a group ring.
 pub fn cancel_group_ring(
     call_manager: *mut IosCallManager,
@@ -633,6 +714,47 @@ pub fn raise_hand(
     Ok(())
 }
 
+pub fn request_to_present(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    take_over: bool,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.request_to_present(client_id, take_over);
+    Ok(())
+}
+
+pub fn watch_group_call_peek(
+    call_manager: *mut IosCallManager,
+    group_id: group_call::GroupId,
+    sfu_url: String,
+    membership_proof: Vec<u8>,
+    members: Vec<GroupMember>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.watch_group_call_peek(group_id, sfu_url, membership_proof, members)
+}
+
+pub fn watch_call_link_peek(
+    call_manager: *mut IosCallManager,
+    sfu_url: String,
+    auth_credential_presentation: Vec<u8>,
+    root_key: CallLinkRootKey,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.watch_call_link_peek(sfu_url, &auth_credential_presentation, root_key)
+}
+
+pub fn unwatch_peek(call_manager: *mut IosCallManager, id: Vec<u8>) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.unwatch_peek(&id)
+}
+
+pub fn refresh_peek(call_manager: *mut IosCallManager, id: Vec<u8>) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.refresh_peek(&id)
+}
+
 pub fn validate_offer(
     opaque: Option<Vec<u8>>,
     age_sec: u64,
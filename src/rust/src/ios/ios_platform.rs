@@ -13,6 +13,7 @@ use crate::{
     },
     core::{
         call::Call,
+        call_record::{self, CallRecord, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         group_call::Reaction,
@@ -21,17 +22,19 @@ use crate::{
     },
     ios::{
         api::call_manager_interface::{
-            AppByteSlice, AppCallContext, AppConnectionInterface, AppIceCandidateArray,
-            AppInterface, AppObject, AppOptionalBool, AppOptionalUInt32, AppRaisedHandsArray,
+            AppByteSlice, AppCallContext, AppCallRecord, AppCallSetupMetrics, AppCallSummary,
+            AppConnectionInterface, AppGroupRingRecord, AppIceCandidateArray, AppInterface,
+            AppObject, AppOptionalBool, AppOptionalUInt32, AppOptionalUInt64, AppRaisedHandsArray,
             AppReaction, AppReactionsArray, AppReceivedAudioLevel, AppReceivedAudioLevelArray,
-            AppRemoteDeviceState, AppRemoteDeviceStateArray, AppUuidArray,
+            AppRemoteDeviceState, AppRemoteDeviceStateArray, AppSpeakingTime, AppSpeakingTimeArray,
+            AppUuidArray,
         },
         error::IosError,
         ios_media_stream::IosMediaStream,
     },
     lite::{
         sfu,
-        sfu::{DemuxId, PeekInfo, PeekResult, UserId},
+        sfu::{DemuxId, PeekInfo, PeekResult, PeekWatchId, UserId},
     },
     webrtc,
     webrtc::{
@@ -530,6 +533,132 @@ impl Platform for IosPlatform {
         Ok(())
     }
 
+    fn on_group_call_invitation(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        info!("on_group_call_invitation(): call_id: {}", call_id);
+
+        let (group_id, root_key, epoch) = match invitation {
+            signaling::GroupCallInvitation::Group { group_id } => (Some(group_id), None, None),
+            signaling::GroupCallInvitation::CallLink { root_key, epoch } => {
+                (None, Some(root_key), *epoch)
+            }
+        };
+
+        (self.app_interface.onGroupCallInvitation)(
+            self.app_interface.object,
+            remote_peer.ptr,
+            u64::from(call_id),
+            app_slice_from_bytes(group_id),
+            app_slice_from_bytes(root_key),
+            app_option_from_u32(epoch),
+        );
+
+        Ok(())
+    }
+
+    fn on_call_transfer_offered(&self, remote_user_id: UserId, call_id: CallId) -> Result<()> {
+        info!("on_call_transfer_offered(): call_id: {}", call_id);
+
+        (self.app_interface.onCallTransferOffered)(
+            self.app_interface.object,
+            app_slice_from_bytes(Some(&remote_user_id)),
+            u64::from(call_id),
+        );
+
+        Ok(())
+    }
+
+    fn on_call_record(&self, remote_peer: &Self::AppRemotePeer, record: CallRecord) -> Result<()> {
+        info!(
+            "on_call_record(): call_id: {}, outcome: {:?}",
+            record.call_id, record.outcome
+        );
+
+        let optional_millis = |offset: Option<Duration>| match offset {
+            Some(offset) => AppOptionalUInt32 {
+                value: offset.as_millis() as u32,
+                valid: true,
+            },
+            None => AppOptionalUInt32 {
+                value: 0,
+                valid: false,
+            },
+        };
+        let setup = &record.setup;
+
+        let app_call_record = AppCallRecord {
+            callId: u64::from(record.call_id),
+            isIncoming: record.direction == CallDirection::Incoming,
+            isVideo: record.media_type == CallMediaType::Video,
+            startedMillis: call_record::millis_since_epoch(record.started),
+            answeredMillis: match record.answered {
+                Some(answered) => AppOptionalUInt64 {
+                    value: call_record::millis_since_epoch(answered),
+                    valid: true,
+                },
+                None => AppOptionalUInt64 {
+                    value: 0,
+                    valid: false,
+                },
+            },
+            endedMillis: call_record::millis_since_epoch(record.ended),
+            outcome: record.outcome as i32,
+            answeredByDeviceId: match record.answered_by_device_id {
+                Some(device_id) => AppOptionalUInt32 {
+                    value: device_id,
+                    valid: true,
+                },
+                None => AppOptionalUInt32 {
+                    value: 0,
+                    valid: false,
+                },
+            },
+            setup: AppCallSetupMetrics {
+                offerAge: optional_millis(setup.offer_age),
+                offerSent: optional_millis(setup.offer_sent),
+                answerSent: optional_millis(setup.answer_sent),
+                answerReceived: optional_millis(setup.answer_received),
+                firstIceCandidateSent: optional_millis(setup.first_ice_candidate_sent),
+                firstIceCandidateReceived: optional_millis(setup.first_ice_candidate_received),
+                iceConnected: optional_millis(setup.ice_connected),
+                accepted: optional_millis(setup.accepted),
+                firstMedia: optional_millis(setup.first_media),
+            },
+        };
+
+        (self.app_interface.onCallRecord)(
+            self.app_interface.object,
+            remote_peer.ptr,
+            app_call_record,
+        );
+
+        Ok(())
+    }
+
+    fn on_group_ring_record(&self, record: GroupRingRecord) -> Result<()> {
+        info!(
+            "on_group_ring_record(): ring_id: {}, outcome: {:?}",
+            record.ring_id, record.outcome
+        );
+
+        let app_group_ring_record = AppGroupRingRecord {
+            groupId: app_slice_from_bytes(Some(&record.group_id)),
+            ringId: record.ring_id.into(),
+            senderUuid: app_slice_from_bytes(Some(&record.sender_id)),
+            receivedMillis: call_record::millis_since_epoch(record.received),
+            endedMillis: call_record::millis_since_epoch(record.ended),
+            outcome: record.outcome as i32,
+        };
+
+        (self.app_interface.onGroupRingRecord)(self.app_interface.object, app_group_ring_record);
+
+        Ok(())
+    }
+
     // Group Calls
 
     fn group_call_ring_update(
@@ -716,6 +845,20 @@ impl Platform for IosPlatform {
         );
     }
 
+    fn handle_presenter_changed(
+        &self,
+        client_id: group_call::ClientId,
+        current_presenter: Option<DemuxId>,
+    ) {
+        info!("handle_presenter_changed(): {:?}", current_presenter);
+
+        (self.app_interface.handlePresenterChanged)(
+            self.app_interface.object,
+            client_id,
+            app_option_from_u32(current_presenter),
+        );
+    }
+
     fn handle_incoming_video_track(
         &self,
         client_id: group_call::ClientId,
@@ -779,6 +922,86 @@ impl Platform for IosPlatform {
         );
     }
 
+    fn handle_call_summary(
+        &self,
+        client_id: group_call::ClientId,
+        summary: group_call::CallSummary,
+    ) {
+        info!("handle_call_summary(): {:?}", summary);
+
+        let app_speaking_times: Vec<AppSpeakingTime> = summary
+            .speaking_time
+            .iter()
+            .map(|(user_id, speaking_time)| AppSpeakingTime {
+                userId: app_slice_from_bytes(Some(user_id)),
+                speakingTimeMillis: speaking_time.as_millis() as u64,
+            })
+            .collect();
+
+        let app_summary = AppCallSummary {
+            callDurationMillis: summary.call_duration.as_millis() as u64,
+            joinDurationMillis: summary.join_duration.as_millis() as u64,
+            peakDeviceCount: summary.peak_device_count as u32,
+            uniqueUserCount: summary.unique_user_count as u32,
+            speakingTimes: AppSpeakingTimeArray {
+                speakingTimes: app_speaking_times.as_ptr(),
+                count: app_speaking_times.len(),
+            },
+            reactionsSent: summary.reactions_sent,
+            reactionsReceived: summary.reactions_received,
+            raisedHandCount: summary.raised_hand_count,
+            averageSendBps: summary.media.average_send_rate.as_bps(),
+            averageReceiveBps: summary.media.average_receive_rate.as_bps(),
+            sendPacketsLostPct: summary.media.send_packets_lost_pct,
+            receivePacketsLostPct: summary.media.receive_packets_lost_pct,
+            reconnectCount: summary.reconnect_count,
+        };
+
+        (self.app_interface.handleCallSummary)(self.app_interface.object, client_id, app_summary);
+    }
+
+    fn handle_watched_peek_changed(&self, id: PeekWatchId, peek_result: PeekResult) {
+        let (status_code, peek_info) = match peek_result {
+            Ok(peek_info) => (200, peek_info),
+            Err(status) => (status.code, PeekInfo::default()),
+        };
+
+        let app_joined_members: Vec<AppByteSlice> = peek_info
+            .unique_users()
+            .into_iter()
+            .map(|member| app_slice_from_bytes(Some(member)))
+            .collect();
+
+        let app_joined_members_array = AppUuidArray {
+            uuids: app_joined_members.as_ptr(),
+            count: app_joined_members.len(),
+        };
+
+        let app_pending_users: Vec<AppByteSlice> = peek_info
+            .unique_pending_users()
+            .iter()
+            .map(|&member| app_slice_from_bytes(Some(member)))
+            .collect();
+
+        let app_pending_users_array = AppUuidArray {
+            uuids: app_pending_users.as_ptr(),
+            count: app_pending_users.len(),
+        };
+
+        (self.app_interface.handleWatchedPeekChanged)(
+            self.app_interface.object,
+            app_slice_from_bytes(Some(&id)),
+            status_code,
+            app_joined_members_array,
+            app_slice_from_bytes(peek_info.creator.as_ref()),
+            app_slice_from_str(peek_info.era_id.as_ref()),
+            app_option_from_u32(peek_info.max_devices),
+            peek_info.device_count_including_pending_devices() as u32,
+            peek_info.devices.len() as u32,
+            app_pending_users_array,
+        );
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
         (self.app_interface.handleEnded)(self.app_interface.object, client_id, reason as i32);
     }
@@ -19,6 +19,7 @@ use libc::size_t;
 
 use crate::common::{CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result};
 use crate::core::call_manager::CallManager;
+use crate::core::call_record::CallRecord;
 use crate::core::group_call;
 use crate::core::group_call::{ClientId, GroupId, SignalingMessageUrgency};
 use crate::core::signaling;
@@ -26,7 +27,7 @@ use crate::core::util::ptr_as_mut;
 
 use crate::java::{
     app_interface::AppInterface,
-    jtypes::{JArrayByte, JByteArray, JByteArray2D, JPString, TringDevice},
+    jtypes::{JArrayByte, JByteArray, JByteArray2D, JPString, TringDevice, TringGroupCallSnapshot},
 };
 
 use crate::lite::http;
@@ -276,6 +277,17 @@ pub enum Event {
     // Whether the remote is sharing its screen or not changed.
     // Like call state, we ID the call by PeerId and assume there is only one.
     RemoteSharingScreenChange(PeerId, bool),
+    // Whether the remote has put the call on hold or not changed.
+    // Like call state, we ID the call by PeerId and assume there is only one.
+    RemoteHoldChange(PeerId, bool),
+    // An incoming call arrived while another call is active.
+    CallWaiting(PeerId, CallId),
+    // The remote peer wants to continue the call as a group call.
+    GroupCallInvitation(PeerId, CallId, signaling::GroupCallInvitation),
+    // Another of the user's devices wants to hand over its call with the given user.
+    CallTransferOffered(UserId, CallId),
+    // How a call went, sent once it has concluded.
+    CallRecord(PeerId, CallRecord),
     // The group call has an update.
     GroupUpdate(GroupUpdate),
     // JavaScript should initiate an HTTP request.
@@ -306,6 +318,11 @@ impl fmt::Display for Event {
             Event::RemoteAudioStateChange(_, _) => "RemoteAudioStateChange".to_string(),
             Event::RemoteVideoStateChange { .. } => "RemoteVideoStateChange".to_string(),
             Event::RemoteSharingScreenChange { .. } => "RemoteSharingScreenChange".to_string(),
+            Event::RemoteHoldChange { .. } => "RemoteHoldChange".to_string(),
+            Event::CallWaiting(_, _) => "CallWaiting".to_string(),
+            Event::GroupCallInvitation(_, _, _) => "GroupCallInvitation".to_string(),
+            Event::CallTransferOffered(_, _) => "CallTransferOffered".to_string(),
+            Event::CallRecord(_, record) => format!("CallRecord({:?})", record.outcome),
             Event::GroupUpdate(update) => {
                 format!("GroupUpdate({:?})", update)
             }
@@ -468,6 +485,40 @@ impl EventReporter {
                     }
                 }
             }
+            Event::RemoteHoldChange(peer_id, on_hold) => {
+                info!("RemoteHoldChange to {}", on_hold);
+                unsafe {
+                    if on_hold {
+                        (self.statusCallback)(1, 1, 22, 51);
+                    } else {
+                        (self.statusCallback)(1, 1, 22, 52);
+                    }
+                }
+            }
+            Event::CallWaiting(_peer_id, call_id) => {
+                info!("[JV] CallWaiting");
+                unsafe {
+                    (self.statusCallback)(call_id.as_u64(), 1, 80, 0);
+                }
+            }
+            Event::GroupCallInvitation(_peer_id, call_id, invitation) => {
+                info!("[JV] GroupCallInvitation: {:?}", invitation);
+                unsafe {
+                    (self.statusCallback)(call_id.as_u64(), 1, 90, 0);
+                }
+            }
+            Event::CallTransferOffered(_remote_user_id, call_id) => {
+                info!("[JV] CallTransferOffered");
+                unsafe {
+                    (self.statusCallback)(call_id.as_u64(), 1, 100, 0);
+                }
+            }
+            Event::CallRecord(_peer_id, record) => {
+                info!("[JV] CallRecord: {:?}", record);
+                unsafe {
+                    (self.statusCallback)(record.call_id.as_u64(), 1, 110, record.outcome as i32);
+                }
+            }
             Event::SendHttpRequest {
                 request_id,
                 request:
@@ -774,6 +825,13 @@ impl EventReporter {
                 );
                 (self.app_interface.groupRing)(JArrayByte::new(group_id), ring_id.into(), JArrayByte::new(sender_id), update as i32);
             }
+            Event::GroupUpdate(GroupUpdate::RingRecord(record)) => {
+                info!("[JV] GroupUpdate::RingRecord: {:?}", record);
+                let ring_id: i64 = record.ring_id.into();
+                unsafe {
+                    (self.statusCallback)(ring_id as u64, 1, 120, record.outcome as i32);
+                }
+            }
             unhandled_event => {
                 info!("Unhandled event detected: {:?}", unhandled_event);
             }
@@ -889,6 +947,43 @@ impl CallStateHandler for EventReporter {
         Ok(())
     }
 
+    fn handle_remote_hold(&self, remote_peer_id: &str, on_hold: bool) -> Result<()> {
+        self.send(Event::RemoteHoldChange(
+            remote_peer_id.to_string(),
+            on_hold,
+        ))?;
+        Ok(())
+    }
+
+    fn handle_call_waiting(&self, remote_peer_id: &str, call_id: CallId) -> Result<()> {
+        self.send(Event::CallWaiting(remote_peer_id.to_string(), call_id))?;
+        Ok(())
+    }
+
+    fn handle_group_call_invitation(
+        &self,
+        remote_peer_id: &str,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        self.send(Event::GroupCallInvitation(
+            remote_peer_id.to_string(),
+            call_id,
+            invitation.clone(),
+        ))?;
+        Ok(())
+    }
+
+    fn handle_call_transfer_offered(&self, remote_user_id: &[u8], call_id: CallId) -> Result<()> {
+        self.send(Event::CallTransferOffered(remote_user_id.to_vec(), call_id))?;
+        Ok(())
+    }
+
+    fn handle_call_record(&self, remote_peer_id: &str, record: CallRecord) -> Result<()> {
+        self.send(Event::CallRecord(remote_peer_id.to_string(), record))?;
+        Ok(())
+    }
+
     fn handle_audio_levels(
         &self,
         remote_peer_id: &str,
@@ -1504,6 +1599,7 @@ pub unsafe extern "C" fn panamaReceivedHttpResponse(
     let body = jbody.to_vec_u8();
     let response = http::Response {
         status: (status_code as u16).into(),
+        headers: HashMap::new(),
         body,
     };
 
@@ -1658,6 +1754,18 @@ pub unsafe extern "C" fn setDataMode(
     1
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn getGroupCallSnapshot(
+    endpoint: i64,
+    client_id: ClientId,
+) -> TringGroupCallSnapshot {
+    let call_endpoint = ptr_as_mut(endpoint as *mut CallEndpoint).unwrap();
+    match call_endpoint.call_manager.group_call_snapshot(client_id) {
+        Some(snapshot) => TringGroupCallSnapshot::from_snapshot(snapshot),
+        None => TringGroupCallSnapshot::empty(),
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn join(endpoint: i64, client_id: ClientId) -> i64 {
     info!("need to join");
@@ -1,5 +1,6 @@
 #![allow(unused_parens)]
 
+use crate::core::group_call;
 use crate::core::signaling;
 use crate::webrtc::peer_connection_factory::AudioDevice;
 
@@ -207,3 +208,70 @@ impl<'a> TringDevice<'a> {
         }
     }
 }
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TringGroupCallSnapshot {
+    pub valid: bool,
+    pub connection_state: i32,
+    pub join_state: i32,
+    // -1 if not pending or joined
+    pub demux_id: i64,
+    pub audio_muted: bool,
+    pub video_muted: bool,
+    pub presenting: bool,
+    pub sharing_screen: bool,
+    // big-endian u32 demux ids
+    pub remote_demux_ids: JArrayByte,
+    // big-endian u32 demux ids
+    pub raised_hands: JArrayByte,
+}
+
+impl TringGroupCallSnapshot {
+    pub fn empty() -> Self {
+        Self {
+            valid: false,
+            connection_state: 0,
+            join_state: 0,
+            demux_id: -1,
+            audio_muted: true,
+            video_muted: true,
+            presenting: false,
+            sharing_screen: false,
+            remote_demux_ids: JArrayByte::new(Vec::new()),
+            raised_hands: JArrayByte::new(Vec::new()),
+        }
+    }
+
+    pub fn from_snapshot(src: group_call::ClientSnapshot) -> Self {
+        let demux_id = match src.join_state {
+            group_call::JoinState::Pending(demux_id) | group_call::JoinState::Joined(demux_id) => {
+                demux_id as i64
+            }
+            _ => -1,
+        };
+        let remote_demux_ids = src
+            .remote_devices
+            .iter()
+            .flat_map(|device| device.demux_id.to_be_bytes())
+            .collect();
+        let raised_hands = src
+            .raised_hands
+            .iter()
+            .flat_map(|demux_id| demux_id.to_be_bytes())
+            .collect();
+        let heartbeat_state = src.outgoing_heartbeat_state;
+        Self {
+            valid: true,
+            connection_state: src.connection_state.ordinal(),
+            join_state: src.join_state.ordinal(),
+            demux_id,
+            audio_muted: heartbeat_state.audio_muted.unwrap_or(true),
+            video_muted: heartbeat_state.video_muted.unwrap_or(true),
+            presenting: heartbeat_state.presenting.unwrap_or(false),
+            sharing_screen: heartbeat_state.sharing_screen.unwrap_or(false),
+            remote_demux_ids: JArrayByte::new(remote_demux_ids),
+            raised_hands: JArrayByte::new(raised_hands),
+        }
+    }
+}
@@ -37,10 +37,12 @@ pub mod core {
     pub mod call_fsm;
     pub mod call_manager;
     pub mod call_mutex;
+    pub mod call_record;
     pub mod connection;
     pub mod connection_fsm;
     pub mod crypto;
     pub mod group_call;
+    pub mod incoming_call_policy;
     pub mod platform;
     pub mod signaling;
     pub mod util;
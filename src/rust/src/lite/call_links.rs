@@ -4,6 +4,8 @@
 //
 
 mod base16;
+mod epoch;
+mod link_url;
 mod member_resolver;
 mod root_key;
 
@@ -13,7 +15,10 @@ use std::{
 };
 
 use base64::{engine::general_purpose::STANDARD as base64, Engine};
+pub use epoch::CallLinkEpoch;
+pub use link_url::{CallLinkUrl, CallLinkUrlError};
 pub use member_resolver::CallLinkMemberResolver;
+use rand::{CryptoRng, RngCore};
 pub use root_key::CallLinkRootKey;
 use serde::{self, Deserialize, Serialize};
 use serde_with::serde_as;
@@ -48,6 +53,10 @@ pub struct CallLinkResponse<'a> {
     pub revoked: bool,
     #[serde(rename = "expiration")]
     pub expiration_unix_timestamp: u64,
+    #[serde(rename = "description", default)]
+    pub encrypted_description: &'a [u8],
+    #[serde(rename = "scheduledStart", default)]
+    pub encrypted_scheduled_start: &'a [u8],
 }
 
 impl<'a> TryFrom<&'a sfu_to_device::peek_info::CallLinkState> for CallLinkResponse<'a> {
@@ -67,43 +76,143 @@ impl<'a> TryFrom<&'a sfu_to_device::peek_info::CallLinkState> for CallLinkRespon
             restrictions: value.restrictions().into(),
             revoked: value.revoked(),
             expiration_unix_timestamp: value.expiration_unix_timestamp(),
+            encrypted_description: value.encrypted_description().as_bytes(),
+            encrypted_scheduled_start: value.encrypted_scheduled_start().as_bytes(),
         })
     }
 }
 
+/// The longest name, in bytes of UTF-8, that can be set on a call link.
+pub const MAX_NAME_BYTES: usize = 256;
+/// The longest description, in bytes of UTF-8, that can be set on a call link.
+pub const MAX_DESCRIPTION_BYTES: usize = 2048;
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CallLinkMetadataError {
+    #[error("call link name is longer than {MAX_NAME_BYTES} bytes")]
+    NameTooLong,
+    #[error("call link description is longer than {MAX_DESCRIPTION_BYTES} bytes")]
+    DescriptionTooLong,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallLinkState {
     pub name: String,
     pub restrictions: CallLinkRestrictions,
     pub revoked: bool,
     pub expiration: SystemTime,
+    pub description: String,
+    /// When the admin expects the call to start, if they've said.
+    pub scheduled_start: Option<SystemTime>,
 }
 
 impl CallLinkState {
     pub fn from_serialized(deserialized: CallLinkResponse<'_>, root_key: &CallLinkRootKey) -> Self {
-        let name = if deserialized.encrypted_name.is_empty() {
-            "".to_string()
-        } else {
-            base64
-                .decode(deserialized.encrypted_name)
-                .ok()
-                .and_then(|encrypted_bytes| root_key.decrypt(&encrypted_bytes).ok())
-                .and_then(|name_bytes| String::from_utf8(name_bytes).ok())
-                .unwrap_or_else(|| {
-                    warn!("encrypted name of call failed to decrypt to a valid string");
-                    Default::default()
-                })
-        };
+        let name = decrypt_string(deserialized.encrypted_name, root_key, "name");
+        let description =
+            decrypt_string(deserialized.encrypted_description, root_key, "description");
+        let scheduled_start = decrypt_field(deserialized.encrypted_scheduled_start, root_key)
+            .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+            .map(|bytes| SystemTime::UNIX_EPOCH + Duration::from_secs(u64::from_be_bytes(bytes)));
+        if scheduled_start.is_none() && !deserialized.encrypted_scheduled_start.is_empty() {
+            warn!("encrypted scheduled start of call failed to decrypt to a valid time");
+        }
         CallLinkState {
             name,
             restrictions: deserialized.restrictions,
             revoked: deserialized.revoked,
             expiration: SystemTime::UNIX_EPOCH
                 + Duration::from_secs(deserialized.expiration_unix_timestamp),
+            description,
+            scheduled_start,
         }
     }
 }
 
+/// Base64-decodes and decrypts an optional field; empty fields are `None` without any warning.
+fn decrypt_field(encrypted: &[u8], root_key: &CallLinkRootKey) -> Option<Vec<u8>> {
+    if encrypted.is_empty() {
+        return None;
+    }
+    base64
+        .decode(encrypted)
+        .ok()
+        .and_then(|encrypted_bytes| root_key.decrypt(&encrypted_bytes).ok())
+}
+
+fn decrypt_string(encrypted: &[u8], root_key: &CallLinkRootKey, field: &str) -> String {
+    if encrypted.is_empty() {
+        return "".to_string();
+    }
+    decrypt_field(encrypted, root_key)
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| {
+            warn!("encrypted {field} of call failed to decrypt to a valid string");
+            Default::default()
+        })
+}
+
+fn encrypt_string(
+    root_key: &CallLinkRootKey,
+    value: &str,
+    rng: impl RngCore + CryptoRng,
+) -> Vec<u8> {
+    if value.is_empty() {
+        vec![]
+    } else {
+        root_key.encrypt(value.as_bytes(), rng)
+    }
+}
+
+/// Encrypts a new name for [`CallLinkUpdateRequest::encrypted_name`].
+///
+/// An empty name stays empty, which clears the link's name.
+pub fn encrypt_name(
+    root_key: &CallLinkRootKey,
+    name: &str,
+    rng: impl RngCore + CryptoRng,
+) -> Result<Vec<u8>, CallLinkMetadataError> {
+    if name.len() > MAX_NAME_BYTES {
+        return Err(CallLinkMetadataError::NameTooLong);
+    }
+    Ok(encrypt_string(root_key, name, rng))
+}
+
+/// Encrypts a new description for [`CallLinkUpdateRequest::encrypted_description`].
+///
+/// An empty description stays empty, which clears the link's description.
+pub fn encrypt_description(
+    root_key: &CallLinkRootKey,
+    description: &str,
+    rng: impl RngCore + CryptoRng,
+) -> Result<Vec<u8>, CallLinkMetadataError> {
+    if description.len() > MAX_DESCRIPTION_BYTES {
+        return Err(CallLinkMetadataError::DescriptionTooLong);
+    }
+    Ok(encrypt_string(root_key, description, rng))
+}
+
+/// Encrypts a new scheduled start for [`CallLinkUpdateRequest::encrypted_scheduled_start`], to
+/// the second.
+///
+/// `None` becomes empty, which clears the link's scheduled start.
+pub fn encrypt_scheduled_start(
+    root_key: &CallLinkRootKey,
+    scheduled_start: Option<SystemTime>,
+    rng: impl RngCore + CryptoRng,
+) -> Vec<u8> {
+    match scheduled_start {
+        Some(scheduled_start) => {
+            let seconds = scheduled_start
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            root_key.encrypt(&seconds.to_be_bytes(), rng)
+        }
+        None => vec![],
+    }
+}
+
 // Use type that serializes to `{}` in JSON
 #[derive(Deserialize, Debug)]
 pub struct Empty {}
@@ -182,6 +291,14 @@ pub struct CallLinkUpdateRequest<'a> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub revoked: Option<bool>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::base64::Base64>")]
+    pub encrypted_description: Option<&'a [u8]>,
+
+    #[serde(rename = "scheduledStart", skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::base64::Base64>")]
+    pub encrypted_scheduled_start: Option<&'a [u8]>,
 }
 
 #[serde_as]
@@ -192,6 +309,17 @@ pub struct CallLinkDeleteRequest<'a> {
     pub admin_passkey: &'a [u8],
 }
 
+#[serde_as]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallLinkResetRequest<'a> {
+    #[serde_as(as = "serde_with::base64::Base64")]
+    admin_passkey: &'a [u8],
+
+    #[serde_as(as = "serde_with::hex::Hex")]
+    new_room_id: &'a [u8],
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_call_link(
     http_client: &dyn http::Client,
@@ -268,6 +396,56 @@ pub fn update_call_link(
     )
 }
 
+/// Moves a call link to `new_epoch`, and thus to a new room ID, keeping its name, restrictions,
+/// and admin passkey. Links without the new epoch stop working.
+///
+/// `root_key` should have the link's current epoch. The key itself doesn't change, since the
+/// name and description stay encrypted with it, so the new link is
+/// `root_key.with_epoch(Some(new_epoch))`.
+///
+/// This does not protect against someone who already has the root key; see [`CallLinkEpoch`].
+pub fn reset_call_link(
+    http_client: &dyn http::Client,
+    sfu_url: &str,
+    root_key: CallLinkRootKey,
+    new_epoch: CallLinkEpoch,
+    auth_presentation: &[u8],
+    admin_passkey: &[u8],
+    result_callback: ReadCallLinkResultCallback,
+) {
+    let new_root_key = root_key.clone().with_epoch(Some(new_epoch));
+    let new_room_id = new_root_key.derive_room_id();
+    http_client.send_request(
+        http::Request {
+            method: http::Method::Post,
+            url: format!("{}/reset", call_link_url_from_sfu_url(sfu_url)),
+            headers: HashMap::from_iter([
+                (
+                    "Authorization".to_string(),
+                    auth_header_from_auth_credential(auth_presentation),
+                ),
+                (
+                    "X-Room-Id".to_string(),
+                    hex::encode(root_key.derive_room_id()),
+                ),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ]),
+            body: Some(
+                serde_json::to_vec(&CallLinkResetRequest {
+                    admin_passkey,
+                    new_room_id: &new_room_id,
+                })
+                .expect("cannot fail to serialize"),
+            ),
+        },
+        Box::new(move |http_response| {
+            let result = http::parse_json_response::<CallLinkResponse>(http_response.as_ref())
+                .map(|response| CallLinkState::from_serialized(response, &new_root_key));
+            result_callback(result);
+        }),
+    )
+}
+
 pub fn delete_call_link(
     http_client: &dyn http::Client,
     sfu_url: &str,
@@ -306,7 +484,7 @@ pub mod ios {
 
     use super::*;
     use crate::lite::{
-        ffi::ios::{cstr, rtc_Bytes, rtc_OptionalU16, rtc_String},
+        ffi::ios::{cstr, rtc_Bytes, rtc_OptionalU16, rtc_OptionalU32, rtc_String, FromOrDefault},
         http,
         sfu::ios::rtc_sfu_Response,
     };
@@ -405,6 +583,53 @@ pub mod ios {
         }
     }
 
+    /// Wrapper around `CallLinkUrl::parse`
+    ///
+    /// # Safety
+    /// - `string` must be a valid, non-null C string
+    /// - `callback` must not be null.
+    #[no_mangle]
+    pub unsafe extern "C" fn rtc_calllinks_CallLinkUrl_parse(
+        string: *const c_char,
+        context: *mut c_void,
+        callback: extern "C" fn(context: *mut c_void, root_key: rtc_Bytes, epoch: rtc_OptionalU32),
+    ) -> bool {
+        let string = CStr::from_ptr(string);
+        let url = string
+            .to_str()
+            .ok()
+            .and_then(|s| CallLinkUrl::parse(s).ok());
+        match url {
+            Some(url) => {
+                callback(
+                    context,
+                    rtc_Bytes::from(url.root_key.bytes().as_slice()),
+                    rtc_OptionalU32::from_or_default(url.root_key.epoch().map(u32::from)),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[no_mangle]
+    pub extern "C" fn rtc_calllinks_CallLinkUrl_format(
+        root_key_bytes: rtc_Bytes,
+        epoch: rtc_OptionalU32,
+        context: *mut c_void,
+        callback: extern "C" fn(context: *mut c_void, result: rtc_String),
+    ) -> *const c_char {
+        match CallLinkRootKey::try_from(root_key_bytes.as_slice()) {
+            Ok(root_key) => {
+                let epoch = epoch.valid.then_some(CallLinkEpoch::from(epoch.value));
+                let url = CallLinkUrl::new(root_key.with_epoch(epoch));
+                callback(context, rtc_String::from(url.to_url_string().as_str()));
+                std::ptr::null()
+            }
+            Err(_) => cstr!("invalid root key").as_ptr(),
+        }
+    }
+
     #[repr(C)]
     #[derive(Default, Debug)]
     pub struct rtc_calllinks_CallLinkState<'a> {
@@ -615,14 +840,24 @@ pub mod ios {
                     } else {
                         Some(CStr::from_ptr(new_name))
                     };
-                    let encrypted_name = new_name.map(|name| {
-                        let name_bytes = name.to_bytes();
-                        if name_bytes.is_empty() {
-                            vec![]
-                        } else {
-                            link_root_key.encrypt(name_bytes, rand::rngs::OsRng)
+                    let encrypted_name = match new_name
+                        .map(|name| {
+                            let name = name.to_str().map_err(|_| "invalid new_name")?;
+                            encrypt_name(&link_root_key, name, rand::rngs::OsRng)
+                                .map_err(|_| "new_name too long")
+                        })
+                        .transpose()
+                    {
+                        Ok(encrypted_name) => encrypted_name,
+                        Err(e) => {
+                            error!("{}", e);
+                            delegate.handle_response(
+                                request_id,
+                                Err(http::ResponseStatus::INVALID_REQUEST),
+                            );
+                            return;
                         }
-                    });
+                    };
                     update_call_link(
                         http_client,
                         sfu_url,
@@ -637,6 +872,7 @@ pub mod ios {
                                 1 => Some(true),
                                 _ => None,
                             },
+                            ..CallLinkUpdateRequest::default()
                         },
                         Box::new(move |result| delegate.handle_response(request_id, result)),
                     )
@@ -691,3 +927,124 @@ pub mod ios {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trip() {
+        let root_key = CallLinkRootKey::generate(rand::thread_rng());
+        let scheduled_start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let json = serde_json::json!({
+            "name": base64.encode(encrypt_name(&root_key, "Weekly sync", rand::thread_rng()).unwrap()),
+            "restrictions": "adminApproval",
+            "revoked": false,
+            "expiration": 1_800_000_000u64,
+            "description": base64.encode(
+                encrypt_description(&root_key, "1. Introductions", rand::thread_rng()).unwrap()
+            ),
+            "scheduledStart": base64.encode(
+                encrypt_scheduled_start(&root_key, Some(scheduled_start), rand::thread_rng())
+            ),
+        })
+        .to_string();
+        let state = CallLinkState::from_serialized(serde_json::from_str(&json).unwrap(), &root_key);
+        assert_eq!("Weekly sync", state.name);
+        assert_eq!("1. Introductions", state.description);
+        assert_eq!(Some(scheduled_start), state.scheduled_start);
+
+        // Links that have never had a description or scheduled start.
+        let json = serde_json::json!({
+            "name": "",
+            "restrictions": "none",
+            "revoked": false,
+            "expiration": 1_800_000_000u64,
+        })
+        .to_string();
+        let state = CallLinkState::from_serialized(serde_json::from_str(&json).unwrap(), &root_key);
+        assert_eq!("", state.description);
+        assert_eq!(None, state.scheduled_start);
+    }
+
+    #[test]
+    fn metadata_length_limits() {
+        let root_key = CallLinkRootKey::generate(rand::thread_rng());
+        assert!(encrypt_name(&root_key, &"a".repeat(MAX_NAME_BYTES), rand::thread_rng()).is_ok());
+        assert_eq!(
+            Err(CallLinkMetadataError::NameTooLong),
+            encrypt_name(
+                &root_key,
+                &"a".repeat(MAX_NAME_BYTES + 1),
+                rand::thread_rng()
+            )
+        );
+        assert!(encrypt_description(
+            &root_key,
+            &"a".repeat(MAX_DESCRIPTION_BYTES),
+            rand::thread_rng()
+        )
+        .is_ok());
+        assert_eq!(
+            Err(CallLinkMetadataError::DescriptionTooLong),
+            encrypt_description(
+                &root_key,
+                &"a".repeat(MAX_DESCRIPTION_BYTES + 1),
+                rand::thread_rng()
+            )
+        );
+        // Clearing doesn't encrypt anything.
+        assert_eq!(Ok(vec![]), encrypt_name(&root_key, "", rand::thread_rng()));
+        assert!(encrypt_scheduled_start(&root_key, None, rand::thread_rng()).is_empty());
+    }
+
+    #[test]
+    fn read_and_reset_replayed_responses() {
+        let http_client = http::ReplayingClient::from_fixture("call_link.json");
+        let root_key =
+            CallLinkRootKey::try_from("bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz").unwrap();
+        let new_epoch = CallLinkEpoch::from(0x01020304);
+
+        let results = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let push_result = || -> ReadCallLinkResultCallback {
+            let results = results.clone();
+            Box::new(move |result| results.lock().unwrap().push(result))
+        };
+        read_call_link(
+            &http_client,
+            "https://sfu.example/",
+            root_key.clone(),
+            b"auth",
+            push_result(),
+        );
+        reset_call_link(
+            &http_client,
+            "https://sfu.example/",
+            root_key.clone(),
+            new_epoch,
+            b"auth",
+            b"passkey",
+            push_result(),
+        );
+        read_call_link(
+            &http_client,
+            "https://sfu.example/",
+            root_key.with_epoch(Some(new_epoch)),
+            b"auth",
+            push_result(),
+        );
+        assert!(http_client.remaining().is_empty());
+
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        let [before, reset, after] = results.try_into().unwrap();
+        let before = before.unwrap();
+        assert_eq!("", before.name);
+        assert_eq!(CallLinkRestrictions::AdminApproval, before.restrictions);
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_800_000_000),
+            before.expiration
+        );
+        assert_eq!(CallLinkRestrictions::None, reset.unwrap().restrictions);
+        assert_eq!(Err(http::ResponseStatus::from(404)), after.map(|_| ()));
+    }
+}
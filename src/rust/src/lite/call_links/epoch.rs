@@ -0,0 +1,95 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+use anyhow::anyhow;
+use rand::{CryptoRng, RngCore};
+
+use super::base16;
+
+/// Distinguishes successive rooms for the same root key, so that a call link can be reset (for
+/// example, after it was shared too widely) without losing its name, restrictions, or admin
+/// passkey.
+///
+/// Links from before epochs existed have no epoch at all.
+///
+/// An epoch is only 32 bits, and resetting keeps the root key, so a reset only cuts off people who
+/// have nothing but an old URL. Anyone who still holds the root key can find the new room by
+/// trying epochs until a peek succeeds; a link that has to shut such people out needs a new
+/// root key instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CallLinkEpoch(u32);
+
+impl CallLinkEpoch {
+    pub fn generate(mut rng: impl RngCore + CryptoRng) -> Self {
+        Self(rng.next_u32())
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn to_formatted_string(self) -> String {
+        format!(
+            "{:-^.2}",
+            base16::ConsonantBase16::from(self.to_be_bytes().as_slice())
+        )
+    }
+}
+
+impl From<u32> for CallLinkEpoch {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<CallLinkEpoch> for u32 {
+    fn from(value: CallLinkEpoch) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<&str> for CallLinkEpoch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let bytes: [u8; 4] = base16::ConsonantBase16::parse_with_separators(value, 2)
+            .map_err(|e| {
+                error!("Parsing error: {:?}", e);
+                anyhow!("invalid epoch string")
+            })?
+            .try_into()
+            .map_err(|_| anyhow!("invalid epoch length"))?;
+        Ok(Self(u32::from_be_bytes(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for value in [0, 1, 0x01020304, u32::MAX] {
+            let epoch = CallLinkEpoch::from(value);
+            let formatted = epoch.to_formatted_string();
+            assert_eq!(
+                epoch,
+                CallLinkEpoch::try_from(formatted.as_str()).unwrap(),
+                "{formatted}"
+            );
+        }
+        assert_eq!(
+            "bcbd-bfbg",
+            CallLinkEpoch::from(0x01020304).to_formatted_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(CallLinkEpoch::try_from("bcbd").is_err());
+        assert!(CallLinkEpoch::try_from("bcbd-bfbg-bhbk").is_err());
+        assert!(CallLinkEpoch::try_from("bcbd-bfba").is_err());
+    }
+}
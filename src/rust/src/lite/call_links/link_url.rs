@@ -0,0 +1,256 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+use super::{base16::ConsonantBase16, CallLinkEpoch, CallLinkRootKey};
+
+const HOST_AND_PATH: &str = "signal.link/call/";
+const SCHEMES: &[&str] = &["https", "sgnl"];
+
+const KEY_PARAM: &str = "key";
+const EPOCH_PARAM: &str = "epoch";
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CallLinkUrlError {
+    #[error("not a call link URL")]
+    NotACallLink,
+    #[error("call link URL has no key")]
+    MissingKey,
+    #[error("call link URL has an invalid key")]
+    InvalidKey,
+    #[error("call link URL has an invalid epoch")]
+    InvalidEpoch,
+    #[error("call link URL has a malformed parameter")]
+    MalformedParameter,
+    #[error("call link URL repeats the '{0}' parameter")]
+    DuplicateParameter(String),
+}
+
+/// Everything in a shareable call link, e.g. `https://signal.link/call/#key=bcdf-ghkm-...`.
+///
+/// The link's secrets are all in the fragment, which browsers don't send to servers.
+#[derive(Clone)]
+pub struct CallLinkUrl {
+    /// Includes the link's epoch, if it has one.
+    pub root_key: CallLinkRootKey,
+    /// Fragment parameters this version doesn't know about, kept so that links round-trip.
+    pub other_params: Vec<(String, String)>,
+}
+
+impl CallLinkUrl {
+    pub fn new(root_key: CallLinkRootKey) -> Self {
+        Self {
+            root_key,
+            other_params: vec![],
+        }
+    }
+
+    /// Parses a call link URL, tolerating surrounding or embedded whitespace (such as from
+    /// line wrapping), any letter case, and missing `-` separators in the key and epoch.
+    pub fn parse(url: &str) -> Result<Self, CallLinkUrlError> {
+        let url: String = url.chars().filter(|c| !c.is_whitespace()).collect();
+        let (location, fragment) = url.split_once('#').ok_or(CallLinkUrlError::NotACallLink)?;
+
+        let location = location.to_ascii_lowercase();
+        let host_and_path = match location.split_once("://") {
+            Some((scheme, rest)) if SCHEMES.contains(&scheme) => rest,
+            Some(_) => return Err(CallLinkUrlError::NotACallLink),
+            None => &location,
+        };
+        if host_and_path.trim_end_matches('/') != HOST_AND_PATH.trim_end_matches('/') {
+            return Err(CallLinkUrlError::NotACallLink);
+        }
+
+        let mut root_key = None;
+        let mut epoch = None;
+        let mut other_params = vec![];
+        for param in fragment.split('&').filter(|param| !param.is_empty()) {
+            let (name, value) = param
+                .split_once('=')
+                .ok_or(CallLinkUrlError::MalformedParameter)?;
+            let duplicate = || CallLinkUrlError::DuplicateParameter(name.to_string());
+            match name.to_ascii_lowercase().as_str() {
+                KEY_PARAM => {
+                    let key = CallLinkRootKey::try_from(
+                        parse_consonant_base16(value)
+                            .ok_or(CallLinkUrlError::InvalidKey)?
+                            .as_slice(),
+                    )
+                    .map_err(|_| CallLinkUrlError::InvalidKey)?;
+                    if root_key.replace(key).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                EPOCH_PARAM => {
+                    let bytes: [u8; 4] = parse_consonant_base16(value)
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .ok_or(CallLinkUrlError::InvalidEpoch)?;
+                    if epoch
+                        .replace(CallLinkEpoch::from(u32::from_be_bytes(bytes)))
+                        .is_some()
+                    {
+                        return Err(duplicate());
+                    }
+                }
+                _ => {
+                    if other_params
+                        .iter()
+                        .any(|(other, _)| other.eq_ignore_ascii_case(name))
+                    {
+                        return Err(duplicate());
+                    }
+                    other_params.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+
+        Ok(Self {
+            root_key: root_key
+                .ok_or(CallLinkUrlError::MissingKey)?
+                .with_epoch(epoch),
+            other_params,
+        })
+    }
+
+    // Not a Display implementation so we don't accidentally log it.
+    pub fn to_url_string(&self) -> String {
+        let mut url = format!(
+            "https://{}#{}={}",
+            HOST_AND_PATH,
+            KEY_PARAM,
+            self.root_key.to_formatted_string()
+        );
+        if let Some(epoch) = self.root_key.epoch() {
+            url += &format!("&{}={}", EPOCH_PARAM, epoch.to_formatted_string());
+        }
+        for (name, value) in &self.other_params {
+            url += &format!("&{}={}", name, value);
+        }
+        url
+    }
+}
+
+/// Accepts consonant base 16 with `-` between every two bytes, as formatted, or with no
+/// separators at all.
+fn parse_consonant_base16(value: &str) -> Option<Vec<u8>> {
+    let value = value.to_ascii_lowercase();
+    let result = if value.contains('-') {
+        ConsonantBase16::parse_with_separators(&value, 2)
+    } else {
+        ConsonantBase16::parse(&value)
+    };
+    result
+        .map_err(|e| {
+            warn!("Failed to parse call link URL parameter: {:?}", e);
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let root_key = CallLinkRootKey::generate(rand::thread_rng());
+        for epoch in [None, Some(0), Some(0x12345678)] {
+            let epoch = epoch.map(CallLinkEpoch::from);
+            let url = CallLinkUrl::new(root_key.clone().with_epoch(epoch)).to_url_string();
+            let parsed = CallLinkUrl::parse(&url).unwrap();
+            assert_eq!(root_key.bytes(), parsed.root_key.bytes(), "{url}");
+            assert_eq!(epoch, parsed.root_key.epoch(), "{url}");
+            assert!(parsed.other_params.is_empty());
+        }
+    }
+
+    #[test]
+    fn format() {
+        let root_key =
+            CallLinkRootKey::try_from("bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz").unwrap();
+        assert_eq!(
+            "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+            CallLinkUrl::new(root_key.clone()).to_url_string()
+        );
+        assert_eq!(
+            "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&epoch=bcbd-bfbg",
+            CallLinkUrl::new(root_key.with_epoch(Some(CallLinkEpoch::from(0x01020304))))
+                .to_url_string()
+        );
+    }
+
+    #[test]
+    fn tolerant_parsing() {
+        let expected = CallLinkRootKey::try_from("bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz")
+            .unwrap()
+            .bytes();
+        for url in [
+            "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+            "  HTTPS://Signal.Link/call/#key=BCDF-GHKM-NPQR-STXZ-BCDF-GHKM-NPQR-STXZ\n",
+            "https://signal.link/call/#key=bcdf-ghkm-npqr-\n  stxz-bcdf-ghkm-npqr-stxz",
+            "https://signal.link/call#key=bcdfghkmnpqrstxzbcdfghkmnpqrstxz",
+            "sgnl://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+            "signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&",
+        ] {
+            let parsed = CallLinkUrl::parse(url).unwrap_or_else(|e| panic!("{e}: {url:?}"));
+            assert_eq!(expected, parsed.root_key.bytes(), "{url:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_unknown_params() {
+        let url =
+            "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&future=Yes";
+        let parsed = CallLinkUrl::parse(url).unwrap();
+        assert_eq!(
+            vec![("future".to_string(), "Yes".to_string())],
+            parsed.other_params
+        );
+        assert_eq!(url, parsed.to_url_string());
+    }
+
+    #[test]
+    fn errors() {
+        for (url, expected) in [
+            (
+                "https://signal.group/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+                CallLinkUrlError::NotACallLink,
+            ),
+            (
+                "ftp://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+                CallLinkUrlError::NotACallLink,
+            ),
+            (
+                "https://signal.link/call/bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+                CallLinkUrlError::NotACallLink,
+            ),
+            ("https://signal.link/call/#", CallLinkUrlError::MissingKey),
+            (
+                "https://signal.link/call/#key=bcdf",
+                CallLinkUrlError::InvalidKey,
+            ),
+            (
+                "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxa",
+                CallLinkUrlError::InvalidKey,
+            ),
+            (
+                "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&epoch=bc",
+                CallLinkUrlError::InvalidEpoch,
+            ),
+            (
+                "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz",
+                CallLinkUrlError::DuplicateParameter("key".to_string()),
+            ),
+            (
+                "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&future=1&FUTURE=2",
+                CallLinkUrlError::DuplicateParameter("FUTURE".to_string()),
+            ),
+            (
+                "https://signal.link/call/#key=bcdf-ghkm-npqr-stxz-bcdf-ghkm-npqr-stxz&future",
+                CallLinkUrlError::MalformedParameter,
+            ),
+        ] {
+            assert_eq!(Some(expected), CallLinkUrl::parse(url).err(), "{url:?}");
+        }
+    }
+}
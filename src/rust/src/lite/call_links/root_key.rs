@@ -12,11 +12,12 @@ use hkdf::Hkdf;
 use rand::{CryptoRng, RngCore};
 use sha2::Sha256;
 
-use super::base16;
+use super::{base16, CallLinkEpoch};
 
 #[derive(Clone)]
 pub struct CallLinkRootKey {
     bytes: [u8; 16],
+    epoch: Option<CallLinkEpoch>,
 }
 
 #[derive(Debug)]
@@ -50,7 +51,16 @@ impl CallLinkRootKey {
             rng.fill_bytes(&mut bytes);
         }
 
-        Self { bytes }
+        Self { bytes, epoch: None }
+    }
+
+    /// Returns the same key for a different epoch, which only changes the derived room ID.
+    pub fn with_epoch(self, epoch: Option<CallLinkEpoch>) -> Self {
+        Self { epoch, ..self }
+    }
+
+    pub fn epoch(&self) -> Option<CallLinkEpoch> {
+        self.epoch
     }
 
     pub fn generate_admin_passkey(mut rng: impl RngCore + CryptoRng) -> Vec<u8> {
@@ -64,10 +74,18 @@ impl CallLinkRootKey {
     pub fn derive_room_id(&self) -> Vec<u8> {
         // There are no constraints on the room ID, other than not being unreasonably long.
         // It's never shown to users, but it does appear in HTTP requests to the calling server.
+        // Keys without an epoch keep the room IDs they had before epochs existed.
         let mut room_id_bytes = [0u8; 32];
+        let epoch_bytes = self.epoch.map(CallLinkEpoch::to_be_bytes);
         Hkdf::<Sha256>::new(None, &self.bytes)
-            .expand(
-                b"20230501-Signal-CallLinkRootKey-RoomId",
+            .expand_multi_info(
+                &[
+                    b"20230501-Signal-CallLinkRootKey-RoomId".as_slice(),
+                    epoch_bytes
+                        .as_ref()
+                        .map(|bytes| bytes.as_slice())
+                        .unwrap_or_default(),
+                ],
                 &mut room_id_bytes,
             )
             .expect("valid output length");
@@ -167,7 +185,7 @@ impl TryFrom<&[u8]> for CallLinkRootKey {
             bail!("invalid root key adjacent bytes");
         }
 
-        Ok(Self { bytes })
+        Ok(Self { bytes, epoch: None })
     }
 }
 
@@ -185,6 +203,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_epochs_change_room_id() {
+        let key = CallLinkRootKey::generate(rand::thread_rng());
+        let room_id = key.derive_room_id();
+        let first_epoch = key.clone().with_epoch(Some(CallLinkEpoch::from(1)));
+        let second_epoch = key.clone().with_epoch(Some(CallLinkEpoch::from(2)));
+        assert_ne!(room_id, first_epoch.derive_room_id());
+        assert_ne!(room_id, second_epoch.derive_room_id());
+        assert_ne!(first_epoch.derive_room_id(), second_epoch.derive_room_id());
+        assert_eq!(room_id, first_epoch.with_epoch(None).derive_room_id());
+
+        // Everything else stays the same.
+        assert_eq!(key.bytes(), second_epoch.bytes());
+        let ciphertext = key.encrypt(b"Secret Hideout", rand::thread_rng());
+        assert_eq!(
+            b"Secret Hideout".as_slice(),
+            second_epoch.decrypt(&ciphertext).unwrap().as_slice()
+        );
+    }
+
     #[test]
     fn test_encrypt() {
         let key = CallLinkRootKey::generate(rand::thread_rng());
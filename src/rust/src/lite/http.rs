@@ -6,9 +6,24 @@
 //! Make calls to the App to do HTTP requests
 //! and define common types like Method, Response, Client, etc.
 
+#[cfg(feature = "native_http")]
+mod native;
+#[cfg(any(test, feature = "sim_http"))]
+mod recording;
+mod retry;
+#[cfg(feature = "native_http")]
+pub use native::{NativeHttpClient, NativeHttpClientConfig, SpkiPin, TrustRoots};
+#[cfg(any(test, feature = "sim_http"))]
+pub use recording::{
+    load_recording, RecordedBody, RecordedExchange, RecordedResponse, RecordingClient,
+    ReplayingClient,
+};
+pub use retry::{CancelHandle, RequestPolicy, RetryMetrics, RetryingClient, ScopedClient};
+
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use serde::Deserialize;
@@ -33,9 +48,21 @@ pub struct Request {
 #[derive(Clone, Debug)]
 pub struct Response {
     pub status: ResponseStatus,
+    /// Not every platform passes these along, so they may be empty.
+    pub headers: HashMap<String, String>,
     pub body: Vec<u8>,
 }
 
+impl Response {
+    /// Looks up a header by name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct ResponseStatus {
     pub code: u16,
@@ -66,10 +93,12 @@ impl ResponseStatus {
     // Artificial codes not actually returned by the server
     pub const INVALID_CLIENT_AUTH: Self = Self { code: 601 };
     pub const REQUEST_FAILED: Self = Self { code: 602 };
+    pub const INVALID_REQUEST: Self = Self { code: 603 };
     pub const INVALID_RESPONSE_BODY_UTF8: Self = Self { code: 701 };
     pub const INVALID_RESPONSE_BODY_JSON: Self = Self { code: 702 };
     pub const CALL_LINK_EXPIRED: Self = Self { code: 703 };
     pub const CALL_LINK_INVALID: Self = Self { code: 704 };
+    pub const CALL_LINK_REVOKED: Self = Self { code: 705 };
 }
 
 impl std::fmt::Display for ResponseStatus {
@@ -136,6 +165,10 @@ pub type ResponseCallback = Box<dyn FnOnce(Option<Response>) + Send>;
 /// Apps should use a platform-specific Client impl.
 pub trait Client {
     fn send_request(&self, request: Request, callback: ResponseCallback);
+
+    /// Drops the callbacks of any requests that haven't completed yet, if the client supports
+    /// cancellation.
+    fn cancel_pending_requests(&self) {}
 }
 
 /// Platform-specific methods that must be provided by
@@ -193,6 +226,27 @@ impl DelegatingClient {
                 request_id
             );
         }
+        self.expire_unanswered(Instant::now());
+    }
+
+    /// Fails any requests the app has stopped answering. Checked whenever a request is sent or
+    /// answered, so a client that's still in use can't hold on to old callbacks.
+    fn expire_unanswered(&self, now: Instant) {
+        let expired = {
+            let mut response_callbacks = self
+                .response_callbacks
+                .lock()
+                .expect("http:DelegatingClient:response_callbacks lock");
+            response_callbacks.take_expired(now)
+        };
+        for (expired_request_id, response_callback) in expired {
+            warn!(
+                "http:DelegatingClient: no response for request {} after {:?}; giving up",
+                expired_request_id,
+                ResponseCallbacks::MAX_RESPONSE_WAIT
+            );
+            response_callback(None);
+        }
     }
 }
 
@@ -203,12 +257,14 @@ impl Client for DelegatingClient {
             "  url: {} method: {:?} headers: {:?}",
             request.url, request.method, request.headers
         );
+        let now = Instant::now();
+        self.expire_unanswered(now);
         let request_id = {
             let mut response_callbacks = self
                 .response_callbacks
                 .lock()
                 .expect("http:DelegatingClient:response_callbacks lock");
-            response_callbacks.push(response_callback)
+            response_callbacks.push(response_callback, now)
         };
         let delegate = self
             .delegate
@@ -220,21 +276,45 @@ impl Client for DelegatingClient {
 
 #[derive(Default)]
 struct ResponseCallbacks {
-    response_callback_by_request_id: HashMap<u32, ResponseCallback>,
+    response_callback_by_request_id: HashMap<u32, (ResponseCallback, Instant)>,
     next_request_id: u32,
 }
 
 impl ResponseCallbacks {
-    fn push(&mut self, response_callback: ResponseCallback) -> u32 {
+    /// Requests the app hasn't answered by now are treated as failed, so that their callbacks
+    /// (and whatever they hold on to) don't live forever. Well past any app's own HTTP timeout.
+    const MAX_RESPONSE_WAIT: Duration = Duration::from_secs(120);
+
+    fn push(&mut self, response_callback: ResponseCallback, now: Instant) -> u32 {
         let request_id = self.next_request_id;
         self.next_request_id = self.next_request_id.wrapping_add(1);
         self.response_callback_by_request_id
-            .insert(request_id, response_callback);
+            .insert(request_id, (response_callback, now));
         request_id
     }
 
+    /// Removes and returns any callbacks that have waited longer than
+    /// [`Self::MAX_RESPONSE_WAIT`], to be called with no response outside the lock.
+    fn take_expired(&mut self, now: Instant) -> Vec<(u32, ResponseCallback)> {
+        let expired_ids: Vec<u32> = self
+            .response_callback_by_request_id
+            .iter()
+            .filter(|(_, (_, sent))| now.saturating_duration_since(*sent) > Self::MAX_RESPONSE_WAIT)
+            .map(|(request_id, _)| *request_id)
+            .collect();
+        expired_ids
+            .into_iter()
+            .filter_map(|request_id| {
+                self.pop(request_id)
+                    .map(|response_callback| (request_id, response_callback))
+            })
+            .collect()
+    }
+
     fn pop(&mut self, request_id: u32) -> Option<ResponseCallback> {
-        self.response_callback_by_request_id.remove(&request_id)
+        self.response_callback_by_request_id
+            .remove(&request_id)
+            .map(|(response_callback, _)| response_callback)
     }
 }
 
@@ -304,6 +384,7 @@ pub mod ios {
     #[derive(Debug)]
     pub struct rtc_http_Response<'a> {
         pub status_code: u16,
+        pub headers: rtc_http_Headers<'a>,
         pub body: rtc_Bytes<'a>,
     }
 
@@ -336,8 +417,19 @@ pub mod ios {
         info!("rtc_http_Client_received_response():");
 
         if let Some(client) = client.as_ref() {
+            let headers = if response.headers.ptr.is_null() {
+                &[]
+            } else {
+                std::slice::from_raw_parts(response.headers.ptr, response.headers.count)
+            };
             let response = Some(http::Response {
                 status: response.status_code.into(),
+                headers: headers
+                    .iter()
+                    .filter_map(|header| {
+                        Some((header.name.to_string()?, header.value.to_string()?))
+                    })
+                    .collect(),
                 body: response.body.to_vec(),
             });
             client.received_response(request_id, response);
@@ -398,145 +490,69 @@ pub mod ios {
 
 #[cfg(feature = "sim_http")]
 pub mod sim {
-    use std::{io::Read, sync::Arc};
-
-    use crate::{
-        common::actor::{Actor, Stopper},
-        lite::http,
-    };
+    use super::native::{NativeHttpClient, NativeHttpClientConfig};
+    use crate::lite::http;
 
+    /// A [`NativeHttpClient`] for tests and command-line tools, which accepts any server
+    /// certificate so that it works with local servers.
     #[derive(Clone)]
-    pub struct HttpClient {
-        actor: Actor<()>,
-    }
+    pub struct HttpClient(NativeHttpClient);
 
     impl HttpClient {
         pub fn start() -> Self {
-            rustls::crypto::ring::default_provider()
-                .install_default()
-                .expect("Failed to install rustls crypto provider");
-            Self {
-                actor: Actor::start("HttpClient", Stopper::new(), |_| Ok(())).unwrap(),
-            }
+            Self(
+                NativeHttpClient::start(NativeHttpClientConfig {
+                    accept_any_certificate_for_testing: true,
+                    ..NativeHttpClientConfig::default()
+                })
+                .expect("Failed to start HttpClient"),
+            )
         }
     }
 
     impl http::Client for HttpClient {
         fn send_request(&self, request: http::Request, response_callback: http::ResponseCallback) {
-            let http::Request {
-                method,
-                url,
-                headers,
-                body,
-            } = request;
-
-            self.actor.send(move |_| {
-                let mut tls_config = rustls::client::ClientConfig::builder()
-                    .with_root_certificates(rustls::RootCertStore::empty())
-                    .with_no_client_auth();
-                tls_config
-                    .dangerous()
-                    .set_certificate_verifier(Arc::new(ServerCertVerifier::new(
-                        rustls::crypto::ring::default_provider(),
-                    )));
-                let agent = ureq::builder().tls_config(Arc::new(tls_config)).build();
-
-                let mut request = match method {
-                    http::Method::Get => agent.get(&url),
-                    http::Method::Put => agent.put(&url),
-                    http::Method::Delete => agent.delete(&url),
-                    http::Method::Post => agent.post(&url),
-                };
-                for (key, value) in headers.iter() {
-                    request = request.set(key, value);
-                }
-                let request_result = match body {
-                    Some(body) => request.send_bytes(&body),
-                    None => request.call(),
-                };
-                match request_result {
-                    Ok(response) => {
-                        let status_code = response.status();
-                        let mut body = Vec::new();
-                        if response.into_reader().read_to_end(&mut body).is_ok() {
-                            response_callback(Some(http::Response {
-                                status: status_code.into(),
-                                body,
-                            }));
-                        } else {
-                            response_callback(None);
-                        }
-                    }
-                    Err(ureq::Error::Status(status_code, response)) => {
-                        let mut body = Vec::new();
-                        if response.into_reader().read_to_end(&mut body).is_ok() {
-                            response_callback(Some(http::Response {
-                                status: status_code.into(),
-                                body,
-                            }));
-                        } else {
-                            response_callback(None);
-                        }
-                    }
-                    Err(ureq::Error::Transport(_)) => {
-                        response_callback(None);
-                    }
-                }
-            });
+            self.0.send_request(request, response_callback)
         }
     }
+}
 
-    #[derive(Debug)]
-    struct ServerCertVerifier(rustls::crypto::CryptoProvider);
-
-    impl ServerCertVerifier {
-        pub fn new(provider: rustls::crypto::CryptoProvider) -> Self {
-            Self(provider)
-        }
-    }
-
-    impl rustls::client::danger::ServerCertVerifier for ServerCertVerifier {
-        fn verify_server_cert(
-            &self,
-            _end_entity: &rustls::pki_types::CertificateDer<'_>,
-            _intermediates: &[rustls::pki_types::CertificateDer<'_>],
-            _server_name: &rustls::pki_types::ServerName<'_>,
-            _ocsp: &[u8],
-            _now: rustls::pki_types::UnixTime,
-        ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
-            Ok(rustls::client::danger::ServerCertVerified::assertion())
-        }
-
-        fn verify_tls12_signature(
-            &self,
-            message: &[u8],
-            cert: &rustls::pki_types::CertificateDer<'_>,
-            dss: &rustls::DigitallySignedStruct,
-        ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-            rustls::crypto::verify_tls12_signature(
-                message,
-                cert,
-                dss,
-                &self.0.signature_verification_algorithms,
-            )
-        }
-
-        fn verify_tls13_signature(
-            &self,
-            message: &[u8],
-            cert: &rustls::pki_types::CertificateDer<'_>,
-            dss: &rustls::DigitallySignedStruct,
-        ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-            rustls::crypto::verify_tls13_signature(
-                message,
-                cert,
-                dss,
-                &self.0.signature_verification_algorithms,
-            )
-        }
-
-        fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-            self.0.signature_verification_algorithms.supported_schemes()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unanswered_response_callbacks_expire() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut response_callbacks = ResponseCallbacks::default();
+        let start = Instant::now();
+
+        let first_id = response_callbacks.push(
+            Box::new(move |response| {
+                let _ = sender.send(response.is_none());
+            }),
+            start,
+        );
+        let second_id = response_callbacks.push(
+            Box::new(|_| {}),
+            start + ResponseCallbacks::MAX_RESPONSE_WAIT,
+        );
+        assert_ne!(first_id, second_id);
+        assert!(response_callbacks
+            .take_expired(start + ResponseCallbacks::MAX_RESPONSE_WAIT)
+            .is_empty());
+
+        let expired = response_callbacks
+            .take_expired(start + ResponseCallbacks::MAX_RESPONSE_WAIT + Duration::from_secs(1));
+        assert_eq!(
+            vec![first_id],
+            expired.iter().map(|(id, _)| *id).collect::<Vec<_>>()
+        );
+        for (_, response_callback) in expired {
+            response_callback(None);
         }
+        assert_eq!(Ok(true), receiver.try_recv());
+        assert!(response_callbacks.pop(first_id).is_none());
+        assert!(response_callbacks.pop(second_id).is_some());
     }
 }
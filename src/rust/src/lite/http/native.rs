@@ -0,0 +1,519 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! An HTTP client for desktop and server builds that don't have an app to make requests for
+//! them, built on ureq and rustls.
+
+use std::{
+    io::Read,
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::CryptoProvider,
+    pki_types::{pem::PemObject, CertificateDer, ServerName, UnixTime},
+    DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{common::Result, lite::http};
+
+/// Which certificate authorities to trust.
+#[derive(Clone, Debug, Default)]
+pub enum TrustRoots {
+    /// The operating system's certificate store.
+    #[default]
+    System,
+    /// PEM-encoded certificates, such as a private CA for a self-hosted SFU.
+    Pem(Vec<u8>),
+    PemFile(PathBuf),
+}
+
+/// Requires the server for `host` to present a certificate whose public key matches, in
+/// addition to the certificate being trusted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpkiPin {
+    pub host: String,
+    /// The SHA-256 of the DER-encoded SubjectPublicKeyInfo, the same as used by HPKP.
+    pub sha256: [u8; 32],
+}
+
+impl SpkiPin {
+    /// Accepts the base64 form printed by
+    /// `openssl x509 -pubkey -noout | openssl pkey -pubin -outform der | openssl dgst -sha256 -binary | base64`,
+    /// optionally prefixed with `sha256/`.
+    pub fn from_base64(host: &str, pin: &str) -> Result<Self> {
+        let pin = pin.strip_prefix("sha256/").unwrap_or(pin);
+        let sha256 = base64
+            .decode(pin)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow!("SPKI pin should be a base64-encoded SHA-256 hash"))?;
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            sha256,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NativeHttpClientConfig {
+    pub trust_roots: TrustRoots,
+    pub spki_pins: Vec<SpkiPin>,
+    /// An `http://`, `https://`, `socks5://`, or `socks4://` URL, optionally with
+    /// `user:password@`.
+    pub proxy: Option<String>,
+    /// How many requests can be in progress at once; the rest wait their turn.
+    pub max_concurrent_requests: usize,
+    pub connect_timeout: Duration,
+    /// The limit for the whole request, including connecting and reading the response.
+    pub request_timeout: Duration,
+    /// Skips certificate verification entirely, for talking to local test servers.
+    #[cfg(feature = "sim_http")]
+    pub accept_any_certificate_for_testing: bool,
+}
+
+impl Default for NativeHttpClientConfig {
+    fn default() -> Self {
+        Self {
+            trust_roots: TrustRoots::default(),
+            spki_pins: vec![],
+            proxy: None,
+            max_concurrent_requests: 4,
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            #[cfg(feature = "sim_http")]
+            accept_any_certificate_for_testing: false,
+        }
+    }
+}
+
+/// Kept as data rather than a closure so that the callback can still be answered if the
+/// workers are gone.
+struct Job {
+    request: http::Request,
+    response_callback: http::ResponseCallback,
+}
+
+/// Sends requests from a fixed pool of worker threads, which share one ureq agent so that
+/// connections are reused.
+///
+/// The workers exit once every clone of the client has been dropped and the queued requests
+/// are done.
+#[derive(Clone)]
+pub struct NativeHttpClient {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl NativeHttpClient {
+    pub fn start(config: NativeHttpClientConfig) -> Result<Self> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let verifier = server_cert_verifier(&config, provider.clone())?;
+        let tls_config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier)
+            .with_no_client_auth();
+
+        let mut agent_builder = ureq::AgentBuilder::new()
+            .tls_config(Arc::new(tls_config))
+            .timeout_connect(config.connect_timeout)
+            .timeout(config.request_timeout);
+        if let Some(proxy) = &config.proxy {
+            agent_builder = agent_builder
+                .proxy(ureq::Proxy::new(proxy).map_err(|e| anyhow!("invalid proxy URL: {}", e))?);
+        }
+        let agent = agent_builder.build();
+
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..config.max_concurrent_requests.max(1) {
+            let agent = agent.clone();
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("HttpClient-{}", index))
+                .spawn(move || loop {
+                    let job = receiver
+                        .lock()
+                        .expect("http:NativeHttpClient:jobs lock")
+                        .recv();
+                    match job {
+                        Ok(Job {
+                            request,
+                            response_callback,
+                        }) => send_with_agent(&agent, request, response_callback),
+                        Err(mpsc::RecvError) => break,
+                    }
+                })?;
+        }
+        Ok(Self { jobs })
+    }
+}
+
+impl http::Client for NativeHttpClient {
+    fn send_request(&self, request: http::Request, response_callback: http::ResponseCallback) {
+        let job = Job {
+            request,
+            response_callback,
+        };
+        if let Err(mpsc::SendError(job)) = self.jobs.send(job) {
+            error!("http:NativeHttpClient: workers have stopped");
+            (job.response_callback)(None);
+        }
+    }
+}
+
+fn send_with_agent(
+    agent: &ureq::Agent,
+    request: http::Request,
+    response_callback: http::ResponseCallback,
+) {
+    let http::Request {
+        method,
+        url,
+        headers,
+        body,
+    } = request;
+
+    let mut request = match method {
+        http::Method::Get => agent.get(&url),
+        http::Method::Put => agent.put(&url),
+        http::Method::Delete => agent.delete(&url),
+        http::Method::Post => agent.post(&url),
+    };
+    for (key, value) in headers.iter() {
+        request = request.set(key, value);
+    }
+    let request_result = match body {
+        Some(body) => request.send_bytes(&body),
+        None => request.call(),
+    };
+    let response = match request_result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(e)) => {
+            warn!("http:NativeHttpClient: request failed: {}", e);
+            response_callback(None);
+            return;
+        }
+    };
+    let status_code = response.status();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name, value))
+        })
+        .collect();
+    let mut body = Vec::new();
+    if response.into_reader().read_to_end(&mut body).is_ok() {
+        response_callback(Some(http::Response {
+            status: status_code.into(),
+            headers,
+            body,
+        }));
+    } else {
+        response_callback(None);
+    }
+}
+
+fn server_cert_verifier(
+    config: &NativeHttpClientConfig,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn ServerCertVerifier>> {
+    #[cfg(feature = "sim_http")]
+    if config.accept_any_certificate_for_testing {
+        warn!("http:NativeHttpClient: accepting any server certificate (for testing only!)");
+        return Ok(Arc::new(AcceptAnyCertificate(provider)));
+    }
+
+    let roots = load_trust_roots(&config.trust_roots)?;
+    let verifier =
+        rustls::client::WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+            .build()?;
+    if config.spki_pins.is_empty() {
+        Ok(verifier)
+    } else {
+        Ok(Arc::new(PinningVerifier {
+            inner: verifier,
+            pins: config.spki_pins.clone(),
+        }))
+    }
+}
+
+fn load_trust_roots(trust_roots: &TrustRoots) -> Result<RootCertStore> {
+    let certs: Vec<CertificateDer<'static>> = match trust_roots {
+        TrustRoots::System => {
+            let result = rustls_native_certs::load_native_certs();
+            for error in &result.errors {
+                warn!(
+                    "http:NativeHttpClient: problem loading system certificates: {}",
+                    error
+                );
+            }
+            result.certs
+        }
+        TrustRoots::Pem(pem) => CertificateDer::pem_slice_iter(pem)
+            .collect::<std::result::Result<_, _>>()
+            .context("invalid PEM certificates")?,
+        TrustRoots::PemFile(path) => CertificateDer::pem_file_iter(path)
+            .and_then(|certs| certs.collect::<std::result::Result<_, _>>())
+            .with_context(|| format!("cannot read certificates from {}", path.display()))?,
+    };
+    let mut roots = RootCertStore::empty();
+    let (added, ignored) = roots.add_parsable_certificates(certs);
+    if ignored > 0 {
+        warn!(
+            "http:NativeHttpClient: ignored {} invalid certificate(s)",
+            ignored
+        );
+    }
+    if added == 0 {
+        return Err(anyhow!(
+            "no trusted certificates found in {:?}",
+            trust_roots
+        ));
+    }
+    Ok(roots)
+}
+
+/// Finds the SubjectPublicKeyInfo in a DER-encoded X.509 certificate, without validating the
+/// rest of it (that's up to the wrapped verifier).
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const EXPLICIT_VERSION: u8 = 0xA0;
+
+    struct Element<'a> {
+        tag: u8,
+        whole: &'a [u8],
+        contents: &'a [u8],
+        rest: &'a [u8],
+    }
+
+    /// Splits off one DER element from the front of `input`.
+    fn split_element(input: &[u8]) -> Option<Element<'_>> {
+        let (&tag, &first_length_byte) = (input.first()?, input.get(1)?);
+        let (header_size, length) = if first_length_byte < 0x80 {
+            (2, first_length_byte as usize)
+        } else {
+            let length_size = (first_length_byte & 0x7f) as usize;
+            if length_size == 0 || length_size > std::mem::size_of::<u32>() {
+                return None;
+            }
+            let length = input
+                .get(2..2 + length_size)?
+                .iter()
+                .fold(0usize, |length, &byte| (length << 8) | byte as usize);
+            (2 + length_size, length)
+        };
+        let (whole, rest) = input.split_at_checked(header_size.checked_add(length)?)?;
+        Some(Element {
+            tag,
+            whole,
+            contents: &whole[header_size..],
+            rest,
+        })
+    }
+
+    let certificate = split_element(certificate).filter(|e| e.tag == SEQUENCE)?;
+    let tbs_certificate = split_element(certificate.contents).filter(|e| e.tag == SEQUENCE)?;
+    let mut fields = tbs_certificate.contents;
+    let first = split_element(fields)?;
+    if first.tag == EXPLICIT_VERSION {
+        fields = first.rest;
+    }
+    // serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        fields = split_element(fields)?.rest;
+    }
+    split_element(fields)
+        .filter(|e| e.tag == SEQUENCE)
+        .map(|e| e.whole)
+}
+
+#[derive(Debug)]
+struct PinningVerifier {
+    inner: Arc<rustls::client::WebPkiServerVerifier>,
+    pins: Vec<SpkiPin>,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let verified =
+            self.inner
+                .verify_server_cert(end_entity, intermediates, server_name, ocsp, now)?;
+
+        let host = match server_name {
+            ServerName::DnsName(name) => name.as_ref().to_ascii_lowercase(),
+            other => other.to_str().to_string(),
+        };
+        let mut pins_for_host = self.pins.iter().filter(|pin| pin.host == host).peekable();
+        if pins_for_host.peek().is_none() {
+            return Ok(verified);
+        }
+        let spki_hash: [u8; 32] =
+            Sha256::digest(subject_public_key_info(end_entity.as_ref()).ok_or(
+                rustls::Error::InvalidCertificate(rustls::CertificateError::BadEncoding),
+            )?)
+            .into();
+        if pins_for_host.any(|pin| pin.sha256 == spki_hash) {
+            Ok(verified)
+        } else {
+            warn!(
+                "http:NativeHttpClient: certificate for {} doesn't match any pinned key",
+                host
+            );
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Checks signatures (so the handshake is well-formed) but trusts any certificate.
+#[cfg(feature = "sim_http")]
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+#[cfg(feature = "sim_http")]
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A self-signed certificate for sfu.example.
+    const TEST_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBmzCCAUGgAwIBAgIURVG1SIKehaq8RadTXh+IQyWSoUAwCgYIKoZIzj0EAwIw
+FjEUMBIGA1UEAwwLc2Z1LmV4YW1wbGUwIBcNMjYxMDE5MDgzODQxWhgPMjEyNjA5
+MjUwODM4NDFaMBYxFDASBgNVBAMMC3NmdS5leGFtcGxlMFkwEwYHKoZIzj0CAQYI
+KoZIzj0DAQcDQgAEX3UQ5WYUZ/cLQNouPuFwJ5jiY2mULmpyd+4OYPPXEzF+VbwX
+ycf58wXUXdjxGFWQhNNf9mHzKdfV1H0pnghsgaNrMGkwHQYDVR0OBBYEFMvwuqgP
+UH58R6YdxKo/2mUmzaJaMB8GA1UdIwQYMBaAFMvwuqgPUH58R6YdxKo/2mUmzaJa
+MA8GA1UdEwEB/wQFMAMBAf8wFgYDVR0RBA8wDYILc2Z1LmV4YW1wbGUwCgYIKoZI
+zj0EAwIDSAAwRQIgQKxE/b8e1FjwtS5OF6yy/w2j2eh/PjEbAOsFeazganwCIQDI
+nxaQKBxaxoe1WwJIn1z90IxBIR+PhXjm6AZ7X/U/lQ==
+-----END CERTIFICATE-----
+";
+
+    // From openssl, as in the SpkiPin::from_base64 docs.
+    const TEST_CERTIFICATE_PIN: &str = "+U1NwBJAQ+J8d5SYzEv8auNKHPYEpFo/mwa7NLMO8uQ=";
+
+    #[test]
+    fn spki_pin_matches_openssl() {
+        let certificate = CertificateDer::from_pem_slice(TEST_CERTIFICATE.as_bytes()).unwrap();
+        let spki = subject_public_key_info(certificate.as_ref()).unwrap();
+        let pin = SpkiPin::from_base64("SFU.example", TEST_CERTIFICATE_PIN).unwrap();
+        assert_eq!("sfu.example", pin.host);
+        assert_eq!(pin.sha256, <[u8; 32]>::from(Sha256::digest(spki)));
+
+        assert_eq!(
+            pin,
+            SpkiPin::from_base64("sfu.example", &format!("sha256/{}", TEST_CERTIFICATE_PIN))
+                .unwrap()
+        );
+        assert!(SpkiPin::from_base64("sfu.example", "AAAA").is_err());
+    }
+
+    #[test]
+    fn subject_public_key_info_rejects_garbage() {
+        let certificate = CertificateDer::from_pem_slice(TEST_CERTIFICATE.as_bytes()).unwrap();
+        let certificate = certificate.as_ref();
+        for length in 0..certificate.len() {
+            assert_eq!(
+                None,
+                subject_public_key_info(&certificate[..length]),
+                "{length}"
+            );
+        }
+        assert_eq!(None, subject_public_key_info(&[0x30, 0x84, 0xff, 0xff]));
+    }
+
+    #[test]
+    fn trust_roots_from_pem() {
+        let roots = load_trust_roots(&TrustRoots::Pem(TEST_CERTIFICATE.as_bytes().to_vec()));
+        assert_eq!(1, roots.unwrap().len());
+        assert!(load_trust_roots(&TrustRoots::Pem(vec![])).is_err());
+        assert!(load_trust_roots(&TrustRoots::PemFile("/nonexistent.pem".into())).is_err());
+    }
+}
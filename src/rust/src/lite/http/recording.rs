@@ -0,0 +1,420 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Records HTTP traffic to a file and replays it, so that tests can use real server responses.
+//!
+//! A recording is a JSON array of exchanges:
+//!
+//! ```json
+//! [{
+//!   "method": "GET",
+//!   "url": "https://sfu.example/v2/conference/participants",
+//!   "headers": {"Authorization": "<redacted>", "X-Room-Id": "..."},
+//!   "body": null,
+//!   "response": {"status": 200, "body": "{\"conferenceId\": ...}"}
+//! }]
+//! ```
+//!
+//! Bodies that aren't valid UTF-8 are written as `{"base64": "..."}` instead. A `null` response
+//! means the request failed without one. Secrets in JSON request bodies, such as a call link's
+//! `adminPasskey`, are redacted like the `Authorization` header.
+//!
+//! Recordings checked in for tests live in `tests/recordings`.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::{Client, Method, Request, Response, ResponseCallback};
+
+const REDACTED: &str = "<redacted>";
+// Top-level fields of JSON request bodies that are as sensitive as credentials.
+const REDACTED_BODY_FIELDS: &[&str] = &["adminPasskey"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordedBody {
+    Text(String),
+    Binary {
+        #[serde(rename = "base64")]
+        encoded: String,
+    },
+}
+
+impl From<&[u8]> for RecordedBody {
+    fn from(body: &[u8]) -> Self {
+        match std::str::from_utf8(body) {
+            Ok(text) => Self::Text(text.to_string()),
+            Err(_) => Self::Binary {
+                encoded: base64.encode(body),
+            },
+        }
+    }
+}
+
+impl RecordedBody {
+    fn redacted(body: &[u8]) -> Self {
+        if let Ok(serde_json::Value::Object(mut fields)) = serde_json::from_slice(body) {
+            let mut redacted = false;
+            for name in REDACTED_BODY_FIELDS {
+                if let Some(value) = fields.get_mut(*name) {
+                    *value = REDACTED.into();
+                    redacted = true;
+                }
+            }
+            if redacted {
+                return Self::Text(serde_json::Value::Object(fields).to_string());
+            }
+        }
+        Self::from(body)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Text(text) => text.as_bytes().to_vec(),
+            // Recordings are test fixtures; a bad one should fail loudly.
+            Self::Binary { encoded } => base64
+                .decode(encoded)
+                .expect("recorded body should be valid base64"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    pub body: RecordedBody,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: String,
+    pub url: String,
+    /// Sorted so that recordings are stable; `Authorization` is always redacted.
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<RecordedBody>,
+    pub response: Option<RecordedResponse>,
+}
+
+fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::Get => "GET",
+        Method::Put => "PUT",
+        Method::Post => "POST",
+        Method::Delete => "DELETE",
+    }
+}
+
+impl RecordedExchange {
+    fn new(request: &Request, response: Option<&Response>) -> Self {
+        Self {
+            method: method_name(request.method).to_string(),
+            url: request.url.clone(),
+            headers: request
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    if name.eq_ignore_ascii_case("Authorization") {
+                        (name.clone(), REDACTED.to_string())
+                    } else {
+                        (name.clone(), value.clone())
+                    }
+                })
+                .collect(),
+            body: request.body.as_deref().map(RecordedBody::redacted),
+            response: response.map(|response| RecordedResponse {
+                status: response.status.code,
+                headers: response.headers.clone().into_iter().collect(),
+                body: RecordedBody::from(response.body.as_slice()),
+            }),
+        }
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        self.method == method_name(request.method) && self.url == request.url
+    }
+
+    fn to_response(&self) -> Option<Response> {
+        self.response.as_ref().map(|response| Response {
+            status: response.status.into(),
+            headers: response.headers.clone().into_iter().collect(),
+            body: response.body.to_bytes(),
+        })
+    }
+}
+
+pub fn load_recording(path: impl AsRef<Path>) -> std::io::Result<Vec<RecordedExchange>> {
+    let file = std::fs::File::open(path)?;
+    Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+}
+
+/// Passes requests to another [`Client`] and remembers each request and its response.
+#[derive(Clone)]
+pub struct RecordingClient<C> {
+    inner: C,
+    exchanges: Arc<Mutex<Vec<RecordedExchange>>>,
+}
+
+impl<C: Client> RecordingClient<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            exchanges: Arc::default(),
+        }
+    }
+
+    /// Exchanges in the order their responses arrived.
+    pub fn exchanges(&self) -> Vec<RecordedExchange> {
+        self.exchanges
+            .lock()
+            .expect("http:RecordingClient:exchanges lock")
+            .clone()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(&self.exchanges()).expect("cannot fail to serialize");
+        std::fs::write(path, json)
+    }
+}
+
+impl<C: Client> Client for RecordingClient<C> {
+    fn send_request(&self, request: Request, callback: ResponseCallback) {
+        let exchanges = self.exchanges.clone();
+        let recorded_request = request.clone();
+        self.inner.send_request(
+            request,
+            Box::new(move |response| {
+                exchanges
+                    .lock()
+                    .expect("http:RecordingClient:exchanges lock")
+                    .push(RecordedExchange::new(&recorded_request, response.as_ref()));
+                callback(response)
+            }),
+        )
+    }
+
+    fn cancel_pending_requests(&self) {
+        self.inner.cancel_pending_requests()
+    }
+}
+
+/// Answers requests from a recording instead of the network, synchronously.
+///
+/// Each request gets the response of the first unused exchange with the same method and URL.
+/// Headers and bodies aren't compared because they usually contain fresh credentials or keys.
+/// A request with no matching exchange fails as if there were no response.
+#[derive(Clone)]
+pub struct ReplayingClient {
+    remaining: Arc<Mutex<VecDeque<RecordedExchange>>>,
+}
+
+impl ReplayingClient {
+    pub fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        Self {
+            remaining: Arc::new(Mutex::new(exchanges.into())),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(load_recording(path)?))
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self::new(serde_json::from_str(json)?))
+    }
+
+    /// Loads one of the recordings checked in under `tests/recordings`.
+    #[cfg(test)]
+    pub fn from_fixture(name: &str) -> Self {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/recordings")
+            .join(name);
+        Self::from_file(&path)
+            .unwrap_or_else(|e| panic!("failed to load {}: {}", path.display(), e))
+    }
+
+    /// Exchanges that haven't been replayed yet, so tests can check that nothing was skipped.
+    pub fn remaining(&self) -> Vec<RecordedExchange> {
+        self.remaining
+            .lock()
+            .expect("http:ReplayingClient:remaining lock")
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl Client for ReplayingClient {
+    fn send_request(&self, request: Request, callback: ResponseCallback) {
+        let exchange = {
+            let mut remaining = self
+                .remaining
+                .lock()
+                .expect("http:ReplayingClient:remaining lock");
+            remaining
+                .iter()
+                .position(|exchange| exchange.matches(&request))
+                .and_then(|index| remaining.remove(index))
+        };
+        match exchange {
+            Some(exchange) => callback(exchange.to_response()),
+            None => {
+                error!(
+                    "http:ReplayingClient: no recorded exchange for {:?} {}",
+                    request.method, request.url
+                );
+                callback(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::lite::http::ResponseStatus;
+
+    /// Answers every request with its own URL, or fails if the URL ends in "fail".
+    struct EchoClient;
+
+    impl Client for EchoClient {
+        fn send_request(&self, request: Request, callback: ResponseCallback) {
+            if request.url.ends_with("fail") {
+                callback(None)
+            } else {
+                callback(Some(Response {
+                    status: ResponseStatus::from(200),
+                    headers: HashMap::new(),
+                    body: request.url.into_bytes(),
+                }))
+            }
+        }
+    }
+
+    fn request(method: Method, url: &str, body: Option<Vec<u8>>) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: HashMap::from_iter([
+                ("Authorization".to_string(), "Bearer secret".to_string()),
+                ("X-Room-Id".to_string(), "abcd".to_string()),
+            ]),
+            body,
+        }
+    }
+
+    fn send(client: &dyn Client, request: Request) -> Option<Response> {
+        let result = Arc::new(Mutex::new(None));
+        let result_for_callback = result.clone();
+        client.send_request(
+            request,
+            Box::new(move |response| *result_for_callback.lock().unwrap() = Some(response)),
+        );
+        let response = result.lock().unwrap().take();
+        response.expect("callback should be called synchronously")
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let recorder = RecordingClient::new(EchoClient);
+        send(&recorder, request(Method::Get, "https://a/1", None));
+        send(
+            &recorder,
+            request(Method::Put, "https://a/2", Some(vec![0xff, 0])),
+        );
+        send(&recorder, request(Method::Get, "https://a/fail", None));
+        send(&recorder, request(Method::Get, "https://a/1", None));
+
+        let exchanges = recorder.exchanges();
+        assert_eq!(4, exchanges.len());
+        assert_eq!("<redacted>", exchanges[0].headers["Authorization"]);
+        assert_eq!("abcd", exchanges[0].headers["X-Room-Id"]);
+        assert_eq!(
+            Some(RecordedBody::Binary {
+                encoded: "/wA=".to_string()
+            }),
+            exchanges[1].body
+        );
+        assert_eq!(None, exchanges[2].response);
+
+        let json = serde_json::to_string(&exchanges).unwrap();
+        let replayer = ReplayingClient::from_json(&json).unwrap();
+        assert_eq!(exchanges, replayer.remaining());
+
+        // Out of order, and with different credentials.
+        let mut put = request(Method::Put, "https://a/2", None);
+        put.headers
+            .insert("Authorization".to_string(), "Bearer other".to_string());
+        let response = send(&replayer, put).unwrap();
+        assert_eq!(b"https://a/2".as_slice(), response.body);
+        assert!(send(&replayer, request(Method::Get, "https://a/fail", None)).is_none());
+        for _ in 0..2 {
+            let response = send(&replayer, request(Method::Get, "https://a/1", None)).unwrap();
+            assert_eq!(200, response.status.code);
+            assert_eq!(b"https://a/1".as_slice(), response.body);
+        }
+        assert!(replayer.remaining().is_empty());
+
+        // Everything has been used up.
+        assert!(send(&replayer, request(Method::Get, "https://a/1", None)).is_none());
+    }
+
+    #[test]
+    fn redacts_admin_passkey() {
+        let recorder = RecordingClient::new(EchoClient);
+        send(
+            &recorder,
+            request(
+                Method::Put,
+                "https://a/1",
+                Some(br#"{"adminPasskey":"c2VjcmV0","iceUfrag":"ufrag"}"#.to_vec()),
+            ),
+        );
+        send(
+            &recorder,
+            request(Method::Put, "https://a/2", Some(b"not json".to_vec())),
+        );
+
+        let exchanges = recorder.exchanges();
+        let Some(RecordedBody::Text(body)) = &exchanges[0].body else {
+            panic!("expected a text body");
+        };
+        assert_eq!(
+            serde_json::json!({"adminPasskey": "<redacted>", "iceUfrag": "ufrag"}),
+            serde_json::from_str::<serde_json::Value>(body).unwrap()
+        );
+        assert_eq!(
+            Some(RecordedBody::Text("not json".to_string())),
+            exchanges[1].body
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let recorder = RecordingClient::new(EchoClient);
+        send(
+            &recorder,
+            request(Method::Post, "https://a/1", Some(b"{}".to_vec())),
+        );
+
+        let path = std::env::temp_dir().join(format!(
+            "ringrtc-http-recording-{}.json",
+            std::process::id()
+        ));
+        recorder.save(&path).unwrap();
+        let loaded = load_recording(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(recorder.exchanges(), loaded.unwrap());
+    }
+}
@@ -0,0 +1,654 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Adds deadlines, retries, and cancellation to any [`Client`].
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use super::{Client, Method, Request, Response, ResponseCallback, ResponseStatusType};
+use crate::common::{
+    actor::{Actor, Stopper},
+    Result,
+};
+
+/// How long to wait for a request, and whether and how to try it again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestPolicy {
+    /// How long to wait for each attempt before treating it as failed.
+    pub timeout: Duration,
+    /// Includes the first attempt, so 1 means "never retry".
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for each one after that.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RequestPolicy {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+    /// Retries only requests that are safe to repeat, which for the SFU and the calling server
+    /// means GETs (peeks and call link reads).
+    pub fn for_method(method: Method) -> Self {
+        match method {
+            Method::Get => Self {
+                timeout: Self::DEFAULT_TIMEOUT,
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(500),
+                max_backoff: Duration::from_secs(4),
+            },
+            Method::Put | Method::Post | Method::Delete => Self {
+                timeout: Self::DEFAULT_TIMEOUT,
+                max_attempts: 1,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+        }
+    }
+
+    fn backoff(&self, failed_attempts: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(1 << failed_attempts.saturating_sub(1).min(16))
+            .min(self.max_backoff)
+    }
+}
+
+/// Counts of what a [`RetryingClient`] has done so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryMetrics {
+    /// Requests passed to the client.
+    pub requests: u64,
+    /// Requests sent again after a failed attempt.
+    pub retries: u64,
+    /// Attempts that got no response before their deadline.
+    pub timeouts: u64,
+    /// Requests cancelled before they completed.
+    pub cancellations: u64,
+    /// Requests that completed with no response or an error status, after any retries.
+    pub failures: u64,
+}
+
+#[derive(Default)]
+struct SharedMetrics {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    timeouts: AtomicU64,
+    cancellations: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl SharedMetrics {
+    fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> RetryMetrics {
+        RetryMetrics {
+            requests: self.requests.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            cancellations: self.cancellations.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Cancels one request sent with [`RetryingClient::send_cancellable_request`].
+///
+/// The request's callback is dropped without being called. Cancelling a request that has
+/// already completed does nothing.
+#[derive(Clone)]
+pub struct CancelHandle {
+    actor: Actor<State>,
+    request_id: u64,
+}
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        let request_id = self.request_id;
+        self.actor.send(move |state| {
+            if state.requests.remove(&request_id).is_some() {
+                debug!("http:RetryingClient: cancelled request {}", request_id);
+                SharedMetrics::increment(&state.metrics.cancellations);
+            }
+        });
+    }
+}
+
+/// Wraps another [`Client`] (such as a [`super::DelegatingClient`] or the `sim_http` client) so
+/// that every request gets a deadline, idempotent requests are retried with backoff, and
+/// requests can be cancelled.
+///
+/// The wrapped client is only used from this client's own thread, and callbacks are called
+/// there too. Responses that arrive after an attempt has timed out are ignored, and dropping
+/// the client drops any outstanding callbacks.
+///
+/// One client can be shared by several users (such as all of a CallManager's group calls) via
+/// [`RetryingClient::scoped`], so that they share metrics but can cancel their own requests.
+pub struct RetryingClient {
+    actor: Actor<State>,
+    next_request_id: Arc<AtomicU64>,
+    metrics: Arc<SharedMetrics>,
+}
+
+struct PendingRequest {
+    request: Request,
+    // Set for requests sent through a ScopedClient, so that they can be cancelled together.
+    scope: Option<u64>,
+    policy: RequestPolicy,
+    callback: ResponseCallback,
+    started: Instant,
+    attempts: u32,
+    // False while waiting to retry, so that nothing from the previous attempt is used.
+    waiting_for_attempt: bool,
+}
+
+struct State {
+    actor: Actor<State>,
+    inner: Box<dyn Client + Send>,
+    policy_for_method: fn(Method) -> RequestPolicy,
+    requests: HashMap<u64, PendingRequest>,
+    metrics: Arc<SharedMetrics>,
+}
+
+impl RetryingClient {
+    pub fn start(inner: impl Client + Send + 'static) -> Result<Self> {
+        Self::start_with_policy(inner, RequestPolicy::for_method)
+    }
+
+    pub fn start_with_policy(
+        inner: impl Client + Send + 'static,
+        policy_for_method: fn(Method) -> RequestPolicy,
+    ) -> Result<Self> {
+        let metrics = Arc::new(SharedMetrics::default());
+        let metrics_for_state = metrics.clone();
+        let actor = Actor::start("RetryingHttpClient", Stopper::new(), move |actor| {
+            Ok(State {
+                actor,
+                inner: Box::new(inner),
+                policy_for_method,
+                requests: HashMap::new(),
+                metrics: metrics_for_state,
+            })
+        })?;
+        Ok(Self {
+            actor,
+            next_request_id: Arc::default(),
+            metrics,
+        })
+    }
+
+    pub fn send_cancellable_request(
+        &self,
+        request: Request,
+        callback: ResponseCallback,
+    ) -> CancelHandle {
+        self.send_request_in_scope(request, callback, None)
+    }
+
+    fn send_request_in_scope(
+        &self,
+        request: Request,
+        callback: ResponseCallback,
+        scope: Option<u64>,
+    ) -> CancelHandle {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        SharedMetrics::increment(&self.metrics.requests);
+        self.actor.send(move |state| {
+            let policy = (state.policy_for_method)(request.method);
+            state.requests.insert(
+                request_id,
+                PendingRequest {
+                    request,
+                    scope,
+                    policy,
+                    callback,
+                    started: Instant::now(),
+                    attempts: 0,
+                    waiting_for_attempt: false,
+                },
+            );
+            State::start_attempt(state, request_id);
+        });
+        CancelHandle {
+            actor: self.actor.clone(),
+            request_id,
+        }
+    }
+
+    /// Cancels every request that hasn't completed yet, such as when a call ends.
+    pub fn cancel_all(&self) {
+        self.actor.send(|state| {
+            if !state.requests.is_empty() {
+                debug!(
+                    "http:RetryingClient: cancelling {} request(s)",
+                    state.requests.len()
+                );
+            }
+            for _ in state.requests.drain() {
+                SharedMetrics::increment(&state.metrics.cancellations);
+            }
+        });
+    }
+
+    pub fn metrics(&self) -> RetryMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// Returns a client that sends its requests through this one, but whose
+    /// `cancel_pending_requests` only cancels the requests sent through it.
+    pub fn scoped(self: &Arc<Self>) -> ScopedClient {
+        ScopedClient {
+            client: self.clone(),
+            scope: self.next_request_id.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    fn cancel_scope(&self, scope: u64) {
+        self.actor.send(move |state| {
+            let metrics = &state.metrics;
+            state.requests.retain(|_, pending| {
+                let cancel = pending.scope == Some(scope);
+                if cancel {
+                    SharedMetrics::increment(&metrics.cancellations);
+                }
+                !cancel
+            });
+        });
+    }
+}
+
+impl Client for RetryingClient {
+    fn send_request(&self, request: Request, callback: ResponseCallback) {
+        self.send_cancellable_request(request, callback);
+    }
+
+    fn cancel_pending_requests(&self) {
+        self.cancel_all();
+    }
+}
+
+/// A share of a [`RetryingClient`], from [`RetryingClient::scoped`].
+pub struct ScopedClient {
+    client: Arc<RetryingClient>,
+    scope: u64,
+}
+
+impl Client for ScopedClient {
+    fn send_request(&self, request: Request, callback: ResponseCallback) {
+        self.client
+            .send_request_in_scope(request, callback, Some(self.scope));
+    }
+
+    fn cancel_pending_requests(&self) {
+        self.client.cancel_scope(self.scope);
+    }
+}
+
+impl Drop for RetryingClient {
+    fn drop(&mut self) {
+        let _join_handles = self.actor.stopper().stop_all_without_joining();
+    }
+}
+
+enum AttemptResult {
+    Response(Option<Response>),
+    TimedOut,
+}
+
+impl State {
+    fn start_attempt(state: &mut State, request_id: u64) {
+        let Some(pending) = state.requests.get_mut(&request_id) else {
+            // Cancelled while waiting to retry.
+            return;
+        };
+        pending.attempts += 1;
+        let attempt = pending.attempts;
+        let timeout = pending.policy.timeout;
+        pending.waiting_for_attempt = true;
+        let request = pending.request.clone();
+
+        let actor = state.actor.clone();
+        state.inner.send_request(
+            request,
+            Box::new(move |response| {
+                actor.send(move |state| {
+                    State::finish_attempt(
+                        state,
+                        request_id,
+                        attempt,
+                        AttemptResult::Response(response),
+                    );
+                });
+            }),
+        );
+        state.actor.send_delayed(timeout, move |state| {
+            State::finish_attempt(state, request_id, attempt, AttemptResult::TimedOut);
+        });
+    }
+
+    fn finish_attempt(state: &mut State, request_id: u64, attempt: u32, result: AttemptResult) {
+        let Some(pending) = state.requests.get_mut(&request_id) else {
+            // Already completed, timed out, or cancelled.
+            return;
+        };
+        if pending.attempts != attempt || !pending.waiting_for_attempt {
+            // A late response or timeout from an earlier attempt.
+            return;
+        }
+        pending.waiting_for_attempt = false;
+
+        let response = match result {
+            AttemptResult::Response(response) => response,
+            AttemptResult::TimedOut => {
+                info!(
+                    "http:RetryingClient: request {} attempt {} timed out after {:?}",
+                    request_id, attempt, pending.policy.timeout
+                );
+                SharedMetrics::increment(&state.metrics.timeouts);
+                None
+            }
+        };
+        let retryable = match &response {
+            None => true,
+            Some(response) => matches!(response.status.r#type(), ResponseStatusType::ServerError),
+        };
+        if retryable && attempt < pending.policy.max_attempts {
+            let backoff = pending.policy.backoff(attempt);
+            info!(
+                "http:RetryingClient: retrying request {} in {:?}",
+                request_id, backoff
+            );
+            SharedMetrics::increment(&state.metrics.retries);
+            state.actor.send_delayed(backoff, move |state| {
+                State::start_attempt(state, request_id);
+            });
+            return;
+        }
+
+        let pending = state.requests.remove(&request_id).expect("checked above");
+        let succeeded = response
+            .as_ref()
+            .is_some_and(|response| !response.status.is_error());
+        if !succeeded {
+            SharedMetrics::increment(&state.metrics.failures);
+        }
+        debug!(
+            "http:RetryingClient: request {} completed after {} attempt(s) in {:?}",
+            request_id,
+            attempt,
+            pending.started.elapsed()
+        );
+        (pending.callback)(response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Mutex};
+
+    use super::*;
+    use crate::lite::http::ResponseStatus;
+
+    /// Records requests and lets the test decide when and how to answer them.
+    #[derive(Clone, Default)]
+    struct FakeClient {
+        pending: Arc<Mutex<Vec<(Request, ResponseCallback)>>>,
+    }
+
+    impl Client for FakeClient {
+        fn send_request(&self, request: Request, callback: ResponseCallback) {
+            self.pending.lock().unwrap().push((request, callback));
+        }
+    }
+
+    impl FakeClient {
+        fn wait_for_requests(&self, count: usize) -> Vec<(Request, ResponseCallback)> {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            loop {
+                {
+                    let mut pending = self.pending.lock().unwrap();
+                    if pending.len() >= count {
+                        return pending.drain(..).collect();
+                    }
+                }
+                assert!(Instant::now() < deadline, "timed out waiting for requests");
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    fn fast_policy(method: Method) -> RequestPolicy {
+        RequestPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            ..RequestPolicy::for_method(method)
+        }
+    }
+
+    fn short_timeout_policy(method: Method) -> RequestPolicy {
+        RequestPolicy {
+            timeout: Duration::from_millis(50),
+            ..fast_policy(method)
+        }
+    }
+
+    fn request(method: Method) -> Request {
+        Request {
+            method,
+            url: "https://sfu.example/peek".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    fn response(code: u16) -> Option<Response> {
+        Some(Response {
+            status: ResponseStatus::from(code),
+            headers: HashMap::new(),
+            body: vec![],
+        })
+    }
+
+    fn send(
+        client: &RetryingClient,
+        method: Method,
+    ) -> (CancelHandle, mpsc::Receiver<Option<Response>>) {
+        let (sender, receiver) = mpsc::channel();
+        let handle = client.send_cancellable_request(
+            request(method),
+            Box::new(move |response| {
+                let _ = sender.send(response);
+            }),
+        );
+        (handle, receiver)
+    }
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn passes_through_success() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), fast_policy).unwrap();
+        let (_handle, receiver) = send(&client, Method::Get);
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(response(200));
+        assert_eq!(
+            Some(200),
+            receiver.recv_timeout(WAIT).unwrap().map(|r| r.status.code)
+        );
+        assert_eq!(
+            RetryMetrics {
+                requests: 1,
+                ..Default::default()
+            },
+            client.metrics()
+        );
+    }
+
+    #[test]
+    fn retries_idempotent_requests() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), fast_policy).unwrap();
+        let (_handle, receiver) = send(&client, Method::Get);
+
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(None);
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(response(503));
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(response(200));
+
+        assert_eq!(
+            Some(200),
+            receiver.recv_timeout(WAIT).unwrap().map(|r| r.status.code)
+        );
+        assert_eq!(2, client.metrics().retries);
+        assert_eq!(0, client.metrics().failures);
+    }
+
+    #[test]
+    fn does_not_retry_client_errors_or_non_idempotent_requests() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), fast_policy).unwrap();
+
+        let (_handle, receiver) = send(&client, Method::Get);
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(response(404));
+        assert_eq!(
+            Some(404),
+            receiver.recv_timeout(WAIT).unwrap().map(|r| r.status.code)
+        );
+
+        let (_handle, receiver) = send(&client, Method::Put);
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        callback(response(503));
+        assert_eq!(
+            Some(503),
+            receiver.recv_timeout(WAIT).unwrap().map(|r| r.status.code)
+        );
+
+        assert_eq!(0, client.metrics().retries);
+        assert_eq!(2, client.metrics().failures);
+    }
+
+    #[test]
+    fn times_out_and_ignores_late_responses() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), short_timeout_policy).unwrap();
+        let (_handle, receiver) = send(&client, Method::Post);
+
+        let (_, late_callback) = fake.wait_for_requests(1).pop().unwrap();
+        assert!(receiver.recv_timeout(WAIT).unwrap().is_none());
+        late_callback(response(200));
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        assert_eq!(1, client.metrics().timeouts);
+        assert_eq!(1, client.metrics().failures);
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), short_timeout_policy).unwrap();
+        let (_handle, receiver) = send(&client, Method::Get);
+
+        // Never answer; every attempt times out.
+        assert!(receiver.recv_timeout(WAIT).unwrap().is_none());
+        assert_eq!(3, fake.wait_for_requests(3).len());
+        assert_eq!(
+            RetryMetrics {
+                requests: 1,
+                retries: 2,
+                timeouts: 3,
+                cancellations: 0,
+                failures: 1,
+            },
+            client.metrics()
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let fake = FakeClient::default();
+        let client = RetryingClient::start_with_policy(fake.clone(), fast_policy).unwrap();
+
+        let (handle, receiver) = send(&client, Method::Get);
+        let (_, callback) = fake.wait_for_requests(1).pop().unwrap();
+        handle.cancel();
+        // Answer anyway. The response is queued behind the cancellation, so it's ignored.
+        callback(response(200));
+        assert_eq!(
+            Err(mpsc::RecvTimeoutError::Disconnected),
+            receiver.recv_timeout(WAIT).map(|_| ())
+        );
+
+        let (_handle1, receiver1) = send(&client, Method::Get);
+        let (_handle2, receiver2) = send(&client, Method::Put);
+        fake.wait_for_requests(2);
+        client.cancel_all();
+        assert!(receiver1.recv_timeout(WAIT).is_err());
+        assert!(receiver2.recv_timeout(WAIT).is_err());
+
+        assert_eq!(3, client.metrics().cancellations);
+        assert_eq!(0, client.metrics().failures);
+    }
+
+    #[test]
+    fn scoped_cancel_only_cancels_own_requests() {
+        let fake = FakeClient::default();
+        let client =
+            Arc::new(RetryingClient::start_with_policy(fake.clone(), fast_policy).unwrap());
+        let scope1 = client.scoped();
+        let scope2 = client.scoped();
+
+        let (sender1, receiver1) = mpsc::channel();
+        scope1.send_request(
+            request(Method::Get),
+            Box::new(move |response| {
+                let _ = sender1.send(response);
+            }),
+        );
+        let (sender2, receiver2) = mpsc::channel();
+        scope2.send_request(
+            request(Method::Get),
+            Box::new(move |response| {
+                let _ = sender2.send(response);
+            }),
+        );
+        let mut callbacks = fake.wait_for_requests(2);
+        scope1.cancel_pending_requests();
+        for (_, callback) in callbacks.drain(..) {
+            callback(response(200));
+        }
+
+        assert_eq!(
+            Err(mpsc::RecvTimeoutError::Disconnected),
+            receiver1.recv_timeout(WAIT).map(|_| ())
+        );
+        assert_eq!(
+            Some(200),
+            receiver2.recv_timeout(WAIT).unwrap().map(|r| r.status.code)
+        );
+        assert_eq!(2, client.metrics().requests);
+        assert_eq!(1, client.metrics().cancellations);
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RequestPolicy::for_method(Method::Get);
+        assert_eq!(Duration::from_millis(500), policy.backoff(1));
+        assert_eq!(Duration::from_millis(1000), policy.backoff(2));
+        assert_eq!(Duration::from_millis(2000), policy.backoff(3));
+        assert_eq!(Duration::from_secs(4), policy.backoff(4));
+        assert_eq!(Duration::from_secs(4), policy.backoff(100));
+    }
+}
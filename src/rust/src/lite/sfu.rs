@@ -6,16 +6,22 @@
 //! Make calls to an SFU to see who is in the call.
 //! and define common types like PeekInfo, MembershipProof, MemberInfo
 
+mod peek_watcher;
+
 use std::{
     collections::{HashMap, HashSet},
     iter::FromIterator,
     net::{IpAddr, SocketAddr},
     str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
 use base64::{engine::general_purpose::STANDARD as base64, Engine};
 use hex::ToHex;
+pub use peek_watcher::{
+    PeekWatchId, PeekWatchTarget, PeekWatcher, PeekWatcherCallback, PeekWatcherConfig,
+};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sha2::{Digest, Sha256};
@@ -224,11 +230,93 @@ impl TryFrom<ProtoPeekDeviceInfo> for SerializedPeekDeviceInfo {
     }
 }
 
+/// Form of the body the SFU sends along with some failures.
 #[derive(Deserialize, Debug)]
-struct SerializedPeekFailure<'a> {
+struct SerializedFailure<'a> {
     reason: &'a str,
 }
 
+/// Why the SFU refused a request, as far as can be told from its response.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SfuError {
+    #[error("The maximum number of devices has been reached")]
+    MaxDevicesReached,
+    #[error("The call link has expired")]
+    CallLinkExpired,
+    #[error("The call link is invalid")]
+    CallLinkInvalid,
+    #[error("The call link has been revoked")]
+    CallLinkRevoked,
+    #[error("An admin denied the request to join")]
+    ApprovalDenied,
+    #[error("The credentials have expired")]
+    AuthExpired,
+    #[error("Rate limited (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("The SFU is unavailable")]
+    ServerUnavailable,
+    #[error("The SFU sent a malformed response")]
+    MalformedResponse,
+    #[error("The request to the SFU failed")]
+    RequestFailed,
+    #[error("The SFU responded with unexpected status code {}", .0.code)]
+    UnexpectedStatus(http::ResponseStatus),
+}
+
+impl SfuError {
+    fn from_reason(reason: &str, retry_after: Option<Duration>) -> Option<Self> {
+        match reason {
+            "expired" => Some(Self::CallLinkExpired),
+            "invalid" => Some(Self::CallLinkInvalid),
+            "revoked" => Some(Self::CallLinkRevoked),
+            "full" => Some(Self::MaxDevicesReached),
+            "denied" => Some(Self::ApprovalDenied),
+            "authExpired" => Some(Self::AuthExpired),
+            "rateLimited" => Some(Self::RateLimited { retry_after }),
+            _ => None,
+        }
+    }
+
+    /// Classifies a failure from [`http::parse_json_response`], preferring the reason in the
+    /// response body when the SFU gave one.
+    pub fn from_failure(status: http::ResponseStatus, response: Option<&http::Response>) -> Self {
+        let response = response.filter(|response| !response.status.is_success());
+        let retry_after = response.and_then(retry_after_from_response);
+        let failure = response
+            .filter(|response| !response.body.is_empty())
+            .and_then(|response| serde_json::from_slice::<SerializedFailure>(&response.body).ok());
+        if let Some(error) =
+            failure.and_then(|failure| Self::from_reason(failure.reason, retry_after))
+        {
+            return error;
+        }
+        match status {
+            http::ResponseStatus::REQUEST_FAILED => Self::RequestFailed,
+            http::ResponseStatus::GROUP_CALL_FULL => Self::MaxDevicesReached,
+            http::ResponseStatus::CALL_LINK_EXPIRED => Self::CallLinkExpired,
+            http::ResponseStatus::CALL_LINK_INVALID => Self::CallLinkInvalid,
+            http::ResponseStatus::INVALID_RESPONSE_BODY_UTF8
+            | http::ResponseStatus::INVALID_RESPONSE_BODY_JSON => Self::MalformedResponse,
+            http::ResponseStatus { code: 401 } => Self::AuthExpired,
+            http::ResponseStatus { code: 429 } => Self::RateLimited { retry_after },
+            http::ResponseStatus { code: 500..=599 } => Self::ServerUnavailable,
+            _ => Self::UnexpectedStatus(status),
+        }
+    }
+}
+
+/// Reads the Retry-After header, which the SFU sends as a number of seconds.
+fn retry_after_from_response(response: &http::Response) -> Option<Duration> {
+    let retry_after = response.header("Retry-After")?;
+    match retry_after.trim().parse() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => {
+            warn!("Ignoring Retry-After header that isn't in seconds: {retry_after}");
+            None
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct SerializedJoinResponse {
     #[serde(rename = "demuxId")]
@@ -515,7 +603,7 @@ fn participants_url_from_sfu_url(sfu_url: &str) -> String {
 }
 
 fn classify_not_found(body: &[u8]) -> Option<http::ResponseStatus> {
-    let parsed: SerializedPeekFailure = match serde_json::from_slice(body) {
+    let parsed: SerializedFailure = match serde_json::from_slice(body) {
         Ok(parsed) => parsed,
         Err(e) => {
             error!("invalid JSON returned from SFU on peek failure: {e}");
@@ -526,9 +614,10 @@ fn classify_not_found(body: &[u8]) -> Option<http::ResponseStatus> {
         "Got group call peek result with status code 404 ({})",
         parsed.reason
     );
-    match parsed.reason {
-        "expired" => Some(http::ResponseStatus::CALL_LINK_EXPIRED),
-        "invalid" => Some(http::ResponseStatus::CALL_LINK_INVALID),
+    match SfuError::from_reason(parsed.reason, None)? {
+        SfuError::CallLinkExpired => Some(http::ResponseStatus::CALL_LINK_EXPIRED),
+        SfuError::CallLinkInvalid => Some(http::ResponseStatus::CALL_LINK_INVALID),
+        SfuError::CallLinkRevoked => Some(http::ResponseStatus::CALL_LINK_REVOKED),
         _ => None,
     }
 }
@@ -594,7 +683,7 @@ pub fn peek(
     )
 }
 
-pub type JoinResult = Result<JoinResponse, http::ResponseStatus>;
+pub type JoinResult = Result<JoinResponse, SfuError>;
 pub type JoinResultCallback = Box<dyn FnOnce(JoinResult) + Send>;
 
 #[serde_as]
@@ -658,7 +747,12 @@ pub fn join(
         Box::new(move |http_response| {
             let result =
                 http::parse_json_response::<SerializedJoinResponse>(http_response.as_ref())
-                    .map(|deserialized| JoinResponse::from(deserialized, &*member_resolver));
+                    .map(|deserialized| JoinResponse::from(deserialized, &*member_resolver))
+                    .map_err(|status| {
+                        let error = SfuError::from_failure(status, http_response.as_ref());
+                        info!("Failed to join group call: {error}");
+                        error
+                    });
             result_callback(result)
         }),
     );
@@ -1213,4 +1307,211 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn peek_replayed_responses() {
+        let http_client = http::ReplayingClient::from_fixture("peek.json");
+        let member_resolver = Arc::new(MemberMap {
+            members: vec![
+                OpaqueUserIdMapping {
+                    user_id: vec![1u8; 4],
+                    opaque_user_id: "u1".to_string(),
+                },
+                OpaqueUserIdMapping {
+                    user_id: vec![2u8; 4],
+                    opaque_user_id: "u2".to_string(),
+                },
+            ],
+        });
+
+        let results = Arc::new(std::sync::Mutex::new(vec![]));
+        for _ in 0..3 {
+            let results = results.clone();
+            peek(
+                &http_client,
+                "https://sfu.example/",
+                None,
+                "Bearer token".to_string(),
+                member_resolver.clone(),
+                None,
+                Box::new(move |result| results.lock().unwrap().push(result)),
+            );
+        }
+        assert!(http_client.remaining().is_empty());
+
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        let [in_call, not_started, failed] = results.try_into().unwrap();
+        let in_call = in_call.unwrap();
+        assert_eq!(Some("paleozoic"), in_call.era_id.as_deref());
+        assert_eq!(Some(16), in_call.max_devices);
+        assert_eq!(Some([1u8; 4].as_slice()), in_call.creator.as_deref());
+        assert_eq!(
+            vec![(0x11111110, Some(vec![1u8; 4]))],
+            in_call
+                .devices
+                .into_iter()
+                .map(|device| (device.demux_id, device.user_id))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0x22222220, Some(vec![2u8; 4]))],
+            in_call
+                .pending_devices
+                .into_iter()
+                .map(|device| (device.demux_id, device.user_id))
+                .collect::<Vec<_>>()
+        );
+        assert!(not_started.unwrap().devices.is_empty());
+        assert_eq!(
+            Err(http::ResponseStatus::REQUEST_FAILED),
+            failed.map(|_| ())
+        );
+    }
+
+    #[test]
+    fn join_replayed_responses() {
+        let http_client = http::ReplayingClient::from_fixture("join.json");
+        let member_resolver = Arc::new(MemberMap {
+            members: vec![OpaqueUserIdMapping {
+                user_id: vec![1u8; 4],
+                opaque_user_id: "u1".to_string(),
+            }],
+        });
+
+        let results = Arc::new(std::sync::Mutex::new(vec![]));
+        for _ in 0..3 {
+            let results = results.clone();
+            join(
+                &http_client,
+                "https://sfu.example/",
+                Some("0102030405060708".to_string()),
+                "Bearer token".to_string(),
+                Some(b"passkey".as_slice()),
+                "client-ufrag",
+                "client-pwd",
+                &[0u8; 32],
+                &[],
+                member_resolver.clone(),
+                Box::new(move |result| results.lock().unwrap().push(result)),
+            );
+        }
+        assert!(http_client.remaining().is_empty());
+
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        let [joined, rate_limited, revoked] = results.try_into().unwrap();
+        let joined = joined.unwrap();
+        assert_eq!(0x11111110, joined.client_demux_id);
+        assert_eq!(
+            vec!["192.0.2.1:10000".parse::<SocketAddr>().unwrap()],
+            joined.server_udp_addresses
+        );
+        assert_eq!(
+            vec!["192.0.2.1:10001".parse::<SocketAddr>().unwrap()],
+            joined.server_tcp_addresses
+        );
+        assert_eq!(
+            vec!["192.0.2.1:443".parse::<SocketAddr>().unwrap()],
+            joined.server_tls_addresses
+        );
+        assert_eq!(Some("sfu.example"), joined.server_hostname.as_deref());
+        assert_eq!("server-ufrag", joined.server_ice_ufrag);
+        assert_eq!("server-pwd", joined.server_ice_pwd);
+        assert_eq!([1u8; 32], joined.server_dhe_pub_key);
+        assert_eq!(Some(vec![1u8; 4]), joined.call_creator);
+        assert_eq!("paleozoic", joined.era_id);
+        assert_eq!(ClientStatus::Pending, joined.client_status);
+        assert_eq!(
+            SfuError::RateLimited {
+                retry_after: Some(Duration::from_secs(30))
+            },
+            rate_limited.unwrap_err()
+        );
+        assert_eq!(SfuError::CallLinkRevoked, revoked.unwrap_err());
+    }
+
+    #[test]
+    fn classify_sfu_errors() {
+        fn failure(code: u16, body: &str) -> SfuError {
+            failure_with_headers(code, &[], body)
+        }
+
+        fn failure_with_headers(code: u16, headers: &[(&str, &str)], body: &str) -> SfuError {
+            let response = http::Response {
+                status: code.into(),
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                body: body.as_bytes().to_vec(),
+            };
+            SfuError::from_failure(response.status, Some(&response))
+        }
+
+        assert_eq!(
+            SfuError::RequestFailed,
+            SfuError::from_failure(http::ResponseStatus::REQUEST_FAILED, None)
+        );
+        assert_eq!(SfuError::MaxDevicesReached, failure(413, ""));
+        assert_eq!(SfuError::AuthExpired, failure(401, ""));
+        assert_eq!(SfuError::ServerUnavailable, failure(503, "<html></html>"));
+        assert_eq!(
+            SfuError::UnexpectedStatus(418.into()),
+            failure(418, r#"{"reason":"teapot"}"#)
+        );
+        assert_eq!(
+            SfuError::RateLimited { retry_after: None },
+            failure(429, "")
+        );
+        assert_eq!(
+            SfuError::RateLimited {
+                retry_after: Some(Duration::from_secs(30))
+            },
+            failure_with_headers(429, &[("retry-after", "30")], r#"{"reason":"rateLimited"}"#)
+        );
+        assert_eq!(
+            SfuError::RateLimited {
+                retry_after: Some(Duration::from_secs(5))
+            },
+            failure_with_headers(429, &[("Retry-After", "5")], "")
+        );
+        // HTTP dates aren't supported.
+        assert_eq!(
+            SfuError::RateLimited { retry_after: None },
+            failure_with_headers(429, &[("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")], "")
+        );
+
+        // The reason in the body wins over the status code.
+        assert_eq!(
+            SfuError::CallLinkRevoked,
+            failure(404, r#"{"reason":"revoked"}"#)
+        );
+        assert_eq!(
+            SfuError::ApprovalDenied,
+            failure(403, r#"{"reason":"denied"}"#)
+        );
+
+        // A successful response that couldn't be parsed.
+        let response = http::Response {
+            status: 200.into(),
+            headers: HashMap::new(),
+            body: b"{}".to_vec(),
+        };
+        assert_eq!(
+            SfuError::MalformedResponse,
+            SfuError::from_failure(
+                http::ResponseStatus::INVALID_RESPONSE_BODY_JSON,
+                Some(&response)
+            )
+        );
+
+        // Peeks report revoked links with their own status.
+        assert_eq!(
+            Some(http::ResponseStatus::CALL_LINK_REVOKED),
+            classify_not_found(br#"{"reason":"revoked"}"#)
+        );
+        assert_eq!(
+            Some(http::ResponseStatus::CALL_LINK_INVALID),
+            classify_not_found(br#"{"reason":"invalid"}"#)
+        );
+    }
 }
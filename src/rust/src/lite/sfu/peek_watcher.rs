@@ -0,0 +1,734 @@
+//
+// Copyright 2025 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Keeps many calls peeked in the background, reporting only when something changes.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+
+use super::{MemberResolver, PeekInfo, PeekResult};
+use crate::lite::{call_links::CallLinkRootKey, http};
+
+/// Identifies a watched call: a group ID, or the room ID of a call link.
+pub type PeekWatchId = Vec<u8>;
+
+/// What's needed to peek one call. See [`super::peek`].
+#[derive(Clone)]
+pub struct PeekWatchTarget {
+    pub sfu_url: String,
+    pub room_id_header: Option<String>,
+    pub auth_header: String,
+    pub member_resolver: Arc<dyn MemberResolver + Send + Sync>,
+    pub call_link_root_key: Option<CallLinkRootKey>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PeekWatcherConfig {
+    /// How often to peek a call that has devices in it.
+    pub active_interval: Duration,
+    /// How often to peek a call that nobody is in.
+    pub empty_interval: Duration,
+    /// After a failure, retries back off exponentially from `active_interval` up to this.
+    pub max_failure_interval: Duration,
+    /// Each interval is randomly lengthened or shortened by up to this fraction so that
+    /// many calls (and many clients) don't end up peeking in lockstep. At most 0.5.
+    pub jitter: f64,
+}
+
+/// Any more jitter and the shortest intervals would get close to zero.
+const MAX_JITTER: f64 = 0.5;
+
+impl Default for PeekWatcherConfig {
+    fn default() -> Self {
+        Self {
+            active_interval: Duration::from_secs(10),
+            empty_interval: Duration::from_secs(60),
+            max_failure_interval: Duration::from_secs(5 * 60),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl PeekWatcherConfig {
+    fn validated(mut self) -> Self {
+        // Also catches NaN.
+        if !(0.0..=MAX_JITTER).contains(&self.jitter) {
+            let jitter = if self.jitter > 0.0 { MAX_JITTER } else { 0.0 };
+            warn!(
+                "peek_watcher: jitter {} is out of range, using {}",
+                self.jitter, jitter
+            );
+            self.jitter = jitter;
+        }
+        self
+    }
+
+    fn next_interval(&self, result: &PeekResult, consecutive_failures: u32) -> Duration {
+        let base = match result {
+            Ok(peek_info) if peek_info.device_count_including_pending_devices() > 0 => {
+                self.active_interval
+            }
+            Ok(_) => self.empty_interval,
+            Err(_) => self
+                .active_interval
+                .saturating_mul(1 << consecutive_failures.saturating_sub(1).min(16))
+                .min(self.max_failure_interval),
+        };
+        if self.jitter > 0.0 {
+            base.mul_f64(1.0 + rand::thread_rng().gen_range(-self.jitter..=self.jitter))
+        } else {
+            base
+        }
+    }
+}
+
+/// Called with the new result whenever a watched call's result changes, including the first
+/// time it's peeked.
+pub type PeekWatcherCallback = Box<dyn Fn(&PeekWatchId, &PeekResult) + Send + Sync>;
+
+/// Whether a new peek result is worth reporting.
+fn peek_result_changed(old: Option<&PeekResult>, new: &PeekResult) -> bool {
+    fn peek_info_changed(old: &PeekInfo, new: &PeekInfo) -> bool {
+        old.era_id != new.era_id
+            || old.devices != new.devices
+            || old.unique_pending_users() != new.unique_pending_users()
+            || old.call_link_state != new.call_link_state
+    }
+
+    match (old, new) {
+        (Some(Ok(old)), Ok(new)) => peek_info_changed(old, new),
+        (Some(Err(old)), Err(new)) => old != new,
+        _ => true,
+    }
+}
+
+struct Watched {
+    target: PeekWatchTarget,
+    // Distinguishes results for a call that was unwatched and then watched again.
+    generation: u64,
+    next_peek: Instant,
+    in_flight: bool,
+    consecutive_failures: u32,
+    last_result: Option<PeekResult>,
+}
+
+#[derive(Default)]
+struct State {
+    stopped: bool,
+    watched: HashMap<PeekWatchId, Watched>,
+    next_generation: u64,
+}
+
+/// A peek to send, along with what's needed to match up its result.
+struct DuePeek {
+    id: PeekWatchId,
+    generation: u64,
+    target: PeekWatchTarget,
+}
+
+// Everything that depends on the time takes `now`, so that tests don't have to wait.
+impl State {
+    /// Returns true if `id` wasn't already being watched, and so is due for a peek.
+    fn watch(&mut self, id: PeekWatchId, target: PeekWatchTarget, now: Instant) -> bool {
+        if let Some(watched) = self.watched.get_mut(&id) {
+            watched.target = target;
+            return false;
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        self.watched.insert(
+            id,
+            Watched {
+                target,
+                generation,
+                next_peek: now,
+                in_flight: false,
+                consecutive_failures: 0,
+                last_result: None,
+            },
+        );
+        true
+    }
+
+    fn unwatch(&mut self, id: &PeekWatchId) {
+        self.watched.remove(id);
+    }
+
+    /// Returns true if `id` is being watched.
+    fn refresh(&mut self, id: &PeekWatchId, now: Instant) -> bool {
+        if let Some(watched) = self.watched.get_mut(id) {
+            watched.next_peek = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Marks the peeks due by `now` as in flight and returns them.
+    fn take_due_peeks(&mut self, now: Instant) -> Vec<DuePeek> {
+        self.watched
+            .iter_mut()
+            .filter(|(_, watched)| !watched.in_flight && watched.next_peek <= now)
+            .map(|(id, watched)| {
+                watched.in_flight = true;
+                DuePeek {
+                    id: id.clone(),
+                    generation: watched.generation,
+                    target: watched.target.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// When the next peek that isn't already in flight is due.
+    fn next_peek(&self) -> Option<Instant> {
+        self.watched
+            .values()
+            .filter(|watched| !watched.in_flight)
+            .map(|watched| watched.next_peek)
+            .min()
+    }
+
+    /// Schedules the next peek for `id` and returns true if `result` should be reported.
+    fn handle_peek_result(
+        &mut self,
+        config: &PeekWatcherConfig,
+        id: &PeekWatchId,
+        generation: u64,
+        result: &PeekResult,
+        now: Instant,
+    ) -> bool {
+        let Some(watched) = self
+            .watched
+            .get_mut(id)
+            .filter(|watched| watched.generation == generation)
+        else {
+            debug!("peek_watcher: ignoring result for a call that is no longer watched");
+            return false;
+        };
+        watched.in_flight = false;
+        if result.is_ok() {
+            watched.consecutive_failures = 0;
+        } else {
+            watched.consecutive_failures = watched.consecutive_failures.saturating_add(1);
+        }
+        watched.next_peek = now + config.next_interval(result, watched.consecutive_failures);
+        let changed = peek_result_changed(watched.last_result.as_ref(), result);
+        watched.last_result = Some(result.clone());
+        changed
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    // Notified when there might be a new peek due, or when stopping.
+    wakeup: Condvar,
+}
+
+/// Peeks a set of calls on a background thread, polling active calls more often than empty
+/// ones and backing off on failures, and invokes a callback only when a result changes.
+pub struct PeekWatcher {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PeekWatcher {
+    pub fn start(
+        http_client: Box<dyn http::Client + Send>,
+        config: PeekWatcherConfig,
+        on_change: PeekWatcherCallback,
+    ) -> Self {
+        let config = config.validated();
+        let shared = Arc::new(Shared {
+            state: Mutex::default(),
+            wakeup: Condvar::new(),
+        });
+        let thread = {
+            let shared = shared.clone();
+            let on_change: Arc<dyn Fn(&PeekWatchId, &PeekResult) + Send + Sync> =
+                Arc::from(on_change);
+            thread::Builder::new()
+                .name("peek_watcher".to_string())
+                .spawn(move || Self::run(shared, http_client, config, on_change))
+                .expect("start peek_watcher thread")
+        };
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// Starts watching a call, peeking it right away.
+    /// If it's already being watched, the new target (such as a refreshed auth header) is used
+    /// from the next peek on, without changing when that happens.
+    pub fn watch(&self, id: PeekWatchId, target: PeekWatchTarget) {
+        let mut state = self.shared.state.lock().expect("peek_watcher state lock");
+        if state.watch(id, target, Instant::now()) {
+            self.shared.wakeup.notify_one();
+        }
+    }
+
+    /// Stops watching a call. A peek already in flight won't be reported.
+    pub fn unwatch(&self, id: &PeekWatchId) {
+        let mut state = self.shared.state.lock().expect("peek_watcher state lock");
+        state.unwatch(id);
+    }
+
+    /// Peeks a watched call as soon as possible, such as when the app has heard that
+    /// something changed.
+    pub fn refresh(&self, id: &PeekWatchId) {
+        let mut state = self.shared.state.lock().expect("peek_watcher state lock");
+        if state.refresh(id, Instant::now()) {
+            self.shared.wakeup.notify_one();
+        }
+    }
+
+    fn run(
+        shared: Arc<Shared>,
+        http_client: Box<dyn http::Client + Send>,
+        config: PeekWatcherConfig,
+        on_change: Arc<dyn Fn(&PeekWatchId, &PeekResult) + Send + Sync>,
+    ) {
+        loop {
+            let due = {
+                let mut state = shared.state.lock().expect("peek_watcher state lock");
+                loop {
+                    if state.stopped {
+                        http_client.cancel_pending_requests();
+                        return;
+                    }
+                    let now = Instant::now();
+                    let due = state.take_due_peeks(now);
+                    if !due.is_empty() {
+                        break due;
+                    }
+                    state = match state.next_peek() {
+                        Some(next_peek) => {
+                            shared
+                                .wakeup
+                                .wait_timeout(state, next_peek.saturating_duration_since(now))
+                                .expect("peek_watcher state lock")
+                                .0
+                        }
+                        None => shared.wakeup.wait(state).expect("peek_watcher state lock"),
+                    };
+                }
+            };
+
+            for DuePeek {
+                id,
+                generation,
+                target,
+            } in due
+            {
+                let shared = shared.clone();
+                let config = config.clone();
+                let on_change = on_change.clone();
+                super::peek(
+                    &*http_client,
+                    &target.sfu_url,
+                    target.room_id_header,
+                    target.auth_header,
+                    target.member_resolver,
+                    target.call_link_root_key,
+                    Box::new(move |result| {
+                        let changed = {
+                            let mut state = shared.state.lock().expect("peek_watcher state lock");
+                            let changed = state.handle_peek_result(
+                                &config,
+                                &id,
+                                generation,
+                                &result,
+                                Instant::now(),
+                            );
+                            shared.wakeup.notify_one();
+                            changed
+                        };
+                        if changed {
+                            on_change(&id, &result);
+                        }
+                    }),
+                );
+            }
+        }
+    }
+}
+
+impl Drop for PeekWatcher {
+    fn drop(&mut self) {
+        self.shared
+            .state
+            .lock()
+            .expect("peek_watcher state lock")
+            .stopped = true;
+        self.shared.wakeup.notify_one();
+        if let Some(thread) = self.thread.take() {
+            if thread.thread().id() == thread::current().id() {
+                // Dropped from the callback, which can run on the watcher thread itself.
+                // It will see that it's been stopped once the callback returns.
+                return;
+            }
+            if thread.join().is_err() {
+                error!("peek_watcher thread panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, sync::mpsc};
+
+    use super::*;
+    use crate::lite::sfu::{MemberMap, PeekDeviceInfo};
+
+    // Responds immediately with queued responses, repeating the last one once they run out.
+    struct FakeClient {
+        responses: Mutex<VecDeque<Option<http::Response>>>,
+    }
+
+    impl FakeClient {
+        fn new(bodies: &[Option<&str>]) -> Self {
+            Self {
+                responses: Mutex::new(
+                    bodies
+                        .iter()
+                        .map(|body| {
+                            body.map(|body| http::Response {
+                                status: 200.into(),
+                                headers: HashMap::new(),
+                                body: body.as_bytes().to_vec(),
+                            })
+                        })
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    impl http::Client for FakeClient {
+        fn send_request(&self, _request: http::Request, callback: http::ResponseCallback) {
+            let response = {
+                let mut responses = self.responses.lock().unwrap();
+                if responses.len() > 1 {
+                    responses.pop_front().unwrap()
+                } else {
+                    responses.front().cloned().flatten()
+                }
+            };
+            callback(response)
+        }
+    }
+
+    fn target() -> PeekWatchTarget {
+        PeekWatchTarget {
+            sfu_url: "https://sfu.example".to_string(),
+            room_id_header: None,
+            auth_header: "auth".to_string(),
+            member_resolver: Arc::new(MemberMap::new(&[])),
+            call_link_root_key: None,
+        }
+    }
+
+    fn fast_config() -> PeekWatcherConfig {
+        PeekWatcherConfig {
+            active_interval: Duration::from_millis(1),
+            empty_interval: Duration::from_millis(1),
+            max_failure_interval: Duration::from_millis(1),
+            jitter: 0.0,
+        }
+    }
+
+    fn test_config() -> PeekWatcherConfig {
+        PeekWatcherConfig {
+            active_interval: Duration::from_secs(10),
+            empty_interval: Duration::from_secs(60),
+            max_failure_interval: Duration::from_secs(35),
+            jitter: 0.0,
+        }
+    }
+
+    // Sends the peeks due at `now` and returns the results worth reporting. FakeClient responds
+    // right away, so nothing is left in flight afterwards.
+    fn tick(
+        state: &mut State,
+        client: &FakeClient,
+        now: Instant,
+    ) -> Vec<(PeekWatchId, PeekResult)> {
+        let results = Arc::new(Mutex::new(vec![]));
+        for DuePeek {
+            id,
+            generation,
+            target,
+        } in state.take_due_peeks(now)
+        {
+            let results = results.clone();
+            crate::lite::sfu::peek(
+                client,
+                &target.sfu_url,
+                target.room_id_header,
+                target.auth_header,
+                target.member_resolver,
+                target.call_link_root_key,
+                Box::new(move |result| results.lock().unwrap().push((id, generation, result))),
+            );
+        }
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        results
+            .into_iter()
+            .filter(|(id, generation, result)| {
+                state.handle_peek_result(&test_config(), id, *generation, result, now)
+            })
+            .map(|(id, _, result)| (id, result))
+            .collect()
+    }
+
+    const ONE_DEVICE: &str = r#"{"conferenceId":"era","participants":[{"demuxId":16}]}"#;
+    const TWO_DEVICES: &str =
+        r#"{"conferenceId":"era","participants":[{"demuxId":16},{"demuxId":32}]}"#;
+
+    fn demux_ids(result: &PeekResult) -> Vec<u32> {
+        result
+            .as_ref()
+            .unwrap()
+            .devices
+            .iter()
+            .map(|device| device.demux_id)
+            .collect()
+    }
+
+    #[test]
+    fn reports_only_changes() {
+        let client = FakeClient::new(&[Some(ONE_DEVICE), Some(ONE_DEVICE), Some(TWO_DEVICES)]);
+        let mut state = State::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        assert!(state.watch(b"group".to_vec(), target(), at(0)));
+
+        let [(id, result)] = <[_; 1]>::try_from(tick(&mut state, &client, at(0))).unwrap();
+        assert_eq!(b"group".to_vec(), id);
+        assert_eq!(vec![16], demux_ids(&result));
+        assert_eq!(Some(at(10)), state.next_peek());
+        assert!(tick(&mut state, &client, at(9)).is_empty());
+
+        // The same devices again.
+        assert!(tick(&mut state, &client, at(10)).is_empty());
+        let [(_, result)] = <[_; 1]>::try_from(tick(&mut state, &client, at(20))).unwrap();
+        assert_eq!(vec![16, 32], demux_ids(&result));
+
+        // TWO_DEVICES is repeated from here on, which shouldn't be reported.
+        assert!(tick(&mut state, &client, at(30)).is_empty());
+    }
+
+    #[test]
+    fn reports_failures_once_and_backs_off() {
+        let client = FakeClient::new(&[Some(ONE_DEVICE), None]);
+        let mut state = State::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        state.watch(b"group".to_vec(), target(), at(0));
+
+        let [(_, result)] = <[_; 1]>::try_from(tick(&mut state, &client, at(0))).unwrap();
+        assert!(result.is_ok());
+        let [(_, result)] = <[_; 1]>::try_from(tick(&mut state, &client, at(10))).unwrap();
+        assert_eq!(Err(http::ResponseStatus::REQUEST_FAILED), result);
+        assert_eq!(Some(at(20)), state.next_peek());
+        assert!(tick(&mut state, &client, at(20)).is_empty());
+        assert_eq!(Some(at(40)), state.next_peek());
+    }
+
+    #[test]
+    fn refresh_peeks_right_away() {
+        let client = FakeClient::new(&[Some(ONE_DEVICE), Some(TWO_DEVICES)]);
+        let mut state = State::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        state.watch(b"group".to_vec(), target(), at(0));
+        assert_eq!(1, tick(&mut state, &client, at(0)).len());
+
+        assert!(state.refresh(&b"group".to_vec(), at(3)));
+        assert!(!state.refresh(&b"other".to_vec(), at(3)));
+        let [(_, result)] = <[_; 1]>::try_from(tick(&mut state, &client, at(3))).unwrap();
+        assert_eq!(vec![16, 32], demux_ids(&result));
+        assert_eq!(Some(at(13)), state.next_peek());
+    }
+
+    #[test]
+    fn unwatch_stops_reports() {
+        let client = FakeClient::new(&[Some(ONE_DEVICE), Some(TWO_DEVICES)]);
+        let mut state = State::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        state.watch(b"group".to_vec(), target(), at(0));
+        assert_eq!(1, tick(&mut state, &client, at(0)).len());
+
+        state.unwatch(&b"group".to_vec());
+        assert_eq!(None, state.next_peek());
+        assert!(tick(&mut state, &client, at(10)).is_empty());
+    }
+
+    #[test]
+    fn ignores_results_from_before_unwatching() {
+        let client = FakeClient::new(&[Some(ONE_DEVICE)]);
+        let mut state = State::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        state.watch(b"group".to_vec(), target(), at(0));
+        let [in_flight] = <[_; 1]>::try_from(state.take_due_peeks(at(0)))
+            .ok()
+            .unwrap();
+
+        state.unwatch(&b"group".to_vec());
+        assert!(state.watch(b"group".to_vec(), target(), at(1)));
+        assert!(!state.handle_peek_result(
+            &test_config(),
+            &in_flight.id,
+            in_flight.generation,
+            &Ok(PeekInfo::default()),
+            at(1),
+        ));
+
+        // The new watch is still peeked right away, and its first result reported.
+        assert_eq!(1, tick(&mut state, &client, at(1)).len());
+    }
+
+    #[test]
+    fn drop_from_callback() {
+        let watcher: Arc<Mutex<Option<PeekWatcher>>> = Default::default();
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        *watcher.lock().unwrap() = Some(PeekWatcher::start(
+            Box::new(FakeClient::new(&[Some(ONE_DEVICE)])),
+            fast_config(),
+            Box::new({
+                let watcher = watcher.clone();
+                move |_, _| {
+                    // FakeClient responds on the watcher thread.
+                    drop(watcher.lock().unwrap().take());
+                    let _ = sender.lock().unwrap().send(());
+                }
+            }),
+        ));
+        watcher
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .watch(b"group".to_vec(), target());
+
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(watcher.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn intervals() {
+        let config = PeekWatcherConfig {
+            active_interval: Duration::from_secs(10),
+            empty_interval: Duration::from_secs(60),
+            max_failure_interval: Duration::from_secs(35),
+            jitter: 0.0,
+        };
+        let active = Ok(PeekInfo {
+            devices: vec![PeekDeviceInfo {
+                demux_id: 16,
+                user_id: None,
+            }],
+            ..Default::default()
+        });
+        let failed = Err(http::ResponseStatus::REQUEST_FAILED);
+
+        assert_eq!(Duration::from_secs(10), config.next_interval(&active, 0));
+        assert_eq!(
+            Duration::from_secs(60),
+            config.next_interval(&Ok(PeekInfo::default()), 0)
+        );
+        assert_eq!(Duration::from_secs(10), config.next_interval(&failed, 1));
+        assert_eq!(Duration::from_secs(20), config.next_interval(&failed, 2));
+        assert_eq!(Duration::from_secs(35), config.next_interval(&failed, 3));
+        assert_eq!(Duration::from_secs(35), config.next_interval(&failed, 100));
+
+        let config = PeekWatcherConfig {
+            jitter: 0.2,
+            ..config
+        };
+        for _ in 0..100 {
+            let interval = config.next_interval(&active, 0);
+            assert!(interval >= Duration::from_secs(8) && interval <= Duration::from_secs(12));
+        }
+    }
+
+    #[test]
+    fn jitter_is_limited() {
+        let with_jitter = |jitter| PeekWatcherConfig {
+            jitter,
+            ..test_config()
+        };
+        for jitter in [0.0, 0.2, 0.5] {
+            assert_eq!(jitter, with_jitter(jitter).validated().jitter);
+        }
+        for jitter in [2.0, f64::INFINITY] {
+            assert_eq!(0.5, with_jitter(jitter).validated().jitter);
+        }
+        for jitter in [-1.0, f64::NAN] {
+            assert_eq!(0.0, with_jitter(jitter).validated().jitter);
+        }
+
+        let config = with_jitter(2.0).validated();
+        for _ in 0..100 {
+            let interval = config.next_interval(&Ok(PeekInfo::default()), 0);
+            assert!(interval >= Duration::from_secs(30) && interval <= Duration::from_secs(90));
+        }
+    }
+
+    #[test]
+    fn changes() {
+        let era = |era_id: &str| {
+            Ok(PeekInfo {
+                era_id: Some(era_id.to_string()),
+                ..Default::default()
+            })
+        };
+        assert!(peek_result_changed(None, &era("a")));
+        assert!(!peek_result_changed(Some(&era("a")), &era("a")));
+        assert!(peek_result_changed(Some(&era("a")), &era("b")));
+
+        // Only the order of first appearance matters for pending users.
+        let pending = |user_ids: &[u8]| {
+            Ok(PeekInfo {
+                pending_devices: user_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(i, user_id)| PeekDeviceInfo {
+                        demux_id: (i as u32 + 1) * 16,
+                        user_id: Some(vec![*user_id]),
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+        };
+        assert!(!peek_result_changed(
+            Some(&pending(&[1, 2])),
+            &pending(&[1, 2, 1])
+        ));
+        assert!(peek_result_changed(
+            Some(&pending(&[1, 2])),
+            &pending(&[2, 1])
+        ));
+
+        let failed = Err(http::ResponseStatus::REQUEST_FAILED);
+        assert!(peek_result_changed(Some(&era("a")), &failed));
+        assert!(!peek_result_changed(Some(&failed), &failed));
+        assert!(peek_result_changed(
+            Some(&failed),
+            &Err(http::ResponseStatus::GROUP_CALL_FULL)
+        ));
+    }
+}
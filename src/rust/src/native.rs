@@ -11,6 +11,7 @@ use crate::{
     },
     core::{
         call::Call,
+        call_record::{CallRecord, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         platform::{Platform, PlatformItem},
@@ -18,7 +19,7 @@ use crate::{
     },
     lite::{
         sfu,
-        sfu::{DemuxId, PeekInfo, PeekResult, UserId},
+        sfu::{DemuxId, PeekInfo, PeekResult, PeekWatchId, UserId},
     },
     webrtc::{
         media::{AudioTrack, MediaStream, VideoSink, VideoTrack},
@@ -122,6 +123,16 @@ pub trait CallStateHandler {
     fn handle_remote_audio_state(&self, remote_peer_id: &str, enabled: bool) -> Result<()>;
     fn handle_remote_video_state(&self, remote_peer_id: &str, enabled: bool) -> Result<()>;
     fn handle_remote_sharing_screen(&self, remote_peer_id: &str, enabled: bool) -> Result<()>;
+    fn handle_remote_hold(&self, remote_peer_id: &str, on_hold: bool) -> Result<()>;
+    fn handle_call_waiting(&self, remote_peer_id: &str, call_id: CallId) -> Result<()>;
+    fn handle_group_call_invitation(
+        &self,
+        remote_peer_id: &str,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()>;
+    fn handle_call_transfer_offered(&self, remote_user_id: &[u8], call_id: CallId) -> Result<()>;
+    fn handle_call_record(&self, remote_peer_id: &str, record: CallRecord) -> Result<()>;
     fn handle_network_route(&self, remote_peer_id: &str, network_route: NetworkRoute)
         -> Result<()>;
     fn handle_audio_levels(
@@ -184,6 +195,7 @@ pub enum EndReason {
     ReceivedOfferExpired { age: Duration },
     ReceivedOfferWhileActive,
     ReceivedOfferWithGlare,
+    ReceivedOfferDeclinedByPolicy,
     SignalingFailure,
     GlareFailure,
     ConnectionFailure,
@@ -192,6 +204,8 @@ pub enum EndReason {
     AcceptedOnAnotherDevice,
     DeclinedOnAnotherDevice,
     BusyOnAnotherDevice,
+    EscalatedToGroupCall,
+    Transferred,
 }
 
 impl fmt::Display for EndReason {
@@ -207,6 +221,7 @@ impl fmt::Display for EndReason {
             EndReason::ReceivedOfferExpired { .. } => "ReceivedOfferExpired",
             EndReason::ReceivedOfferWhileActive => "ReceivedOfferWhileActive",
             EndReason::ReceivedOfferWithGlare => "ReceivedOfferWithGlare",
+            EndReason::ReceivedOfferDeclinedByPolicy => "ReceivedOfferDeclinedByPolicy",
             EndReason::SignalingFailure => "SignalingFailure",
             EndReason::GlareFailure => "GlareFailure",
             EndReason::ConnectionFailure => "ConnectionFailure",
@@ -215,6 +230,8 @@ impl fmt::Display for EndReason {
             EndReason::AcceptedOnAnotherDevice => "AcceptedOnAnotherDevice",
             EndReason::DeclinedOnAnotherDevice => "DeclinedOnAnotherDevice",
             EndReason::BusyOnAnotherDevice => "BusyOnAnotherDevice",
+            EndReason::EscalatedToGroupCall => "EscalatedToGroupCall",
+            EndReason::Transferred => "Transferred",
         };
         write!(f, "({})", display)
     }
@@ -240,6 +257,11 @@ pub enum GroupUpdate {
         request_id: u32,
         peek_result: PeekResult,
     },
+    WatchedPeekChanged {
+        id: PeekWatchId,
+        peek_result: PeekResult,
+    },
+    CallSummary(group_call::ClientId, group_call::CallSummary),
     Ended(group_call::ClientId, group_call::EndReason),
     Ring {
         group_id: group_call::GroupId,
@@ -247,6 +269,7 @@ pub enum GroupUpdate {
         sender_id: UserId,
         update: group_call::RingUpdate,
     },
+    RingRecord(GroupRingRecord),
     NetworkRouteChanged(group_call::ClientId, NetworkRoute),
     AudioLevels(group_call::ClientId, AudioLevel, Vec<ReceivedAudioLevel>),
     LowBandwidthForVideo {
@@ -255,10 +278,12 @@ pub enum GroupUpdate {
     },
     Reactions(group_call::ClientId, Vec<group_call::Reaction>),
     RaisedHands(group_call::ClientId, Vec<DemuxId>),
+    PresenterChanged(group_call::ClientId, Option<DemuxId>),
     RtcStatsReportComplete {
         report_json: String,
     },
     SpeechEvent(group_call::ClientId, group_call::SpeechEvent),
+    AdminActionResults(group_call::ClientId, Vec<group_call::AdminActionResult>),
 }
 
 impl fmt::Display for GroupUpdate {
@@ -271,8 +296,11 @@ impl fmt::Display for GroupUpdate {
             GroupUpdate::RemoteDeviceStatesChanged(_, _) => "RemoteDeviceStatesChanged".to_string(),
             GroupUpdate::PeekChanged { .. } => "PeekChanged".to_string(),
             GroupUpdate::PeekResult { .. } => "PeekResult".to_string(),
+            GroupUpdate::WatchedPeekChanged { .. } => "WatchedPeekChanged".to_string(),
+            GroupUpdate::CallSummary(_, summary) => format!("CallSummary({:?})", summary),
             GroupUpdate::Ended(_, reason) => format!("Ended({:?})", reason),
             GroupUpdate::Ring { update, .. } => format!("Ring({:?})", update),
+            GroupUpdate::RingRecord(record) => format!("RingRecord({:?})", record.outcome),
             GroupUpdate::NetworkRouteChanged(_, network_route) => {
                 format!("NetworkRouteChanged({:?})", network_route)
             }
@@ -288,10 +316,16 @@ impl fmt::Display for GroupUpdate {
             GroupUpdate::RaisedHands(_, raised_hands) => {
                 format!("RaisedHands({:?})", raised_hands)
             }
+            GroupUpdate::PresenterChanged(_, current_presenter) => {
+                format!("PresenterChanged({:?})", current_presenter)
+            }
             GroupUpdate::RtcStatsReportComplete { .. } => "RtcStatsReportComplete".to_string(),
             GroupUpdate::SpeechEvent(_, event) => {
                 format!("SpeechEvent({:?}", event)
             }
+            GroupUpdate::AdminActionResults(_, results) => {
+                format!("AdminActionResults({} results)", results.len())
+            }
         };
         write!(f, "({})", display)
     }
@@ -381,6 +415,14 @@ impl NativePlatform {
             .handle_remote_sharing_screen(peer_id, enabled)
     }
 
+    fn send_remote_hold(&self, peer_id: &str, on_hold: bool) -> Result<()> {
+        self.state_handler.handle_remote_hold(peer_id, on_hold)
+    }
+
+    fn send_call_waiting(&self, peer_id: &str, call_id: CallId) -> Result<()> {
+        self.state_handler.handle_call_waiting(peer_id, call_id)
+    }
+
     fn send_signaling(
         &self,
         recipient_id: &str,
@@ -608,6 +650,9 @@ impl Platform for NativePlatform {
                 call_id,
                 CallState::Ended(EndReason::ReceivedOfferWithGlare),
             ),
+            // Sent just before the call is started, so the app can tell the incoming call
+            // apart from a call that replaces the active one.
+            ApplicationEvent::ReceivedCallWaiting => self.send_call_waiting(remote_peer, call_id),
             ApplicationEvent::EndedRemoteHangupAccepted => self.send_state(
                 remote_peer,
                 call_id,
@@ -637,6 +682,25 @@ impl Platform for NativePlatform {
             ApplicationEvent::RemoteSharingScreenDisable => {
                 self.send_remote_sharing_screen(remote_peer, false)
             }
+            ApplicationEvent::EndedEscalatedToGroupCall => self.send_state(
+                remote_peer,
+                call_id,
+                CallState::Ended(EndReason::EscalatedToGroupCall),
+            ),
+            ApplicationEvent::RemoteHold => self.send_remote_hold(remote_peer, true),
+            ApplicationEvent::RemoteResume => self.send_remote_hold(remote_peer, false),
+            ApplicationEvent::EndedTransferred => self.send_state(
+                remote_peer,
+                call_id,
+                CallState::Ended(EndReason::Transferred),
+            ),
+            // Like the other ReceivedOffer* events, this is for a call the app was never
+            // told had started.
+            ApplicationEvent::ReceivedOfferDeclinedByPolicy => self.send_state(
+                remote_peer,
+                call_id,
+                CallState::Ended(EndReason::ReceivedOfferDeclinedByPolicy),
+            ),
         }?;
         Ok(())
     }
@@ -711,6 +775,49 @@ impl Platform for NativePlatform {
         Ok(())
     }
 
+    fn on_call_record(&self, remote_peer: &Self::AppRemotePeer, record: CallRecord) -> Result<()> {
+        info!(
+            "NativePlatform::on_call_record(): remote_peer: {}, outcome: {:?}",
+            remote_peer, record.outcome
+        );
+
+        self.state_handler.handle_call_record(remote_peer, record)
+    }
+
+    fn on_group_ring_record(&self, record: GroupRingRecord) -> Result<()> {
+        info!(
+            "NativePlatform::on_group_ring_record(): id: {}, outcome: {:?}",
+            record.ring_id, record.outcome
+        );
+
+        self.send_group_update(GroupUpdate::RingRecord(record))
+    }
+
+    fn on_group_call_invitation(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        info!(
+            "NativePlatform::on_group_call_invitation(): remote_peer: {}, call_id: {}",
+            remote_peer, call_id
+        );
+
+        self.state_handler
+            .handle_group_call_invitation(remote_peer, call_id, invitation)
+    }
+
+    fn on_call_transfer_offered(&self, remote_user_id: UserId, call_id: CallId) -> Result<()> {
+        info!(
+            "NativePlatform::on_call_transfer_offered(): call_id: {}",
+            call_id
+        );
+
+        self.state_handler
+            .handle_call_transfer_offered(&remote_user_id, call_id)
+    }
+
     fn assume_messages_sent(&self) -> bool {
         self.should_assume_messages_sent
     }
@@ -958,6 +1065,23 @@ impl Platform for NativePlatform {
         }
     }
 
+    fn handle_presenter_changed(
+        &self,
+        client_id: group_call::ClientId,
+        current_presenter: Option<DemuxId>,
+    ) {
+        info!(
+            "NativePlatform::handle_presenter_changed(): id: {}",
+            client_id
+        );
+
+        let result =
+            self.send_group_update(GroupUpdate::PresenterChanged(client_id, current_presenter));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
     fn handle_join_state_changed(
         &self,
         client_id: group_call::ClientId,
@@ -1033,6 +1157,35 @@ impl Platform for NativePlatform {
         }
     }
 
+    fn handle_admin_action_results(
+        &self,
+        client_id: group_call::ClientId,
+        results: Vec<group_call::AdminActionResult>,
+    ) {
+        info!(
+            "NativePlatform::handle_admin_action_results(): id: {}",
+            client_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::AdminActionResults(client_id, results));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_call_summary(
+        &self,
+        client_id: group_call::ClientId,
+        summary: group_call::CallSummary,
+    ) {
+        info!("NativePlatform::handle_call_summary(): id: {}", client_id);
+
+        let result = self.send_group_update(GroupUpdate::CallSummary(client_id, summary));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
         info!("NativePlatform::handle_ended(): id: {}", client_id);
 
@@ -1042,6 +1195,15 @@ impl Platform for NativePlatform {
         }
     }
 
+    fn handle_watched_peek_changed(&self, id: PeekWatchId, peek_result: PeekResult) {
+        info!("NativePlatform::handle_watched_peek_changed()");
+
+        let result = self.send_group_update(GroupUpdate::WatchedPeekChanged { id, peek_result });
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
     fn group_call_ring_update(
         &self,
         group_id: group_call::GroupId,
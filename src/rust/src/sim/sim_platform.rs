@@ -22,6 +22,7 @@ use crate::{
     core::{
         call::Call,
         call_manager::CallManager,
+        call_record::{CallRecord, GroupRingRecord},
         connection::{Connection, ConnectionType},
         group_call,
         platform::{Platform, PlatformItem},
@@ -110,6 +111,14 @@ pub struct SimPlatform {
     group_call_ring_updates: Arc<Mutex<Vec<GroupCallRingUpdate>>>,
     /// Track outgoing opaque messages
     outgoing_call_messages: Arc<Mutex<Vec<OutgoingCallMessage>>>,
+    /// Track invitations to continue 1:1 calls as group calls
+    group_call_invitations: Arc<Mutex<Vec<signaling::GroupCallInvitation>>>,
+    /// Track calls offered by the user's other devices
+    call_transfers: Arc<Mutex<Vec<(UserId, CallId)>>>,
+    /// Track records of concluded 1:1 calls
+    call_records: Arc<Mutex<Vec<CallRecord>>>,
+    /// Track records of finished group rings
+    group_ring_records: Arc<Mutex<Vec<GroupRingRecord>>>,
     /// Call Manager
     call_manager: Arc<Mutex<Option<CallManager<Self>>>>,
     /// True to manually require message_sent() to be invoked for Ice messages.
@@ -500,6 +509,50 @@ impl Platform for SimPlatform {
         Ok(())
     }
 
+    fn on_group_call_invitation(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        invitation: &signaling::GroupCallInvitation,
+    ) -> Result<()> {
+        info!(
+            "on_group_call_invitation(): call_id: {}, invitation: {:?}",
+            call_id, invitation
+        );
+        self.group_call_invitations
+            .lock()
+            .unwrap()
+            .push(invitation.clone());
+        Ok(())
+    }
+
+    fn on_call_transfer_offered(&self, remote_user_id: UserId, call_id: CallId) -> Result<()> {
+        info!("on_call_transfer_offered(): call_id: {}", call_id);
+        self.call_transfers
+            .lock()
+            .unwrap()
+            .push((remote_user_id, call_id));
+        Ok(())
+    }
+
+    fn on_call_record(&self, _remote_peer: &Self::AppRemotePeer, record: CallRecord) -> Result<()> {
+        info!(
+            "on_call_record(): call_id: {}, outcome: {:?}",
+            record.call_id, record.outcome
+        );
+        self.call_records.lock().unwrap().push(record);
+        Ok(())
+    }
+
+    fn on_group_ring_record(&self, record: GroupRingRecord) -> Result<()> {
+        info!(
+            "on_group_ring_record(): ring_id: {}, outcome: {:?}",
+            record.ring_id, record.outcome
+        );
+        self.group_ring_records.lock().unwrap().push(record);
+        Ok(())
+    }
+
     fn on_call_concluded(
         &self,
         _remote_peer: &Self::AppRemotePeer,
@@ -713,6 +766,8 @@ impl SimPlatform {
             ApplicationEvent::EndedSignalingFailure,
             ApplicationEvent::EndedConnectionFailure,
             ApplicationEvent::EndedAppDroppedCall,
+            ApplicationEvent::EndedEscalatedToGroupCall,
+            ApplicationEvent::EndedTransferred,
         ];
         for event in ended_events {
             ends += self.event_count(event);
@@ -794,4 +849,20 @@ impl SimPlatform {
     pub fn take_outgoing_call_messages(&self) -> Vec<OutgoingCallMessage> {
         std::mem::take(&mut *self.outgoing_call_messages.lock().unwrap())
     }
+
+    pub fn take_group_call_invitations(&self) -> Vec<signaling::GroupCallInvitation> {
+        std::mem::take(&mut *self.group_call_invitations.lock().unwrap())
+    }
+
+    pub fn take_call_transfers(&self) -> Vec<(UserId, CallId)> {
+        std::mem::take(&mut *self.call_transfers.lock().unwrap())
+    }
+
+    pub fn take_call_records(&self) -> Vec<CallRecord> {
+        std::mem::take(&mut *self.call_records.lock().unwrap())
+    }
+
+    pub fn take_group_ring_records(&self) -> Vec<GroupRingRecord> {
+        std::mem::take(&mut *self.group_ring_records.lock().unwrap())
+    }
 }
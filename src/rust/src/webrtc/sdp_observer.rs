@@ -234,6 +234,7 @@ impl SessionDescription {
             ice_pwd: Some(ice_pwd),
             receive_video_codecs,
             max_bitrate_bps: Some(data_mode.max_bitrate().as_bps()),
+            supports_ice_restart: Some(true),
         })
     }
 
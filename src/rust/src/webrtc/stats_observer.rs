@@ -21,7 +21,10 @@ pub use crate::webrtc::ffi::stats_observer::RffiStatsObserver;
 use crate::webrtc::sim::stats_observer as stats;
 #[cfg(feature = "sim")]
 pub use crate::webrtc::sim::stats_observer::RffiStatsObserver;
-use crate::{common::CallId, webrtc};
+use crate::{
+    common::{units::DataRate, CallId},
+    webrtc,
+};
 
 /// How often to clean up old stats.
 const CLEAN_UP_STATS_TICKS: u32 = 60;
@@ -40,8 +43,68 @@ struct Stats {
     video_send: HashMap<u32, VideoSenderStatistics>,
     audio_recv: HashMap<u32, (Instant, AudioReceiverStatistics)>,
     video_recv: HashMap<u32, (Instant, VideoReceiverStatistics)>,
+    video_recv_history: HashMap<u32, VideoReceiveHistory>,
 
     report_json: Mutex<String>,
+    receiver_samples: Mutex<Vec<ReceiverSample>>,
+    media_totals: Mutex<MediaTotals>,
+}
+
+/// Running totals across every stats report, used to build a [MediaSummary].
+#[derive(Debug, Default)]
+struct MediaTotals {
+    seconds_elapsed: f32,
+    bytes_sent: u64,
+    packets_sent: i64,
+    remote_packets_lost: i64,
+    bytes_received: u64,
+    packets_received: i64,
+    packets_lost: i64,
+}
+
+impl MediaTotals {
+    fn add_sent(&mut self, bytes_sent: u64, packets_sent: u32, remote_packets_lost: i32) {
+        self.bytes_sent += bytes_sent;
+        self.packets_sent += packets_sent as i64;
+        self.remote_packets_lost += remote_packets_lost as i64;
+    }
+
+    fn add_received(&mut self, bytes_received: u64, packets_received: u32, packets_lost: i32) {
+        self.bytes_received += bytes_received;
+        self.packets_received += packets_received as i64;
+        self.packets_lost += packets_lost as i64;
+    }
+
+    fn summary(&self) -> MediaSummary {
+        let rate = |bytes: u64| {
+            if self.seconds_elapsed > 0.0 {
+                DataRate::from_bps((bytes as f32 * 8.0 / self.seconds_elapsed) as u64)
+            } else {
+                DataRate::default()
+            }
+        };
+        MediaSummary {
+            average_send_rate: rate(self.bytes_sent),
+            average_receive_rate: rate(self.bytes_received),
+            send_packets_lost_pct: StatsObserver::compute_packets_lost_pct(
+                self.remote_packets_lost,
+                self.packets_sent,
+            ),
+            receive_packets_lost_pct: StatsObserver::compute_packets_lost_pct(
+                self.packets_lost,
+                self.packets_received + self.packets_lost,
+            ),
+        }
+    }
+}
+
+/// Averages over all the stats reports received during a call.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaSummary {
+    pub average_send_rate: DataRate,
+    pub average_receive_rate: DataRate,
+    pub send_packets_lost_pct: f32,
+    pub receive_packets_lost_pct: f32,
 }
 /// Collector object for obtaining statistics.
 #[derive(Debug)]
@@ -70,6 +133,20 @@ impl StatsObserver {
                 system,\
                 cpu_usage_pct"
         );
+        info!(
+            "ringrtc_stats!,\
+                setup,\
+                call_id,\
+                offer_age,\
+                offer_sent,\
+                answer_sent,\
+                answer_received,\
+                first_ice_candidate_sent,\
+                first_ice_candidate_received,\
+                ice_connected,\
+                accepted,\
+                first_media"
+        );
         info!(
             "ringrtc_stats!,\
                 audio,\
@@ -176,7 +253,7 @@ impl StatsObserver {
             packets_per_second = packets_sent as f32 / seconds_elapsed,
             average_packet_size = if packets_sent > 0 { bytes_sent as f32 / packets_sent as f32 } else { 0.0 },
             bitrate = bytes_sent as f32 * 8.0 / seconds_elapsed,
-            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_sent as i32),
+            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost.into(), packets_sent.into()),
             remote_jitter = audio_sender.remote_jitter * 1000.0,
             remote_round_trip_time = audio_sender.remote_round_trip_time * 1000.0,
             audio_energy = audio_sender.total_audio_energy - prev_audio_sender.total_audio_energy,
@@ -211,7 +288,7 @@ impl StatsObserver {
             pli_count = video_sender.pli_count - prev_video_sender.pli_count,
             quality_limitation_reason = video_sender.quality_limitation_reason_description(),
             quality_limitation_resolution_changes = video_sender.quality_limitation_resolution_changes - prev_video_sender.quality_limitation_resolution_changes,
-            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_sent as i32),
+            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost.into(), packets_sent.into()),
             remote_jitter = video_sender.remote_jitter * 1000.0,
             remote_round_trip_time = video_sender.remote_round_trip_time * 1000.0,
         );
@@ -233,7 +310,7 @@ impl StatsObserver {
             ssrc = audio_receiver.ssrc,
             packets_per_second = (audio_receiver.packets_received - prev_audio_receiver.packets_received) as f32
                 / seconds_elapsed,
-            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost),
+            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost.into(), i64::from(packets_received) + i64::from(packets_lost)),
             bitrate = (audio_receiver.bytes_received - prev_audio_receiver.bytes_received) as f32 * 8.0
                 / seconds_elapsed,
             jitter = audio_receiver.jitter * 1000.0,
@@ -263,7 +340,7 @@ impl StatsObserver {
             ssrc = video_receiver.ssrc,
             packets_per_second = (video_receiver.packets_received - prev_video_receiver.packets_received) as f32
                 / seconds_elapsed,
-            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost),
+            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost.into(), i64::from(packets_received) + i64::from(packets_lost)),
             bitrate = (video_receiver.bytes_received - prev_video_receiver.bytes_received) as f32 * 8.0
                 / seconds_elapsed,
             framerate = frames_decoded as f32 / seconds_elapsed,
@@ -278,7 +355,7 @@ impl StatsObserver {
         );
     }
 
-    fn compute_packets_lost_pct(packets_lost: i32, packets: i32) -> f32 {
+    fn compute_packets_lost_pct(packets_lost: i64, packets: i64) -> f32 {
         if packets > 0 {
             packets_lost as f32 / packets as f32 * 100.0
         } else if packets_lost < 0 {
@@ -333,6 +410,10 @@ impl StatsObserver {
         *stats_report_json = report_json;
         drop(stats_report_json);
 
+        let mut receiver_samples = Vec::new();
+        let mut media_totals = stats.media_totals.lock().unwrap();
+        media_totals.seconds_elapsed += seconds_elapsed;
+
         if media_statistics.audio_sender_statistics_size > 0 {
             let audio_senders = unsafe {
                 if media_statistics.audio_sender_statistics.is_null() {
@@ -348,6 +429,15 @@ impl StatsObserver {
                 let prev_audio_send_stats = stats.audio_send.entry(audio_sender.ssrc).or_default();
 
                 Self::print_audio_sender(audio_sender, prev_audio_send_stats, seconds_elapsed);
+                media_totals.add_sent(
+                    audio_sender
+                        .bytes_sent
+                        .saturating_sub(prev_audio_send_stats.bytes_sent),
+                    audio_sender
+                        .packets_sent
+                        .saturating_sub(prev_audio_send_stats.packets_sent),
+                    audio_sender.remote_packets_lost - prev_audio_send_stats.remote_packets_lost,
+                );
 
                 *prev_audio_send_stats = audio_sender.clone();
             }
@@ -372,6 +462,15 @@ impl StatsObserver {
                 }
 
                 Self::print_video_sender(video_sender, prev_video_send_stats, seconds_elapsed);
+                media_totals.add_sent(
+                    video_sender
+                        .bytes_sent
+                        .saturating_sub(prev_video_send_stats.bytes_sent),
+                    video_sender
+                        .packets_sent
+                        .saturating_sub(prev_video_send_stats.packets_sent),
+                    video_sender.remote_packets_lost - prev_video_send_stats.remote_packets_lost,
+                );
 
                 *prev_video_send_stats = video_sender.clone();
             }
@@ -395,6 +494,19 @@ impl StatsObserver {
                     .or_insert_with(|| (Instant::now(), Default::default()));
 
                 Self::print_audio_receiver(audio_receiver, prev_audio_recv_stats, seconds_elapsed);
+                media_totals.add_received(
+                    audio_receiver
+                        .bytes_received
+                        .saturating_sub(prev_audio_recv_stats.bytes_received),
+                    audio_receiver
+                        .packets_received
+                        .saturating_sub(prev_audio_recv_stats.packets_received),
+                    audio_receiver.packets_lost - prev_audio_recv_stats.packets_lost,
+                );
+                receiver_samples.push(ReceiverSample::from_audio_receiver(
+                    audio_receiver,
+                    prev_audio_recv_stats,
+                ));
 
                 *updated_at = Instant::now();
                 *prev_audio_recv_stats = audio_receiver.clone();
@@ -419,12 +531,32 @@ impl StatsObserver {
                     .or_insert_with(|| (Instant::now(), Default::default()));
 
                 Self::print_video_receiver(video_receiver, prev_video_recv_stats, seconds_elapsed);
+                media_totals.add_received(
+                    video_receiver
+                        .bytes_received
+                        .saturating_sub(prev_video_recv_stats.bytes_received),
+                    video_receiver
+                        .packets_received
+                        .saturating_sub(prev_video_recv_stats.packets_received),
+                    video_receiver.packets_lost - prev_video_recv_stats.packets_lost,
+                );
+                receiver_samples.push(ReceiverSample::from_video_receiver(
+                    video_receiver,
+                    prev_video_recv_stats,
+                    stats
+                        .video_recv_history
+                        .entry(video_receiver.ssrc)
+                        .or_default(),
+                    seconds_elapsed,
+                ));
 
                 *updated_at = Instant::now();
                 *prev_video_recv_stats = video_receiver.clone();
             }
         }
 
+        drop(media_totals);
+        *stats.receiver_samples.lock().unwrap() = receiver_samples;
         stats.timestamp_us = media_statistics.timestamp_us;
 
         self.stats_received_count += 1;
@@ -443,6 +575,11 @@ impl StatsObserver {
         self.stats
             .video_recv
             .retain(|_, (ts, _)| ts.elapsed() < MAX_STATS_AGE);
+
+        let video_recv = &self.stats.video_recv;
+        self.stats
+            .video_recv_history
+            .retain(|ssrc, _| video_recv.contains_key(ssrc));
     }
 
     /// Set the RFFI observer object.
@@ -464,6 +601,17 @@ impl StatsObserver {
         }
     }
 
+    /// Returns the per-stream receiver samples from the most recent stats report, if they
+    /// haven't already been taken.
+    pub fn take_receiver_samples(&self) -> Vec<ReceiverSample> {
+        std::mem::take(&mut *self.stats.receiver_samples.lock().unwrap())
+    }
+
+    /// Summarizes the media sent and received across all stats reports so far.
+    pub fn media_summary(&self) -> MediaSummary {
+        self.stats.media_totals.lock().unwrap().summary()
+    }
+
     pub fn set_collect_raw_stats_report(&self, collect_raw_stats_report: bool) {
         unsafe {
             stats::Rust_setCollectRawStatsReport(self.rffi.as_borrowed(), collect_raw_stats_report)
@@ -560,6 +708,146 @@ pub struct VideoReceiverStatistics {
     pub frame_height: u32,
 }
 
+/// A summary of how a single incoming stream fared since the previous stats report.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReceiverSample {
+    pub ssrc: u32,
+    pub is_video: bool,
+    pub packets_received: u32,
+    pub packets_lost_pct: f32,
+    /// In seconds.
+    pub jitter: f64,
+    /// Always 0 for audio.
+    pub frames_decoded: u32,
+    /// How many frames would have been decoded at the frame rate the stream recently
+    /// managed. Always 0 for audio, and until the stream has decoded any frames.
+    pub frames_expected: u32,
+    /// How long packets have been arriving without any frames being decoded, across
+    /// consecutive reports. Always zero for audio.
+    pub freeze_duration: Duration,
+}
+
+impl ReceiverSample {
+    fn from_audio_receiver(
+        audio_receiver: &AudioReceiverStatistics,
+        prev_audio_receiver: &AudioReceiverStatistics,
+    ) -> Self {
+        let packets_lost = audio_receiver.packets_lost - prev_audio_receiver.packets_lost;
+        let packets_received = audio_receiver
+            .packets_received
+            .saturating_sub(prev_audio_receiver.packets_received);
+        Self {
+            ssrc: audio_receiver.ssrc,
+            is_video: false,
+            packets_received,
+            packets_lost_pct: StatsObserver::compute_packets_lost_pct(
+                packets_lost.into(),
+                i64::from(packets_received) + i64::from(packets_lost),
+            ),
+            jitter: audio_receiver.jitter,
+            frames_decoded: 0,
+            frames_expected: 0,
+            freeze_duration: Duration::ZERO,
+        }
+    }
+
+    fn from_video_receiver(
+        video_receiver: &VideoReceiverStatistics,
+        prev_video_receiver: &VideoReceiverStatistics,
+        history: &mut VideoReceiveHistory,
+        seconds_elapsed: f32,
+    ) -> Self {
+        let packets_lost = video_receiver.packets_lost - prev_video_receiver.packets_lost;
+        let packets_received = video_receiver
+            .packets_received
+            .saturating_sub(prev_video_receiver.packets_received);
+        let frames_decoded = video_receiver
+            .frames_decoded
+            .saturating_sub(prev_video_receiver.frames_decoded);
+        let frames_expected = history.update(packets_received, frames_decoded, seconds_elapsed);
+        Self {
+            ssrc: video_receiver.ssrc,
+            is_video: true,
+            packets_received,
+            packets_lost_pct: StatsObserver::compute_packets_lost_pct(
+                packets_lost.into(),
+                i64::from(packets_received) + i64::from(packets_lost),
+            ),
+            // Video jitter isn't collected.
+            jitter: 0.0,
+            frames_decoded,
+            frames_expected,
+            freeze_duration: history.frozen_for,
+        }
+    }
+}
+
+/// How quickly the recent frame rate of an incoming video stream is forgotten, per report.
+const EXPECTED_FRAMERATE_DECAY: f32 = 0.9;
+
+/// What is remembered about an incoming video stream across stats reports, since freezes
+/// and drops in frame rate can only be judged against what came before.
+#[derive(Debug, Default)]
+struct VideoReceiveHistory {
+    /// The best frame rate the stream has recently managed. It decays so that a sender
+    /// that deliberately lowers its frame rate is eventually no longer held to the old one.
+    expected_framerate: f32,
+    /// How long packets have been arriving without any frames being decoded.
+    frozen_for: Duration,
+}
+
+impl VideoReceiveHistory {
+    /// Updates the history with a new report, returning how many frames were expected to
+    /// be decoded since the previous one.
+    fn update(&mut self, packets_received: u32, frames_decoded: u32, seconds_elapsed: f32) -> u32 {
+        if seconds_elapsed <= 0.0 {
+            return 0;
+        }
+        let frames_expected = (self.expected_framerate * seconds_elapsed).round() as u32;
+
+        let framerate = frames_decoded as f32 / seconds_elapsed;
+        self.expected_framerate = framerate.max(self.expected_framerate * EXPECTED_FRAMERATE_DECAY);
+
+        if frames_decoded == 0 && packets_received > 0 {
+            self.frozen_for += Duration::from_secs_f32(seconds_elapsed);
+        } else {
+            // Either frames are being decoded, or the sender has paused video.
+            self.frozen_for = Duration::ZERO;
+        }
+
+        frames_expected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn video_receive_history() {
+        let mut history = VideoReceiveHistory::default();
+        // Nothing is expected of a new stream.
+        assert_eq!(0, history.update(100, 30, 1.0));
+        assert_eq!(Duration::ZERO, history.frozen_for);
+
+        // It managed 30fps, so that's expected next time.
+        assert_eq!(30, history.update(100, 10, 1.0));
+        // And slightly less after a slow second.
+        assert_eq!(27, history.update(100, 27, 1.0));
+
+        // Packets without frames are a freeze, which grows until a frame is decoded.
+        history.update(100, 0, 1.0);
+        history.update(100, 0, 0.5);
+        assert_eq!(Duration::from_millis(1500), history.frozen_for);
+        history.update(100, 1, 1.0);
+        assert_eq!(Duration::ZERO, history.frozen_for);
+
+        // No packets at all means the sender paused video, which isn't a freeze.
+        history.update(0, 0, 1.0);
+        assert_eq!(Duration::ZERO, history.frozen_for);
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct ConnectionStatistics {
@@ -178,6 +178,14 @@ impl TestContext {
         platform.last_ice_sent()
     }
 
+    pub fn last_ice_restart_sent(&self) -> Option<signaling::IceRestart> {
+        self.last_ice_sent()?
+            .ice
+            .candidates
+            .iter()
+            .find_map(|candidate| candidate.ice_restart())
+    }
+
     pub fn normal_hangups_sent(&self) -> usize {
         let platform = self.call_manager.platform().unwrap();
         platform.normal_hangups_sent()
@@ -280,6 +288,7 @@ pub fn random_received_offer(_prng: &Prng, age: Duration) -> signaling::Received
             ice_pwd: None,
             receive_video_codecs: vec![],
             max_bitrate_bps: None,
+            supports_ice_restart: Some(true),
         },
     )
     .unwrap();
@@ -299,6 +308,23 @@ pub fn random_received_offer(_prng: &Prng, age: Duration) -> signaling::Received
 pub fn random_received_answer(
     _prng: &Prng,
     sender_device_id: DeviceId,
+) -> signaling::ReceivedAnswer {
+    received_answer(sender_device_id, Some(true))
+}
+
+/// An answer from a client that predates ICE restarts.
+#[allow(dead_code)]
+pub fn received_answer_without_ice_restart(
+    _prng: &Prng,
+    sender_device_id: DeviceId,
+) -> signaling::ReceivedAnswer {
+    received_answer(sender_device_id, None)
+}
+
+#[allow(dead_code)]
+fn received_answer(
+    sender_device_id: DeviceId,
+    supports_ice_restart: Option<bool>,
 ) -> signaling::ReceivedAnswer {
     let local_public_key = rand::thread_rng().gen::<[u8; 32]>().to_vec();
     let answer = signaling::Answer::from_v4(protobuf::signaling::ConnectionParametersV4 {
@@ -307,6 +333,7 @@ pub fn random_received_answer(
         ice_pwd: None,
         receive_video_codecs: vec![],
         max_bitrate_bps: None,
+        supports_ice_restart,
     })
     .unwrap();
     signaling::ReceivedAnswer {
@@ -324,6 +351,23 @@ pub fn random_ice_candidate(prng: &Prng) -> signaling::IceCandidate {
     signaling::IceCandidate::new(ice_candidate.opaque)
 }
 
+pub fn received_ice_restart(
+    generation: u32,
+    credentials: Option<(&str, &str)>,
+    sender_device_id: DeviceId,
+) -> signaling::ReceivedIce {
+    let restart = signaling::IceRestart {
+        generation,
+        credentials: credentials.map(|(ufrag, pwd)| (ufrag.to_owned(), pwd.to_owned())),
+    };
+    signaling::ReceivedIce {
+        ice: signaling::Ice {
+            candidates: vec![signaling::IceCandidate::from_ice_restart(restart).unwrap()],
+        },
+        sender_device_id,
+    }
+}
+
 pub fn random_received_ice_candidate(prng: &Prng) -> signaling::ReceivedIce {
     let candidate = random_ice_candidate(prng);
     signaling::ReceivedIce {
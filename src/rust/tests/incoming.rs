@@ -15,10 +15,20 @@ use std::time::Duration;
 use prost::Message;
 use ringrtc::{
     common::{
-        units::DataRate, ApplicationEvent, CallConfig, CallId, CallState, ConnectionState, DataMode,
+        units::DataRate, ApplicationEvent, CallConfig, CallDirection, CallId, CallState,
+        ConnectionState, DataMode,
     },
-    core::{call_manager::MAX_MESSAGE_AGE, group_call, signaling},
-    protobuf, webrtc,
+    core::{
+        call::Call,
+        call_manager::MAX_MESSAGE_AGE,
+        call_record::{CallOutcome, GroupRingOutcome},
+        group_call,
+        incoming_call_policy::{DoNotDisturb, QuietHours},
+        signaling,
+    },
+    protobuf,
+    sim::sim_platform::SimPlatform,
+    webrtc,
     webrtc::{
         media::MediaStream,
         peer_connection_observer::{NetworkAdapterType, NetworkRoute, TransportProtocol},
@@ -27,7 +37,10 @@ use ringrtc::{
 
 #[macro_use]
 mod common;
-use common::{random_received_ice_candidate, random_received_offer, test_init, TestContext};
+use common::{
+    random_received_ice_candidate, random_received_offer, received_ice_restart, test_init,
+    TestContext,
+};
 
 // Create an inbound call session up to the ConnectingBeforeAccepted state.
 //
@@ -211,6 +224,82 @@ fn inbound_call_hangup_accepted() {
     assert!(!cm.busy());
 }
 
+#[test]
+fn inbound_call_record_accepted() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let (call_id, local_device_id) = {
+        let active_call = context.active_call();
+        (active_call.call_id(), active_call.local_device_id())
+    };
+
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+    assert_eq!(context.call_concluded_count(), 1);
+
+    let records = cm.platform().expect(error_line!()).take_call_records();
+    match &records[..] {
+        [record] => {
+            assert_eq!(call_id, record.call_id);
+            assert_eq!(CallDirection::Incoming, record.direction);
+            assert_eq!(CallOutcome::Accepted, record.outcome);
+            assert_eq!(Some(local_device_id), record.answered_by_device_id);
+            let answered = record.answered.expect(error_line!());
+            assert!(record.started <= answered && answered <= record.ended);
+
+            let setup = &record.setup;
+            assert_eq!(Some(Duration::from_secs(0)), setup.offer_age);
+            assert_eq!(None, setup.offer_sent);
+            assert_eq!(None, setup.answer_received);
+            assert!(setup.first_ice_candidate_received.is_some());
+            let ice_connected = setup.ice_connected.expect(error_line!());
+            let first_media = setup.first_media.expect(error_line!());
+            let accepted = setup.accepted.expect(error_line!());
+            assert!(ice_connected <= accepted);
+            assert!(first_media <= accepted);
+        }
+        _ => panic!("unexpected call records: {:?}", records),
+    }
+}
+
+#[test]
+fn inbound_call_record_accepted_on_another_device() {
+    test_init();
+
+    let context = start_inbound_call();
+    let mut cm = context.cm();
+    let call_id = context.active_call().call_id();
+
+    cm.received_hangup(
+        call_id,
+        signaling::ReceivedHangup {
+            sender_device_id: 1,
+            hangup: signaling::Hangup::AcceptedOnAnotherDevice(2),
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.call_concluded_count(), 1);
+
+    let records = cm.platform().expect(error_line!()).take_call_records();
+    match &records[..] {
+        [record] => {
+            assert_eq!(call_id, record.call_id);
+            assert_eq!(CallOutcome::AcceptedOnAnotherDevice, record.outcome);
+            assert_eq!(None, record.answered);
+            assert_eq!(Some(2), record.answered_by_device_id);
+        }
+        _ => panic!("unexpected call records: {:?}", records),
+    }
+}
+
 #[test]
 fn inbound_call_hangup_declined() {
     test_init();
@@ -644,84 +733,893 @@ fn update_data_mode_when_relayed() {
 }
 
 #[test]
-fn start_inbound_call_with_error() {
+fn start_inbound_call_with_error() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert!(cm.active_call().is_ok());
+    assert_eq!(context.start_outgoing_count(), 0);
+    assert_eq!(context.start_incoming_count(), 1);
+
+    let active_call = context.active_call();
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::WaitingToProceed
+    );
+
+    // cause the sending of the answer to fail.
+    context.force_internal_fault(true);
+
+    cm.proceed(
+        active_call.call_id(),
+        format!("CONTEXT-{}", context.prng.gen::<u16>()),
+        CallConfig::default().with_data_mode(DataMode::Normal),
+        None,
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    active_call.get_connection(1).expect_err(error_line!());
+
+    assert_eq!(context.error_count(), 1);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.answers_sent(), 0);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert!(!cm.busy());
+}
+
+#[test]
+fn receive_offer_while_active() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferWhileActive),
+        1
+    );
+    assert_eq!(context.busys_sent(), 1);
+    assert_eq!(context.call_concluded_count(), 1);
+    assert!(cm.busy());
+}
+
+// Quiet hours that last all day, so that the policy applies whenever the test runs.
+const ALL_DAY: QuietHours = QuietHours {
+    start_minute: 0,
+    end_minute: 0,
+    utc_offset_minutes: 0,
+};
+
+#[test]
+fn receive_offer_declined_by_policy() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.set_incoming_call_policy(Some(Box::new(DoNotDisturb::<SimPlatform> {
+        allowed_peers: vec!["FAVORITE".to_string()],
+        quiet_hours: Some(ALL_DAY),
+        ..Default::default()
+    })))
+    .expect(error_line!());
+
+    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferDeclinedByPolicy),
+        1
+    );
+    assert_eq!(context.start_incoming_count(), 0);
+    assert_eq!(context.normal_hangups_sent(), 1);
+    assert_eq!(context.busys_sent(), 0);
+    assert_eq!(context.call_concluded_count(), 1);
+    assert!(!cm.busy());
+
+    let outcomes: Vec<_> = cm
+        .platform()
+        .expect(error_line!())
+        .take_call_records()
+        .into_iter()
+        .map(|record| record.outcome)
+        .collect();
+    assert_eq!(vec![CallOutcome::DeclinedByPolicy], outcomes);
+
+    // Callers in the allow-list still ring.
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        "FAVORITE".to_string(),
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferDeclinedByPolicy),
+        1
+    );
+    assert_eq!(context.start_incoming_count(), 1);
+    assert!(cm.busy());
+}
+
+#[test]
+fn receive_offer_from_non_contact() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.set_incoming_call_policy(Some(Box::new(DoNotDisturb::<SimPlatform> {
+        contacts_only: true,
+        contact_peers: vec!["CONTACT".to_string()],
+        ..Default::default()
+    })))
+    .expect(error_line!());
+
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        "STRANGER".to_string(),
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferDeclinedByPolicy),
+        1
+    );
+    assert_eq!(context.start_incoming_count(), 0);
+    assert_eq!(context.need_permission_hangups_sent(), 1);
+    assert_eq!(context.normal_hangups_sent(), 0);
+    assert_eq!(context.call_concluded_count(), 1);
+
+    // Clearing the policy lets everyone ring again.
+    cm.set_incoming_call_policy(None).expect(error_line!());
+
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        "STRANGER".to_string(),
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.start_incoming_count(), 1);
+    assert!(cm.busy());
+}
+
+// Receive a second offer during the connected inbound call with call waiting
+// enabled, and bring the waiting call up to the ConnectedBeforeAccepted state.
+fn start_waiting_call(context: &TestContext) -> Call<SimPlatform> {
+    let mut cm = context.cm();
+    cm.set_call_waiting_enabled(true).expect(error_line!());
+
+    let waiting_events = context.event_count(ApplicationEvent::ReceivedCallWaiting);
+    let incoming_starts = context.start_incoming_count();
+    let busys = context.busys_sent();
+
+    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedCallWaiting),
+        waiting_events + 1
+    );
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferWhileActive),
+        0
+    );
+    assert_eq!(context.busys_sent(), busys);
+    assert_eq!(context.start_incoming_count(), incoming_starts + 1);
+
+    let waiting_call = cm
+        .waiting_call()
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(waiting_call.call_id(), call_id);
+    assert_ne!(context.active_call().call_id(), call_id);
+
+    cm.proceed(
+        call_id,
+        format!("CONTEXT-{}", context.prng.gen::<u16>()),
+        CallConfig::default().with_data_mode(DataMode::Normal),
+        None,
+    )
+    .expect(error_line!());
+    cm.received_ice(call_id, random_received_ice_candidate(&context.prng))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    let mut connection = waiting_call.get_connection(1).expect(error_line!());
+    connection.inject_ice_connected().expect(error_line!());
+    connection
+        .handle_received_incoming_media(MediaStream::new(webrtc::Arc::null()))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        waiting_call.state().expect(error_line!()),
+        CallState::ConnectedBeforeAccepted
+    );
+    assert_eq!(context.error_count(), 0);
+
+    waiting_call
+}
+
+fn outgoing_audio_enabled(call: &Call<SimPlatform>) -> bool {
+    call.active_connection()
+        .expect(error_line!())
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_enabled()
+}
+
+#[test]
+fn call_waiting_hold_and_swap() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let first_call = context.active_call();
+    let second_call = start_waiting_call(&context);
+
+    info!("test: accepting waiting call");
+    cm.accept_call(second_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        second_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.active_call().call_id(), second_call.call_id());
+    let held_call = cm.held_call().expect(error_line!()).expect(error_line!());
+    assert_eq!(held_call.call_id(), first_call.call_id());
+    assert!(cm.waiting_call().expect(error_line!()).is_none());
+    assert!(!outgoing_audio_enabled(&first_call));
+    assert!(outgoing_audio_enabled(&second_call));
+    assert_eq!(
+        first_call
+            .active_connection()
+            .expect(error_line!())
+            .last_sent_hold(),
+        Some(true)
+    );
+
+    info!("test: swapping calls");
+    cm.swap_calls().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.active_call().call_id(), first_call.call_id());
+    let held_call = cm.held_call().expect(error_line!()).expect(error_line!());
+    assert_eq!(held_call.call_id(), second_call.call_id());
+    assert!(outgoing_audio_enabled(&first_call));
+    assert!(!outgoing_audio_enabled(&second_call));
+
+    info!("test: hanging up the first call");
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+    assert!(cm.active_call().is_err());
+    assert!(cm.held_call().expect(error_line!()).is_some());
+    assert!(cm.busy());
+
+    info!("test: resuming the second call");
+    cm.swap_calls().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.active_call().call_id(), second_call.call_id());
+    assert!(cm.held_call().expect(error_line!()).is_none());
+    assert!(outgoing_audio_enabled(&second_call));
+
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 2);
+    assert_eq!(context.error_count(), 0);
+    assert!(!cm.busy());
+}
+
+#[test]
+fn call_waiting_drop_and_promote() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let first_call = context.active_call();
+    let second_call = start_waiting_call(&context);
+
+    info!("test: dropping waiting call");
+    let normal_hangups = context.normal_hangups_sent();
+    cm.drop_call(second_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedAppDroppedCall),
+        1
+    );
+    assert_eq!(context.busys_sent(), 0);
+    assert_eq!(context.normal_hangups_sent(), normal_hangups + 1);
+    assert!(cm.waiting_call().expect(error_line!()).is_none());
+    assert_eq!(context.active_call().call_id(), first_call.call_id());
+    assert!(outgoing_audio_enabled(&first_call));
+    assert!(cm.busy());
+
+    // A second waiting call takes over once the active call ends.
+    let third_call = start_waiting_call(&context);
+    cm.received_hangup(
+        first_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1,
+            hangup: signaling::Hangup::Normal,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+    assert!(cm.waiting_call().expect(error_line!()).is_none());
+    assert_eq!(context.active_call().call_id(), third_call.call_id());
+    assert!(cm.busy());
+
+    cm.accept_call(third_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        third_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert!(cm.held_call().expect(error_line!()).is_none());
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn hold_and_resume() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    cm.set_on_hold(true).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert!(!outgoing_audio_enabled(&active_call));
+    assert_eq!(active_connection.last_sent_hold(), Some(true));
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+
+    cm.set_on_hold(false).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert!(outgoing_audio_enabled(&active_call));
+    assert_eq!(active_connection.last_sent_hold(), Some(false));
+
+    active_connection
+        .inject_received_sender_status_via_rtp_data(
+            active_call.call_id(),
+            signaling::SenderStatus {
+                on_hold: Some(true),
+                ..Default::default()
+            },
+            1,
+        )
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::RemoteHold), 1);
+    assert_eq!(context.event_count(ApplicationEvent::RemoteResume), 0);
+
+    // Other changes carry the hold along, but don't report it again.
+    active_connection
+        .inject_received_sender_status_via_rtp_data(
+            active_call.call_id(),
+            signaling::SenderStatus {
+                video_enabled: Some(true),
+                on_hold: Some(true),
+                ..Default::default()
+            },
+            2,
+        )
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::RemoteVideoEnable), 1);
+    assert_eq!(context.event_count(ApplicationEvent::RemoteHold), 1);
+    assert_eq!(context.event_count(ApplicationEvent::RemoteResume), 0);
+
+    active_connection
+        .inject_received_sender_status_via_rtp_data(
+            active_call.call_id(),
+            signaling::SenderStatus {
+                video_enabled: Some(true),
+                on_hold: Some(false),
+                ..Default::default()
+            },
+            3,
+        )
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::RemoteHold), 1);
+    assert_eq!(context.event_count(ApplicationEvent::RemoteResume), 1);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn escalate_to_group_call() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let active_connection = context.active_connection();
+
+    let group_id = vec![1, 2, 3];
+    let client_id = context
+        .create_group_call(group_id.clone())
+        .expect(error_line!());
+    cm.escalate_to_group_call(client_id).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let invitation = active_connection
+        .last_sent_group_call_invitation()
+        .expect(error_line!());
+    assert_eq!(invitation.id, Some(u64::from(active_call.call_id())));
+    assert_eq!(invitation.group_id, Some(group_id));
+    assert_eq!(invitation.call_link_root_key, None);
+
+    // The 1:1 call carries on while the group call is being joined.
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert!(cm.busy());
+
+    group_call::Observer::handle_join_state_changed(
+        &cm,
+        client_id,
+        group_call::JoinState::Joined(1),
+    );
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedEscalatedToGroupCall),
+        1
+    );
+    assert_eq!(context.normal_hangups_sent(), 1);
+    assert!(cm.active_call().is_err());
+    // The group call is busy now.
+    assert!(cm.busy());
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn received_group_call_invitation() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    let invitation = signaling::GroupCallInvitation::Group {
+        group_id: vec![1, 2, 3],
+    };
+    // The invitation is repeated with every RTP data message.
+    for _ in 0..2 {
+        active_connection
+            .inject_received_group_call_invitation_via_rtp_data(
+                active_call.call_id(),
+                invitation.clone(),
+            )
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    let invitations = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_invitations();
+    assert_eq!(invitations, vec![invitation]);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn remote_joining_group_call_first_ends_call_as_escalated() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    active_connection
+        .inject_received_group_call_invitation_via_rtp_data(
+            active_call.call_id(),
+            signaling::GroupCallInvitation::Group {
+                group_id: vec![1, 2, 3],
+            },
+        )
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The remote joined the group call before we did.
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1,
+            hangup: signaling::Hangup::Normal,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedEscalatedToGroupCall),
+        1
+    );
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 0);
+    assert!(cm.active_call().is_err());
+
+    // Following along still joins the group call.
+    let client_id = context
+        .create_group_call(vec![1, 2, 3])
+        .expect(error_line!());
+    cm.escalate_to_group_call(client_id).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn transfer_call_to_own_device() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let active_connection = context.active_connection();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let remote_user_id = vec![1, 2, 3];
+    let target_device_id = 3;
+    cm.transfer_call(remote_user_id.clone(), target_device_id)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The remote peer is told which device to expect.
+    let transfer = active_connection.last_sent_transfer().expect(error_line!());
+    assert_eq!(transfer.id, Some(u64::from(active_call.call_id())));
+    assert_eq!(transfer.device_id, Some(target_device_id));
+
+    use protobuf::signaling::call_message::transfer::Type as TransferType;
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+    match &messages[..] {
+        [message] => {
+            assert_eq!(&self_uuid[..], &message.recipient_id[..]);
+            let call_message = protobuf::signaling::CallMessage::decode(&message.message[..])
+                .expect(error_line!());
+            assert_eq!(
+                protobuf::signaling::CallMessage {
+                    transfer: Some(protobuf::signaling::call_message::Transfer {
+                        r#type: Some(TransferType::Offer.into()),
+                        call_id: Some(active_call.call_id().as_u64()),
+                        peer_id: Some(remote_user_id),
+                        device_id: Some(target_device_id),
+                    }),
+                    ..Default::default()
+                },
+                call_message
+            );
+        }
+        _ => panic!("unexpected messages: {:?}", messages),
+    }
+
+    // The call carries on until the target device has taken over.
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+
+    let message = protobuf::signaling::CallMessage {
+        transfer: Some(protobuf::signaling::call_message::Transfer {
+            r#type: Some(TransferType::Completed.into()),
+            call_id: Some(active_call.call_id().as_u64()),
+            peer_id: None,
+            device_id: Some(active_call.local_device_id()),
+        }),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    message
+        .encode(&mut buf)
+        .expect("cannot fail encoding to Vec");
+
+    cm.received_call_message(
+        self_uuid,
+        target_device_id,
+        active_call.local_device_id(),
+        buf,
+        Duration::ZERO,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::EndedTransferred), 1);
+    assert_eq!(context.normal_hangups_sent(), 0);
+    assert!(cm.active_call().is_err());
+    assert!(!cm.busy());
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn received_call_transfer() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let remote_user_id = vec![1, 2, 3];
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    let local_device_id = 2;
+
+    // Only the target device takes the call.
+    for device_id in [3, local_device_id] {
+        let message = protobuf::signaling::CallMessage {
+            transfer: Some(protobuf::signaling::call_message::Transfer {
+                r#type: Some(protobuf::signaling::call_message::transfer::Type::Offer.into()),
+                call_id: Some(call_id.as_u64()),
+                peer_id: Some(remote_user_id.clone()),
+                device_id: Some(device_id),
+            }),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        message
+            .encode(&mut buf)
+            .expect("cannot fail encoding to Vec");
+
+        cm.received_call_message(self_uuid.clone(), 1, local_device_id, buf, Duration::ZERO)
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    let transfers = cm.platform().expect(error_line!()).take_call_transfers();
+    assert_eq!(transfers, vec![(remote_user_id, call_id)]);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn transfer_from_remote_device() {
     test_init();
 
-    let context = TestContext::new();
+    let context = connect_inbound_call();
     let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
 
-    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
-    let call_id = CallId::new(context.prng.gen::<u64>());
-    cm.received_offer(
-        remote_peer,
-        call_id,
-        random_received_offer(&context.prng, Duration::from_secs(0)),
-    )
-    .expect(error_line!());
+    let remote_peer = {
+        let remote_peer = active_call.remote_peer().expect(error_line!());
+        remote_peer.to_owned()
+    };
 
+    // The peer's active device announces which device is taking over.
+    active_connection
+        .inject_received_transfer_via_rtp_data(active_call.call_id(), 2)
+        .expect(error_line!());
     cm.synchronize().expect(error_line!());
 
-    assert!(cm.active_call().is_ok());
-    assert_eq!(context.start_outgoing_count(), 0);
+    // That device calls with the same call_id.
+    let mut received = random_received_offer(&context.prng, Duration::from_secs(0));
+    received.sender_device_id = 2;
+    cm.received_offer(remote_peer, active_call.call_id(), received)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.busys_sent(), 0);
     assert_eq!(context.start_incoming_count(), 1);
+    assert_eq!(active_call.active_device_id().expect(error_line!()), 1);
 
-    let active_call = context.active_call();
+    let mut transfer_connection = active_call.get_connection(2).expect(error_line!());
+    transfer_connection
+        .handle_received_incoming_media(MediaStream::new(webrtc::Arc::null()))
+        .expect(error_line!());
+    transfer_connection
+        .inject_ice_connected()
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The new device takes over without interrupting the call.
+    assert_eq!(active_call.active_device_id().expect(error_line!()), 2);
+    assert!(active_call.get_connection(1).is_err());
+    assert_eq!(
+        transfer_connection.state().expect(error_line!()),
+        ConnectionState::ConnectedAndAccepted
+    );
     assert_eq!(
         active_call.state().expect(error_line!()),
-        CallState::WaitingToProceed
+        CallState::ConnectedAndAccepted
     );
+    assert_eq!(context.ended_count(), 0);
+    assert_eq!(context.error_count(), 0);
+}
 
-    // cause the sending of the answer to fail.
-    context.force_internal_fault(true);
+#[test]
+fn unannounced_transfer_from_remote_device_is_busy() {
+    test_init();
 
-    cm.proceed(
-        active_call.call_id(),
-        format!("CONTEXT-{}", context.prng.gen::<u16>()),
-        CallConfig::default().with_data_mode(DataMode::Normal),
-        None,
-    )
-    .expect(error_line!());
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    let remote_peer = {
+        let remote_peer = active_call.remote_peer().expect(error_line!());
+        remote_peer.to_owned()
+    };
 
+    active_connection
+        .inject_received_transfer_via_rtp_data(active_call.call_id(), 3)
+        .expect(error_line!());
     cm.synchronize().expect(error_line!());
 
-    active_call.get_connection(1).expect_err(error_line!());
+    // A device other than the announced one can't take over the call, even
+    // with the same call_id.
+    let mut received = random_received_offer(&context.prng, Duration::from_secs(0));
+    received.sender_device_id = 2;
+    cm.received_offer(remote_peer, active_call.call_id(), received)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
 
-    assert_eq!(context.error_count(), 1);
-    assert_eq!(context.ended_count(), 1);
-    assert_eq!(context.answers_sent(), 0);
+    assert_eq!(context.busys_sent(), 1);
+    assert!(active_call.get_connection(2).is_err());
+    assert_eq!(active_call.active_device_id().expect(error_line!()), 1);
     assert_eq!(
         active_call.state().expect(error_line!()),
-        CallState::Terminated
+        CallState::ConnectedAndAccepted
     );
-    assert!(!cm.busy());
+    assert_eq!(context.error_count(), 0);
 }
 
 #[test]
-fn receive_offer_while_active() {
+fn inbound_call_ice_restart() {
     test_init();
 
     let context = connect_inbound_call();
     let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
 
-    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
-    let call_id = CallId::new(context.prng.gen::<u64>());
-    cm.received_offer(
-        remote_peer,
-        call_id,
-        random_received_offer(&context.prng, Duration::from_secs(0)),
+    info!("test: injecting ice connection failed");
+    active_connection.inject_ice_failed().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // The callee can't restart by itself, so it asks the caller to.
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ReconnectingAfterAccepted
+    );
+    assert_eq!(
+        context.last_ice_restart_sent(),
+        Some(signaling::IceRestart {
+            generation: 0,
+            credentials: None,
+        })
+    );
+
+    info!("test: receiving restart");
+    cm.received_ice(
+        active_call.call_id(),
+        received_ice_restart(1, Some(("ufrg", "0123456789012345678901")), 1),
     )
     .expect(error_line!());
 
     cm.synchronize().expect(error_line!());
 
-    assert_eq!(context.error_count(), 0);
+    // The callee replies with its own new credentials.
+    let restart = context.last_ice_restart_sent().expect(error_line!());
+    assert_eq!(restart.generation, 1);
+    let (ice_ufrag, ice_pwd) = restart.credentials.expect(error_line!());
+    assert_eq!(ice_ufrag.len(), 4);
+    assert_eq!(ice_pwd.len(), 22);
+    let ice_sent = context.ice_candidates_sent();
+
+    info!("test: receiving the same restart again");
+    cm.received_ice(
+        active_call.call_id(),
+        received_ice_restart(1, Some(("ufrg", "0123456789012345678901")), 1),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.ice_candidates_sent(), ice_sent);
+
+    active_connection
+        .inject_ice_connected()
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
     assert_eq!(
-        context.event_count(ApplicationEvent::ReceivedOfferWhileActive),
-        1
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
     );
-    assert_eq!(context.busys_sent(), 1);
-    assert_eq!(context.call_concluded_count(), 1);
-    assert!(cm.busy());
+    assert_eq!(context.event_count(ApplicationEvent::Reconnected), 1);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
 }
 
 #[test]
@@ -1805,6 +2703,243 @@ fn group_call_ring_timeout() {
     );
 }
 
+fn ring_message(group_id: &group_call::GroupId, ring_id: group_call::RingId) -> Vec<u8> {
+    let message = protobuf::signaling::CallMessage {
+        ring_intention: Some(protobuf::signaling::call_message::RingIntention {
+            group_id: Some(group_id.clone()),
+            ring_id: Some(ring_id.into()),
+            r#type: Some(protobuf::signaling::call_message::ring_intention::Type::Ring.into()),
+        }),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    message
+        .encode(&mut buf)
+        .expect("cannot fail encoding to Vec");
+    buf
+}
+
+fn take_ring_response_types(context: &TestContext) -> Vec<i32> {
+    context
+        .cm()
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages()
+        .iter()
+        .map(|message| {
+            protobuf::signaling::CallMessage::decode(&message.message[..])
+                .expect(error_line!())
+                .ring_response
+                .and_then(|response| response.r#type)
+                .expect(error_line!())
+        })
+        .collect()
+}
+
+#[test]
+fn group_call_ring_declined_by_policy() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.set_self_uuid(vec![1, 0, 1]).expect(error_line!());
+    cm.set_incoming_call_policy(Some(Box::new(DoNotDisturb::<SimPlatform> {
+        allowed_users: [vec![9, 9, 9]].into_iter().collect(),
+        quiet_hours: Some(ALL_DAY),
+        ..Default::default()
+    })))
+    .expect(error_line!());
+
+    let group_id = vec![1, 1, 1];
+    let ring_id = group_call::RingId::from(42);
+
+    cm.received_call_message(
+        vec![1, 2, 3],
+        1,
+        2,
+        ring_message(&group_id, ring_id),
+        Duration::ZERO,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let updates: Vec<_> = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_ring_updates()
+        .into_iter()
+        .map(|update| update.update)
+        .collect();
+    assert_eq!(vec![group_call::RingUpdate::DeclinedByPolicy], updates);
+    assert_eq!(
+        vec![protobuf::signaling::call_message::ring_response::Type::Declined as i32],
+        take_ring_response_types(&context)
+    );
+
+    // Senders in the allow-list still ring.
+    let ring_id = group_call::RingId::from(43);
+    cm.received_call_message(
+        vec![9, 9, 9],
+        1,
+        2,
+        ring_message(&group_id, ring_id),
+        Duration::ZERO,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let updates: Vec<_> = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_ring_updates()
+        .into_iter()
+        .map(|update| update.update)
+        .collect();
+    assert_eq!(vec![group_call::RingUpdate::Requested], updates);
+    assert!(take_ring_response_types(&context).is_empty());
+
+    assert!(!cm.busy());
+}
+
+#[test]
+fn group_call_ring_limited_by_policy() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.set_self_uuid(vec![1, 0, 1]).expect(error_line!());
+    cm.set_incoming_call_policy(Some(Box::new(DoNotDisturb::<SimPlatform> {
+        max_concurrent_group_rings: Some(1),
+        ..Default::default()
+    })))
+    .expect(error_line!());
+
+    let sender_id = vec![1, 2, 3];
+    for (group_id, ring_id) in [
+        (vec![1, 1, 1], 42),
+        (vec![1, 1, 1], 43),
+        (vec![2, 2, 2], 44),
+    ] {
+        cm.received_call_message(
+            sender_id.clone(),
+            1,
+            2,
+            ring_message(&group_id, group_call::RingId::from(ring_id)),
+            Duration::ZERO,
+        )
+        .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    // A new ring for the same group replaces the old one rather than adding to it.
+    let updates: Vec<_> = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_ring_updates()
+        .into_iter()
+        .map(|update| update.update)
+        .collect();
+    assert_eq!(
+        vec![
+            group_call::RingUpdate::Requested,
+            group_call::RingUpdate::Requested,
+            group_call::RingUpdate::DeclinedByPolicy,
+        ],
+        updates
+    );
+    assert_eq!(
+        vec![protobuf::signaling::call_message::ring_response::Type::Busy as i32],
+        take_ring_response_types(&context)
+    );
+}
+
+#[test]
+fn group_call_ring_records() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let group_id = vec![1, 1, 1];
+    let sender_id = vec![1, 2, 3];
+    let ring_id = group_call::RingId::from(42);
+
+    cm.received_call_message(
+        sender_id.clone(),
+        1,
+        2,
+        ring_message(&group_id, ring_id),
+        Duration::ZERO,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The ring is still going.
+    assert!(cm
+        .platform()
+        .expect(error_line!())
+        .take_group_ring_records()
+        .is_empty());
+
+    // Another of the user's devices declines it.
+    let response = protobuf::signaling::CallMessage {
+        ring_response: Some(protobuf::signaling::call_message::RingResponse {
+            group_id: Some(group_id.clone()),
+            ring_id: Some(ring_id.into()),
+            r#type: Some(protobuf::signaling::call_message::ring_response::Type::Declined.into()),
+        }),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    response
+        .encode(&mut buf)
+        .expect("cannot fail encoding to Vec");
+    cm.received_call_message(self_uuid, 2, 1, buf, Duration::ZERO)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let records = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_ring_records();
+    match &records[..] {
+        [record] => {
+            assert_eq!(group_id, record.group_id);
+            assert_eq!(ring_id, record.ring_id);
+            assert_eq!(sender_id, record.sender_id);
+            assert_eq!(GroupRingOutcome::DeclinedOnAnotherDevice, record.outcome);
+            assert!(record.received <= record.ended);
+        }
+        _ => panic!("unexpected ring records: {:?}", records),
+    }
+
+    // A ring that arrives too late is over right away.
+    let ring_id = group_call::RingId::from(43);
+    cm.received_call_message(
+        sender_id,
+        1,
+        2,
+        ring_message(&group_id, ring_id),
+        MAX_MESSAGE_AGE + Duration::from_millis(1),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let outcomes: Vec<_> = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_ring_records()
+        .into_iter()
+        .map(|record| (record.ring_id, record.outcome))
+        .collect();
+    assert_eq!(vec![(ring_id, GroupRingOutcome::Expired)], outcomes);
+}
+
 #[test]
 fn received_status_before_accepted() {
     let context = start_inbound_call();
@@ -1827,6 +2962,7 @@ fn received_status_before_accepted() {
                 video_enabled: Some(true),
                 sharing_screen: None,
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             1,
         )
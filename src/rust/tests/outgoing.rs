@@ -32,7 +32,8 @@ use ringrtc::{
 mod common;
 use common::{
     random_ice_candidate, random_received_answer, random_received_ice_candidate,
-    random_received_offer, test_init, TestContext,
+    random_received_offer, received_answer_without_ice_restart, received_ice_restart, test_init,
+    Prng, TestContext,
 };
 
 // Simple test that:
@@ -109,6 +110,13 @@ fn start_outbound_and_proceed() -> TestContext {
 //
 // Now in the Connecting state.
 fn start_outbound_n_remote_call(n_remotes: u16) -> TestContext {
+    start_outbound_n_remote_call_with_answers(n_remotes, random_received_answer)
+}
+
+fn start_outbound_n_remote_call_with_answers(
+    n_remotes: u16,
+    received_answer: fn(&Prng, DeviceId) -> signaling::ReceivedAnswer,
+) -> TestContext {
     let context = TestContext::new();
     let mut cm = context.cm();
 
@@ -144,11 +152,8 @@ fn start_outbound_n_remote_call(n_remotes: u16) -> TestContext {
     // add a received answer for each remote
     for i in 1..(n_remotes + 1) {
         let call_id = active_call.call_id();
-        cm.received_answer(
-            call_id,
-            random_received_answer(&context.prng, i as DeviceId),
-        )
-        .expect(error_line!());
+        cm.received_answer(call_id, received_answer(&context.prng, i as DeviceId))
+            .expect(error_line!());
 
         cm.received_ice(call_id, random_received_ice_candidate(&context.prng))
             .expect(error_line!());
@@ -241,7 +246,10 @@ fn connected_outbound_call() -> TestContext {
 //
 // Now in the ConnectedAndAccepted state.
 fn connected_and_accepted_outbound_call() -> TestContext {
-    let context = start_outbound_call();
+    connect_and_accept_outbound_call(start_outbound_call())
+}
+
+fn connect_and_accept_outbound_call(context: TestContext) -> TestContext {
     let mut cm = context.cm();
     let active_call = context.active_call();
     let mut active_connection = context.active_connection();
@@ -661,10 +669,14 @@ fn outbound_call_connected_ice_failed() {
     let active_call = context.active_call();
     let mut active_connection = context.active_connection();
 
-    info!("test: injecting ice connection failed");
-    active_connection.inject_ice_failed().expect(error_line!());
+    // The first failures restart ICE, and the last one gives up.
+    let max_attempts = CallConfig::default().ice_restart_config.max_attempts;
+    for _ in 0..=max_attempts {
+        info!("test: injecting ice connection failed");
+        active_connection.inject_ice_failed().expect(error_line!());
 
-    cm.synchronize().expect(error_line!());
+        cm.synchronize().expect(error_line!());
+    }
 
     assert_eq!(
         active_connection.state().expect(error_line!()),
@@ -679,9 +691,280 @@ fn outbound_call_connected_ice_failed() {
         context.event_count(ApplicationEvent::EndedConnectionFailure),
         1
     );
+    assert_eq!(
+        context
+            .last_ice_restart_sent()
+            .expect(error_line!())
+            .generation,
+        max_attempts
+    );
     assert!(!cm.busy());
 }
 
+#[test]
+fn outbound_call_connected_ice_failed_and_restarted() {
+    test_init();
+
+    let context = connected_and_accepted_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    info!("test: injecting ice connection failed");
+    active_connection.inject_ice_failed().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // The caller restarts ICE and sends its new credentials to the callee.
+    assert_eq!(
+        active_connection.state().expect(error_line!()),
+        ConnectionState::ReconnectingAfterAccepted
+    );
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ReconnectingAfterAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::Reconnecting), 1);
+    assert_eq!(context.ended_count(), 0);
+    let restart = context.last_ice_restart_sent().expect(error_line!());
+    assert_eq!(restart.generation, 1);
+    assert!(restart.credentials.is_some());
+    assert_eq!(
+        context
+            .last_ice_sent()
+            .expect(error_line!())
+            .receiver_device_id,
+        Some(1)
+    );
+
+    info!("test: receiving restart reply");
+    cm.received_ice(
+        active_call.call_id(),
+        received_ice_restart(1, Some(("ufrg", "0123456789012345678901")), 1),
+    )
+    .expect(error_line!());
+    active_connection
+        .inject_ice_connected()
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::Reconnected), 1);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+
+    info!("test: injecting ice connection failed again");
+    active_connection.inject_ice_failed().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // Reconnecting resets the attempts, so this is another restart.
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ReconnectingAfterAccepted
+    );
+    assert_eq!(
+        context
+            .last_ice_restart_sent()
+            .expect(error_line!())
+            .generation,
+        2
+    );
+    assert_eq!(context.ended_count(), 0);
+    assert!(cm.busy());
+}
+
+#[test]
+fn outbound_call_ice_restart_requested_by_callee() {
+    test_init();
+
+    let context = connected_and_accepted_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    info!("test: receiving stale restart request");
+    cm.received_ice(active_call.call_id(), received_ice_restart(1, None, 1))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert!(context.last_ice_restart_sent().is_none());
+
+    info!("test: receiving restart request");
+    cm.received_ice(active_call.call_id(), received_ice_restart(0, None, 1))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    let restart = context.last_ice_restart_sent().expect(error_line!());
+    assert_eq!(restart.generation, 1);
+    assert!(restart.credentials.is_some());
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn outbound_call_network_change_restarts_ice() {
+    test_init();
+
+    let context = connected_and_accepted_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    let route = |local_adapter_type| NetworkRoute {
+        local_adapter_type,
+        local_adapter_type_under_vpn: NetworkAdapterType::Unknown,
+        local_relayed: false,
+        local_relay_protocol: TransportProtocol::Unknown,
+        remote_relayed: false,
+    };
+
+    info!("test: connecting over wifi");
+    active_connection
+        .inject_ice_network_route_changed(route(NetworkAdapterType::Wifi))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert!(context.last_ice_restart_sent().is_none());
+
+    info!("test: switching to cellular");
+    active_connection
+        .inject_ice_network_route_changed(route(NetworkAdapterType::Cellular4G))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    let restart = context.last_ice_restart_sent().expect(error_line!());
+    assert_eq!(restart.generation, 1);
+    assert_eq!(
+        active_connection.state().expect(error_line!()),
+        ConnectionState::ConnectedAndAccepted
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn outbound_call_network_change_keeps_ice_restart_attempts() {
+    test_init();
+
+    let context = connected_and_accepted_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    let route = |local_adapter_type| NetworkRoute {
+        local_adapter_type,
+        local_adapter_type_under_vpn: NetworkAdapterType::Unknown,
+        local_relayed: false,
+        local_relay_protocol: TransportProtocol::Unknown,
+        remote_relayed: false,
+    };
+
+    // More network changes than there are attempts, none of which fail.
+    let max_attempts = CallConfig::default().ice_restart_config.max_attempts;
+    let network_changes = max_attempts + 1;
+    for adapter_type in [NetworkAdapterType::Wifi, NetworkAdapterType::Cellular4G]
+        .into_iter()
+        .cycle()
+        .take(network_changes as usize + 1)
+    {
+        info!("test: switching to {:?}", adapter_type);
+        active_connection
+            .inject_ice_network_route_changed(route(adapter_type))
+            .expect(error_line!());
+
+        cm.synchronize().expect(error_line!());
+    }
+    assert_eq!(
+        context
+            .last_ice_restart_sent()
+            .expect(error_line!())
+            .generation,
+        network_changes
+    );
+
+    // Failures still get every attempt.
+    for _ in 0..max_attempts {
+        info!("test: injecting ice connection failed");
+        active_connection.inject_ice_failed().expect(error_line!());
+
+        cm.synchronize().expect(error_line!());
+    }
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ReconnectingAfterAccepted
+    );
+    assert_eq!(
+        context
+            .last_ice_restart_sent()
+            .expect(error_line!())
+            .generation,
+        network_changes + max_attempts
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn outbound_call_ice_restart_not_supported_by_callee() {
+    test_init();
+
+    let context = connect_and_accept_outbound_call(start_outbound_n_remote_call_with_answers(
+        1,
+        received_answer_without_ice_restart,
+    ));
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    let route = |local_adapter_type| NetworkRoute {
+        local_adapter_type,
+        local_adapter_type_under_vpn: NetworkAdapterType::Unknown,
+        local_relayed: false,
+        local_relay_protocol: TransportProtocol::Unknown,
+        remote_relayed: false,
+    };
+
+    info!("test: switching from wifi to cellular");
+    active_connection
+        .inject_ice_network_route_changed(route(NetworkAdapterType::Wifi))
+        .expect(error_line!());
+    active_connection
+        .inject_ice_network_route_changed(route(NetworkAdapterType::Cellular4G))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert!(context.last_ice_restart_sent().is_none());
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+
+    info!("test: injecting ice connection failed");
+    active_connection.inject_ice_failed().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert!(context.last_ice_restart_sent().is_none());
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedConnectionFailure),
+        1
+    );
+    assert_eq!(context.error_count(), 0);
+}
+
 #[test]
 fn outbound_call_connected_local_hangup() {
     test_init();
@@ -855,6 +1138,7 @@ fn update_sender_status() {
             video_enabled: Some(false),
             sharing_screen: None,
             audio_enabled: Some(false),
+            on_hold: None,
         })
         .expect(error_line!());
 
@@ -867,6 +1151,7 @@ fn update_sender_status() {
             video_enabled: Some(false),
             sharing_screen: None,
             audio_enabled: Some(false),
+            on_hold: None,
         }),
         active_connection.last_sent_sender_status()
     );
@@ -876,6 +1161,7 @@ fn update_sender_status() {
             video_enabled: Some(true),
             sharing_screen: None,
             audio_enabled: Some(true),
+            on_hold: None,
         })
         .expect(error_line!());
 
@@ -884,6 +1170,7 @@ fn update_sender_status() {
             video_enabled: None,
             sharing_screen: Some(true),
             audio_enabled: None,
+            on_hold: None,
         })
         .expect(error_line!());
 
@@ -896,6 +1183,7 @@ fn update_sender_status() {
             video_enabled: Some(true),
             sharing_screen: Some(true),
             audio_enabled: Some(true),
+            on_hold: None,
         }),
         active_connection.last_sent_sender_status()
     );
@@ -905,6 +1193,7 @@ fn update_sender_status() {
             video_enabled: None,
             sharing_screen: Some(false),
             audio_enabled: None,
+            on_hold: None,
         })
         .expect(error_line!());
 
@@ -917,6 +1206,7 @@ fn update_sender_status() {
             video_enabled: Some(true),
             sharing_screen: Some(false),
             audio_enabled: Some(true),
+            on_hold: None,
         }),
         active_connection.last_sent_sender_status()
     );
@@ -1376,6 +1666,69 @@ fn received_remote_hangup_before_connection_with_message_in_flight() {
     assert!(!cm.busy());
 }
 
+#[test]
+fn busy_sent_ahead_of_queued_ice_candidates() {
+    test_init();
+
+    let context = start_outbound_and_proceed();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut parent_connection = active_call.get_parent_connection().expect(error_line!());
+
+    // Leave the first ICE candidate message 'in-flight' so that later
+    // messages are queued until message_sent() is called.
+    context.no_auto_message_sent_for_ice(true);
+
+    let force_send = true;
+    parent_connection
+        .inject_local_ice_candidate(random_ice_candidate(&context.prng), force_send, "", None)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.ice_candidates_sent(), 1);
+
+    // These are coalesced into a single message.
+    for _ in 0..3 {
+        parent_connection
+            .inject_local_ice_candidate(random_ice_candidate(&context.prng), force_send, "", None)
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    // An offer from someone else while the call is active is rejected as busy.
+    let remote_peer = format!("REMOTE_PEER-{}", context.prng.gen::<u16>());
+    cm.received_offer(
+        remote_peer,
+        CallId::new(context.prng.gen::<u64>()),
+        random_received_offer(&context.prng, Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferWhileActive),
+        1
+    );
+    assert_eq!(context.busys_sent(), 0);
+    assert_eq!(context.ice_candidates_sent(), 1);
+
+    // Now free the message queue; the Busy goes out before the ICE candidates.
+    cm.message_sent(active_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.busys_sent(), 1);
+    assert_eq!(context.ice_candidates_sent(), 4);
+    assert_eq!(
+        context
+            .last_ice_sent()
+            .expect(error_line!())
+            .ice
+            .candidates
+            .len(),
+        3
+    );
+}
+
 #[test]
 fn received_remote_hangup_before_connection_for_permission() {
     test_init();
@@ -1552,6 +1905,7 @@ fn received_remote_video_status() {
                     video_enabled: Some(enable),
                     sharing_screen: None,
                     audio_enabled: None,
+                    on_hold: None,
                 },
                 i,
             )
@@ -1587,6 +1941,7 @@ fn received_remote_video_status() {
                 video_enabled: Some(true),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             1,
         )
@@ -1599,6 +1954,7 @@ fn received_remote_video_status() {
                 video_enabled: Some(false),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             2,
         )
@@ -1641,6 +1997,7 @@ fn ignore_duplicate_remote_video_status() {
                 video_enabled: Some(true),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             0,
         )
@@ -1654,6 +2011,7 @@ fn ignore_duplicate_remote_video_status() {
                 video_enabled: Some(true),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             1,
         )
@@ -1667,6 +2025,7 @@ fn ignore_duplicate_remote_video_status() {
                 video_enabled: Some(false),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             2,
         )
@@ -1680,6 +2039,7 @@ fn ignore_duplicate_remote_video_status() {
                 video_enabled: Some(false),
                 sharing_screen: None,
                 audio_enabled: None,
+                on_hold: None,
             },
             3,
         )
@@ -1725,6 +2085,7 @@ fn received_remote_sharing_screen_status() {
                     video_enabled: None,
                     sharing_screen: Some(enable),
                     audio_enabled: None,
+                    on_hold: None,
                 },
                 i,
             )
@@ -1754,6 +2115,7 @@ fn received_remote_sharing_screen_status() {
                 video_enabled: None,
                 sharing_screen: Some(true),
                 audio_enabled: None,
+                on_hold: None,
             },
             1,
         )
@@ -1766,6 +2128,7 @@ fn received_remote_sharing_screen_status() {
                 video_enabled: None,
                 sharing_screen: Some(false),
                 audio_enabled: None,
+                on_hold: None,
             },
             2,
         )
@@ -1802,6 +2165,7 @@ fn ignore_duplicate_remote_screen_sharing_status() {
                 video_enabled: None,
                 sharing_screen: Some(true),
                 audio_enabled: None,
+                on_hold: None,
             },
             0,
         )
@@ -1815,6 +2179,7 @@ fn ignore_duplicate_remote_screen_sharing_status() {
                 video_enabled: None,
                 sharing_screen: Some(true),
                 audio_enabled: None,
+                on_hold: None,
             },
             1,
         )
@@ -1828,6 +2193,7 @@ fn ignore_duplicate_remote_screen_sharing_status() {
                 video_enabled: None,
                 sharing_screen: Some(false),
                 audio_enabled: None,
+                on_hold: None,
             },
             2,
         )
@@ -1841,6 +2207,7 @@ fn ignore_duplicate_remote_screen_sharing_status() {
                 video_enabled: None,
                 sharing_screen: Some(false),
                 audio_enabled: None,
+                on_hold: None,
             },
             3,
         )
@@ -1892,6 +2259,7 @@ fn received_remote_audio_status() {
                     video_enabled: None,
                     sharing_screen: None,
                     audio_enabled: Some(enable),
+                    on_hold: None,
                 },
                 i,
             )
@@ -1927,6 +2295,7 @@ fn received_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             1,
         )
@@ -1939,6 +2308,7 @@ fn received_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(false),
+                on_hold: None,
             },
             2,
         )
@@ -1981,6 +2351,7 @@ fn ignore_duplicate_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             0,
         )
@@ -1994,6 +2365,7 @@ fn ignore_duplicate_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             1,
         )
@@ -2007,6 +2379,7 @@ fn ignore_duplicate_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(false),
+                on_hold: None,
             },
             2,
         )
@@ -2020,6 +2393,7 @@ fn ignore_duplicate_remote_audio_status() {
                 video_enabled: None,
                 sharing_screen: None,
                 audio_enabled: Some(false),
+                on_hold: None,
             },
             3,
         )
@@ -2048,6 +2422,7 @@ fn received_remote_multiple_status() {
                 video_enabled: Some(false),
                 sharing_screen: Some(true),
                 audio_enabled: Some(false),
+                on_hold: None,
             },
             1,
         )
@@ -2060,6 +2435,7 @@ fn received_remote_multiple_status() {
                 video_enabled: Some(true),
                 sharing_screen: Some(false),
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             2,
         )
@@ -2102,6 +2478,7 @@ fn received_status_before_accepted() {
                 video_enabled: Some(true),
                 sharing_screen: None,
                 audio_enabled: Some(true),
+                on_hold: None,
             },
             1,
         )